@@ -0,0 +1,45 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use voicsh::audio::resample::{Resampler, resample};
+
+/// One second of a 1 kHz tone at `rate`
+fn tone(rate: u32) -> Vec<i16> {
+    (0..rate)
+        .map(|i| {
+            let t = i as f64 / rate as f64;
+            (10000.0 * (2.0 * std::f64::consts::PI * 1000.0 * t).sin()) as i16
+        })
+        .collect()
+}
+
+/// Criterion benchmark for converting microphone rates to 16 kHz
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resample_to_16k");
+
+    for rate in [44100u32, 48000] {
+        let audio = tone(rate);
+        group.throughput(Throughput::Elements(audio.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("one_shot", rate), &audio, |b, audio| {
+            b.iter(|| black_box(resample(black_box(audio), rate, 16000)));
+        });
+
+        // 10ms blocks, like audio callbacks
+        group.bench_with_input(BenchmarkId::new("streaming", rate), &audio, |b, audio| {
+            b.iter(|| {
+                let mut resampler = Resampler::new(rate, 16000);
+                let mut total = 0;
+                for block in audio.chunks(rate as usize / 100) {
+                    total += resampler.process(black_box(block)).len();
+                }
+                total += resampler.flush().len();
+                black_box(total)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,147 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::f32::consts::PI;
+use std::fs::File;
+use std::hint::black_box;
+use std::io::BufReader;
+use std::sync::Arc;
+use std::time::Duration;
+use voicsh::audio::vad::{MockClock, VadConfig, VadEvent, VoiceDetector, create_detector};
+use voicsh::audio::wav::WavAudioSource;
+use voicsh::config::VadBackend;
+use voicsh::defaults;
+use voicsh::models::download::is_vad_model_installed;
+
+/// 20ms frames, a typical audio callback size
+const FRAME: usize = 320;
+
+/// Five seconds of each noise fixture
+const NOISE_LEN: usize = 5 * 16000;
+
+fn load_speech_fixture() -> Vec<i16> {
+    let file =
+        File::open("tests/fixtures/quick_brown_fox.wav").expect("Failed to open test fixture");
+    WavAudioSource::from_reader(Box::new(BufReader::new(file)))
+        .expect("Failed to create WAV source")
+        .into_samples()
+}
+
+/// Deterministic white noise (xorshift)
+fn white_noise(len: usize, amplitude: f32, seed: u32) -> Vec<i16> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            ((state as f32 / u32::MAX as f32 * 2.0 - 1.0) * amplitude) as i16
+        })
+        .collect()
+}
+
+/// Fan / HVAC rumble: low-passed noise
+fn fan(len: usize) -> Vec<i16> {
+    let mut y = 0.0;
+    white_noise(len, 4000.0, 7)
+        .into_iter()
+        .map(|x| {
+            y = 0.97 * y + 0.03 * x as f32 * 8.0;
+            y as i16
+        })
+        .collect()
+}
+
+/// Typing: 8ms clicks of noise about six times per second
+fn typing(len: usize) -> Vec<i16> {
+    let clicks = white_noise(len, 12000.0, 11);
+    (0..len)
+        .map(|i| if i % 2700 < 128 { clicks[i] } else { 0 })
+        .collect()
+}
+
+/// Sustained synth chord (A major), harder for detectors than plain noise
+fn chord(len: usize) -> Vec<i16> {
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / 16000.0;
+            let sum: f32 = [220.0, 277.2, 329.6]
+                .iter()
+                .map(|f| (2.0 * PI * f * t).sin())
+                .sum();
+            (sum * 2500.0) as i16
+        })
+        .collect()
+}
+
+fn backends() -> Vec<VadBackend> {
+    let mut backends = vec![VadBackend::Rms, VadBackend::Spectral];
+    if is_vad_model_installed(defaults::DEFAULT_VAD_MODEL) {
+        backends.push(VadBackend::Neural);
+    } else {
+        eprintln!("Skipping neural: VAD model not installed");
+    }
+    backends
+}
+
+fn detector(backend: VadBackend) -> (Box<dyn VoiceDetector>, MockClock) {
+    let clock = MockClock::new();
+    let config = VadConfig {
+        backend,
+        ..Default::default()
+    };
+    (create_detector(config, Arc::new(clock.clone())), clock)
+}
+
+/// Fraction of frames flagged as speech
+fn speech_rate(backend: VadBackend, audio: &[i16]) -> f32 {
+    let (mut vad, clock) = detector(backend);
+    let mut flagged = 0;
+    let mut frames = 0;
+    for frame in audio.chunks(FRAME) {
+        let event = vad.process(frame, 16000);
+        clock.advance(Duration::from_millis(20));
+        frames += 1;
+        if matches!(event, VadEvent::SpeechStart | VadEvent::Speech) {
+            flagged += 1;
+        }
+    }
+    flagged as f32 / frames.max(1) as f32
+}
+
+/// Prints false-trigger rates on noise and hit rates on speech, then times each detector
+fn criterion_benchmark(c: &mut Criterion) {
+    let speech = load_speech_fixture();
+    let noises = [
+        ("white", white_noise(NOISE_LEN, 3000.0, 3)),
+        ("fan", fan(NOISE_LEN)),
+        ("typing", typing(NOISE_LEN)),
+        ("chord", chord(NOISE_LEN)),
+    ];
+    let backends = backends();
+
+    println!("\nFrames flagged as speech (noise: lower is better, speech: higher is better)");
+    print!("{:<10}", "backend");
+    for (name, _) in &noises {
+        print!("{name:>9}");
+    }
+    println!("{:>9}", "speech");
+    for &backend in &backends {
+        print!("{:<10}", backend.to_string());
+        for (_, audio) in &noises {
+            print!("{:>8.1}%", speech_rate(backend, audio) * 100.0);
+        }
+        println!("{:>8.1}%", speech_rate(backend, &speech) * 100.0);
+    }
+    println!();
+
+    let mut group = c.benchmark_group("vad_backends");
+    group.throughput(Throughput::Elements(speech.len() as u64));
+    for &backend in &backends {
+        group.bench_with_input(BenchmarkId::from_parameter(backend), &speech, |b, audio| {
+            b.iter(|| black_box(speech_rate(backend, black_box(audio))));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -297,7 +297,8 @@ export default class VoicshExtension extends Extension {
                 // Filtered transcription — visible in follow/debug log
                 break;
             case 'config_changed':
-                if (event.key === 'language' && event.value) this._language = event.value;
+                if ((event.key === 'language' || event.key === 'stt.language') && event.value)
+                    this._language = event.value;
                 if (event.key === 'model' && event.value) this._modelName = event.value;
                 this._updateUi();
                 break;
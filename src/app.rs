@@ -3,11 +3,17 @@
 //! Orchestrates the complete voice-to-text flow:
 //! record → transcribe → inject
 
-use crate::audio::capture::CpalAudioSource;
+use crate::audio::capture::CpalDevices;
+use crate::audio::decode;
+use crate::audio::hotplug::HotplugSource;
+use crate::audio::neural_vad;
+use crate::audio::paced::PacedSource;
+use crate::audio::raw::RawPcmSpec;
 use crate::audio::recorder::AudioSource;
 use crate::audio::vad::VadConfig;
-use crate::audio::wav::WavAudioSource;
-use crate::config::{Config, InjectionMethod, resolve_hallucination_filters};
+use crate::config::{
+    Config, InjectionMethod, SttBackend, VadBackend, resolve_hallucination_filters,
+};
 use crate::defaults;
 use crate::error::{Result, VoicshError};
 use crate::inject::injector::SystemCommandExecutor;
@@ -16,16 +22,21 @@ use crate::models::download::{
     download_model, find_any_installed_model, is_model_installed, model_path,
 };
 use crate::pipeline::adaptive_chunker::AdaptiveChunkerConfig;
+use crate::pipeline::archive::ArchiveSettings;
 use crate::pipeline::orchestrator::{Pipeline, PipelineConfig};
 use crate::pipeline::post_processor::build_post_processors;
-use crate::pipeline::sink::{CollectorSink, InjectorSink, StdoutSink};
+use crate::pipeline::sink::{CollectorSink, InjectorSink, StdoutSink, TextSink, TranscriptSink};
+use crate::replay::{ReplayInput, render_side_by_side, word_error_rate};
 use crate::stt::fan_out::FanOutTranscriber;
 use crate::stt::transcriber::Transcriber;
 use crate::stt::whisper::{WhisperConfig, WhisperTranscriber};
 use crate::sys::suppress_audio_warnings;
+use crate::transcript::TranscriptFormat;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 #[cfg(feature = "portal")]
 use crate::inject::portal::PortalSession;
@@ -74,7 +85,7 @@ struct PipelineRunConfig {
     post_speech_ms: Option<u32>,
 }
 
-/// Run pipe mode: read WAV from stdin → transcribe → write to stdout.
+/// Run pipe mode: read audio (WAV, FLAC, Ogg, MP3 or raw PCM) from stdin → transcribe → write to stdout.
 ///
 /// # Arguments
 /// * `config` - Base configuration (can be overridden by CLI args)
@@ -83,9 +94,12 @@ struct PipelineRunConfig {
 /// * `quiet` - Suppress status messages
 /// * `verbosity` - Verbosity level (0=default, 1=clean output, 2=full diagnostics)
 /// * `no_download` - Prevent automatic model download
+/// * `output_format` - Transcript format; `txt` streams lines as they are transcribed
+/// * `raw` - Read stdin as headerless PCM with this layout instead of detecting the format
 ///
 /// # Returns
 /// Ok(()) on success, or an error if any step fails
+#[allow(clippy::too_many_arguments)]
 pub async fn run_pipe_command(
     mut config: Config,
     model: Option<String>,
@@ -94,8 +108,81 @@ pub async fn run_pipe_command(
     verbosity: u8,
     no_download: bool,
     buffer_secs: u64,
+    output_format: TranscriptFormat,
+    raw: Option<RawPcmSpec>,
 ) -> Result<()> {
-    // Apply CLI overrides
+    let transcriber =
+        load_overridden_transcriber(&mut config, model, language, quiet, verbosity, no_download)
+            .await?;
+
+    let audio_source = decode::open_stdin(raw)?;
+
+    // Plain text streams to stdout; timed formats need the whole transcript first
+    let sink: Box<dyn TextSink> = match output_format {
+        TranscriptFormat::Txt => Box::new(StdoutSink),
+        format => Box::new(TranscriptSink::new(format)),
+    };
+    if let Some(transcript) = run_finite_pipeline(
+        &config,
+        audio_source,
+        transcriber,
+        sink,
+        verbosity,
+        buffer_secs,
+    )? {
+        print_transcript(&transcript);
+    }
+    Ok(())
+}
+
+/// Print a rendered transcript, ending it with exactly one newline unless it
+/// already ends with one (subtitle formats end with a blank line).
+pub fn print_transcript(transcript: &str) {
+    if transcript.ends_with('\n') {
+        print!("{}", transcript);
+    } else {
+        println!("{}", transcript);
+    }
+}
+
+/// Transcribe a finite audio source in-process (no daemon, no injection).
+///
+/// Returns the transcript rendered in `output_format`.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_transcribe_command(
+    mut config: Config,
+    audio_source: Box<dyn AudioSource>,
+    output_format: TranscriptFormat,
+    model: Option<String>,
+    language: Option<String>,
+    quiet: bool,
+    verbosity: u8,
+    no_download: bool,
+    buffer_secs: u64,
+) -> Result<Option<String>> {
+    let transcriber =
+        load_overridden_transcriber(&mut config, model, language, quiet, verbosity, no_download)
+            .await?;
+
+    run_finite_pipeline(
+        &config,
+        audio_source,
+        transcriber,
+        Box::new(TranscriptSink::new(output_format)),
+        verbosity,
+        buffer_secs,
+    )
+}
+
+/// Apply CLI model/language overrides to `config` and load the transcriber.
+async fn load_overridden_transcriber(
+    config: &mut Config,
+    model: Option<String>,
+    language: Option<String>,
+    quiet: bool,
+    verbosity: u8,
+    no_download: bool,
+) -> Result<Arc<dyn Transcriber>> {
     if let Some(m) = model {
         config.stt.model = m;
     }
@@ -103,26 +190,56 @@ pub async fn run_pipe_command(
         config.stt.language = l;
     }
 
-    // Load model
     if verbosity >= 1 {
-        eprintln!(
+        announce_transcriber(config);
+    }
+    prepare_vad_model(config, quiet, no_download).await;
+    create_transcriber(config, quiet, verbosity, no_download).await
+}
+
+/// Tell the user which model is being loaded, or which server transcribes.
+fn announce_transcriber(config: &Config) {
+    match config.stt.backend {
+        SttBackend::Local => eprintln!(
             "Loading model '{}'... ({})",
             config.stt.model,
             defaults::gpu_backend()
-        );
+        ),
+        SttBackend::Http => eprintln!("Transcribing via {}", config.stt.http.url),
+        SttBackend::Deepgram => eprintln!(
+            "Streaming audio to {} (leaves this machine)",
+            config.stt.deepgram.url
+        ),
+        SttBackend::Candle => eprintln!("Loading model '{}' (candle)...", config.stt.candle.repo),
     }
-    let transcriber: Arc<dyn Transcriber> =
-        create_transcriber(&config, quiet, verbosity, no_download).await?;
+}
 
-    // Read WAV from stdin
-    let audio_source: Box<dyn AudioSource> = Box::new(WavAudioSource::from_stdin()?);
+/// Download the neural VAD model when it is selected and missing.
+async fn prepare_vad_model(config: &Config, quiet: bool, no_download: bool) {
+    if config.audio.vad_backend == VadBackend::Neural {
+        neural_vad::prepare_model(&config.audio.vad_model, quiet, no_download).await;
+    }
+}
 
+/// Run a finite audio source (file or stdin) through the pipeline to the end.
+///
+/// Blocks until every chunk has been transcribed and returns the sink result.
+fn run_finite_pipeline(
+    config: &Config,
+    audio_source: Box<dyn AudioSource>,
+    transcriber: Arc<dyn Transcriber>,
+    sink: Box<dyn TextSink>,
+    verbosity: u8,
+    buffer_secs: u64,
+) -> Result<Option<String>> {
     let hallucination_filters =
         resolve_hallucination_filters(&config.transcription.hallucination_filters);
     let pipeline_config = PipelineConfig {
         vad: VadConfig {
             speech_threshold: config.audio.vad_threshold,
             silence_duration_ms: config.audio.silence_duration_ms,
+            backend: config.audio.vad_backend,
+            model: crate::models::vad_catalog::resolve_vad_model(&config.audio.vad_model),
             ..Default::default()
         },
         chunker: AdaptiveChunkerConfig::default(),
@@ -132,16 +249,16 @@ pub async fn run_pipe_command(
         sample_rate: defaults::SAMPLE_RATE,
         chunk_buffer: chunk_buffer_capacity(buffer_secs, 3),
         hallucination_filters,
+        language_tracking: config.stt.tracks_language(),
+        denoise: Arc::new(std::sync::RwLock::new(config.audio.denoise.clone())),
         ..Default::default()
     };
 
-    let sink = StdoutSink;
     let pipeline = Pipeline::new(pipeline_config);
-    let handle = pipeline.start(audio_source, transcriber, Box::new(sink))?;
+    let handle = pipeline.start(audio_source, transcriber, sink)?;
 
-    // Stop the pipeline (triggers channel cascade shutdown)
-    handle.stop();
-    Ok(())
+    // Wait for the source to drain (triggers channel cascade shutdown)
+    Ok(handle.wait())
 }
 
 /// CLI overrides for the record command.
@@ -223,14 +340,11 @@ pub async fn run_record_command(record: RecordConfig) -> Result<()> {
 
     // Load model ONCE before the loop (this is the slow part)
     if !quiet {
-        eprintln!(
-            "Loading model '{}'... ({})",
-            config.stt.model,
-            defaults::gpu_backend()
-        );
+        announce_transcriber(&config);
     }
     let transcriber: Arc<dyn Transcriber> =
         create_transcriber(&config, quiet, verbosity, no_download).await?;
+    prepare_vad_model(&config, quiet, no_download).await;
     if !quiet {
         eprintln!("Ready. Listening...");
     }
@@ -358,8 +472,10 @@ fn build_pipeline_config(config: &Config, run_config: &PipelineRunConfig) -> Pip
         resolve_hallucination_filters(&config.transcription.hallucination_filters);
     PipelineConfig {
         vad: VadConfig {
-            speech_threshold: config.audio.vad_threshold,
+            speech_threshold: config.audio.effective_vad_threshold(),
             silence_duration_ms: config.audio.silence_duration_ms,
+            backend: config.audio.vad_backend,
+            model: crate::models::vad_catalog::resolve_vad_model(&config.audio.vad_model),
             ..Default::default()
         },
         chunker: chunker_config_from_secs(
@@ -368,11 +484,15 @@ fn build_pipeline_config(config: &Config, run_config: &PipelineRunConfig) -> Pip
             run_config.post_speech_ms,
         ),
         verbosity: run_config.verbosity,
-        auto_level: true,
+        // A calibrated device keeps its measured threshold
+        auto_level: config.audio.calibrated_threshold().is_none(),
         quiet: run_config.quiet,
         sample_rate: defaults::SAMPLE_RATE,
         chunk_buffer: chunk_buffer_capacity(run_config.buffer_secs, run_config.chunk_secs),
         hallucination_filters,
+        language_tracking: config.stt.tracks_language(),
+        denoise: Arc::new(std::sync::RwLock::new(config.audio.denoise.clone())),
+        archive: ArchiveSettings::from_config(&config.debug),
         ..Default::default()
     }
 }
@@ -385,8 +505,10 @@ async fn run_continuous(
     make_sink: impl FnOnce(&Config) -> InjectorSink<SystemCommandExecutor>,
 ) -> Result<()> {
     let quiet = run_config.quiet;
-    let device_name = config.audio.device.as_deref();
-    let audio_source: Box<dyn AudioSource> = Box::new(CpalAudioSource::new(device_name)?);
+    let audio_source: Box<dyn AudioSource> = Box::new(HotplugSource::open(
+        CpalDevices,
+        config.audio.device_chain(),
+    )?);
     let pipeline_config = build_pipeline_config(config, &run_config);
 
     let sink = make_sink(config);
@@ -426,8 +548,10 @@ async fn run_single_session(
 ) -> Result<()> {
     let quiet = run_config.quiet;
     let verbosity = run_config.verbosity;
-    let device_name = config.audio.device.as_deref();
-    let audio_source: Box<dyn AudioSource> = Box::new(CpalAudioSource::new(device_name)?);
+    let audio_source: Box<dyn AudioSource> = Box::new(HotplugSource::open(
+        CpalDevices,
+        config.audio.device_chain(),
+    )?);
     let pipeline_config = build_pipeline_config(config, &run_config);
 
     let sink = CollectorSink::new();
@@ -472,6 +596,119 @@ async fn run_single_session(
     Ok(())
 }
 
+/// CLI overrides for the replay command.
+pub struct ReplayConfig {
+    pub config: Config,
+    /// Session directory, archived session id (or `latest`), or audio file.
+    pub target: String,
+    /// Playback speed relative to real time.
+    pub speed: f32,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub quiet: bool,
+    pub verbosity: u8,
+    pub no_download: bool,
+    pub buffer_secs: u64,
+    /// Target chunk duration in seconds for the adaptive chunker.
+    pub chunk_secs: u32,
+    /// Optional override for pre-speech buffer duration (ms).
+    pub pre_speech_ms: Option<u32>,
+    /// Optional override for post-speech padding duration (ms).
+    pub post_speech_ms: Option<u32>,
+}
+
+/// Run the replay command: play recorded audio through the full pipeline as
+/// if it came from the microphone, then compare with the original text.
+///
+/// Nothing is injected and the replay is not archived.
+pub async fn run_replay_command(replay: ReplayConfig) -> Result<()> {
+    let ReplayConfig {
+        mut config,
+        target,
+        speed,
+        model,
+        language,
+        quiet,
+        verbosity,
+        no_download,
+        buffer_secs,
+        chunk_secs,
+        pre_speech_ms,
+        post_speech_ms,
+    } = replay;
+
+    let input = ReplayInput::resolve(&target, &config.debug.archive_path())?;
+    let source = PacedSource::new(
+        decode::open_file(&input.audio, None)?,
+        defaults::SAMPLE_RATE,
+        speed,
+    )?;
+    let finished = source.finished_flag();
+
+    let transcriber =
+        load_overridden_transcriber(&mut config, model, language, quiet, verbosity, no_download)
+            .await?;
+    let model_name = transcriber.model_name().to_string();
+
+    let run_config = PipelineRunConfig {
+        quiet,
+        verbosity,
+        buffer_secs,
+        chunk_secs,
+        pre_speech_ms,
+        post_speech_ms,
+    };
+    let pipeline_config = PipelineConfig {
+        archive: None,
+        ..build_pipeline_config(&config, &run_config)
+    };
+
+    if !quiet {
+        eprintln!(
+            "Replaying {} with '{model_name}' at {speed}x...",
+            input.audio.display()
+        );
+    }
+    let handle = Pipeline::new(pipeline_config).start_with_post_processors(
+        Box::new(source),
+        transcriber,
+        Box::new(CollectorSink::new()),
+        build_post_processors(&config),
+    )?;
+    while !finished.load(Ordering::SeqCst) {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let text = tokio::task::spawn_blocking(move || handle.finish())
+        .await
+        .map_err(|e| VoicshError::Other(format!("Replay pipeline panicked: {e}")))?
+        .unwrap_or_default();
+
+    match input.original_text {
+        Some(original) => {
+            let original_title = match input.original_model {
+                Some(m) => format!("original ({m})"),
+                None => "original".to_string(),
+            };
+            print!(
+                "{}",
+                render_side_by_side(
+                    &original_title,
+                    &original,
+                    &format!("replay ({model_name})"),
+                    &text
+                )
+            );
+            println!();
+            println!(
+                "Word error rate against the original: {:.1}%",
+                word_error_rate(&original, &text) * 100.0
+            );
+        }
+        None => println!("{text}"),
+    }
+    Ok(())
+}
+
 /// Create the transcriber, handling model download and fan-out if needed.
 async fn create_transcriber(
     config: &Config,
@@ -479,6 +716,13 @@ async fn create_transcriber(
     verbosity: u8,
     no_download: bool,
 ) -> Result<Arc<dyn Transcriber>> {
+    match config.stt.backend {
+        SttBackend::Http => return crate::daemon::create_http_transcriber(config, quiet),
+        SttBackend::Deepgram => return crate::daemon::create_deepgram_transcriber(config),
+        SttBackend::Candle => return crate::daemon::create_candle_transcriber(config),
+        SttBackend::Local => {}
+    }
+
     let resolved_model = resolve_model_for_language(&config.stt.model, &config.stt.language, quiet);
 
     // Fan-out: run multilingual + English models in parallel
@@ -493,10 +737,13 @@ async fn create_transcriber(
         if verbosity >= 1 {
             eprintln!("Fan-out: {} + {}", ml.model_name(), en.model_name());
         }
-        return Ok(Arc::new(FanOutTranscriber::new(vec![
+        let fan_out = FanOutTranscriber::new(vec![
             Arc::new(ml) as Arc<dyn Transcriber>,
             Arc::new(en) as Arc<dyn Transcriber>,
-        ])));
+        ])
+        .with_strategy(config.stt.fan_out_strategy);
+        return crate::daemon::with_language_routing(config, Arc::new(fan_out), quiet, no_download)
+            .await;
     }
 
     // Warn if fan-out was requested but won't be used
@@ -509,7 +756,7 @@ async fn create_transcriber(
 
     let transcriber =
         load_single_model(&resolved_model, &config.stt.language, quiet, no_download).await?;
-    Ok(Arc::new(transcriber))
+    crate::daemon::with_language_routing(config, Arc::new(transcriber), quiet, no_download).await
 }
 
 /// Load a single Whisper model, downloading if needed.
@@ -0,0 +1,318 @@
+//! Microphone calibration.
+//!
+//! Measures the background noise floor and speech level of an input device
+//! and derives a VAD threshold between the two, so `voicsh calibrate` can
+//! store a per-device value instead of relying on the fixed default.
+
+use crate::audio::recorder::AudioSource;
+use crate::audio::vad::calculate_rms;
+use crate::error::{Result, VoicshError};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Analysis frame: 20ms at 16kHz.
+const FRAME: usize = 320;
+
+/// Threshold bounds, the same range auto-leveling uses.
+const MIN_THRESHOLD: f32 = 0.002;
+const MAX_THRESHOLD: f32 = 0.2;
+
+/// The threshold stays at least this far above the noise floor.
+const NOISE_MARGIN: f32 = 1.5;
+
+/// Samples at or above this magnitude count as clipped (about -0.02 dBFS).
+const CLIP_LEVEL: i32 = i16::MAX as i32 - 64;
+
+/// Warn when more than this fraction of speech samples clip.
+const CLIP_WARN_FRACTION: f32 = 0.001;
+
+/// Speech below this level is too quiet for reliable detection.
+const QUIET_SPEECH_LEVEL: f32 = 0.01;
+
+/// Below this level the input is digital silence (muted or disconnected).
+const MUTED_LEVEL: f32 = 0.0001;
+
+/// Speech should be at least this many times louder than the noise floor.
+const MIN_CONTRAST: f32 = 3.0;
+
+/// Sentence the user reads aloud during calibration.
+pub const SAMPLE_SENTENCE: &str = "The quick brown fox jumps over the lazy dog.";
+
+/// Problems found while calibrating.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalibrationWarning {
+    /// Both recordings are digital silence.
+    Muted,
+    /// Part of the speech sample hit full scale.
+    Clipping { fraction: f32 },
+    /// Speech is barely above digital silence.
+    TooQuiet { speech_level: f32 },
+    /// Speech is not clearly louder than the background.
+    LowContrast { ratio: f32 },
+}
+
+impl fmt::Display for CalibrationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Muted => write!(
+                f,
+                "No signal from the microphone. Check that it is connected, selected and not muted."
+            ),
+            Self::Clipping { fraction } => write!(
+                f,
+                "Input is clipping ({:.1}% of samples at full scale). Lower the input gain.",
+                fraction * 100.0
+            ),
+            Self::TooQuiet { speech_level } => write!(
+                f,
+                "Speech is very quiet (level {speech_level:.4}). Raise the input gain or move closer to the microphone."
+            ),
+            Self::LowContrast { ratio } => write!(
+                f,
+                "Speech is only {ratio:.1}x louder than the background noise. Detection will be unreliable; reduce the noise or enable audio.denoise."
+            ),
+        }
+    }
+}
+
+/// Result of analysing a silence and a speech recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    /// RMS level that background noise stays below (90th percentile of frames).
+    pub noise_floor: f32,
+    /// Typical RMS level while speaking (75th percentile of frames).
+    pub speech_level: f32,
+    /// Highest absolute sample in the speech recording (0.0 to 1.0).
+    pub peak: f32,
+    /// Suggested VAD threshold.
+    pub threshold: f32,
+    pub warnings: Vec<CalibrationWarning>,
+}
+
+/// Derive a VAD threshold from a recording of silence and one of speech.
+///
+/// The threshold sits halfway between noise floor and speech level on a
+/// logarithmic scale, but never closer than [`NOISE_MARGIN`] to the noise.
+pub fn analyze(silence: &[i16], speech: &[i16]) -> Calibration {
+    let noise_floor = percentile(&frame_levels(silence), 0.9);
+    let speech_level = percentile(&frame_levels(speech), 0.75);
+    let peak = speech
+        .iter()
+        .map(|&s| (s as i32).unsigned_abs())
+        .max()
+        .unwrap_or(0) as f32
+        / i16::MAX as f32;
+
+    let threshold = (noise_floor * speech_level)
+        .sqrt()
+        .max(noise_floor * NOISE_MARGIN)
+        .clamp(MIN_THRESHOLD, MAX_THRESHOLD);
+    // Four decimals keep the value readable in config.toml
+    let threshold = (threshold * 10_000.0).round() / 10_000.0;
+
+    let mut warnings = Vec::new();
+    if speech_level < MUTED_LEVEL && noise_floor < MUTED_LEVEL {
+        warnings.push(CalibrationWarning::Muted);
+    } else {
+        let clipped = speech
+            .iter()
+            .filter(|&&s| (s as i32).abs() >= CLIP_LEVEL)
+            .count();
+        let fraction = clipped as f32 / speech.len().max(1) as f32;
+        if fraction > CLIP_WARN_FRACTION {
+            warnings.push(CalibrationWarning::Clipping { fraction });
+        }
+        if speech_level < QUIET_SPEECH_LEVEL {
+            warnings.push(CalibrationWarning::TooQuiet { speech_level });
+        }
+        let ratio = speech_level / noise_floor.max(MUTED_LEVEL);
+        if ratio < MIN_CONTRAST {
+            warnings.push(CalibrationWarning::LowContrast { ratio });
+        }
+    }
+
+    Calibration {
+        noise_floor,
+        speech_level,
+        peak,
+        threshold,
+        warnings,
+    }
+}
+
+/// Record `samples` samples from a started live source.
+///
+/// Polls the source every 20ms. Fails if it delivers nothing within
+/// `timeout`; a short recording is returned as-is.
+pub fn record(source: &mut dyn AudioSource, samples: usize, timeout: Duration) -> Result<Vec<i16>> {
+    let deadline = Instant::now() + timeout;
+    let mut recorded = Vec::with_capacity(samples);
+    while recorded.len() < samples && Instant::now() < deadline {
+        recorded.extend(source.read_samples()?);
+        if recorded.len() < samples {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+    if recorded.is_empty() {
+        return Err(VoicshError::AudioCapture {
+            message: "No audio received from the input device".to_string(),
+        });
+    }
+    recorded.truncate(samples);
+    Ok(recorded)
+}
+
+/// RMS level of each full analysis frame.
+fn frame_levels(samples: &[i16]) -> Vec<f32> {
+    samples.chunks_exact(FRAME).map(calculate_rms).collect()
+}
+
+/// Value below which `p` (0.0 to 1.0) of the levels fall.
+fn percentile(levels: &[f32], p: f32) -> f32 {
+    if levels.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = levels.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let idx = ((sorted.len() - 1) as f32 * p).round() as usize;
+    sorted[idx]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: usize = 16000;
+
+    /// Deterministic noise at roughly the given RMS level.
+    fn noise(len: usize, level: f32) -> Vec<i16> {
+        let mut state: u32 = 0x9e37_79b9;
+        let amplitude = level * 3f32.sqrt() * i16::MAX as f32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                ((state as f32 / u32::MAX as f32 * 2.0 - 1.0) * amplitude) as i16
+            })
+            .collect()
+    }
+
+    /// 200 Hz tone at roughly the given RMS level, with a pause every other 250ms.
+    fn spoken(len: usize, level: f32) -> Vec<i16> {
+        let amplitude = level * 2f32.sqrt() * i16::MAX as f32;
+        (0..len)
+            .map(|i| {
+                if (i / 4000) % 2 == 1 {
+                    return 0;
+                }
+                let t = i as f32 / RATE as f32;
+                ((2.0 * std::f32::consts::PI * 200.0 * t).sin() * amplitude) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_threshold_between_noise_and_speech() {
+        let cal = analyze(&noise(3 * RATE, 0.002), &spoken(5 * RATE, 0.1));
+        assert!((cal.noise_floor - 0.002).abs() < 0.0005, "{cal:?}");
+        assert!((cal.speech_level - 0.1).abs() < 0.01, "{cal:?}");
+        assert!(
+            cal.threshold > cal.noise_floor * NOISE_MARGIN && cal.threshold < cal.speech_level,
+            "{cal:?}"
+        );
+        assert!(cal.warnings.is_empty(), "{:?}", cal.warnings);
+    }
+
+    #[test]
+    fn test_threshold_is_rounded_and_clamped() {
+        let cal = analyze(&noise(RATE, 0.3), &spoken(RATE, 0.6));
+        assert_eq!(cal.threshold, MAX_THRESHOLD);
+        let cal = analyze(&vec![0; RATE], &spoken(RATE, 0.1));
+        assert_eq!(cal.threshold, MIN_THRESHOLD);
+        let cal = analyze(&noise(RATE, 0.003), &spoken(RATE, 0.05));
+        assert_eq!(cal.threshold, (cal.threshold * 10_000.0).round() / 10_000.0);
+    }
+
+    #[test]
+    fn test_warns_on_clipping() {
+        let mut speech = spoken(RATE, 0.1);
+        for s in speech.iter_mut().step_by(100) {
+            *s = i16::MAX;
+        }
+        let cal = analyze(&noise(RATE, 0.002), &speech);
+        assert!(
+            matches!(cal.warnings[..], [CalibrationWarning::Clipping { fraction }] if fraction > 0.005),
+            "{:?}",
+            cal.warnings
+        );
+        assert_eq!(cal.peak, 1.0);
+    }
+
+    #[test]
+    fn test_warns_when_too_quiet() {
+        let cal = analyze(&noise(RATE, 0.0005), &spoken(RATE, 0.004));
+        assert_eq!(
+            cal.warnings,
+            vec![CalibrationWarning::TooQuiet {
+                speech_level: cal.speech_level
+            }]
+        );
+    }
+
+    #[test]
+    fn test_warns_on_low_contrast() {
+        let cal = analyze(&noise(RATE, 0.02), &spoken(RATE, 0.03));
+        assert!(
+            cal.warnings
+                .iter()
+                .any(|w| matches!(w, CalibrationWarning::LowContrast { .. })),
+            "{:?}",
+            cal.warnings
+        );
+        assert!(cal.threshold >= cal.noise_floor * NOISE_MARGIN - 0.0001);
+    }
+
+    #[test]
+    fn test_muted_input_reports_only_muted() {
+        let cal = analyze(&vec![0; RATE], &vec![0; RATE]);
+        assert_eq!(cal.warnings, vec![CalibrationWarning::Muted]);
+    }
+
+    struct ChunkSource {
+        chunks: Vec<Vec<i16>>,
+    }
+
+    impl AudioSource for ChunkSource {
+        fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+        fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+        fn read_samples(&mut self) -> Result<Vec<i16>> {
+            Ok(if self.chunks.is_empty() {
+                Vec::new()
+            } else {
+                self.chunks.remove(0)
+            })
+        }
+    }
+
+    #[test]
+    fn test_record_collects_and_truncates() {
+        let mut source = ChunkSource {
+            chunks: vec![vec![1; 300], Vec::new(), vec![2; 300]],
+        };
+        let samples = record(&mut source, 500, Duration::from_secs(1)).unwrap();
+        assert_eq!(samples.len(), 500);
+        assert_eq!(samples[299..301], [1, 2]);
+    }
+
+    #[test]
+    fn test_record_fails_without_audio() {
+        let mut source = ChunkSource { chunks: Vec::new() };
+        let err = record(&mut source, 500, Duration::from_millis(50)).unwrap_err();
+        assert!(err.to_string().contains("No audio received"), "{err}");
+    }
+}
@@ -1,13 +1,19 @@
 //! Real audio capture using CPAL (Cross-Platform Audio Library).
 
+use crate::audio::hotplug::DeviceProvider;
 use crate::audio::recorder::AudioSource;
+use crate::audio::resample::Resampler;
 use crate::defaults;
 use crate::error::{Result, VoicshError};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 pub use crate::sys::suppress_audio_warnings;
 
+/// A started stream that delivers no callbacks for this long is treated as lost.
+const STALL_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Preferred device names for GNOME/PipeWire environments.
 const PREFERRED_DEVICES: &[&str] = &["pipewire", "pulse", "PulseAudio"];
 
@@ -140,6 +146,10 @@ pub struct CpalAudioSource {
     stream: Arc<Mutex<Option<SendableStream>>>,
     buffer: Arc<Mutex<Vec<i16>>>,
     callback_count: Arc<std::sync::atomic::AtomicU64>,
+    /// Set by the stream error callback (device removed, backend failure)
+    stream_error: Arc<Mutex<Option<String>>>,
+    /// Callback count at the last read, and when it last increased
+    last_progress: (u64, Instant),
     sample_rate: u32,
 }
 
@@ -194,10 +204,22 @@ impl CpalAudioSource {
             stream: Arc::new(Mutex::new(None)),
             buffer: Arc::new(Mutex::new(Vec::new())),
             callback_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            stream_error: Arc::new(Mutex::new(None)),
+            last_progress: (0, Instant::now()),
             sample_rate: defaults::SAMPLE_RATE,
         })
     }
 
+    /// Stream error callback: remember the error so `read_samples` can report it.
+    fn error_callback(&self) -> impl FnMut(cpal::StreamError) + Send + 'static {
+        let stream_error = Arc::clone(&self.stream_error);
+        move |err| {
+            if let Ok(mut slot) = stream_error.lock() {
+                slot.get_or_insert_with(|| err.to_string());
+            }
+        }
+    }
+
     /// Build the audio stream with the configured format.
     ///
     /// Tries in order:
@@ -216,10 +238,6 @@ impl CpalAudioSource {
             buffer_size: cpal::BufferSize::Default,
         };
 
-        let err_callback = |err| {
-            eprintln!("Audio stream error: {}", err);
-        };
-
         // Try i16/16kHz/mono — works with PipeWire/PulseAudio which convert transparently
         let buffer = Arc::clone(&self.buffer);
         let counter = Arc::clone(&self.callback_count);
@@ -231,7 +249,7 @@ impl CpalAudioSource {
                     buf.extend_from_slice(data);
                 }
             },
-            err_callback,
+            self.error_callback(),
             None,
         ) {
             return Ok(stream);
@@ -251,7 +269,7 @@ impl CpalAudioSource {
                     );
                 }
             },
-            err_callback,
+            self.error_callback(),
             None,
         ) {
             return Ok(stream);
@@ -288,12 +306,10 @@ impl CpalAudioSource {
             default_config.sample_format(),
         );
 
-        let err_callback = |err| {
-            eprintln!("Audio stream error: {}", err);
-        };
-
         let buffer = Arc::clone(&self.buffer);
         let counter = Arc::clone(&self.callback_count);
+        // Owned by the data callback, so filter state carries across callbacks
+        let mut resampler = Resampler::new(native_rate, target_rate);
 
         match default_config.sample_format() {
             SampleFormat::I16 => self
@@ -302,17 +318,13 @@ impl CpalAudioSource {
                     &stream_config,
                     move |data: &[i16], _: &cpal::InputCallbackInfo| {
                         counter.fetch_add(1, Ordering::Relaxed);
-                        let converted = convert_to_mono_16khz_i16(
-                            data,
-                            native_channels,
-                            native_rate,
-                            target_rate,
-                        );
+                        let converted =
+                            convert_to_mono_16khz_i16(data, native_channels, &mut resampler);
                         if let Ok(mut buf) = buffer.lock() {
                             buf.extend_from_slice(&converted);
                         }
                     },
-                    err_callback,
+                    self.error_callback(),
                     None,
                 )
                 .map_err(|e| VoicshError::AudioCapture {
@@ -328,17 +340,13 @@ impl CpalAudioSource {
                             .iter()
                             .map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
                             .collect();
-                        let converted = convert_to_mono_16khz_i16(
-                            &i16_data,
-                            native_channels,
-                            native_rate,
-                            target_rate,
-                        );
+                        let converted =
+                            convert_to_mono_16khz_i16(&i16_data, native_channels, &mut resampler);
                         if let Ok(mut buf) = buffer.lock() {
                             buf.extend_from_slice(&converted);
                         }
                     },
-                    err_callback,
+                    self.error_callback(),
                     None,
                 )
                 .map_err(|e| VoicshError::AudioCapture {
@@ -355,12 +363,33 @@ impl CpalAudioSource {
     }
 }
 
+/// Opens CPAL input devices by name for [`HotplugSource`](crate::audio::hotplug::HotplugSource).
+pub struct CpalDevices;
+
+impl DeviceProvider for CpalDevices {
+    fn available(&self) -> Result<Vec<String>> {
+        let host = cpal::default_host();
+        let devices = host
+            .input_devices()
+            .map_err(|e| VoicshError::AudioCapture {
+                message: format!("Failed to enumerate input devices: {}", e),
+            })?;
+        Ok(devices
+            .filter_map(|d| d.description().ok().map(|d| d.name().to_string()))
+            .collect())
+    }
+
+    fn open(&self, name: &str) -> Result<Box<dyn AudioSource>> {
+        let device = (name != defaults::DEFAULT_DEVICE_KEY).then_some(name);
+        Ok(Box::new(CpalAudioSource::new(device)?))
+    }
+}
+
 /// Mix multi-channel audio to mono and resample to the target rate.
 fn convert_to_mono_16khz_i16(
     samples: &[i16],
     channels: usize,
-    source_rate: u32,
-    target_rate: u32,
+    resampler: &mut Resampler,
 ) -> Vec<i16> {
     // Mix to mono by averaging channels
     let mono: Vec<i16> = if channels == 1 {
@@ -375,12 +404,7 @@ fn convert_to_mono_16khz_i16(
             .collect()
     };
 
-    // Resample if needed
-    if source_rate == target_rate {
-        mono
-    } else {
-        crate::audio::wav::resample(&mono, source_rate, target_rate)
-    }
+    resampler.process(&mono)
 }
 
 impl AudioSource for CpalAudioSource {
@@ -427,6 +451,7 @@ impl AudioSource for CpalAudioSource {
             message: format!("Failed to lock stream: {}", e),
         })?;
         *stream_guard = Some(SendableStream(final_stream));
+        self.last_progress = (self.callback_count.load(Ordering::Relaxed), Instant::now());
         Ok(())
     }
 
@@ -447,6 +472,25 @@ impl AudioSource for CpalAudioSource {
     }
 
     fn read_samples(&mut self) -> Result<Vec<i16>> {
+        use std::sync::atomic::Ordering;
+
+        if let Some(err) = self.stream_error.lock().ok().and_then(|e| e.clone()) {
+            return Err(VoicshError::AudioCapture {
+                message: format!("Audio stream error: {err}"),
+            });
+        }
+
+        // Unplugged devices often stop calling back without reporting an error
+        let started = self.stream.lock().is_ok_and(|s| s.is_some());
+        let count = self.callback_count.load(Ordering::Relaxed);
+        if count != self.last_progress.0 {
+            self.last_progress = (count, Instant::now());
+        } else if started && self.last_progress.1.elapsed() > STALL_TIMEOUT {
+            return Err(VoicshError::AudioCapture {
+                message: format!("No audio from the device for {}s", STALL_TIMEOUT.as_secs()),
+            });
+        }
+
         let mut buffer = self.buffer.lock().map_err(|e| VoicshError::AudioCapture {
             message: format!("Failed to lock audio buffer: {}", e),
         })?;
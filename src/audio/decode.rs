@@ -0,0 +1,508 @@
+//! Open encoded audio (files, stdin, IPC byte streams) as an [`AudioSource`].
+//!
+//! The container is detected from magic bytes; a format hint overrides
+//! detection. Headerless PCM has no magic and needs an explicit
+//! [`RawPcmSpec`] such as `s16le:48000:2`.
+
+use crate::audio::raw::{RawPcmAudioSource, RawPcmSpec};
+use crate::audio::recorder::AudioSource;
+use crate::audio::wav::WavAudioSource;
+#[cfg(any(feature = "audio-codecs", feature = "opus"))]
+use crate::audio::wav::to_mono_16k;
+use crate::error::{Result, VoicshError};
+use std::io::Read;
+use std::path::Path;
+
+/// Audio container formats accepted for file and stream transcription.
+pub const SUPPORTED_FORMATS: &[&str] = &[
+    "wav",
+    #[cfg(feature = "audio-codecs")]
+    "flac",
+    #[cfg(feature = "audio-codecs")]
+    "ogg",
+    #[cfg(feature = "opus")]
+    "opus",
+    #[cfg(feature = "audio-codecs")]
+    "mp3",
+];
+
+/// Bytes read up front for format detection (covers the first Ogg page header).
+const SNIFF_LEN: usize = 64;
+
+/// Detect the container format from the first bytes of a stream.
+pub fn detect_format(header: &[u8]) -> Option<&'static str> {
+    if header.starts_with(b"RIFF") && header.get(8..12) == Some(b"WAVE") {
+        Some("wav")
+    } else if header.starts_with(b"fLaC") {
+        Some("flac")
+    } else if header.starts_with(b"OggS") {
+        // The first page carries the codec identification header
+        if header.windows(8).any(|w| w == b"OpusHead") {
+            Some("opus")
+        } else {
+            Some("ogg")
+        }
+    } else if header.starts_with(b"ID3")
+        || (header.len() >= 2 && header[0] == 0xFF && header[1] & 0xE0 == 0xE0)
+    {
+        Some("mp3")
+    } else {
+        None
+    }
+}
+
+/// Decode audio from a reader into a finite 16kHz mono source.
+///
+/// `format` is an optional hint (e.g. `"flac"`, or a raw PCM spec like
+/// `"s16le:48000:2"`); `None` means auto-detect.
+pub fn open_reader(
+    mut reader: Box<dyn Read + Send>,
+    format: Option<&str>,
+) -> Result<Box<dyn AudioSource>> {
+    let hint = format.map(str::to_lowercase);
+    if let Some(spec) = hint.as_deref().and_then(|h| h.parse::<RawPcmSpec>().ok()) {
+        return Ok(Box::new(RawPcmAudioSource::new(reader, spec)));
+    }
+
+    let header = read_header(&mut reader)?;
+    let detected = detect_format(&header);
+    let reader: Box<dyn Read + Send> = Box::new(std::io::Cursor::new(header).chain(reader));
+
+    let format = match (hint.as_deref(), detected) {
+        // Ogg is a container; the codec inside decides the decoder
+        (Some("ogg"), Some("opus")) => "opus",
+        (Some(hint), _) => hint,
+        (None, Some(detected)) => detected,
+        (None, None) => {
+            return Err(VoicshError::AudioCapture {
+                message: format!(
+                    "Unrecognized audio format. Supported: {} \
+                     (use --raw FORMAT:RATE:CHANNELS for headerless PCM)",
+                    SUPPORTED_FORMATS.join(", ")
+                ),
+            });
+        }
+    };
+
+    match format {
+        "wav" => Ok(Box::new(WavAudioSource::from_reader(reader)?)),
+        "flac" | "ogg" | "mp3" => open_compressed(reader, format),
+        "opus" => open_opus(reader),
+        other => Err(VoicshError::AudioCapture {
+            message: format!(
+                "Unsupported audio format '{}'. Supported: {}",
+                other,
+                SUPPORTED_FORMATS.join(", ")
+            ),
+        }),
+    }
+}
+
+/// Read up to [`SNIFF_LEN`] bytes for format detection.
+fn read_header(reader: &mut Box<dyn Read + Send>) -> Result<Vec<u8>> {
+    let mut header = Vec::with_capacity(SNIFF_LEN);
+    reader
+        .by_ref()
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut header)
+        .map_err(|e| VoicshError::AudioCapture {
+            message: format!("Failed to read audio data: {}", e),
+        })?;
+    Ok(header)
+}
+
+/// Decode an in-memory audio buffer.
+pub fn open_bytes(bytes: Vec<u8>, format: Option<&str>) -> Result<Box<dyn AudioSource>> {
+    open_reader(Box::new(std::io::Cursor::new(bytes)), format)
+}
+
+/// Open and decode an audio file.
+pub fn open_file(path: &Path, format: Option<&str>) -> Result<Box<dyn AudioSource>> {
+    let file = std::fs::File::open(path).map_err(|e| VoicshError::AudioCapture {
+        message: format!("Failed to open audio file '{}': {}", path.display(), e),
+    })?;
+    open_reader(Box::new(std::io::BufReader::new(file)), format)
+}
+
+/// Open stdin: streamed as raw PCM when `raw` is set, otherwise auto-detected.
+pub fn open_stdin(raw: Option<RawPcmSpec>) -> Result<Box<dyn AudioSource>> {
+    let stdin = Box::new(std::io::BufReader::new(std::io::stdin()));
+    match raw {
+        Some(spec) => Ok(Box::new(RawPcmAudioSource::new(stdin, spec))),
+        None => open_reader(stdin, None),
+    }
+}
+
+#[cfg(any(feature = "audio-codecs", feature = "opus"))]
+fn read_all(mut reader: Box<dyn Read + Send>) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|e| VoicshError::AudioCapture {
+            message: format!("Failed to read audio data: {}", e),
+        })?;
+    Ok(bytes)
+}
+
+/// Decode FLAC, Ogg Vorbis or MP3 with symphonia.
+#[cfg(feature = "audio-codecs")]
+fn open_compressed(reader: Box<dyn Read + Send>, format: &str) -> Result<Box<dyn AudioSource>> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let decode_error = |e: SymphoniaError| VoicshError::AudioCapture {
+        message: format!("Failed to decode {} audio: {}", format, e),
+    };
+
+    let bytes = read_all(reader)?;
+    let stream = MediaSourceStream::new(Box::new(std::io::Cursor::new(bytes)), Default::default());
+    let mut hint = Hint::new();
+    hint.with_extension(format);
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(decode_error)?;
+    let mut container = probed.format;
+
+    let track = container
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| VoicshError::AudioCapture {
+            message: format!("No audio track found in {} data", format),
+        })?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(decode_error)?;
+
+    let mut samples = Vec::new();
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut channels = track.codec_params.channels.map_or(1, |c| c.count() as u16);
+    loop {
+        let packet = match container.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(decode_error(e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                sample_rate = spec.rate;
+                channels = spec.channels.count() as u16;
+                let mut buffer = SampleBuffer::<i16>::new(decoded.capacity() as u64, spec);
+                buffer.copy_interleaved_ref(decoded);
+                samples.extend_from_slice(buffer.samples());
+            }
+            // Corrupt frames are skipped, as players do
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(decode_error(e)),
+        }
+    }
+
+    if sample_rate == 0 {
+        return Err(VoicshError::AudioCapture {
+            message: format!("Unknown sample rate in {} data", format),
+        });
+    }
+    Ok(Box::new(DecodedAudioSource::new(to_mono_16k(
+        samples,
+        sample_rate,
+        channels,
+    ))))
+}
+
+#[cfg(not(feature = "audio-codecs"))]
+fn open_compressed(_reader: Box<dyn Read + Send>, format: &str) -> Result<Box<dyn AudioSource>> {
+    Err(VoicshError::AudioCapture {
+        message: format!(
+            "{} input requires voicsh built with the 'audio-codecs' feature",
+            format.to_uppercase()
+        ),
+    })
+}
+
+/// Decode Ogg/Opus with libopus, always at 48kHz mono.
+#[cfg(feature = "opus")]
+fn open_opus(reader: Box<dyn Read + Send>) -> Result<Box<dyn AudioSource>> {
+    const OPUS_RATE: u32 = 48000;
+    // Largest Opus frame: 120ms at 48kHz
+    const MAX_FRAME: usize = 5760;
+
+    let opus_error = |message: String| VoicshError::AudioCapture {
+        message: format!("Failed to decode opus audio: {}", message),
+    };
+
+    let bytes = read_all(reader)?;
+    let mut packets = ogg::PacketReader::new(std::io::Cursor::new(bytes));
+    let mut decoder = opus::Decoder::new(OPUS_RATE, opus::Channels::Mono)
+        .map_err(|e| opus_error(e.to_string()))?;
+
+    let mut samples = Vec::new();
+    let mut frame = vec![0i16; MAX_FRAME];
+    let mut pre_skip = 0usize;
+    let mut index = 0usize;
+    while let Some(packet) = packets
+        .read_packet()
+        .map_err(|e| opus_error(e.to_string()))?
+    {
+        match index {
+            // Identification header; pre-skip samples are encoder delay
+            0 => {
+                if !packet.data.starts_with(b"OpusHead") || packet.data.len() < 12 {
+                    return Err(opus_error("missing OpusHead header".to_string()));
+                }
+                pre_skip = u16::from_le_bytes([packet.data[10], packet.data[11]]) as usize;
+            }
+            // Comment header
+            1 => {}
+            _ => {
+                let decoded = decoder
+                    .decode(&packet.data, &mut frame, false)
+                    .map_err(|e| opus_error(e.to_string()))?;
+                samples.extend_from_slice(&frame[..decoded]);
+            }
+        }
+        index += 1;
+    }
+
+    samples.drain(..pre_skip.min(samples.len()));
+    Ok(Box::new(DecodedAudioSource::new(to_mono_16k(
+        samples, OPUS_RATE, 1,
+    ))))
+}
+
+#[cfg(not(feature = "opus"))]
+fn open_opus(_reader: Box<dyn Read + Send>) -> Result<Box<dyn AudioSource>> {
+    Err(VoicshError::AudioCapture {
+        message: "Opus input requires voicsh built with the 'opus' feature".to_string(),
+    })
+}
+
+/// Finite source over fully decoded 16kHz mono samples, read in 100ms chunks.
+#[cfg(any(feature = "audio-codecs", feature = "opus"))]
+struct DecodedAudioSource {
+    samples: Vec<i16>,
+    position: usize,
+}
+
+#[cfg(any(feature = "audio-codecs", feature = "opus"))]
+impl DecodedAudioSource {
+    const CHUNK_SIZE: usize = 1600;
+
+    fn new(samples: Vec<i16>) -> Self {
+        Self {
+            samples,
+            position: 0,
+        }
+    }
+}
+
+#[cfg(any(feature = "audio-codecs", feature = "opus"))]
+impl AudioSource for DecodedAudioSource {
+    fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn is_finite(&self) -> bool {
+        true
+    }
+
+    fn read_samples(&mut self) -> Result<Vec<i16>> {
+        let end = (self.position + Self::CHUNK_SIZE).min(self.samples.len());
+        let chunk = self.samples[self.position..end].to_vec();
+        self.position = end;
+        Ok(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav_bytes(samples: &[i16]) -> Vec<u8> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        for &s in samples {
+            writer.write_sample(s).unwrap();
+        }
+        writer.finalize().unwrap();
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn test_open_bytes_wav() {
+        let mut source = open_bytes(wav_bytes(&[1, 2, 3]), None).unwrap();
+        assert!(source.is_finite());
+        assert_eq!(source.read_samples().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_open_bytes_format_hint_case_insensitive() {
+        assert!(open_bytes(wav_bytes(&[0; 10]), Some("WAV")).is_ok());
+    }
+
+    #[test]
+    fn test_open_bytes_unsupported_format() {
+        let err = open_bytes(vec![], Some("mp4")).err().unwrap().to_string();
+        assert!(err.contains("Unsupported audio format 'mp4'"), "got: {err}");
+    }
+
+    /// Minimal 16kHz mono 16-bit FLAC stream with one verbatim frame.
+    #[cfg(feature = "audio-codecs")]
+    fn flac_bytes(samples: &[i16]) -> Vec<u8> {
+        fn crc8(data: &[u8]) -> u8 {
+            data.iter().fold(0u8, |mut crc, &byte| {
+                crc ^= byte;
+                for _ in 0..8 {
+                    crc = if crc & 0x80 != 0 {
+                        (crc << 1) ^ 0x07
+                    } else {
+                        crc << 1
+                    };
+                }
+                crc
+            })
+        }
+        fn crc16(data: &[u8]) -> u16 {
+            data.iter().fold(0u16, |mut crc, &byte| {
+                crc ^= (byte as u16) << 8;
+                for _ in 0..8 {
+                    crc = if crc & 0x8000 != 0 {
+                        (crc << 1) ^ 0x8005
+                    } else {
+                        crc << 1
+                    };
+                }
+                crc
+            })
+        }
+
+        let block = samples.len() as u16;
+        let mut out = b"fLaC".to_vec();
+        // Last metadata block, STREAMINFO, 34 bytes
+        out.extend_from_slice(&[0x80, 0, 0, 34]);
+        out.extend_from_slice(&block.to_be_bytes());
+        out.extend_from_slice(&block.to_be_bytes());
+        out.extend_from_slice(&[0; 6]);
+        let info: u64 = (16000u64 << 44) | (15 << 36) | samples.len() as u64;
+        out.extend_from_slice(&info.to_be_bytes());
+        out.extend_from_slice(&[0; 16]);
+
+        // Frame header: fixed blocking, 8-bit block size, 16kHz, mono, 16-bit
+        let mut frame = vec![0xFF, 0xF8, 0x65, 0x08, 0x00, (block - 1) as u8];
+        frame.push(crc8(&frame));
+        // Verbatim subframe
+        frame.push(0x02);
+        for s in samples {
+            frame.extend_from_slice(&s.to_be_bytes());
+        }
+        let crc = crc16(&frame);
+        frame.extend_from_slice(&crc.to_be_bytes());
+        out.extend_from_slice(&frame);
+        out
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format(&wav_bytes(&[0])), Some("wav"));
+        assert_eq!(detect_format(b"fLaC\x00\x00"), Some("flac"));
+        assert_eq!(detect_format(b"ID3\x04"), Some("mp3"));
+        assert_eq!(detect_format(&[0xFF, 0xFB, 0x90]), Some("mp3"));
+        assert_eq!(
+            detect_format(b"OggS\x00\x02....................\x01\x13OpusHead"),
+            Some("opus")
+        );
+        assert_eq!(
+            detect_format(b"OggS\x00\x02....................\x01\x1e\x01vorbis"),
+            Some("ogg")
+        );
+        assert_eq!(detect_format(b"hello"), None);
+        assert_eq!(detect_format(&[]), None);
+    }
+
+    #[test]
+    fn test_open_bytes_unrecognized_suggests_raw() {
+        let err = open_bytes(vec![1, 2, 3, 4], None)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Unrecognized audio format"), "got: {err}");
+        assert!(err.contains("--raw"), "got: {err}");
+    }
+
+    #[test]
+    fn test_open_bytes_raw_spec_hint() {
+        let bytes: Vec<u8> = [100i16, -100]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let mut source = open_bytes(bytes, Some("s16le:16000:1")).unwrap();
+        assert!(source.is_finite());
+        assert_eq!(source.read_samples().unwrap(), vec![100, -100]);
+        assert!(source.read_samples().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "audio-codecs")]
+    fn test_open_bytes_flac_detected() {
+        let samples: Vec<i16> = (0..200).map(|i| (i * 50 - 5000) as i16).collect();
+        let mut source = open_bytes(flac_bytes(&samples), None).unwrap();
+        assert!(source.is_finite());
+        assert_eq!(source.read_samples().unwrap(), samples);
+        assert!(source.read_samples().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature = "audio-codecs"))]
+    fn test_open_bytes_flac_requires_feature() {
+        let err = open_bytes(b"fLaC".to_vec(), None)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("audio-codecs"), "got: {err}");
+    }
+
+    #[test]
+    fn test_open_file_missing() {
+        let err = open_file(Path::new("/nonexistent/voicsh.wav"), None)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("/nonexistent/voicsh.wav"), "got: {err}");
+    }
+
+    #[test]
+    fn test_open_file_reads_wav() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("clip.wav");
+        std::fs::write(&path, wav_bytes(&[5; 100])).unwrap();
+        let mut source = open_file(&path, Some("wav")).unwrap();
+        assert_eq!(source.read_samples().unwrap().len(), 100);
+    }
+}
@@ -0,0 +1,92 @@
+//! Small radix-2 FFT shared by the spectral audio processors.
+
+use std::f32::consts::PI;
+
+/// Precomputed in-place FFT for a fixed power-of-two length.
+pub(crate) struct Fft {
+    len: usize,
+    /// Twiddle factors `e^{-2πik/N}` for `k < N/2`.
+    twiddles: Vec<(f32, f32)>,
+}
+
+impl Fft {
+    /// Creates an FFT of length `len`, which must be a power of two.
+    pub(crate) fn new(len: usize) -> Self {
+        debug_assert!(len.is_power_of_two(), "FFT length must be a power of two");
+        let twiddles = (0..len / 2)
+            .map(|k| {
+                let angle = -2.0 * PI * k as f32 / len as f32;
+                (angle.cos(), angle.sin())
+            })
+            .collect();
+        Self { len, twiddles }
+    }
+
+    /// In-place iterative radix-2 transform (unnormalized).
+    pub(crate) fn process(&self, re: &mut [f32], im: &mut [f32], inverse: bool) {
+        let n = self.len;
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                re.swap(i, j);
+                im.swap(i, j);
+            }
+        }
+
+        let mut len = 2;
+        while len <= n {
+            let stride = n / len;
+            for start in (0..n).step_by(len) {
+                for k in 0..len / 2 {
+                    let (wr, wi) = self.twiddles[k * stride];
+                    let wi = if inverse { -wi } else { wi };
+                    let (a, b) = (start + k, start + k + len / 2);
+                    let tr = re[b] * wr - im[b] * wi;
+                    let ti = re[b] * wi + im[b] * wr;
+                    re[b] = re[a] - tr;
+                    im[b] = im[a] - ti;
+                    re[a] += tr;
+                    im[a] += ti;
+                }
+            }
+            len <<= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tone_lands_in_its_bin() {
+        let fft = Fft::new(64);
+        let mut re: Vec<f32> = (0..64)
+            .map(|n| (2.0 * PI * 5.0 * n as f32 / 64.0).cos())
+            .collect();
+        let mut im = vec![0.0; 64];
+        fft.process(&mut re, &mut im, false);
+        assert!((re[5] - 32.0).abs() < 1e-3);
+        assert!((re[59] - 32.0).abs() < 1e-3);
+        assert!(re[3].abs() < 1e-3 && im[3].abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_inverse_round_trip() {
+        let fft = Fft::new(16);
+        let input: Vec<f32> = (0..16).map(|n| (n as f32 * 0.7).sin()).collect();
+        let mut re = input.clone();
+        let mut im = vec![0.0; 16];
+        fft.process(&mut re, &mut im, false);
+        fft.process(&mut re, &mut im, true);
+        for (out, x) in re.iter().zip(&input) {
+            assert!((out / 16.0 - x).abs() < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,448 @@
+//! Input device hot-plug recovery.
+//!
+//! [`HotplugSource`] wraps a live audio source and re-opens a device from a
+//! priority list when the stream fails or the device disappears. While it runs
+//! on a fallback device it periodically checks whether a more preferred one has
+//! come back and switches to it.
+
+use crate::audio::recorder::AudioSource;
+use crate::defaults;
+use crate::error::{Result, VoicshError};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// How often to retry opening a device after the current one was lost.
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often to look for a more preferred device while on a fallback.
+const RESCAN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Enumerates and opens input devices by name.
+///
+/// [`defaults::DEFAULT_DEVICE_KEY`] names the system default input and is
+/// always considered present.
+pub trait DeviceProvider: Send {
+    /// Names of the input devices currently present.
+    fn available(&self) -> Result<Vec<String>>;
+
+    /// Open (but do not start) the named device.
+    fn open(&self, name: &str) -> Result<Box<dyn AudioSource>>;
+}
+
+/// Why the input device changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceChangeReason {
+    /// The previous device failed or was unplugged.
+    Disconnected,
+    /// A more preferred device became available again.
+    PreferredAvailable,
+}
+
+impl fmt::Display for DeviceChangeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disconnected => write!(f, "disconnected"),
+            Self::PreferredAvailable => write!(f, "preferred device available"),
+        }
+    }
+}
+
+/// A switch from one input device to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceChange {
+    pub previous: String,
+    pub device: String,
+    pub reason: DeviceChangeReason,
+}
+
+type ChangeCallback = Box<dyn FnMut(&DeviceChange) + Send>;
+
+/// Audio source that survives device removal.
+///
+/// Errors from the active device are not passed on: the device is dropped and
+/// the next entry of the priority list that opens is used instead. While no
+/// device is available, reads return no samples and opening is retried every
+/// second, so a recording resumes when a device is plugged back in.
+pub struct HotplugSource<P: DeviceProvider> {
+    provider: P,
+    priority: Vec<String>,
+    /// Position in `priority` and the open device
+    active: Option<(usize, Box<dyn AudioSource>)>,
+    /// Device that delivered audio last, reported as `previous` on a change
+    last_device: String,
+    started: bool,
+    on_change: Option<ChangeCallback>,
+    retry_interval: Duration,
+    rescan_interval: Duration,
+    last_retry: Option<Instant>,
+    last_rescan: Instant,
+}
+
+impl<P: DeviceProvider> HotplugSource<P> {
+    /// Open the first device in `priority` that can be opened.
+    ///
+    /// Returns the error for the most preferred device when none opens.
+    pub fn open(provider: P, priority: Vec<String>) -> Result<Self> {
+        let mut first_error = None;
+        for (idx, name) in priority.iter().enumerate() {
+            match provider.open(name) {
+                Ok(source) => {
+                    if let Some(e) = &first_error {
+                        eprintln!(
+                            "voicsh: input device '{}' unavailable ({e}), using '{name}'",
+                            priority[0]
+                        );
+                    }
+                    return Ok(Self {
+                        last_device: name.clone(),
+                        active: Some((idx, source)),
+                        provider,
+                        priority,
+                        started: false,
+                        on_change: None,
+                        retry_interval: RETRY_INTERVAL,
+                        rescan_interval: RESCAN_INTERVAL,
+                        last_retry: None,
+                        last_rescan: Instant::now(),
+                    });
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        Err(
+            first_error.unwrap_or_else(|| VoicshError::AudioDeviceNotFound {
+                device: defaults::DEFAULT_DEVICE_KEY.to_string(),
+            }),
+        )
+    }
+
+    /// Call `callback` whenever the input switches to another device.
+    pub fn with_notifier(mut self, callback: impl FnMut(&DeviceChange) + Send + 'static) -> Self {
+        self.on_change = Some(Box::new(callback));
+        self
+    }
+
+    /// Override the reopen retry and preferred-device rescan intervals.
+    pub fn with_intervals(mut self, retry: Duration, rescan: Duration) -> Self {
+        self.retry_interval = retry;
+        self.rescan_interval = rescan;
+        self
+    }
+
+    /// Name of the device currently capturing, if any.
+    pub fn device(&self) -> Option<&str> {
+        self.active
+            .as_ref()
+            .map(|(idx, _)| self.priority[*idx].as_str())
+    }
+
+    /// Open and start `priority[idx]`.
+    fn open_started(&self, idx: usize) -> Result<Box<dyn AudioSource>> {
+        let mut source = self.provider.open(&self.priority[idx])?;
+        if self.started {
+            source.start()?;
+        }
+        Ok(source)
+    }
+
+    /// Make `priority[idx]` the active device and report the change.
+    fn activate(&mut self, idx: usize, source: Box<dyn AudioSource>, reason: DeviceChangeReason) {
+        if let Some((_, mut old)) = self.active.replace((idx, source)) {
+            // The old device may already be gone; nothing to report
+            old.stop().ok();
+        }
+        let device = self.priority[idx].clone();
+        if device == self.last_device {
+            return;
+        }
+        let change = DeviceChange {
+            previous: std::mem::replace(&mut self.last_device, device.clone()),
+            device,
+            reason,
+        };
+        eprintln!(
+            "voicsh: switched input from '{}' to '{}' ({})",
+            change.previous, change.device, change.reason
+        );
+        if let Some(callback) = &mut self.on_change {
+            callback(&change);
+        }
+    }
+
+    /// After losing the device, open the most preferred one that works.
+    fn reopen(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_retry
+            .is_some_and(|t| now.duration_since(t) < self.retry_interval)
+        {
+            return;
+        }
+        self.last_retry = Some(now);
+        for idx in 0..self.priority.len() {
+            if let Ok(source) = self.open_started(idx) {
+                self.last_retry = None;
+                self.last_rescan = now;
+                self.activate(idx, source, DeviceChangeReason::Disconnected);
+                return;
+            }
+        }
+    }
+
+    /// On a fallback device, switch to a more preferred one that has reappeared.
+    fn rescan(&mut self, current: usize) {
+        if current == 0 || self.last_rescan.elapsed() < self.rescan_interval {
+            return;
+        }
+        self.last_rescan = Instant::now();
+        let Ok(available) = self.provider.available() else {
+            return;
+        };
+        for idx in 0..current {
+            let name = &self.priority[idx];
+            if name != defaults::DEFAULT_DEVICE_KEY && !available.contains(name) {
+                continue;
+            }
+            if let Ok(source) = self.open_started(idx) {
+                self.activate(idx, source, DeviceChangeReason::PreferredAvailable);
+                return;
+            }
+        }
+    }
+}
+
+impl<P: DeviceProvider> AudioSource for HotplugSource<P> {
+    fn start(&mut self) -> Result<()> {
+        self.started = true;
+        self.last_rescan = Instant::now();
+        match &mut self.active {
+            Some((_, source)) => source.start(),
+            None => Ok(()),
+        }
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.started = false;
+        match &mut self.active {
+            Some((_, source)) => source.stop(),
+            None => Ok(()),
+        }
+    }
+
+    fn read_samples(&mut self) -> Result<Vec<i16>> {
+        if let Some((idx, source)) = &mut self.active {
+            let idx = *idx;
+            match source.read_samples() {
+                Ok(samples) => {
+                    self.rescan(idx);
+                    return Ok(samples);
+                }
+                Err(e) => {
+                    eprintln!("voicsh: input device '{}' failed: {e}", self.priority[idx]);
+                    source.stop().ok();
+                    self.active = None;
+                }
+            }
+        }
+        self.reopen();
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Devices that can be plugged, unplugged and broken from the test.
+    #[derive(Clone, Default)]
+    struct MockDevices {
+        present: Arc<Mutex<Vec<String>>>,
+        broken: Arc<Mutex<Vec<(String, Arc<AtomicBool>)>>>,
+    }
+
+    impl MockDevices {
+        fn with(names: &[&str]) -> Self {
+            let devices = Self::default();
+            for name in names {
+                devices.plug(name);
+            }
+            devices
+        }
+
+        fn plug(&self, name: &str) {
+            self.present.lock().unwrap().push(name.to_string());
+        }
+
+        /// Remove the device and make its open streams fail.
+        fn unplug(&self, name: &str) {
+            self.present.lock().unwrap().retain(|n| n != name);
+            for (n, failed) in self.broken.lock().unwrap().iter() {
+                if n == name {
+                    failed.store(true, Ordering::SeqCst);
+                }
+            }
+        }
+    }
+
+    impl DeviceProvider for MockDevices {
+        fn available(&self) -> Result<Vec<String>> {
+            Ok(self.present.lock().unwrap().clone())
+        }
+
+        fn open(&self, name: &str) -> Result<Box<dyn AudioSource>> {
+            if !self.present.lock().unwrap().iter().any(|n| n == name) {
+                return Err(VoicshError::AudioDeviceNotFound {
+                    device: name.to_string(),
+                });
+            }
+            let failed = Arc::new(AtomicBool::new(false));
+            self.broken
+                .lock()
+                .unwrap()
+                .push((name.to_string(), failed.clone()));
+            Ok(Box::new(MockSource {
+                tag: name.len() as i16,
+                failed,
+            }))
+        }
+    }
+
+    /// Delivers samples equal to the device name length until it fails.
+    struct MockSource {
+        tag: i16,
+        failed: Arc<AtomicBool>,
+    }
+
+    impl AudioSource for MockSource {
+        fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+        fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+        fn read_samples(&mut self) -> Result<Vec<i16>> {
+            if self.failed.load(Ordering::SeqCst) {
+                return Err(VoicshError::AudioCapture {
+                    message: "stream error".to_string(),
+                });
+            }
+            Ok(vec![self.tag; 4])
+        }
+    }
+
+    fn priority(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn source(devices: &MockDevices, names: &[&str]) -> HotplugSource<MockDevices> {
+        let mut source = HotplugSource::open(devices.clone(), priority(names))
+            .unwrap()
+            .with_intervals(Duration::ZERO, Duration::ZERO);
+        source.start().unwrap();
+        source
+    }
+
+    #[test]
+    fn test_open_falls_back_to_next_device() {
+        let devices = MockDevices::with(&["default"]);
+        let source = source(&devices, &["usb-headset", "default"]);
+        assert_eq!(source.device(), Some("default"));
+    }
+
+    #[test]
+    fn test_open_reports_most_preferred_error() {
+        let devices = MockDevices::default();
+        let err = HotplugSource::open(devices, priority(&["usb-headset", "default"]))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("usb-headset"), "{err}");
+    }
+
+    #[test]
+    fn test_recovers_on_next_device_after_unplug() {
+        let devices = MockDevices::with(&["usb-headset", "default"]);
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let seen = changes.clone();
+        let mut source =
+            source(&devices, &["usb-headset", "default"]).with_notifier(move |change| {
+                seen.lock().unwrap().push(change.clone());
+            });
+        assert_eq!(source.read_samples().unwrap(), vec![11; 4]);
+
+        devices.unplug("usb-headset");
+        assert!(source.read_samples().unwrap().is_empty());
+        assert_eq!(source.device(), Some("default"));
+        assert_eq!(source.read_samples().unwrap(), vec![7; 4]);
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![DeviceChange {
+                previous: "usb-headset".to_string(),
+                device: "default".to_string(),
+                reason: DeviceChangeReason::Disconnected,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_switches_back_when_preferred_returns() {
+        let devices = MockDevices::with(&["usb-headset", "default"]);
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let seen = changes.clone();
+        let mut source =
+            source(&devices, &["usb-headset", "default"]).with_notifier(move |change| {
+                seen.lock().unwrap().push(change.reason);
+            });
+
+        devices.unplug("usb-headset");
+        source.read_samples().unwrap();
+        source.read_samples().unwrap();
+        assert_eq!(source.device(), Some("default"));
+
+        devices.plug("usb-headset");
+        source.read_samples().unwrap();
+        assert_eq!(source.device(), Some("usb-headset"));
+        assert_eq!(source.read_samples().unwrap(), vec![11; 4]);
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![
+                DeviceChangeReason::Disconnected,
+                DeviceChangeReason::PreferredAvailable
+            ]
+        );
+    }
+
+    #[test]
+    fn test_waits_for_a_device_when_none_is_left() {
+        let devices = MockDevices::with(&["usb-headset"]);
+        let mut source = source(&devices, &["usb-headset"]);
+
+        devices.unplug("usb-headset");
+        for _ in 0..3 {
+            assert!(source.read_samples().unwrap().is_empty());
+        }
+        assert_eq!(source.device(), None);
+
+        devices.plug("usb-headset");
+        source.read_samples().unwrap();
+        assert_eq!(source.read_samples().unwrap(), vec![11; 4]);
+    }
+
+    #[test]
+    fn test_retry_is_rate_limited() {
+        let devices = MockDevices::with(&["usb-headset"]);
+        let mut source = HotplugSource::open(devices.clone(), priority(&["usb-headset"]))
+            .unwrap()
+            .with_intervals(Duration::from_secs(60), Duration::ZERO);
+        source.start().unwrap();
+
+        devices.unplug("usb-headset");
+        source.read_samples().unwrap(); // fails, first retry finds nothing
+        devices.plug("usb-headset");
+        source.read_samples().unwrap();
+        assert_eq!(source.device(), None, "retry should wait for the interval");
+    }
+}
@@ -1,7 +1,17 @@
 //! Audio capture and voice activity detection.
 
+pub mod calibrate;
 #[cfg(feature = "cpal-audio")]
 pub mod capture;
+pub mod decode;
+pub mod denoise;
+mod fft;
+pub mod hotplug;
+pub mod neural_vad;
+pub mod paced;
+pub mod raw;
 pub mod recorder;
+pub mod resample;
+pub mod spectral_vad;
 pub mod vad;
 pub mod wav;
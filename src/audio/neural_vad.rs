@@ -0,0 +1,431 @@
+//! Neural voice activity detection.
+//!
+//! Runs a Silero VAD model through whisper.cpp. The model scores 32ms windows
+//! of 16kHz audio with a speech probability. whisper.cpp resets the model's
+//! LSTM state on every call, so each update re-scores the last half second of
+//! audio and keeps the probability of the newest window. Speech starts at
+//! [`SPEECH_PROBABILITY`] and ends below [`SILENCE_PROBABILITY`], and the RMS
+//! threshold still gates very quiet frames (distant voices, TV in another room).
+
+use crate::audio::vad::{
+    Clock, SpeechTracker, SystemClock, VadConfig, VadResult, VadState, VoiceDetector, calculate_rms,
+};
+use crate::error::{Result, VoicshError};
+use std::path::Path;
+
+/// Samples per model window (32ms at 16kHz).
+pub const WINDOW_LEN: usize = 512;
+
+/// Windows re-scored on each update so the model has context.
+const CONTEXT_WINDOWS: usize = 16;
+
+/// Probability at which speech starts.
+pub const SPEECH_PROBABILITY: f32 = 0.5;
+
+/// Probability below which speech ends (hysteresis).
+pub const SILENCE_PROBABILITY: f32 = 0.35;
+
+/// A model that scores 16kHz audio with per-window speech probabilities.
+pub trait SpeechModel: Send {
+    /// Returns one probability per [`WINDOW_LEN`] window of `samples`.
+    fn probabilities(&mut self, samples: &[f32]) -> Result<Vec<f32>>;
+}
+
+/// Silero VAD model loaded by whisper.cpp.
+#[cfg(feature = "whisper")]
+pub struct SileroModel {
+    ctx: whisper_rs::WhisperVadContext,
+}
+
+// SAFETY: The whisper.cpp VAD context has no thread affinity. It is owned by a
+// single detector, which is moved into the VAD station thread and only used
+// there through `&mut self`.
+#[cfg(feature = "whisper")]
+unsafe impl Send for SileroModel {}
+
+#[cfg(feature = "whisper")]
+impl SileroModel {
+    /// Loads a ggml Silero model from `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        crate::stt::whisper::suppress_whisper_logging();
+
+        if !path.exists() {
+            return Err(VoicshError::TranscriptionModelNotFound {
+                path: path.display().to_string(),
+            });
+        }
+        let mut params = whisper_rs::WhisperVadContextParams::new();
+        params.set_n_threads(1);
+        params.set_use_gpu(false);
+        let ctx =
+            whisper_rs::WhisperVadContext::new(&path.to_string_lossy(), params).map_err(|e| {
+                VoicshError::Inference {
+                    message: format!("Failed to load VAD model {}: {e}", path.display()),
+                }
+            })?;
+        Ok(Self { ctx })
+    }
+}
+
+#[cfg(feature = "whisper")]
+impl SpeechModel for SileroModel {
+    fn probabilities(&mut self, samples: &[f32]) -> Result<Vec<f32>> {
+        self.ctx
+            .detect_speech(samples)
+            .map_err(|e| VoicshError::Inference {
+                message: format!("VAD inference failed: {e}"),
+            })?;
+        Ok(self.ctx.probabilities().to_vec())
+    }
+}
+
+/// Silero VAD model (stub when the whisper feature is disabled).
+#[cfg(not(feature = "whisper"))]
+pub struct SileroModel {
+    _private: (),
+}
+
+#[cfg(not(feature = "whisper"))]
+impl SileroModel {
+    /// Always fails: the neural VAD runs on whisper.cpp.
+    pub fn load(_path: &Path) -> Result<Self> {
+        Err(VoicshError::Inference {
+            message: "Neural VAD requires the 'whisper' feature".to_string(),
+        })
+    }
+}
+
+#[cfg(not(feature = "whisper"))]
+impl SpeechModel for SileroModel {
+    fn probabilities(&mut self, _samples: &[f32]) -> Result<Vec<f32>> {
+        Err(VoicshError::Inference {
+            message: "Neural VAD requires the 'whisper' feature".to_string(),
+        })
+    }
+}
+
+/// Loads the catalog model `name` from the models directory.
+pub fn load_model(name: &str) -> Result<SileroModel> {
+    #[cfg(feature = "model-download")]
+    {
+        SileroModel::load(&crate::models::download::vad_model_path(name))
+    }
+    #[cfg(not(feature = "model-download"))]
+    {
+        Err(VoicshError::Inference {
+            message: format!("VAD model '{name}' requires the 'model-download' feature"),
+        })
+    }
+}
+
+/// Voice activity detector driven by a neural speech probability.
+pub struct NeuralVad<C: Clock = SystemClock> {
+    config: VadConfig,
+    tracker: SpeechTracker<C>,
+    model: Box<dyn SpeechModel>,
+    /// The most recent audio, normalized, at most `CONTEXT_WINDOWS` windows.
+    context: Vec<f32>,
+    /// Samples received since the model last ran.
+    pending: usize,
+    probability: f32,
+    speaking: bool,
+    /// Whether an inference error has already been reported.
+    error_reported: bool,
+}
+
+impl<C: Clock> NeuralVad<C> {
+    /// Creates a neural VAD around a loaded model.
+    pub fn with_model(config: VadConfig, model: Box<dyn SpeechModel>, clock: C) -> Self {
+        Self {
+            tracker: SpeechTracker::new(&config, clock),
+            config,
+            model,
+            context: Vec::with_capacity(CONTEXT_WINDOWS * WINDOW_LEN),
+            pending: 0,
+            probability: 0.0,
+            speaking: false,
+            error_reported: false,
+        }
+    }
+
+    /// Speech probability of the newest scored window.
+    pub fn probability(&self) -> f32 {
+        self.probability
+    }
+
+    /// Adds samples to the context and re-scores once a new window is complete.
+    fn update_probability(&mut self, samples: &[i16]) {
+        self.context
+            .extend(samples.iter().map(|&s| s as f32 / i16::MAX as f32));
+        let max = CONTEXT_WINDOWS * WINDOW_LEN;
+        if self.context.len() > max {
+            self.context.drain(..self.context.len() - max);
+        }
+
+        self.pending += samples.len();
+        let usable = self.context.len() / WINDOW_LEN * WINDOW_LEN;
+        if self.pending < WINDOW_LEN || usable == 0 {
+            return;
+        }
+        self.pending = 0;
+
+        match self
+            .model
+            .probabilities(&self.context[self.context.len() - usable..])
+        {
+            Ok(probabilities) => {
+                if let Some(&p) = probabilities.last() {
+                    self.probability = p;
+                }
+            }
+            Err(e) => {
+                if !self.error_reported {
+                    self.error_reported = true;
+                    eprintln!("voicsh: {e}");
+                }
+            }
+        }
+    }
+}
+
+impl<C: Clock> VoiceDetector for NeuralVad<C> {
+    fn process_with_info(&mut self, samples: &[i16], _sample_rate: u32) -> VadResult {
+        let rms = calculate_rms(samples);
+        self.update_probability(samples);
+
+        let start_at = if self.speaking {
+            SILENCE_PROBABILITY
+        } else {
+            SPEECH_PROBABILITY
+        };
+        self.speaking = self.probability >= start_at;
+        let is_speech = self.speaking && rms > self.config.speech_threshold;
+        let (event, silence_ms) = self.tracker.update(is_speech);
+
+        VadResult {
+            event,
+            level: rms,
+            threshold: self.config.speech_threshold,
+            silence_ms,
+            silence_duration_ms: self.config.silence_duration_ms,
+        }
+    }
+
+    fn state(&self) -> VadState {
+        self.tracker.state()
+    }
+
+    fn reset(&mut self) {
+        self.tracker.reset();
+        self.context.clear();
+        self.pending = 0;
+        self.probability = 0.0;
+        self.speaking = false;
+    }
+
+    fn set_threshold(&mut self, threshold: f32) {
+        self.config.speech_threshold = threshold;
+    }
+}
+
+/// Downloads the configured VAD model if it is missing and its checksum is
+/// pinned.
+///
+/// Failures only warn: the VAD station falls back to the spectral detector
+/// when the model cannot be loaded.
+#[cfg(feature = "model-download")]
+pub async fn prepare_model(name: &str, quiet: bool, no_download: bool) {
+    if crate::models::download::is_vad_model_installed(name) {
+        return;
+    }
+    if no_download {
+        eprintln!("voicsh: VAD model '{name}' not installed (--no-download), using spectral VAD.");
+        return;
+    }
+    if let Err(e) = crate::models::download::download_vad_model(name, !quiet).await {
+        eprintln!("voicsh: {e}. Using spectral VAD.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::recorder::FramePhase;
+    use crate::audio::vad::{MockClock, VadEvent};
+    use crate::config::VadBackend;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Scores a window by its peak amplitude, recording the input lengths.
+    struct PeakModel {
+        calls: Arc<Mutex<Vec<usize>>>,
+    }
+
+    impl SpeechModel for PeakModel {
+        fn probabilities(&mut self, samples: &[f32]) -> Result<Vec<f32>> {
+            self.calls.lock().unwrap().push(samples.len());
+            Ok(samples
+                .chunks(WINDOW_LEN)
+                .map(|w| w.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+                .collect())
+        }
+    }
+
+    struct FailingModel;
+
+    impl SpeechModel for FailingModel {
+        fn probabilities(&mut self, _samples: &[f32]) -> Result<Vec<f32>> {
+            Err(VoicshError::Inference {
+                message: "boom".to_string(),
+            })
+        }
+    }
+
+    fn config() -> VadConfig {
+        VadConfig {
+            speech_threshold: 0.01,
+            silence_duration_ms: 100,
+            min_speech_ms: 50,
+            backend: VadBackend::Neural,
+            ..Default::default()
+        }
+    }
+
+    fn vad_with_calls() -> (NeuralVad<MockClock>, MockClock, Arc<Mutex<Vec<usize>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let clock = MockClock::new();
+        let model = PeakModel {
+            calls: calls.clone(),
+        };
+        let vad = NeuralVad::with_model(config(), Box::new(model), clock.clone());
+        (vad, clock, calls)
+    }
+
+    /// Constant-amplitude frame: the peak model scores it `amplitude / i16::MAX`.
+    fn level(amplitude: i16) -> Vec<i16> {
+        (0..WINDOW_LEN)
+            .map(|i| if i % 2 == 0 { amplitude } else { -amplitude })
+            .collect()
+    }
+
+    fn run(
+        vad: &mut NeuralVad<MockClock>,
+        clock: &MockClock,
+        phases: &[FramePhase],
+    ) -> Vec<VadEvent> {
+        let mut events = Vec::new();
+        for phase in phases {
+            for _ in 0..phase.count {
+                events.push(vad.process(&phase.samples, 16000));
+                clock.advance(Duration::from_millis(32));
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn test_scores_once_per_complete_window() {
+        let (mut vad, _clock, calls) = vad_with_calls();
+        vad.process(&[0; 200], 16000);
+        vad.process(&[0; 200], 16000);
+        assert!(calls.lock().unwrap().is_empty());
+        vad.process(&[0; 200], 16000);
+        // 600 samples: one full window scored
+        assert_eq!(*calls.lock().unwrap(), vec![WINDOW_LEN]);
+    }
+
+    #[test]
+    fn test_context_is_bounded() {
+        let (mut vad, _clock, calls) = vad_with_calls();
+        for _ in 0..40 {
+            vad.process(&level(100), 16000);
+        }
+        assert_eq!(
+            *calls.lock().unwrap().last().unwrap(),
+            CONTEXT_WINDOWS * WINDOW_LEN
+        );
+    }
+
+    #[test]
+    fn test_hysteresis_keeps_speech_between_thresholds() {
+        let (mut vad, clock, _calls) = vad_with_calls();
+        let loud = (0.8 * i16::MAX as f32) as i16;
+        let middle = (0.4 * i16::MAX as f32) as i16;
+        let events = run(
+            &mut vad,
+            &clock,
+            &[
+                // 0.4 is below the start threshold
+                FramePhase {
+                    samples: level(middle),
+                    count: 3,
+                },
+                FramePhase {
+                    samples: level(loud),
+                    count: 3,
+                },
+                // ...but above the end threshold once speaking
+                FramePhase {
+                    samples: level(middle),
+                    count: 3,
+                },
+            ],
+        );
+        assert!(events[..3].iter().all(|e| *e == VadEvent::Silence));
+        assert_eq!(events[3], VadEvent::SpeechStart);
+        assert!(events[4..].iter().all(|e| *e == VadEvent::Speech));
+    }
+
+    #[test]
+    fn test_speech_ends_after_silence() {
+        let (mut vad, clock, _calls) = vad_with_calls();
+        let events = run(
+            &mut vad,
+            &clock,
+            &[
+                FramePhase {
+                    samples: level(i16::MAX),
+                    count: 5,
+                },
+                FramePhase {
+                    samples: vec![0; WINDOW_LEN],
+                    count: 10,
+                },
+            ],
+        );
+        assert_eq!(events[0], VadEvent::SpeechStart);
+        assert!(events.contains(&VadEvent::SpeechEnd));
+        assert_eq!(vad.probability(), 0.0);
+    }
+
+    #[test]
+    fn test_rms_threshold_gates_probable_speech() {
+        let (mut vad, _clock, _calls) = vad_with_calls();
+        vad.set_threshold(0.9);
+        assert_eq!(vad.process(&level(20000), 16000), VadEvent::Silence);
+        assert!(vad.probability() > SPEECH_PROBABILITY);
+    }
+
+    #[test]
+    fn test_model_errors_are_treated_as_silence() {
+        let mut vad = NeuralVad::with_model(config(), Box::new(FailingModel), MockClock::new());
+        assert_eq!(vad.process(&level(i16::MAX), 16000), VadEvent::Silence);
+        assert!(vad.error_reported);
+    }
+
+    #[test]
+    fn test_reset_clears_context() {
+        let (mut vad, _clock, _calls) = vad_with_calls();
+        vad.process(&level(i16::MAX), 16000);
+        vad.reset();
+        assert_eq!(vad.state(), VadState::Idle);
+        assert_eq!(vad.probability(), 0.0);
+        assert!(vad.context.is_empty());
+    }
+
+    #[cfg(not(feature = "whisper"))]
+    #[test]
+    fn test_silero_requires_whisper_feature() {
+        assert!(SileroModel::load(Path::new("/nonexistent")).is_err());
+    }
+}
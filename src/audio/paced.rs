@@ -0,0 +1,202 @@
+//! Real-time pacing for recorded audio.
+//!
+//! [`PacedSource`] plays a finite source (a decoded file) back as if it were
+//! a microphone: samples become available as the clock advances, and the
+//! source reports itself as live so the pipeline treats empty reads as "no
+//! audio yet" rather than end of stream. Used by `voicsh replay` to reproduce
+//! how a live session was chunked.
+
+use crate::audio::recorder::AudioSource;
+use crate::audio::vad::{Clock, SystemClock};
+use crate::error::{Result, VoicshError};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// Wraps a finite source and releases its samples at `speed` × real time.
+pub struct PacedSource {
+    inner: Box<dyn AudioSource>,
+    sample_rate: u32,
+    speed: f64,
+    clock: Arc<dyn Clock>,
+    started: Option<Instant>,
+    buffer: VecDeque<i16>,
+    delivered: u64,
+    inner_done: bool,
+    finished: Arc<AtomicBool>,
+}
+
+impl PacedSource {
+    /// Pace `inner` at `speed` × real time (1.0 = as recorded).
+    ///
+    /// Higher speeds deliver larger reads, so the VAD sees coarser frames.
+    pub fn new(inner: Box<dyn AudioSource>, sample_rate: u32, speed: f32) -> Result<Self> {
+        if !(speed.is_finite() && speed > 0.0) {
+            return Err(VoicshError::AudioCapture {
+                message: format!("Playback speed must be a positive number, got {speed}"),
+            });
+        }
+        Ok(Self {
+            inner,
+            sample_rate,
+            speed: f64::from(speed),
+            clock: Arc::new(SystemClock),
+            started: None,
+            buffer: VecDeque::new(),
+            delivered: 0,
+            inner_done: false,
+            finished: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Sets a custom clock (for deterministic testing).
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Flag set once every sample has been delivered or the source failed.
+    pub fn finished_flag(&self) -> Arc<AtomicBool> {
+        self.finished.clone()
+    }
+}
+
+impl AudioSource for PacedSource {
+    fn start(&mut self) -> Result<()> {
+        self.inner.start()?;
+        self.started = Some(self.clock.now());
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.inner.stop()
+    }
+
+    fn read_samples(&mut self) -> Result<Vec<i16>> {
+        let Some(started) = self.started else {
+            return Ok(Vec::new());
+        };
+        let elapsed = self.clock.now().saturating_duration_since(started);
+        let due = (elapsed.as_secs_f64() * f64::from(self.sample_rate) * self.speed) as u64;
+        let wanted = due.saturating_sub(self.delivered) as usize;
+
+        while self.buffer.len() < wanted && !self.inner_done {
+            let samples = match self.inner.read_samples() {
+                Ok(samples) => samples,
+                Err(e) => {
+                    self.inner_done = true;
+                    self.finished.store(true, Ordering::SeqCst);
+                    return Err(e);
+                }
+            };
+            if samples.is_empty() {
+                self.inner_done = true;
+            }
+            self.buffer.extend(samples);
+        }
+
+        let n = wanted.min(self.buffer.len());
+        let out: Vec<i16> = self.buffer.drain(..n).collect();
+        self.delivered += n as u64;
+        if self.inner_done && self.buffer.is_empty() {
+            self.finished.store(true, Ordering::SeqCst);
+        }
+        Ok(out)
+    }
+
+    fn is_finite(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::recorder::MockAudioSource;
+    use crate::audio::vad::MockClock;
+    use std::time::Duration;
+
+    /// Finite source serving `total` samples (value = index) in 1000-sample reads.
+    fn counting_source(total: usize) -> Box<dyn AudioSource> {
+        let samples: Vec<i16> = (0..total).map(|i| i as i16).collect();
+        let chunks: Vec<Vec<i16>> = samples.chunks(1000).map(<[i16]>::to_vec).collect();
+        Box::new(FiniteChunks(chunks.into()))
+    }
+
+    struct FiniteChunks(VecDeque<Vec<i16>>);
+
+    impl AudioSource for FiniteChunks {
+        fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+        fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+        fn read_samples(&mut self) -> Result<Vec<i16>> {
+            Ok(self.0.pop_front().unwrap_or_default())
+        }
+        fn is_finite(&self) -> bool {
+            true
+        }
+    }
+
+    fn paced(total: usize, speed: f32) -> (PacedSource, MockClock) {
+        let clock = MockClock::new();
+        let source = PacedSource::new(counting_source(total), 16000, speed)
+            .unwrap()
+            .with_clock(Arc::new(clock.clone()));
+        (source, clock)
+    }
+
+    #[test]
+    fn test_delivers_samples_in_real_time() {
+        let (mut source, clock) = paced(16000, 1.0);
+        assert!(!source.is_finite());
+        assert!(source.read_samples().unwrap().is_empty(), "not started");
+
+        source.start().unwrap();
+        assert!(source.read_samples().unwrap().is_empty());
+        clock.advance(Duration::from_millis(100));
+        let first = source.read_samples().unwrap();
+        assert_eq!(first.len(), 1600);
+        clock.advance(Duration::from_millis(150));
+        let second = source.read_samples().unwrap();
+        assert_eq!(second.len(), 2400);
+        assert_eq!(
+            second[0], 1600,
+            "samples continue where the last read ended"
+        );
+    }
+
+    #[test]
+    fn test_speed_scales_delivery() {
+        let (mut source, clock) = paced(16000, 4.0);
+        source.start().unwrap();
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(source.read_samples().unwrap().len(), 6400);
+    }
+
+    #[test]
+    fn test_finished_after_all_samples_delivered() {
+        let (mut source, clock) = paced(2500, 1.0);
+        let finished = source.finished_flag();
+        source.start().unwrap();
+        clock.advance(Duration::from_millis(100));
+        source.read_samples().unwrap();
+        assert!(!finished.load(Ordering::SeqCst));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(source.read_samples().unwrap().len(), 900);
+        assert!(finished.load(Ordering::SeqCst));
+        // Stays live: further reads are empty, not an error
+        assert!(source.read_samples().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_invalid_speed() {
+        for speed in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(PacedSource::new(Box::new(MockAudioSource::new()), 16000, speed).is_err());
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Headerless PCM audio source (e.g. `pw-record -` or `arecord -t raw` output).
+
+use crate::audio::recorder::AudioSource;
+use crate::audio::resample::Resampler;
+use crate::audio::wav::downmix;
+use crate::error::{Result, VoicshError};
+use std::io::Read;
+
+/// Sample encoding of a raw PCM stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawSampleFormat {
+    U8,
+    S16Le,
+    S16Be,
+    S32Le,
+    F32Le,
+}
+
+impl RawSampleFormat {
+    /// Size of one sample in bytes.
+    pub fn bytes_per_sample(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::S16Le | Self::S16Be => 2,
+            Self::S32Le | Self::F32Le => 4,
+        }
+    }
+
+    /// Convert one encoded sample to 16-bit PCM.
+    fn to_i16(self, bytes: &[u8]) -> i16 {
+        match self {
+            Self::U8 => ((bytes[0] as i16) - 128) << 8,
+            Self::S16Le => i16::from_le_bytes([bytes[0], bytes[1]]),
+            Self::S16Be => i16::from_be_bytes([bytes[0], bytes[1]]),
+            Self::S32Le => {
+                (i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) >> 16) as i16
+            }
+            Self::F32Le => {
+                let value = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                (value.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for RawSampleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::U8 => write!(f, "u8"),
+            Self::S16Le => write!(f, "s16le"),
+            Self::S16Be => write!(f, "s16be"),
+            Self::S32Le => write!(f, "s32le"),
+            Self::F32Le => write!(f, "f32le"),
+        }
+    }
+}
+
+impl std::str::FromStr for RawSampleFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "u8" => Ok(Self::U8),
+            "s16le" | "s16" => Ok(Self::S16Le),
+            "s16be" => Ok(Self::S16Be),
+            "s32le" | "s32" => Ok(Self::S32Le),
+            "f32le" | "f32" => Ok(Self::F32Le),
+            other => Err(format!(
+                "Unknown sample format '{}'. Valid options: u8, s16le, s16be, s32le, f32le",
+                other
+            )),
+        }
+    }
+}
+
+/// Layout of a raw PCM stream, written as `FORMAT:RATE:CHANNELS` (e.g. `s16le:48000:2`).
+///
+/// Rate and channels may be omitted and default to 16000 Hz mono.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawPcmSpec {
+    pub format: RawSampleFormat,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl RawPcmSpec {
+    /// Size of one interleaved frame in bytes.
+    pub fn frame_bytes(&self) -> usize {
+        self.format.bytes_per_sample() * self.channels as usize
+    }
+}
+
+impl std::fmt::Display for RawPcmSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.format, self.sample_rate, self.channels)
+    }
+}
+
+impl std::str::FromStr for RawPcmSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let format = parts
+            .next()
+            .unwrap_or_default()
+            .parse::<RawSampleFormat>()?;
+        let sample_rate = match parts.next() {
+            Some(rate) => rate
+                .parse::<u32>()
+                .ok()
+                .filter(|&r| r > 0)
+                .ok_or_else(|| format!("Invalid sample rate '{}'", rate))?,
+            None => crate::defaults::SAMPLE_RATE,
+        };
+        let channels = match parts.next() {
+            Some(ch) => ch
+                .parse::<u16>()
+                .ok()
+                .filter(|&c| c > 0)
+                .ok_or_else(|| format!("Invalid channel count '{}'", ch))?,
+            None => 1,
+        };
+        if parts.next().is_some() {
+            return Err(format!(
+                "Invalid raw PCM spec '{}'. Expected FORMAT:RATE:CHANNELS, e.g. s16le:48000:2",
+                s
+            ));
+        }
+        Ok(Self {
+            format,
+            sample_rate,
+            channels,
+        })
+    }
+}
+
+/// Audio source that streams headerless PCM from a reader, converting to 16kHz mono.
+///
+/// Reads 100ms at a time, so live producers like `pw-record -` are transcribed
+/// as they speak; the resampler keeps its filter state between reads. The
+/// source is finite: it ends when the reader reaches EOF.
+pub struct RawPcmAudioSource {
+    reader: Box<dyn Read + Send>,
+    spec: RawPcmSpec,
+    buffer: Vec<u8>,
+    resampler: Resampler,
+    eof: bool,
+}
+
+impl RawPcmAudioSource {
+    pub fn new(reader: Box<dyn Read + Send>, spec: RawPcmSpec) -> Self {
+        // 100ms of audio at the source rate
+        let frames_per_chunk = (spec.sample_rate as usize / 10).max(1);
+        Self {
+            reader,
+            spec,
+            buffer: vec![0; frames_per_chunk * spec.frame_bytes()],
+            resampler: Resampler::new(spec.sample_rate, crate::defaults::SAMPLE_RATE),
+            eof: false,
+        }
+    }
+
+    /// Fill the buffer, returning the number of bytes read (short only at EOF).
+    fn fill_buffer(&mut self) -> Result<usize> {
+        let mut filled = 0;
+        while filled < self.buffer.len() {
+            match self.reader.read(&mut self.buffer[filled..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    return Err(VoicshError::AudioCapture {
+                        message: format!("Failed to read raw PCM: {}", e),
+                    });
+                }
+            }
+        }
+        Ok(filled)
+    }
+}
+
+impl AudioSource for RawPcmAudioSource {
+    fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn is_finite(&self) -> bool {
+        true
+    }
+
+    fn read_samples(&mut self) -> Result<Vec<i16>> {
+        if self.eof {
+            return Ok(Vec::new());
+        }
+        let filled = self.fill_buffer()?;
+
+        // A trailing partial frame at EOF is dropped
+        let frame_bytes = self.spec.frame_bytes();
+        let usable = filled - filled % frame_bytes;
+        let bytes_per_sample = self.spec.format.bytes_per_sample();
+        let samples: Vec<i16> = self.buffer[..usable]
+            .chunks_exact(bytes_per_sample)
+            .map(|bytes| self.spec.format.to_i16(bytes))
+            .collect();
+
+        let mut output = self
+            .resampler
+            .process(&downmix(samples, self.spec.channels));
+        if self.eof {
+            output.extend(self.resampler.flush());
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn s16le_bytes(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_spec_from_str() {
+        let spec: RawPcmSpec = "s16le:48000:2".parse().unwrap();
+        assert_eq!(spec.format, RawSampleFormat::S16Le);
+        assert_eq!(spec.sample_rate, 48000);
+        assert_eq!(spec.channels, 2);
+        assert_eq!(spec.frame_bytes(), 4);
+        assert_eq!(spec.to_string(), "s16le:48000:2");
+    }
+
+    #[test]
+    fn test_spec_defaults_to_16khz_mono() {
+        let spec: RawPcmSpec = "F32LE".parse().unwrap();
+        assert_eq!(spec.to_string(), "f32le:16000:1");
+    }
+
+    #[test]
+    fn test_spec_rejects_invalid() {
+        assert!("wav:16000:1".parse::<RawPcmSpec>().is_err());
+        assert!("s16le:0:1".parse::<RawPcmSpec>().is_err());
+        assert!("s16le:16000:0".parse::<RawPcmSpec>().is_err());
+        assert!("s16le:16000:1:x".parse::<RawPcmSpec>().is_err());
+    }
+
+    #[test]
+    fn test_sample_conversion() {
+        assert_eq!(RawSampleFormat::U8.to_i16(&[128]), 0);
+        assert_eq!(RawSampleFormat::U8.to_i16(&[0]), i16::MIN);
+        assert_eq!(RawSampleFormat::S16Be.to_i16(&[0x01, 0x00]), 256);
+        assert_eq!(
+            RawSampleFormat::S32Le.to_i16(&0x1234_0000i32.to_le_bytes()),
+            0x1234
+        );
+        assert_eq!(
+            RawSampleFormat::F32Le.to_i16(&1.5f32.to_le_bytes()),
+            i16::MAX
+        );
+    }
+
+    #[test]
+    fn test_reads_100ms_chunks_until_eof() {
+        let spec: RawPcmSpec = "s16le:16000:1".parse().unwrap();
+        let bytes = s16le_bytes(&vec![7i16; 2000]);
+        let mut source = RawPcmAudioSource::new(Box::new(Cursor::new(bytes)), spec);
+        assert!(source.is_finite());
+        assert_eq!(source.read_samples().unwrap().len(), 1600);
+        assert_eq!(source.read_samples().unwrap(), vec![7i16; 400]);
+        assert!(source.read_samples().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_downmixes_and_resamples_48khz_stereo() {
+        let spec: RawPcmSpec = "s16le:48000:2".parse().unwrap();
+        // 100ms of stereo frames (100, 300) at 48kHz
+        let frames: Vec<i16> = (0..4800).flat_map(|_| [100i16, 300]).collect();
+        let mut source = RawPcmAudioSource::new(Box::new(Cursor::new(s16le_bytes(&frames))), spec);
+        // The resampler holds back its look-ahead window until EOF
+        let mut samples = Vec::new();
+        loop {
+            let chunk = source.read_samples().unwrap();
+            if chunk.is_empty() {
+                break;
+            }
+            samples.extend(chunk);
+        }
+        assert_eq!(samples.len(), 1600);
+        assert!(samples.iter().all(|&s| s == 200));
+    }
+
+    #[test]
+    fn test_drops_trailing_partial_frame() {
+        let spec: RawPcmSpec = "s16le:16000:2".parse().unwrap();
+        let mut bytes = s16le_bytes(&[10, 20, 30, 40]);
+        bytes.push(0xFF);
+        let mut source = RawPcmAudioSource::new(Box::new(Cursor::new(bytes)), spec);
+        assert_eq!(source.read_samples().unwrap(), vec![15, 35]);
+    }
+}
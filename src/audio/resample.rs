@@ -0,0 +1,305 @@
+//! Band-limited sample rate conversion.
+//!
+//! A polyphase windowed-sinc resampler: the low-pass kernel is precomputed for
+//! [`PHASES`] fractional offsets, and each output sample is a dot product of
+//! the nearest phase with the surrounding input. When downsampling, the cutoff
+//! moves to the target Nyquist frequency, so 44.1/48 kHz input is filtered
+//! before decimation instead of aliasing into the speech band.
+
+use std::f64::consts::PI;
+
+/// Zero crossings of the sinc kernel on each side of the centre.
+const ZERO_CROSSINGS: usize = 16;
+
+/// Number of precomputed fractional offsets between two input samples.
+const PHASES: usize = 512;
+
+/// Streaming resampler for mono 16-bit audio.
+///
+/// Filter state is kept across [`Resampler::process`] calls, so feeding audio
+/// in blocks gives the same output as feeding it all at once. Call
+/// [`Resampler::flush`] at end of stream to emit the samples held back for
+/// look-ahead.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    from_rate: u32,
+    to_rate: u32,
+    /// Kernel taps on each side of an output position.
+    half_width: usize,
+    /// `PHASES + 1` rows of `2 * half_width` normalized taps.
+    table: Vec<f32>,
+    /// Pending input, including `half_width` samples of left context.
+    history: Vec<f32>,
+    /// Absolute input index of `history[0]` (negative while in the start padding).
+    base: i64,
+    input_len: u64,
+    produced: u64,
+    flushed: bool,
+}
+
+impl Resampler {
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        // Cutoff relative to the input Nyquist frequency
+        let cutoff = (to_rate as f64 / from_rate as f64).min(1.0);
+        let half_width = if from_rate == to_rate {
+            0
+        } else {
+            (ZERO_CROSSINGS as f64 / cutoff).ceil() as usize
+        };
+        Self {
+            from_rate,
+            to_rate,
+            half_width,
+            table: build_table(half_width, cutoff),
+            history: Vec::new(),
+            base: 0,
+            input_len: 0,
+            produced: 0,
+            flushed: false,
+        }
+    }
+
+    /// Resample the next block of input, returning every output sample whose
+    /// filter window is fully available.
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        if self.from_rate == self.to_rate {
+            return input.to_vec();
+        }
+        if input.is_empty() {
+            return Vec::new();
+        }
+        if self.input_len == 0 {
+            // Edge replication: the first sample extends to the left
+            self.history = vec![input[0] as f32; self.half_width];
+            self.base = -(self.half_width as i64);
+        }
+        self.history.extend(input.iter().map(|&s| s as f32));
+        self.input_len += input.len() as u64;
+        self.emit(None)
+    }
+
+    /// Emit the remaining output at end of stream.
+    ///
+    /// The total output is `ceil(input_len * to_rate / from_rate)` samples.
+    pub fn flush(&mut self) -> Vec<i16> {
+        if self.from_rate == self.to_rate || self.input_len == 0 || self.flushed {
+            return Vec::new();
+        }
+        self.flushed = true;
+        // Edge replication: the last sample extends to the right
+        let last = self.history.last().copied().unwrap_or_default();
+        self.history
+            .extend(std::iter::repeat_n(last, self.half_width));
+        let target = (self.input_len * self.to_rate as u64).div_ceil(self.from_rate as u64);
+        self.emit(Some(target))
+    }
+
+    /// Input index and fractional offset of output sample `n`.
+    fn position(&self, n: u64) -> (i64, f64) {
+        let scaled = n * self.from_rate as u64;
+        let index = (scaled / self.to_rate as u64) as i64;
+        let frac = (scaled % self.to_rate as u64) as f64 / self.to_rate as f64;
+        (index, frac)
+    }
+
+    fn emit(&mut self, target: Option<u64>) -> Vec<i16> {
+        let taps = 2 * self.half_width;
+        let available_end = self.base + self.history.len() as i64;
+        let mut output = Vec::new();
+        loop {
+            if target.is_some_and(|t| self.produced >= t) {
+                break;
+            }
+            let (index, frac) = self.position(self.produced);
+            // Window covers index - half_width + 1 ..= index + half_width
+            if index + self.half_width as i64 >= available_end {
+                break;
+            }
+            let start = (index - self.half_width as i64 + 1 - self.base) as usize;
+            let phase = (frac * PHASES as f64).round() as usize;
+            let row = &self.table[phase * taps..(phase + 1) * taps];
+            let value: f32 = self.history[start..start + taps]
+                .iter()
+                .zip(row)
+                .map(|(s, h)| s * h)
+                .sum();
+            output.push(value.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16);
+            self.produced += 1;
+        }
+
+        // Drop input no longer needed by the next output's window
+        let (next_index, _) = self.position(self.produced);
+        let keep_from = next_index - self.half_width as i64 + 1 - self.base;
+        if keep_from > 0 {
+            let drain = (keep_from as usize).min(self.history.len());
+            self.history.drain(..drain);
+            self.base += drain as i64;
+        }
+        output
+    }
+}
+
+/// Precompute the Blackman-windowed sinc kernel for every phase.
+///
+/// Each row is normalized to unit sum so DC passes unchanged.
+fn build_table(half_width: usize, cutoff: f64) -> Vec<f32> {
+    let taps = 2 * half_width;
+    let mut table = Vec::with_capacity((PHASES + 1) * taps);
+    for phase in 0..=PHASES {
+        let frac = phase as f64 / PHASES as f64;
+        let row: Vec<f64> = (0..taps)
+            .map(|k| {
+                // Distance from the output position to input tap k
+                let x = k as f64 - half_width as f64 + 1.0 - frac;
+                windowed_sinc(x, cutoff, half_width as f64)
+            })
+            .collect();
+        let sum: f64 = row.iter().sum();
+        table.extend(row.iter().map(|h| (h / sum) as f32));
+    }
+    table
+}
+
+fn windowed_sinc(x: f64, cutoff: f64, half_width: f64) -> f64 {
+    if x.abs() >= half_width {
+        return 0.0;
+    }
+    let sinc = if x == 0.0 {
+        1.0
+    } else {
+        let t = PI * cutoff * x;
+        t.sin() / t
+    };
+    // Blackman window over [-half_width, half_width]
+    let w = 0.5 + 0.5 * x / half_width;
+    let window = 0.42 - 0.5 * (2.0 * PI * w).cos() + 0.08 * (4.0 * PI * w).cos();
+    sinc * window
+}
+
+/// Resample a complete buffer.
+pub fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    let mut resampler = Resampler::new(from_rate, to_rate);
+    let mut output = resampler.process(samples);
+    output.extend(resampler.flush());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(freq: f64, rate: u32, secs: f64, amplitude: f64) -> Vec<i16> {
+        let len = (rate as f64 * secs) as usize;
+        (0..len)
+            .map(|i| (amplitude * (2.0 * PI * freq * i as f64 / rate as f64).sin()) as i16)
+            .collect()
+    }
+
+    /// RMS of the middle half, away from edge effects.
+    fn rms(samples: &[i16]) -> f64 {
+        let mid = &samples[samples.len() / 4..samples.len() * 3 / 4];
+        (mid.iter().map(|&s| (s as f64).powi(2)).sum::<f64>() / mid.len() as f64).sqrt()
+    }
+
+    /// Amplitude of `freq` in `samples` (single-bin DFT over the middle half).
+    fn tone_amplitude(samples: &[i16], freq: f64, rate: u32) -> f64 {
+        let start = samples.len() / 4;
+        let mid = &samples[start..samples.len() * 3 / 4];
+        let (re, im) = mid
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (i, &s)| {
+                let phase = 2.0 * PI * freq * (start + i) as f64 / rate as f64;
+                (re + s as f64 * phase.cos(), im + s as f64 * phase.sin())
+            });
+        2.0 * (re * re + im * im).sqrt() / mid.len() as f64
+    }
+
+    #[test]
+    fn test_output_length() {
+        assert_eq!(resample(&vec![0; 48000], 48000, 16000).len(), 16000);
+        assert_eq!(resample(&vec![0; 44100], 44100, 16000).len(), 16000);
+        assert_eq!(resample(&vec![0; 4410], 44100, 16000).len(), 1600);
+        assert_eq!(resample(&[1, 2, 3], 8000, 16000).len(), 6);
+        assert!(resample(&[], 48000, 16000).is_empty());
+    }
+
+    #[test]
+    fn test_same_rate_is_identity() {
+        let samples = vec![1, -2, 3, i16::MAX, i16::MIN];
+        assert_eq!(resample(&samples, 16000, 16000), samples);
+    }
+
+    #[test]
+    fn test_passband_tone_preserved() {
+        // 1 kHz at 48 kHz → 16 kHz keeps its amplitude
+        let input = tone(1000.0, 48000, 1.0, 10000.0);
+        let output = resample(&input, 48000, 16000);
+        let amplitude = tone_amplitude(&output, 1000.0, 16000);
+        assert!((amplitude - 10000.0).abs() < 100.0, "amplitude {amplitude}");
+    }
+
+    #[test]
+    fn test_sibilant_band_tone_preserved() {
+        // 6 kHz (sibilants) at 44.1 kHz → 16 kHz stays below the new Nyquist
+        let input = tone(6000.0, 44100, 1.0, 10000.0);
+        let output = resample(&input, 44100, 16000);
+        let amplitude = tone_amplitude(&output, 6000.0, 16000);
+        assert!((amplitude - 10000.0).abs() < 300.0, "amplitude {amplitude}");
+    }
+
+    #[test]
+    fn test_above_nyquist_tone_rejected() {
+        // 12 kHz would alias to 4 kHz with linear interpolation
+        let input = tone(12000.0, 48000, 1.0, 10000.0);
+        let output = resample(&input, 48000, 16000);
+        assert!(rms(&output) < 30.0, "alias rms {}", rms(&output));
+        assert!(tone_amplitude(&output, 4000.0, 16000) < 30.0);
+    }
+
+    #[test]
+    fn test_upsampling_tone_preserved() {
+        let input = tone(440.0, 8000, 1.0, 8000.0);
+        let output = resample(&input, 8000, 16000);
+        let amplitude = tone_amplitude(&output, 440.0, 16000);
+        assert!((amplitude - 8000.0).abs() < 80.0, "amplitude {amplitude}");
+    }
+
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        let input = tone(3000.0, 44100, 0.5, 12000.0);
+        let expected = resample(&input, 44100, 16000);
+
+        let mut resampler = Resampler::new(44100, 16000);
+        let mut streamed = Vec::new();
+        // Uneven block sizes, like audio callbacks
+        for block in input.chunks(441).flat_map(|c| c.chunks(300)) {
+            streamed.extend(resampler.process(block));
+        }
+        streamed.extend(resampler.flush());
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_streaming_emits_with_bounded_latency() {
+        let mut resampler = Resampler::new(48000, 16000);
+        let out = resampler.process(&vec![0; 4800]);
+        // 100ms in, all but the look-ahead window out
+        assert!(out.len() > 1500 && out.len() <= 1600, "got {}", out.len());
+        assert!(resampler.history.len() < 200);
+    }
+
+    #[test]
+    fn test_flush_is_idempotent() {
+        let mut resampler = Resampler::new(48000, 16000);
+        resampler.process(&[100; 10]);
+        resampler.flush();
+        assert!(resampler.flush().is_empty());
+    }
+
+    #[test]
+    fn test_dc_preserved_at_edges() {
+        let output = resample(&[1000; 100], 16000, 8000);
+        assert!(output.iter().all(|&s| s == 1000), "{output:?}");
+    }
+}
@@ -0,0 +1,394 @@
+//! Spectral voice activity detection.
+//!
+//! Classifies frames with three cheap DSP features instead of loudness alone:
+//! the share of energy in the speech band (300–3400 Hz), the zero-crossing
+//! rate, and the spectral flatness inside the speech band. Voiced speech is
+//! band-limited, crosses zero slowly and has a harmonic (peaky) spectrum, while
+//! fans sit below the band and typing or hiss is flat and crosses zero often.
+//! A hangover keeps speech active briefly after the last voiced frame so
+//! unvoiced consonants and short dips do not split utterances.
+
+use crate::audio::fft::Fft;
+use crate::audio::vad::{
+    Clock, SpeechTracker, SystemClock, VadConfig, VadResult, VadState, VoiceDetector, calculate_rms,
+};
+use std::f32::consts::PI;
+
+/// Analysis window length in samples (32ms at 16kHz).
+const WINDOW_LEN: usize = 512;
+
+/// Lower edge of the speech band in Hz.
+const SPEECH_BAND_LOW_HZ: f32 = 300.0;
+
+/// Upper edge of the speech band in Hz.
+const SPEECH_BAND_HIGH_HZ: f32 = 3400.0;
+
+/// Minimum share of the window energy inside the speech band.
+const MIN_BAND_RATIO: f32 = 0.5;
+
+/// Maximum spectral flatness in the speech band (white noise is about 0.56).
+const MAX_FLATNESS: f32 = 0.35;
+
+/// Maximum zero crossings per sample (white noise is about 0.5).
+const MAX_ZERO_CROSSING_RATE: f32 = 0.3;
+
+/// How long speech stays active after the last voiced frame.
+const HANGOVER_MS: u32 = 200;
+
+/// Avoids `ln(0)` and division by zero in silent windows.
+const EPSILON: f32 = 1e-12;
+
+/// Per-frame features, exposed for diagnostics and tests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralFeatures {
+    /// Share of window energy between 300 and 3400 Hz (0.0 to 1.0).
+    pub band_ratio: f32,
+    /// Zero crossings per sample in the frame (0.0 to 1.0).
+    pub zero_crossing_rate: f32,
+    /// Geometric over arithmetic mean of the speech band power (0.0 to 1.0).
+    pub flatness: f32,
+}
+
+impl SpectralFeatures {
+    /// Whether all three features look like voiced speech.
+    ///
+    /// Each feature alone has blind spots: rumble is peaky and crosses zero
+    /// slowly but lies below the band, band-limited hiss lies in the band but
+    /// is flat.
+    pub fn is_voiced(&self) -> bool {
+        self.band_ratio >= MIN_BAND_RATIO
+            && self.zero_crossing_rate <= MAX_ZERO_CROSSING_RATE
+            && self.flatness <= MAX_FLATNESS
+    }
+}
+
+/// Voice activity detector using speech-band energy, zero-crossing rate and
+/// spectral flatness, gated by the RMS threshold.
+pub struct SpectralVad<C: Clock = SystemClock> {
+    config: VadConfig,
+    tracker: SpeechTracker<C>,
+    fft: Fft,
+    window: Vec<f32>,
+    /// The most recent `WINDOW_LEN` samples, normalized.
+    history: Vec<f32>,
+    /// Samples of hangover left before speech is released.
+    hangover_remaining: usize,
+}
+
+impl<C: Clock> SpectralVad<C> {
+    /// Creates a new spectral VAD with the given configuration and clock.
+    pub fn with_clock(config: VadConfig, clock: C) -> Self {
+        let window = (0..WINDOW_LEN)
+            .map(|n| 0.5 - 0.5 * (2.0 * PI * n as f32 / WINDOW_LEN as f32).cos())
+            .collect();
+        Self {
+            tracker: SpeechTracker::new(&config, clock),
+            config,
+            fft: Fft::new(WINDOW_LEN),
+            window,
+            history: vec![0.0; WINDOW_LEN],
+            hangover_remaining: 0,
+        }
+    }
+
+    /// Computes the features of a frame, updating the analysis window.
+    pub fn analyze(&mut self, samples: &[i16], sample_rate: u32) -> SpectralFeatures {
+        let incoming = samples.len().min(WINDOW_LEN);
+        self.history.drain(..incoming);
+        self.history.extend(
+            samples[samples.len() - incoming..]
+                .iter()
+                .map(|&s| s as f32 / i16::MAX as f32),
+        );
+
+        let mut re: Vec<f32> = self
+            .history
+            .iter()
+            .zip(&self.window)
+            .map(|(s, w)| s * w)
+            .collect();
+        let mut im = vec![0.0; WINDOW_LEN];
+        self.fft.process(&mut re, &mut im, false);
+
+        let bin_hz = sample_rate as f32 / WINDOW_LEN as f32;
+        let low = (SPEECH_BAND_LOW_HZ / bin_hz).ceil() as usize;
+        let high = ((SPEECH_BAND_HIGH_HZ / bin_hz).floor() as usize).min(WINDOW_LEN / 2);
+
+        let mut total = 0.0;
+        let mut band = 0.0;
+        let mut log_sum = 0.0;
+        for k in 0..=WINDOW_LEN / 2 {
+            let power = re[k] * re[k] + im[k] * im[k];
+            total += power;
+            if (low..=high).contains(&k) {
+                band += power;
+                log_sum += (power + EPSILON).ln();
+            }
+        }
+        let band_bins = high.saturating_sub(low) + 1;
+        let band_mean = band / band_bins as f32;
+        let flatness = if band_mean > EPSILON {
+            ((log_sum / band_bins as f32).exp() / (band_mean + EPSILON)).min(1.0)
+        } else {
+            1.0
+        };
+
+        SpectralFeatures {
+            band_ratio: if total > EPSILON { band / total } else { 0.0 },
+            zero_crossing_rate: zero_crossing_rate(samples),
+            flatness,
+        }
+    }
+}
+
+impl<C: Clock> VoiceDetector for SpectralVad<C> {
+    fn process_with_info(&mut self, samples: &[i16], sample_rate: u32) -> VadResult {
+        let rms = calculate_rms(samples);
+        let features = self.analyze(samples, sample_rate);
+        let voiced = rms > self.config.speech_threshold && features.is_voiced();
+
+        let is_speech = if voiced {
+            self.hangover_remaining = (HANGOVER_MS as usize * sample_rate as usize) / 1000;
+            true
+        } else if self.hangover_remaining > 0 {
+            self.hangover_remaining = self.hangover_remaining.saturating_sub(samples.len());
+            true
+        } else {
+            false
+        };
+        let (event, silence_ms) = self.tracker.update(is_speech);
+
+        VadResult {
+            event,
+            level: rms,
+            threshold: self.config.speech_threshold,
+            silence_ms,
+            silence_duration_ms: self.config.silence_duration_ms,
+        }
+    }
+
+    fn state(&self) -> VadState {
+        self.tracker.state()
+    }
+
+    fn reset(&mut self) {
+        self.tracker.reset();
+        self.history.fill(0.0);
+        self.hangover_remaining = 0;
+    }
+
+    fn set_threshold(&mut self, threshold: f32) {
+        self.config.speech_threshold = threshold;
+    }
+}
+
+impl SpectralVad<SystemClock> {
+    /// Creates a new spectral VAD using the system clock.
+    pub fn new(config: VadConfig) -> Self {
+        Self::with_clock(config, SystemClock)
+    }
+}
+
+/// Fraction of adjacent sample pairs whose signs differ.
+fn zero_crossing_rate(samples: &[i16]) -> f32 {
+    if samples.len() < 2 {
+        return 0.0;
+    }
+    let crossings = samples
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0) != (pair[1] >= 0))
+        .count();
+    crossings as f32 / (samples.len() - 1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::recorder::FramePhase;
+    use crate::audio::vad::{MockClock, VadEvent};
+    use crate::config::VadBackend;
+    use std::time::Duration;
+
+    /// 20ms frames at 16kHz.
+    const FRAME: usize = 320;
+
+    fn config() -> VadConfig {
+        VadConfig {
+            speech_threshold: 0.02,
+            silence_duration_ms: 300,
+            min_speech_ms: 100,
+            backend: VadBackend::Spectral,
+            ..Default::default()
+        }
+    }
+
+    /// Harmonic vowel-like signal: 200 Hz fundamental, energy mostly in the speech band.
+    fn voiced(len: usize, offset: usize) -> Vec<i16> {
+        (0..len)
+            .map(|i| {
+                let t = (i + offset) as f32 / 16000.0;
+                let sum: f32 = (1..=16)
+                    .map(|h| {
+                        let f = 200.0 * h as f32;
+                        let weight = if (400.0..=2000.0).contains(&f) {
+                            1.0
+                        } else {
+                            0.2
+                        };
+                        weight * (2.0 * PI * f * t).sin()
+                    })
+                    .sum();
+                (sum * 1200.0) as i16
+            })
+            .collect()
+    }
+
+    /// Deterministic white noise (xorshift), like keyboard clatter or hiss.
+    fn white_noise(len: usize, amplitude: f32, seed: u32) -> Vec<i16> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                ((state as f32 / u32::MAX as f32 * 2.0 - 1.0) * amplitude) as i16
+            })
+            .collect()
+    }
+
+    /// Low-frequency rumble like a fan or HVAC (white noise through a one-pole low-pass).
+    fn rumble(len: usize, amplitude: f32) -> Vec<i16> {
+        let mut y = 0.0;
+        white_noise(len, amplitude, 17)
+            .into_iter()
+            .map(|x| {
+                y = 0.97 * y + 0.03 * x as f32 * 8.0;
+                y.clamp(i16::MIN as f32, i16::MAX as f32) as i16
+            })
+            .collect()
+    }
+
+    /// Feeds each phase's samples `count` times, advancing the clock 20ms per frame.
+    fn run(phases: &[FramePhase]) -> Vec<VadEvent> {
+        let clock = MockClock::new();
+        let mut vad = SpectralVad::with_clock(config(), clock.clone());
+        let mut events = Vec::new();
+        for phase in phases {
+            for _ in 0..phase.count {
+                events.push(vad.process(&phase.samples, 16000));
+                clock.advance(Duration::from_millis(20));
+            }
+        }
+        events
+    }
+
+    fn phase(samples: Vec<i16>, count: u32) -> FramePhase {
+        FramePhase { samples, count }
+    }
+
+    #[test]
+    fn test_voiced_frame_features() {
+        let mut vad = SpectralVad::new(config());
+        let features = vad.analyze(&voiced(WINDOW_LEN, 0), 16000);
+        assert!(features.band_ratio > 0.8, "{features:?}");
+        assert!(features.flatness < 0.1, "{features:?}");
+        assert!(features.is_voiced());
+    }
+
+    #[test]
+    fn test_white_noise_features() {
+        let mut vad = SpectralVad::new(config());
+        let features = vad.analyze(&white_noise(WINDOW_LEN, 8000.0, 3), 16000);
+        assert!(features.flatness > 0.4, "{features:?}");
+        assert!(features.zero_crossing_rate > 0.4, "{features:?}");
+        assert!(!features.is_voiced());
+    }
+
+    #[test]
+    fn test_zero_crossing_rate() {
+        assert_eq!(zero_crossing_rate(&[]), 0.0);
+        assert_eq!(zero_crossing_rate(&[100, -100, 100, -100, 100]), 1.0);
+        assert_eq!(zero_crossing_rate(&[5, 6, 7]), 0.0);
+    }
+
+    #[test]
+    fn test_speech_starts_and_ends() {
+        let events = run(&[
+            phase(vec![0; FRAME], 5),
+            phase(voiced(FRAME, 0), 20),
+            phase(vec![0; FRAME], 40),
+        ]);
+        assert_eq!(events[5], VadEvent::SpeechStart);
+        assert!(events[6..25].iter().all(|e| *e == VadEvent::Speech));
+        assert_eq!(
+            events.iter().filter(|e| **e == VadEvent::SpeechEnd).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_loud_noise_is_not_speech() {
+        // Both are far above the RMS threshold that the RMS detector would use
+        let events = run(&[
+            phase(white_noise(FRAME, 6000.0, 9), 25),
+            phase(rumble(FRAME, 6000.0), 25),
+        ]);
+        assert!(events.iter().all(|e| *e == VadEvent::Silence), "{events:?}");
+    }
+
+    #[test]
+    fn test_quiet_dc_offset_is_not_speech() {
+        let events = run(&[phase(vec![3000; FRAME], 10)]);
+        assert!(events.iter().all(|e| *e == VadEvent::Silence));
+    }
+
+    #[test]
+    fn test_hangover_bridges_short_gaps() {
+        // A 100ms dip (shorter than the hangover) keeps speech active
+        let events = run(&[
+            phase(voiced(FRAME, 0), 10),
+            phase(vec![0; FRAME], 5),
+            phase(voiced(FRAME, 0), 10),
+        ]);
+        assert_eq!(events[0], VadEvent::SpeechStart);
+        assert!(
+            events[1..].iter().all(|e| *e == VadEvent::Speech),
+            "{events:?}"
+        );
+    }
+
+    #[test]
+    fn test_hangover_expires() {
+        let clock = MockClock::new();
+        let mut vad = SpectralVad::with_clock(config(), clock.clone());
+        for _ in 0..10 {
+            vad.process(&voiced(FRAME, 0), 16000);
+            clock.advance(Duration::from_millis(20));
+        }
+        // 200ms hangover = 10 frames of 20ms
+        for _ in 0..10 {
+            assert_eq!(vad.process(&[0; FRAME], 16000), VadEvent::Speech);
+        }
+        assert_eq!(vad.process(&[0; FRAME], 16000), VadEvent::Silence);
+        assert_eq!(vad.state(), VadState::MaybeSilence);
+    }
+
+    #[test]
+    fn test_threshold_gates_quiet_speech() {
+        let clock = MockClock::new();
+        let mut vad = SpectralVad::with_clock(config(), clock);
+        vad.set_threshold(0.9);
+        assert_eq!(vad.process(&voiced(FRAME, 0), 16000), VadEvent::Silence);
+        vad.set_threshold(0.02);
+        assert_eq!(vad.process(&voiced(FRAME, 0), 16000), VadEvent::SpeechStart);
+    }
+
+    #[test]
+    fn test_reset_clears_hangover() {
+        let mut vad = SpectralVad::new(config());
+        vad.process(&voiced(FRAME, 0), 16000);
+        vad.reset();
+        assert_eq!(vad.state(), VadState::Idle);
+        assert_eq!(vad.process(&[0; FRAME], 16000), VadEvent::Silence);
+    }
+}
@@ -1,8 +1,11 @@
 //! Voice Activity Detection (VAD) module.
 //!
 //! Detects speech activity in audio streams using RMS-based thresholding
-//! and state machine logic to handle silence intervals.
+//! and state machine logic to handle silence intervals. The
+//! [`VoiceDetector`] trait lets the pipeline swap in other detectors such as
+//! [`SpectralVad`](crate::audio::spectral_vad::SpectralVad).
 
+use crate::config::VadBackend;
 use crate::defaults;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -74,6 +77,10 @@ pub struct VadConfig {
     pub silence_duration_ms: u32,
     /// Minimum duration of speech before it's considered valid (milliseconds).
     pub min_speech_ms: u32,
+    /// Which detector decides whether a frame is speech.
+    pub backend: VadBackend,
+    /// Catalog name of the model used by the neural backend.
+    pub model: &'static str,
 }
 
 impl Default for VadConfig {
@@ -82,6 +89,8 @@ impl Default for VadConfig {
             speech_threshold: defaults::VAD_THRESHOLD,
             silence_duration_ms: defaults::SILENCE_DURATION_MS,
             min_speech_ms: 300,
+            backend: VadBackend::default(),
+            model: defaults::DEFAULT_VAD_MODEL,
         }
     }
 }
@@ -127,20 +136,72 @@ pub struct VadResult {
     pub silence_duration_ms: u32,
 }
 
-/// Voice Activity Detector state machine.
-pub struct Vad<C: Clock = SystemClock> {
-    config: VadConfig,
+/// A detector that turns audio frames into speech start/end events.
+pub trait VoiceDetector: Send {
+    /// Processes audio samples and returns detailed VAD result with level info.
+    ///
+    /// # Arguments
+    /// * `samples` - Audio samples as 16-bit PCM
+    /// * `sample_rate` - Sample rate in Hz
+    fn process_with_info(&mut self, samples: &[i16], sample_rate: u32) -> VadResult;
+
+    /// Processes audio samples and returns the corresponding VAD event.
+    fn process(&mut self, samples: &[i16], sample_rate: u32) -> VadEvent {
+        self.process_with_info(samples, sample_rate).event
+    }
+
+    /// Returns the current VAD state.
+    fn state(&self) -> VadState;
+
+    /// Resets the detector to idle state.
+    fn reset(&mut self);
+
+    /// Updates the level threshold without resetting state.
+    fn set_threshold(&mut self, threshold: f32);
+}
+
+/// Creates the detector selected by `config.backend`.
+///
+/// Falls back to the spectral detector when the neural model cannot be loaded.
+pub fn create_detector(config: VadConfig, clock: Arc<dyn Clock>) -> Box<dyn VoiceDetector> {
+    use crate::audio::{neural_vad, spectral_vad::SpectralVad};
+
+    match config.backend {
+        VadBackend::Rms => Box::new(Vad::with_clock(config, clock)),
+        VadBackend::Spectral => Box::new(SpectralVad::with_clock(config, clock)),
+        VadBackend::Neural => match neural_vad::load_model(config.model) {
+            Ok(model) => Box::new(neural_vad::NeuralVad::with_model(
+                config,
+                Box::new(model),
+                clock,
+            )),
+            Err(e) => {
+                eprintln!("voicsh: neural VAD unavailable ({e}), using spectral VAD.");
+                Box::new(SpectralVad::with_clock(config, clock))
+            }
+        },
+    }
+}
+
+/// Speech start/end state machine shared by the detectors.
+///
+/// Turns per-frame speech decisions into events, waiting for
+/// `silence_duration_ms` of silence before ending speech and discarding
+/// utterances shorter than `min_speech_ms`.
+pub(crate) struct SpeechTracker<C: Clock> {
+    silence_duration_ms: u32,
+    min_speech_ms: u32,
     state: VadState,
     silence_start: Option<Instant>,
     speech_start: Option<Instant>,
     clock: C,
 }
 
-impl<C: Clock> Vad<C> {
-    /// Creates a new VAD instance with the given configuration and clock.
-    pub fn with_clock(config: VadConfig, clock: C) -> Self {
+impl<C: Clock> SpeechTracker<C> {
+    pub(crate) fn new(config: &VadConfig, clock: C) -> Self {
         Self {
-            config,
+            silence_duration_ms: config.silence_duration_ms,
+            min_speech_ms: config.min_speech_ms,
             state: VadState::Idle,
             silence_start: None,
             speech_start: None,
@@ -148,26 +209,11 @@ impl<C: Clock> Vad<C> {
         }
     }
 
-    /// Processes audio samples and returns the corresponding VAD event.
-    ///
-    /// # Arguments
-    /// * `samples` - Audio samples as 16-bit PCM
-    /// * `sample_rate` - Sample rate in Hz (used for timing calculations)
-    pub fn process(&mut self, samples: &[i16], sample_rate: u32) -> VadEvent {
-        self.process_with_info(samples, sample_rate).event
-    }
-
-    /// Processes audio samples and returns detailed VAD result with level info.
-    ///
-    /// # Arguments
-    /// * `samples` - Audio samples as 16-bit PCM
-    /// * `sample_rate` - Sample rate in Hz (used for timing calculations)
-    pub fn process_with_info(&mut self, samples: &[i16], _sample_rate: u32) -> VadResult {
-        let rms = calculate_rms(samples);
-        let is_speech = rms > self.config.speech_threshold;
+    /// Advances the state machine, returning the event and elapsed silence (ms).
+    pub(crate) fn update(&mut self, is_speech: bool) -> (VadEvent, u32) {
         let now = self.clock.now();
 
-        let (event, silence_ms) = match self.state {
+        match self.state {
             VadState::Idle => {
                 if is_speech {
                     self.state = VadState::Speaking;
@@ -199,7 +245,7 @@ impl<C: Clock> Vad<C> {
                         .map(|start| now.duration_since(start).as_millis() as u32)
                         .unwrap_or(0);
 
-                    if silence_elapsed >= self.config.silence_duration_ms {
+                    if silence_elapsed >= self.silence_duration_ms {
                         // Discard speech that was too short to be intentional (noise gate).
                         // Speaking duration = time from speech_start to silence_start.
                         let speaking_ms = match (self.speech_start, self.silence_start) {
@@ -210,7 +256,7 @@ impl<C: Clock> Vad<C> {
                         };
                         self.silence_start = None;
                         self.speech_start = None;
-                        if speaking_ms < self.config.min_speech_ms {
+                        if speaking_ms < self.min_speech_ms {
                             self.state = VadState::Idle;
                             (VadEvent::Silence, 0)
                         } else {
@@ -235,7 +281,41 @@ impl<C: Clock> Vad<C> {
                     (VadEvent::Silence, 0)
                 }
             }
-        };
+        }
+    }
+
+    pub(crate) fn state(&self) -> VadState {
+        self.state
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.state = VadState::Idle;
+        self.silence_start = None;
+        self.speech_start = None;
+    }
+}
+
+/// Voice Activity Detector state machine driven by RMS thresholding.
+pub struct Vad<C: Clock = SystemClock> {
+    config: VadConfig,
+    tracker: SpeechTracker<C>,
+}
+
+impl<C: Clock> Vad<C> {
+    /// Creates a new VAD instance with the given configuration and clock.
+    pub fn with_clock(config: VadConfig, clock: C) -> Self {
+        Self {
+            tracker: SpeechTracker::new(&config, clock),
+            config,
+        }
+    }
+}
+
+impl<C: Clock> VoiceDetector for Vad<C> {
+    fn process_with_info(&mut self, samples: &[i16], _sample_rate: u32) -> VadResult {
+        let rms = calculate_rms(samples);
+        let is_speech = rms > self.config.speech_threshold;
+        let (event, silence_ms) = self.tracker.update(is_speech);
 
         VadResult {
             event,
@@ -246,20 +326,15 @@ impl<C: Clock> Vad<C> {
         }
     }
 
-    /// Returns the current VAD state.
-    pub fn state(&self) -> VadState {
-        self.state
+    fn state(&self) -> VadState {
+        self.tracker.state()
     }
 
-    /// Resets the VAD to idle state.
-    pub fn reset(&mut self) {
-        self.state = VadState::Idle;
-        self.silence_start = None;
-        self.speech_start = None;
+    fn reset(&mut self) {
+        self.tracker.reset();
     }
 
-    /// Updates the speech threshold without resetting state.
-    pub fn set_threshold(&mut self, threshold: f32) {
+    fn set_threshold(&mut self, threshold: f32) {
         self.config.speech_threshold = threshold;
     }
 }
@@ -436,6 +511,7 @@ mod tests {
             speech_threshold: 0.02,
             silence_duration_ms: 100, // Short duration for testing
             min_speech_ms: 50,
+            ..Default::default()
         };
         let clock = MockClock::new();
         let mut vad = Vad::with_clock(config, clock.clone());
@@ -487,6 +563,7 @@ mod tests {
             speech_threshold: 0.02,
             silence_duration_ms: 100,
             min_speech_ms: 50,
+            ..Default::default()
         };
         let clock = MockClock::new();
         let mut vad = Vad::with_clock(config, clock.clone());
@@ -514,6 +591,7 @@ mod tests {
             speech_threshold: 0.02,
             silence_duration_ms: 100,
             min_speech_ms: 50,
+            ..Default::default()
         };
         let clock = MockClock::new();
         let mut vad = Vad::with_clock(config, clock.clone());
@@ -548,6 +626,7 @@ mod tests {
             speech_threshold: 0.02,
             silence_duration_ms: 100,
             min_speech_ms: 50,
+            ..Default::default()
         };
         let clock = MockClock::new();
         let mut vad = Vad::with_clock(config, clock.clone());
@@ -586,6 +665,7 @@ mod tests {
             speech_threshold: 0.02,
             silence_duration_ms: 100,
             min_speech_ms: 200, // Require 200ms of speech
+            ..Default::default()
         };
         let clock = MockClock::new();
         let mut vad = Vad::with_clock(config, clock.clone());
@@ -1,6 +1,7 @@
 //! WAV file audio source for pipe mode.
 
 use crate::audio::recorder::AudioSource;
+use crate::audio::resample::resample;
 use crate::defaults::SAMPLE_RATE;
 use crate::error::{Result, VoicshError};
 use std::io::Read;
@@ -33,26 +34,7 @@ impl WavAudioSource {
                 message: format!("Failed to read WAV samples: {}", e),
             })?;
 
-        // Convert to mono if stereo
-        let mono_samples = if source_channels == 2 {
-            raw_samples
-                .chunks_exact(2)
-                .map(|chunk| {
-                    let left = chunk[0] as i32;
-                    let right = chunk[1] as i32;
-                    ((left + right) / 2) as i16
-                })
-                .collect()
-        } else {
-            raw_samples
-        };
-
-        // Resample to 16kHz if needed
-        let samples = if source_rate != SAMPLE_RATE {
-            resample(&mono_samples, source_rate, SAMPLE_RATE)
-        } else {
-            mono_samples
-        };
+        let samples = to_mono_16k(raw_samples, source_rate, source_channels);
 
         // 100ms chunks at 16kHz
         let chunk_size = 1600;
@@ -112,32 +94,31 @@ impl AudioSource for WavAudioSource {
     }
 }
 
-/// Simple linear interpolation resampling.
-pub(crate) fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
-    if from_rate == to_rate {
-        return samples.to_vec();
+/// Average interleaved frames into mono samples.
+pub(crate) fn downmix(samples: Vec<i16>, channels: u16) -> Vec<i16> {
+    if channels <= 1 {
+        return samples;
     }
-
-    let ratio = from_rate as f64 / to_rate as f64;
-    let output_len = (samples.len() as f64 / ratio).ceil() as usize;
-
-    (0..output_len)
-        .map(|i| {
-            let source_pos = i as f64 * ratio;
-            let source_idx = source_pos.floor() as usize;
-            let fraction = source_pos - source_idx as f64;
-
-            if source_idx + 1 >= samples.len() {
-                samples[source_idx]
-            } else {
-                let left = samples[source_idx] as f64;
-                let right = samples[source_idx + 1] as f64;
-                (left + (right - left) * fraction) as i16
-            }
+    samples
+        .chunks_exact(channels as usize)
+        .map(|frame| {
+            let sum: i32 = frame.iter().map(|&s| s as i32).sum();
+            (sum / channels as i32) as i16
         })
         .collect()
 }
 
+/// Downmix interleaved samples to mono and resample to 16kHz.
+pub(crate) fn to_mono_16k(samples: Vec<i16>, sample_rate: u32, channels: u16) -> Vec<i16> {
+    let mono_samples = downmix(samples, channels);
+
+    if sample_rate != SAMPLE_RATE {
+        resample(&mono_samples, sample_rate, SAMPLE_RATE)
+    } else {
+        mono_samples
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -361,6 +342,12 @@ mod tests {
         assert_eq!(source.samples, vec![0i16, 0]);
     }
 
+    #[test]
+    fn to_mono_16k_averages_all_channels() {
+        let samples = vec![300i16, 600, 900, -300, -600, -900];
+        assert_eq!(to_mono_16k(samples, 16000, 3), vec![600i16, -600]);
+    }
+
     #[test]
     fn resample_preserves_signal_amplitude() {
         let samples = vec![1000i16; 100];
@@ -2,6 +2,8 @@
 //!
 //! Provides argument parsing using clap derive macros.
 
+use crate::audio::raw::RawPcmSpec;
+use crate::transcript::TranscriptFormat;
 use clap::{Parser, Subcommand};
 use clap_complete::Shell;
 use std::path::PathBuf;
@@ -69,6 +71,14 @@ pub struct Cli {
     /// Transcription buffer duration (default: 10s). Examples: 30s, 5m, 1h30m
     #[arg(long, short = 'b', value_name = "DURATION", default_value = "10s", value_parser = parse_buffer_secs)]
     pub buffer: u64,
+
+    /// Pipe mode output format: txt, srt, vtt, json, tsv
+    #[arg(long, value_name = "FORMAT", default_value = "txt")]
+    pub output_format: TranscriptFormat,
+
+    /// Pipe mode: read stdin as headerless PCM, FORMAT:RATE:CHANNELS (e.g. s16le:48000:2)
+    #[arg(long, value_name = "SPEC")]
+    pub raw: Option<RawPcmSpec>,
 }
 
 /// Parse a buffer duration string into seconds.
@@ -93,6 +103,20 @@ pub enum Commands {
     #[cfg(feature = "cpal-audio")]
     Devices,
 
+    /// Measure background noise and speech level, and store a VAD threshold for the device
+    #[cfg(feature = "cpal-audio")]
+    Calibrate {
+        /// Seconds of silence to record for the noise floor
+        #[arg(long, value_name = "SECONDS", default_value = "3")]
+        silence_secs: u32,
+        /// Seconds to record while reading the sample sentence
+        #[arg(long, value_name = "SECONDS", default_value = "5")]
+        speech_secs: u32,
+        /// Show the measured levels without writing the config file
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Manage Whisper models
     #[cfg(feature = "model-download")]
     Models {
@@ -101,6 +125,13 @@ pub enum Commands {
         action: ModelsAction,
     },
 
+    /// Inspect archived sessions (recorded with `[debug] archive_audio`)
+    Sessions {
+        /// Action to perform
+        #[command(subcommand)]
+        action: SessionsAction,
+    },
+
     /// Check system dependencies
     Check,
 
@@ -110,6 +141,55 @@ pub enum Commands {
         /// Path to Unix socket (default: $XDG_RUNTIME_DIR/voicsh.sock)
         #[arg(long, value_name = "PATH")]
         socket: Option<PathBuf>,
+
+        /// Reload configuration automatically when the config file changes
+        #[arg(long)]
+        watch_config: bool,
+
+        /// Also serve the model as a Wyoming ASR service (e.g. for Home Assistant)
+        #[arg(long, value_name = "HOST:PORT")]
+        wyoming: Option<std::net::SocketAddr>,
+    },
+
+    /// Transcribe an audio file (uses the running daemon when available)
+    #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+    Transcribe {
+        /// Audio file to transcribe, or "-" for stdin
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Audio format (default: detect from content), or FORMAT:RATE:CHANNELS for raw PCM
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// Output format: txt, srt, vtt, json, tsv
+        #[arg(long, value_name = "FORMAT", default_value = "txt")]
+        output_format: TranscriptFormat,
+
+        /// Path to Unix socket (default: $XDG_RUNTIME_DIR/voicsh.sock)
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+
+        /// Always transcribe in-process, even when a daemon is running
+        /// (implied by --model and --language)
+        #[arg(long)]
+        no_daemon: bool,
+    },
+
+    /// Replay an archived session or audio file through the pipeline and diff the text
+    #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+    Replay {
+        /// Session directory, session id (or "latest"), or audio file
+        #[arg(value_name = "SESSION|FILE", default_value = "latest")]
+        target: String,
+
+        /// Whisper model to replay with (default: the configured model)
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Playback speed: 1 = real time, 4 = four times faster
+        #[arg(long, value_name = "FACTOR", default_value = "1")]
+        speed: f32,
     },
 
     /// Start recording via IPC
@@ -140,6 +220,13 @@ pub enum Commands {
         socket: Option<PathBuf>,
     },
 
+    /// Reload the daemon configuration from the config file via IPC
+    Reload {
+        /// Path to Unix socket (default: $XDG_RUNTIME_DIR/voicsh.sock)
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+    },
+
     /// Follow daemon events (live volume meter, recording state, transcriptions)
     Follow {
         /// Path to Unix socket (default: $XDG_RUNTIME_DIR/voicsh.sock)
@@ -233,13 +320,28 @@ pub enum ConfigAction {
     Get {
         /// Dotted key path (e.g., stt.model, audio.sample_rate)
         key: String,
+        /// Read the value from the running daemon instead of the config file
+        #[arg(long)]
+        live: bool,
+        /// Path to Unix socket (default: $XDG_RUNTIME_DIR/voicsh.sock)
+        #[arg(long, value_name = "PATH", requires = "live")]
+        socket: Option<PathBuf>,
     },
     /// Set a configuration value by key
     Set {
         /// Dotted key path (e.g., stt.model, audio.sample_rate)
         key: String,
-        /// Value to set
+        /// Value to set (lists: comma-separated, e.g. en,de; empty unsets optional keys)
         value: String,
+        /// Apply the value to the running daemon immediately (also saves unless --no-save)
+        #[arg(long)]
+        live: bool,
+        /// With --live, change the running daemon only and leave the config file as-is
+        #[arg(long, requires = "live")]
+        no_save: bool,
+        /// Path to Unix socket (default: $XDG_RUNTIME_DIR/voicsh.sock)
+        #[arg(long, value_name = "PATH", requires = "live")]
+        socket: Option<PathBuf>,
     },
     /// List current configuration values (optionally filtered by section or language)
     List {
@@ -251,6 +353,16 @@ pub enum ConfigAction {
     },
     /// Dump a commented configuration template
     Dump,
+    /// Check the config file for unknown keys, type errors and out-of-range values
+    Validate,
+    /// Print a JSON Schema for config.toml (for editor completion, e.g. taplo)
+    Schema,
+    /// Upgrade the config file to the current format (keeps a .bak copy)
+    Migrate {
+        /// Show the changes without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Debug diagnostic actions
@@ -264,6 +376,25 @@ pub enum DebugAction {
     },
 }
 
+/// Session archive actions
+#[derive(Subcommand, Debug)]
+pub enum SessionsAction {
+    /// List archived sessions, newest first
+    List,
+    /// Show chunk boundaries, VAD decisions, timings and text of a session
+    Show {
+        /// Session id, unique id prefix, or "latest"
+        #[arg(default_value = "latest")]
+        id: String,
+    },
+    /// Delete archived sessions
+    Purge {
+        /// Keep the newest N sessions
+        #[arg(long, value_name = "N", default_value = "0")]
+        keep: usize,
+    },
+}
+
 /// Model management actions
 #[derive(Subcommand, Debug)]
 pub enum ModelsAction {
@@ -271,7 +402,7 @@ pub enum ModelsAction {
     List,
     /// Download and install a model
     Install {
-        /// Model name (e.g., base.en, small.en, tiny)
+        /// Model name (e.g., base.en, small.en, tiny, or VAD model silero-v5.1.2)
         name: String,
     },
     /// Set the default STT model
@@ -352,6 +483,22 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "cpal-audio")]
+    fn test_parse_calibrate() {
+        let cli =
+            Cli::try_parse_from(["voicsh", "--device", "hw:1", "calibrate", "--dry-run"]).unwrap();
+        assert_eq!(cli.device.as_deref(), Some("hw:1"));
+        match cli.command {
+            Some(Commands::Calibrate {
+                silence_secs: 3,
+                speech_secs: 5,
+                dry_run: true,
+            }) => {}
+            other => panic!("Expected Calibrate command, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_parse_global_config() {
         let cli = Cli::try_parse_from(["voicsh", "--config", "/path/to/config.toml"]).unwrap();
@@ -451,6 +598,59 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+    fn test_parse_replay() {
+        let cli = Cli::try_parse_from([
+            "voicsh",
+            "--config",
+            "alt.toml",
+            "replay",
+            "/tmp/session",
+            "--model",
+            "small",
+            "--speed",
+            "4",
+        ])
+        .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("alt.toml")));
+        match cli.command {
+            Some(Commands::Replay {
+                target,
+                model,
+                speed,
+            }) => {
+                assert_eq!(target, "/tmp/session");
+                assert_eq!(model.as_deref(), Some("small"));
+                assert_eq!(speed, 4.0);
+            }
+            other => panic!("Expected Replay command, got {other:?}"),
+        }
+        let cli = Cli::try_parse_from(["voicsh", "replay"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Replay { target, model: None, speed }) if target == "latest" && speed == 1.0
+        ));
+    }
+
+    #[test]
+    fn test_parse_sessions_actions() {
+        let cli = Cli::try_parse_from(["voicsh", "sessions", "show"]).unwrap();
+        match cli.command {
+            Some(Commands::Sessions {
+                action: SessionsAction::Show { id },
+            }) => assert_eq!(id, "latest"),
+            other => panic!("Expected Sessions show, got {other:?}"),
+        }
+        let cli = Cli::try_parse_from(["voicsh", "sessions", "purge", "--keep", "3"]).unwrap();
+        match cli.command {
+            Some(Commands::Sessions {
+                action: SessionsAction::Purge { keep: 3 },
+            }) => {}
+            other => panic!("Expected Sessions purge, got {other:?}"),
+        }
+    }
+
     #[test]
     #[cfg(feature = "model-download")]
     fn test_parse_models_install() {
@@ -571,8 +771,14 @@ mod tests {
     fn test_parse_daemon() {
         let cli = Cli::try_parse_from(["voicsh", "daemon"]).unwrap();
         match cli.command {
-            Some(Commands::Daemon { socket }) => {
+            Some(Commands::Daemon {
+                socket,
+                watch_config,
+                wyoming,
+            }) => {
                 assert!(socket.is_none());
+                assert!(!watch_config);
+                assert!(wyoming.is_none());
             }
             _ => panic!("Expected Daemon command"),
         }
@@ -583,13 +789,39 @@ mod tests {
     fn test_parse_daemon_with_socket() {
         let cli = Cli::try_parse_from(["voicsh", "daemon", "--socket", "/tmp/test.sock"]).unwrap();
         match cli.command {
-            Some(Commands::Daemon { socket }) => {
+            Some(Commands::Daemon { socket, .. }) => {
                 assert_eq!(socket, Some(PathBuf::from("/tmp/test.sock")));
             }
             _ => panic!("Expected Daemon command"),
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+    fn test_parse_daemon_watch_config() {
+        let cli = Cli::try_parse_from(["voicsh", "daemon", "--watch-config"]).unwrap();
+        match cli.command {
+            Some(Commands::Daemon { watch_config, .. }) => {
+                assert!(watch_config);
+            }
+            _ => panic!("Expected Daemon command"),
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+    fn test_parse_daemon_wyoming() {
+        let cli =
+            Cli::try_parse_from(["voicsh", "daemon", "--wyoming", "127.0.0.1:10300"]).unwrap();
+        match cli.command {
+            Some(Commands::Daemon { wyoming, .. }) => {
+                assert_eq!(wyoming, Some("127.0.0.1:10300".parse().unwrap()));
+            }
+            _ => panic!("Expected Daemon command"),
+        }
+        assert!(Cli::try_parse_from(["voicsh", "daemon", "--wyoming", "not-an-address"]).is_err());
+    }
+
     #[test]
     fn test_parse_start() {
         let cli = Cli::try_parse_from(["voicsh", "start"]).unwrap();
@@ -634,6 +866,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_reload() {
+        let cli = Cli::try_parse_from(["voicsh", "reload"]).unwrap();
+        match cli.command {
+            Some(Commands::Reload { socket }) => {
+                assert!(socket.is_none());
+            }
+            _ => panic!("Expected Reload command"),
+        }
+    }
+
     #[test]
     fn test_parse_install_service() {
         let cli = Cli::try_parse_from(["voicsh", "install-service"]).unwrap();
@@ -876,6 +1119,90 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+    fn test_parse_transcribe() {
+        let cli = Cli::try_parse_from(["voicsh", "transcribe", "meeting.wav"]).unwrap();
+        match cli.command {
+            Some(Commands::Transcribe {
+                file,
+                format,
+                output_format,
+                socket,
+                no_daemon,
+            }) => {
+                assert_eq!(file, PathBuf::from("meeting.wav"));
+                assert!(format.is_none());
+                assert_eq!(output_format, TranscriptFormat::Txt);
+                assert!(socket.is_none());
+                assert!(!no_daemon);
+            }
+            _ => panic!("Expected Transcribe command"),
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+    fn test_parse_transcribe_stdin_no_daemon() {
+        let cli = Cli::try_parse_from([
+            "voicsh",
+            "transcribe",
+            "-",
+            "--format",
+            "wav",
+            "--no-daemon",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Transcribe {
+                file,
+                format,
+                no_daemon,
+                ..
+            }) => {
+                assert_eq!(file, PathBuf::from("-"));
+                assert_eq!(format.as_deref(), Some("wav"));
+                assert!(no_daemon);
+            }
+            _ => panic!("Expected Transcribe command"),
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+    fn test_parse_transcribe_output_format() {
+        let cli = Cli::try_parse_from([
+            "voicsh",
+            "transcribe",
+            "standup.wav",
+            "--output-format",
+            "srt",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Transcribe { output_format, .. }) => {
+                assert_eq!(output_format, TranscriptFormat::Srt);
+            }
+            _ => panic!("Expected Transcribe command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_pipe_output_format() {
+        let cli = Cli::try_parse_from(["voicsh", "--output-format", "vtt"]).unwrap();
+        assert_eq!(cli.output_format, TranscriptFormat::Vtt);
+        assert!(Cli::try_parse_from(["voicsh", "--output-format", "docx"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_pipe_raw_spec() {
+        let cli = Cli::try_parse_from(["voicsh", "--raw", "s16le:48000:2"]).unwrap();
+        let spec = cli.raw.unwrap();
+        assert_eq!((spec.sample_rate, spec.channels), (48000, 2));
+        assert!(Cli::try_parse_from(["voicsh"]).unwrap().raw.is_none());
+        assert!(Cli::try_parse_from(["voicsh", "--raw", "s16le:fast"]).is_err());
+    }
+
     // ── Buffer parsing tests ─────────────────────────────────────────────
 
     #[test]
@@ -974,8 +1301,9 @@ mod tests {
         let cli = Cli::try_parse_from(["voicsh", "config", "get", "stt.model"]).unwrap();
         match cli.command {
             Some(Commands::Config { action }) => match action {
-                ConfigAction::Get { key } => {
+                ConfigAction::Get { key, live, .. } => {
                     assert_eq!(key, "stt.model");
+                    assert!(!live, "live should default to false");
                 }
                 _ => panic!("Expected Get action"),
             },
@@ -989,9 +1317,18 @@ mod tests {
             Cli::try_parse_from(["voicsh", "config", "set", "stt.model", "small.en"]).unwrap();
         match cli.command {
             Some(Commands::Config { action }) => match action {
-                ConfigAction::Set { key, value } => {
+                ConfigAction::Set {
+                    key,
+                    value,
+                    live,
+                    no_save,
+                    socket,
+                } => {
                     assert_eq!(key, "stt.model");
                     assert_eq!(value, "small.en");
+                    assert!(!live);
+                    assert!(!no_save);
+                    assert!(socket.is_none());
                 }
                 _ => panic!("Expected Set action"),
             },
@@ -999,6 +1336,91 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_config_set_live_no_save() {
+        let cli = Cli::try_parse_from([
+            "voicsh",
+            "config",
+            "set",
+            "--live",
+            "--no-save",
+            "audio.vad_threshold",
+            "0.03",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Config {
+                action:
+                    ConfigAction::Set {
+                        key, live, no_save, ..
+                    },
+            }) => {
+                assert_eq!(key, "audio.vad_threshold");
+                assert!(live);
+                assert!(no_save);
+            }
+            _ => panic!("Expected Config Set command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_config_set_no_save_requires_live() {
+        let result =
+            Cli::try_parse_from(["voicsh", "config", "set", "--no-save", "stt.model", "base"]);
+        assert!(
+            result.is_err(),
+            "--no-save without --live should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_parse_config_get_live() {
+        let cli =
+            Cli::try_parse_from(["voicsh", "config", "get", "--live", "stt.language"]).unwrap();
+        match cli.command {
+            Some(Commands::Config {
+                action: ConfigAction::Get { key, live, .. },
+            }) => {
+                assert_eq!(key, "stt.language");
+                assert!(live);
+            }
+            _ => panic!("Expected Config Get command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_config_validate() {
+        let cli = Cli::try_parse_from(["voicsh", "config", "validate"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::Validate
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_config_schema() {
+        let cli = Cli::try_parse_from(["voicsh", "config", "schema"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::Schema
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_config_migrate_dry_run() {
+        let cli = Cli::try_parse_from(["voicsh", "config", "migrate", "--dry-run"]).unwrap();
+        match cli.command {
+            Some(Commands::Config {
+                action: ConfigAction::Migrate { dry_run },
+            }) => assert!(dry_run),
+            _ => panic!("Expected Config Migrate command"),
+        }
+    }
+
     #[test]
     fn test_parse_config_list() {
         let cli = Cli::try_parse_from(["voicsh", "config", "list"]).unwrap();
@@ -10,15 +10,33 @@ use std::path::PathBuf;
 pub const FILTER_PUNCTUATION: [char; 9] = ['.', '!', '?', ',', ';', '。', '、', '！', '？'];
 
 /// Root configuration structure
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default)]
 pub struct Config {
+    /// Config file format version (see [`crate::config_migration`])
+    pub config_version: u32,
     pub audio: AudioConfig,
     pub stt: SttConfig,
-    #[serde(alias = "input")]
     pub injection: InjectionConfig,
     pub voice_commands: VoiceCommandConfig,
     pub transcription: TranscriptionConfig,
+    pub debug: DebugConfig,
+    pub api: ApiConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            config_version: crate::config_migration::CURRENT_VERSION,
+            audio: AudioConfig::default(),
+            stt: SttConfig::default(),
+            injection: InjectionConfig::default(),
+            voice_commands: VoiceCommandConfig::default(),
+            transcription: TranscriptionConfig::default(),
+            debug: DebugConfig::default(),
+            api: ApiConfig::default(),
+        }
+    }
 }
 
 /// Audio capture configuration
@@ -26,9 +44,43 @@ pub struct Config {
 #[serde(default)]
 pub struct AudioConfig {
     pub device: Option<String>,
+    /// Fallback input devices, most preferred first. Used when `device` fails or
+    /// is unplugged; the system default (`"default"`) is always tried last.
+    pub device_priority: Vec<String>,
     pub sample_rate: u32,
     pub vad_threshold: f32,
     pub silence_duration_ms: u32,
+    /// Voice activity detector: RMS threshold, spectral features or neural model
+    pub vad_backend: VadBackend,
+    /// Neural VAD model name (used when `vad_backend = "neural"`)
+    pub vad_model: String,
+    /// Noise suppression before voice activity detection
+    pub denoise: DenoiseConfig,
+    /// Calibrated VAD thresholds by device name (written by `voicsh calibrate`).
+    /// The system default input device is stored as `"default"`.
+    pub device_thresholds: HashMap<String, f32>,
+}
+
+/// Noise suppression configuration (`[audio.denoise]`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DenoiseConfig {
+    /// Enable spectral noise suppression (applies live, mid-recording).
+    pub enabled: bool,
+    /// Over-subtraction factor: 1.0 removes the estimated noise, up to 2.0 for aggressive suppression.
+    pub strength: f32,
+    /// Maximum attenuation in dB. Lower values remove more noise but add artefacts.
+    pub min_gain_db: f32,
+}
+
+impl Default for DenoiseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            strength: 1.0,
+            min_gain_db: -15.0,
+        }
+    }
 }
 
 /// Speech-to-text configuration
@@ -38,12 +90,189 @@ pub struct SttConfig {
     pub model: String,
     pub language: String,
     pub fan_out: bool,
+    /// How fan-out combines the results of its models.
+    pub fan_out_strategy: FanOutStrategy,
     /// Language allowlist for auto-detect mode. Empty = accept all.
     /// When non-empty, only transcriptions in these languages are accepted.
     pub allowed_languages: Vec<String>,
     /// Minimum confidence threshold. Transcriptions below this are dropped.
     /// 0.0 = accept all (default).
     pub min_confidence: f32,
+    /// In auto mode, keep a running language estimate over the session and
+    /// transcribe in that language instead of detecting each chunk alone.
+    pub language_tracking: bool,
+    /// Per-language models for auto mode: detected language → model name.
+    /// Languages not listed are transcribed with `model`.
+    pub language_models: HashMap<String, String>,
+    /// Memory budget in MB for models loaded through `language_models`.
+    pub language_models_budget_mb: u64,
+    /// Idle time (e.g. `"15m"`) after which the daemon unloads the model
+    /// until the next recording. Unset keeps it loaded.
+    pub unload_after: Option<String>,
+    /// Where transcription runs: a local Whisper model or a remote server.
+    pub backend: SttBackend,
+    /// Remote server settings (used when `backend = "http"`).
+    pub http: HttpSttConfig,
+    /// Streaming cloud settings (used when `backend = "deepgram"`).
+    pub deepgram: DeepgramSttConfig,
+    /// Pure-Rust Whisper settings (used when `backend = "candle"`).
+    pub candle: CandleSttConfig,
+}
+
+/// Speech-to-text engine selection
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SttBackend {
+    /// Local whisper.cpp model (`stt.model`)
+    #[default]
+    Local,
+    /// OpenAI-compatible `/v1/audio/transcriptions` endpoint (`[stt.http]`)
+    Http,
+    /// Deepgram-compatible streaming WebSocket (`[stt.deepgram]`, `cloud-stt` feature)
+    Deepgram,
+    /// Whisper safetensors model run by candle (`[stt.candle]`, `candle-whisper` feature)
+    Candle,
+}
+
+impl std::fmt::Display for SttBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Http => write!(f, "http"),
+            Self::Deepgram => write!(f, "deepgram"),
+            Self::Candle => write!(f, "candle"),
+        }
+    }
+}
+
+impl std::str::FromStr for SttBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "local" => Ok(Self::Local),
+            "http" => Ok(Self::Http),
+            "deepgram" => Ok(Self::Deepgram),
+            "candle" => Ok(Self::Candle),
+            other => Err(format!(
+                "Unknown STT backend '{}'. Valid options: local, http, deepgram, candle",
+                other
+            )),
+        }
+    }
+}
+
+/// How fan-out mode combines the results of its models
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FanOutStrategy {
+    /// Keep the result with the highest confidence
+    #[default]
+    Best,
+    /// Align the word sequences and vote per word, weighted by probability
+    Vote,
+}
+
+impl std::fmt::Display for FanOutStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Best => write!(f, "best"),
+            Self::Vote => write!(f, "vote"),
+        }
+    }
+}
+
+impl std::str::FromStr for FanOutStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "best" => Ok(Self::Best),
+            "vote" => Ok(Self::Vote),
+            other => Err(format!(
+                "Unknown fan-out strategy '{}'. Valid options: best, vote",
+                other
+            )),
+        }
+    }
+}
+
+/// Remote transcription server configuration (`[stt.http]`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HttpSttConfig {
+    /// Full URL of the OpenAI-compatible transcription endpoint.
+    pub url: String,
+    /// Model name sent with each request.
+    pub model: String,
+    /// Bearer token (also read from `VOICSH_STT_API_KEY`).
+    pub api_key: Option<String>,
+    /// Timeout for one request in seconds.
+    pub timeout_secs: u64,
+    /// Retries after a timeout, connection error or 5xx response.
+    pub retries: u32,
+    /// Transcribe with the installed local `stt.model` while the server is unreachable.
+    pub fallback: bool,
+}
+
+impl Default for HttpSttConfig {
+    fn default() -> Self {
+        Self {
+            url: defaults::HTTP_STT_URL.to_string(),
+            model: defaults::HTTP_STT_MODEL.to_string(),
+            api_key: None,
+            timeout_secs: defaults::HTTP_STT_TIMEOUT_SECS,
+            retries: defaults::HTTP_STT_RETRIES,
+            fallback: true,
+        }
+    }
+}
+
+/// Streaming cloud transcription configuration (`[stt.deepgram]`).
+///
+/// The API key is deliberately not a config value: it comes from
+/// `VOICSH_DEEPGRAM_API_KEY`, `DEEPGRAM_API_KEY` or `api_key_file`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DeepgramSttConfig {
+    /// WebSocket URL of the Deepgram-compatible `/v1/listen` endpoint.
+    pub url: String,
+    /// Model requested from the service.
+    pub model: String,
+    /// File holding the API key (e.g. a systemd credential or secrets mount).
+    pub api_key_file: Option<String>,
+    /// Show interim hypotheses while speaking.
+    pub interim_results: bool,
+}
+
+impl Default for DeepgramSttConfig {
+    fn default() -> Self {
+        Self {
+            url: defaults::DEEPGRAM_URL.to_string(),
+            model: defaults::DEEPGRAM_MODEL.to_string(),
+            api_key_file: None,
+            interim_results: true,
+        }
+    }
+}
+
+/// Pure-Rust Whisper configuration (`[stt.candle]`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CandleSttConfig {
+    /// HuggingFace repository with `config.json`, `tokenizer.json` and `model.safetensors`.
+    pub repo: String,
+    /// Beam width; also the number of hypotheses kept.
+    pub beam_size: usize,
+}
+
+impl Default for CandleSttConfig {
+    fn default() -> Self {
+        Self {
+            repo: defaults::CANDLE_WHISPER_REPO.to_string(),
+            beam_size: defaults::CANDLE_WHISPER_BEAM_SIZE,
+        }
+    }
 }
 
 /// Injection configuration
@@ -162,6 +391,120 @@ pub struct HallucinationFilterConfig {
     pub overrides: HashMap<String, Vec<String>>,
 }
 
+/// Debugging aids (`[debug]`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DebugConfig {
+    /// Save each session's audio, chunks and results for later inspection.
+    pub archive_audio: bool,
+    /// Archive location (default: `$XDG_DATA_HOME/voicsh/sessions`).
+    pub archive_dir: Option<String>,
+    /// Keep at most this many sessions; older ones are removed (0 = unlimited).
+    pub max_sessions: u32,
+    /// Keep at most this many megabytes of archived sessions (0 = unlimited).
+    pub max_archive_mb: u32,
+}
+
+impl Default for DebugConfig {
+    fn default() -> Self {
+        Self {
+            archive_audio: false,
+            archive_dir: None,
+            max_sessions: defaults::ARCHIVE_MAX_SESSIONS,
+            max_archive_mb: defaults::ARCHIVE_MAX_MB,
+        }
+    }
+}
+
+/// Local OpenAI-compatible transcription API served by the daemon (`[api]`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ApiConfig {
+    /// Serve `/v1/audio/transcriptions` and `/v1/models` while the daemon runs.
+    pub enabled: bool,
+    /// Loopback `host:port`, or an absolute Unix socket path.
+    pub listen: String,
+    /// Require `Authorization: Bearer <key>` when set.
+    pub api_key: Option<String>,
+    /// Largest accepted upload in megabytes.
+    pub max_upload_mb: u32,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: defaults::API_LISTEN.to_string(),
+            api_key: None,
+            max_upload_mb: defaults::API_MAX_UPLOAD_MB,
+        }
+    }
+}
+
+impl DebugConfig {
+    /// Directory holding archived sessions.
+    ///
+    /// Uses `archive_dir` when set (a leading `~/` is expanded), otherwise
+    /// `~/.local/share/voicsh/sessions/`.
+    pub fn archive_path(&self) -> std::path::PathBuf {
+        let home =
+            || std::path::PathBuf::from(std::env::var("HOME").unwrap_or_else(|_| ".".to_string()));
+        match self.archive_dir.as_deref().filter(|d| !d.is_empty()) {
+            Some(dir) => match dir.strip_prefix("~/") {
+                Some(rest) => home().join(rest),
+                None => std::path::PathBuf::from(dir),
+            },
+            None => std::env::var("XDG_DATA_HOME")
+                .map(std::path::PathBuf::from)
+                .unwrap_or_else(|_| home().join(".local/share"))
+                .join("voicsh")
+                .join("sessions"),
+        }
+    }
+}
+
+/// Voice activity detector selection
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VadBackend {
+    /// Speech when the frame RMS exceeds `vad_threshold`
+    #[default]
+    #[serde(alias = "Rms")]
+    Rms,
+    /// Speech-band energy, zero-crossing rate and spectral flatness, gated by `vad_threshold`
+    #[serde(alias = "Spectral")]
+    Spectral,
+    /// Silero neural model run by whisper.cpp (downloads `vad_model` on first use)
+    #[serde(alias = "Neural")]
+    Neural,
+}
+
+impl std::fmt::Display for VadBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rms => write!(f, "rms"),
+            Self::Spectral => write!(f, "spectral"),
+            Self::Neural => write!(f, "neural"),
+        }
+    }
+}
+
+impl std::str::FromStr for VadBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rms" => Ok(Self::Rms),
+            "spectral" => Ok(Self::Spectral),
+            "neural" => Ok(Self::Neural),
+            other => Err(format!(
+                "Unknown VAD backend '{}'. Valid options: rms, spectral, neural",
+                other
+            )),
+        }
+    }
+}
+
 /// Injection method enumeration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -228,10 +571,65 @@ impl Default for AudioConfig {
     fn default() -> Self {
         Self {
             device: None,
+            device_priority: Vec::new(),
             sample_rate: defaults::SAMPLE_RATE,
             vad_threshold: defaults::VAD_THRESHOLD,
             silence_duration_ms: defaults::SILENCE_DURATION_MS,
+            vad_backend: VadBackend::default(),
+            vad_model: defaults::DEFAULT_VAD_MODEL.to_string(),
+            denoise: DenoiseConfig::default(),
+            device_thresholds: HashMap::new(),
+        }
+    }
+}
+
+impl AudioConfig {
+    /// Key of the configured device in `device_thresholds`.
+    pub fn device_key(&self) -> &str {
+        self.device
+            .as_deref()
+            .unwrap_or(defaults::DEFAULT_DEVICE_KEY)
+    }
+
+    /// Input devices to try, most preferred first.
+    ///
+    /// `device` (if set), then `device_priority`, then the system default,
+    /// without duplicates.
+    pub fn device_chain(&self) -> Vec<String> {
+        let mut chain: Vec<String> = Vec::new();
+        let candidates = self
+            .device
+            .iter()
+            .chain(&self.device_priority)
+            .map(String::as_str)
+            .chain([defaults::DEFAULT_DEVICE_KEY]);
+        for name in candidates {
+            if !chain.iter().any(|c| c == name) {
+                chain.push(name.to_string());
+            }
         }
+        chain
+    }
+
+    /// Calibrated threshold for the configured device, if it has one.
+    pub fn calibrated_threshold(&self) -> Option<f32> {
+        self.calibrated_threshold_for(self.device_key())
+    }
+
+    /// Calibrated threshold for `device` (a `device_thresholds` key), if it has one.
+    pub fn calibrated_threshold_for(&self, device: &str) -> Option<f32> {
+        self.device_thresholds.get(device).copied()
+    }
+
+    /// VAD threshold for the configured device: its calibrated value, else `vad_threshold`.
+    pub fn effective_vad_threshold(&self) -> f32 {
+        self.vad_threshold_for(self.device_key())
+    }
+
+    /// VAD threshold for `device`: its calibrated value, else `vad_threshold`.
+    pub fn vad_threshold_for(&self, device: &str) -> f32 {
+        self.calibrated_threshold_for(device)
+            .unwrap_or(self.vad_threshold)
     }
 }
 
@@ -241,12 +639,37 @@ impl Default for SttConfig {
             model: defaults::DEFAULT_MODEL.to_string(),
             language: defaults::DEFAULT_LANGUAGE.to_string(),
             fan_out: false,
+            fan_out_strategy: FanOutStrategy::default(),
+            language_tracking: true,
+            language_models: HashMap::new(),
+            language_models_budget_mb: defaults::LANGUAGE_MODELS_BUDGET_MB,
+            unload_after: None,
             allowed_languages: Vec::new(),
             min_confidence: 0.0,
+            backend: SttBackend::default(),
+            http: HttpSttConfig::default(),
+            deepgram: DeepgramSttConfig::default(),
+            candle: CandleSttConfig::default(),
         }
     }
 }
 
+impl SttConfig {
+    /// Whether recordings track the session language (`language_tracking`
+    /// only applies to automatic detection).
+    pub fn tracks_language(&self) -> bool {
+        self.language_tracking && self.language == defaults::AUTO_LANGUAGE
+    }
+
+    /// Parsed `unload_after`; `None` when unset, zero or invalid.
+    pub fn unload_after(&self) -> Option<std::time::Duration> {
+        self.unload_after
+            .as_deref()
+            .and_then(parse_duration)
+            .filter(|d| !d.is_zero())
+    }
+}
+
 impl Default for InjectionConfig {
     fn default() -> Self {
         Self {
@@ -257,11 +680,16 @@ impl Default for InjectionConfig {
     }
 }
 
+/// Placeholder shown instead of a secret value.
+pub const REDACTED: &str = "<redacted>";
+
 impl Config {
     /// Load configuration from a TOML file
     ///
     /// Returns an error if the file contains invalid TOML.
-    /// Missing fields will use default values.
+    /// Missing fields will use default values. Files written in an older
+    /// format are migrated in memory; the file itself is left untouched
+    /// until `voicsh config migrate`.
     pub fn load(path: &Path) -> crate::error::Result<Self> {
         let contents = fs::read_to_string(path).map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
@@ -272,6 +700,16 @@ impl Config {
                 crate::error::VoicshError::Io(e)
             }
         })?;
+        let contents = if crate::config_migration::text_version(&contents)
+            < crate::config_migration::CURRENT_VERSION
+        {
+            // Syntax errors fall through so toml reports them below
+            crate::config_migration::migrate_str(&contents)
+                .map(|m| m.text)
+                .unwrap_or(contents)
+        } else {
+            contents
+        };
         let config: Config = toml::from_str(&contents)?;
         Ok(config)
     }
@@ -297,6 +735,7 @@ impl Config {
     /// - VOICSH_LANGUAGE → stt.language
     /// - VOICSH_AUDIO_DEVICE → audio.device
     /// - VOICSH_BACKEND → injection.backend
+    /// - VOICSH_STT_API_KEY → stt.http.api_key
     pub fn with_env_overrides(mut self) -> Self {
         if let Ok(model) = std::env::var("VOICSH_MODEL")
             && !model.is_empty()
@@ -310,6 +749,12 @@ impl Config {
             self.stt.language = language;
         }
 
+        if let Ok(key) = std::env::var("VOICSH_STT_API_KEY")
+            && !key.is_empty()
+        {
+            self.stt.http.api_key = Some(key);
+        }
+
         if let Ok(device) = std::env::var("VOICSH_AUDIO_DEVICE")
             && !device.is_empty()
         {
@@ -384,47 +829,187 @@ impl Config {
         config.save(path)
     }
 
+    /// Load existing config from `path` (or defaults if missing), store a
+    /// calibrated VAD threshold for `device_key`, and save back.
+    ///
+    /// This is used by `voicsh calibrate`.
+    pub fn update_device_threshold(
+        path: &Path,
+        device_key: &str,
+        threshold: f32,
+    ) -> crate::error::Result<()> {
+        let mut config = match Self::load(path) {
+            Ok(cfg) => cfg,
+            Err(crate::error::VoicshError::ConfigFileNotFound { .. }) => Self::default(),
+            Err(e) => return Err(e),
+        };
+        config
+            .audio
+            .device_thresholds
+            .insert(device_key.to_string(), threshold);
+        config.save(path)
+    }
+
     /// Get a config value by dotted path (e.g. "stt.model").
     pub fn get_value_by_path(&self, key: &str) -> crate::error::Result<String> {
+        self.get_toml_value(key).map(|v| format_toml_value(&v))
+    }
+
+    /// Get a config value by dotted path as a TOML value.
+    pub fn get_toml_value(&self, key: &str) -> crate::error::Result<toml::Value> {
         let value = toml::Value::try_from(self).map_err(|e| {
             crate::error::VoicshError::ConfigSerialize {
                 message: format!("TOML value conversion failed: {e}"),
             }
         })?;
-        let leaf = navigate_toml_path(&value, key)?;
-        Ok(format_toml_value(leaf))
+        navigate_toml_path(&value, key).cloned()
     }
 
-    /// Set a config value by dotted path and save.
+    /// Return a copy with secrets (API keys) replaced by a placeholder, for
+    /// showing the config to IPC clients.
+    pub fn redacted(&self) -> Config {
+        let mut config = self.clone();
+        for secret in [&mut config.stt.http.api_key, &mut config.api.api_key] {
+            if secret.is_some() {
+                *secret = Some(REDACTED.to_string());
+            }
+        }
+        config
+    }
+
+    /// Return a copy of this config with one value changed.
     ///
-    /// Loads the existing config (or defaults), sets the value, validates
-    /// by deserializing back, then saves.
-    pub fn set_value_by_path(path: &Path, key: &str, value_str: &str) -> crate::error::Result<()> {
-        let config = match Self::load(path) {
-            Ok(cfg) => cfg,
-            Err(crate::error::VoicshError::ConfigFileNotFound { .. }) => Self::default(),
-            Err(e) => return Err(e),
-        };
+    /// The raw string is parsed and validated against
+    /// [`config_schema`](crate::config_schema) (type, range, allowed values),
+    /// then the result is checked by deserializing back. An empty value for
+    /// an optional key (e.g. `audio.device`) unsets it.
+    pub fn with_value(&self, key: &str, raw: &str) -> crate::error::Result<Config> {
+        let spec = crate::config_schema::find_key(key).ok_or_else(|| {
+            crate::error::VoicshError::ConfigInvalidValue {
+                key: key.to_string(),
+                message: "unknown config key".to_string(),
+            }
+        })?;
+        let parsed = spec.parse(raw, key)?;
 
-        let mut root = toml::Value::try_from(&config).map_err(|e| {
+        let mut root = toml::Value::try_from(self).map_err(|e| {
             crate::error::VoicshError::ConfigSerialize {
                 message: format!("TOML value conversion failed: {e}"),
             }
         })?;
+        match parsed {
+            Some(value) => set_toml_path(&mut root, key, value)?,
+            None => remove_toml_path(&mut root, key),
+        }
 
-        let new_value = parse_toml_value(value_str);
-        set_toml_path(&mut root, key, new_value)?;
+        root.try_into().map_err(|e: toml::de::Error| {
+            crate::error::VoicshError::ConfigInvalidValue {
+                key: key.to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
 
-        // Validate by deserializing back
-        let toml_str = toml::to_string_pretty(&root).map_err(|e| {
-            crate::error::VoicshError::ConfigSerialize {
-                message: format!("TOML serialization failed: {e}"),
+    /// List the dotted keys whose values differ between `self` and `other`.
+    ///
+    /// Tables are compared recursively; arrays and scalars are compared as
+    /// whole leaf values. Keys present on only one side are reported too.
+    /// The result is sorted for stable output.
+    pub fn diff_keys(&self, other: &Config) -> crate::error::Result<Vec<String>> {
+        let to_value = |cfg: &Config| {
+            toml::Value::try_from(cfg).map_err(|e| crate::error::VoicshError::ConfigSerialize {
+                message: format!("TOML value conversion failed: {e}"),
+            })
+        };
+        let old = to_value(self)?;
+        let new = to_value(other)?;
+
+        let mut keys = Vec::new();
+        collect_changed_keys(Some(&old), Some(&new), "", &mut keys);
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Return a copy of this config with the values of `keys` taken from `other`.
+    ///
+    /// Keys absent from `other` (e.g. an unset optional value or a removed
+    /// map entry) are removed from the copy.
+    pub fn with_values_from(
+        &self,
+        other: &Config,
+        keys: &[String],
+    ) -> crate::error::Result<Config> {
+        let to_value = |cfg: &Config| {
+            toml::Value::try_from(cfg).map_err(|e| crate::error::VoicshError::ConfigSerialize {
+                message: format!("TOML value conversion failed: {e}"),
+            })
+        };
+        let mut root = to_value(self)?;
+        let source = to_value(other)?;
+        for key in keys {
+            match navigate_toml_path(&source, key) {
+                Ok(value) => set_toml_path(&mut root, key, value.clone())?,
+                Err(_) => remove_toml_path(&mut root, key),
             }
-        })?;
-        let _validated: Config = toml::from_str(&toml_str)?;
+        }
+
+        root.try_into().map_err(|e: toml::de::Error| {
+            crate::error::VoicshError::ConfigInvalidValue {
+                key: keys.join(", "),
+                message: e.to_string(),
+            }
+        })
+    }
+
+    /// Set a config value by dotted path and save.
+    ///
+    /// Loads the existing config (or defaults), sets and validates the value
+    /// via [`Config::with_value`], then writes only that key back, keeping
+    /// the rest of the file (comments, formatting) as it is. A missing file
+    /// or one in an older format is written out whole instead. Keys missing
+    /// from the schema only produce a warning here; the value is set untyped
+    /// and the result checked by deserializing, as before schema validation
+    /// existed.
+    pub fn set_value_by_path(path: &Path, key: &str, value_str: &str) -> crate::error::Result<()> {
+        let config = match Self::load(path) {
+            Ok(cfg) => cfg,
+            Err(crate::error::VoicshError::ConfigFileNotFound { .. }) => Self::default(),
+            Err(e) => return Err(e),
+        };
+        let (validated, value) = if crate::config_schema::find_key(key).is_some() {
+            let updated = config.with_value(key, value_str)?;
+            let value = updated.get_toml_value(key).ok();
+            (updated, value)
+        } else {
+            eprintln!("voicsh: warning: '{key}' is not a known config key");
+            let value = parse_toml_value(value_str);
+            let mut root = toml::Value::try_from(&config).map_err(|e| {
+                crate::error::VoicshError::ConfigSerialize {
+                    message: format!("TOML value conversion failed: {e}"),
+                }
+            })?;
+            set_toml_path(&mut root, key, value.clone())?;
+            let validated: Config = root.try_into().map_err(|e: toml::de::Error| {
+                crate::error::VoicshError::ConfigInvalidValue {
+                    key: key.to_string(),
+                    message: e.to_string(),
+                }
+            })?;
+            (validated, Some(value))
+        };
 
-        // Save the validated config
-        _validated.save(path)
+        match fs::read_to_string(path) {
+            Ok(contents)
+                if crate::config_migration::text_version(&contents)
+                    >= crate::config_migration::CURRENT_VERSION =>
+            {
+                let updated = set_value_in_document(&contents, key, value.as_ref())?;
+                fs::write(path, updated).map_err(|e| crate::error::VoicshError::ConfigSerialize {
+                    message: format!("Failed to write config to '{}': {e}", path.display()),
+                })
+            }
+            _ => validated.save(path),
+        }
     }
 
     /// Serialize this configuration to pretty TOML for display.
@@ -518,9 +1103,15 @@ impl Config {
         out.push_str("# voicsh configuration\n");
         out.push_str("# Save to: ~/.config/voicsh/config.toml\n");
         out.push('\n');
+        out.push_str(&format!(
+            "config_version = {}  # Config format version (managed by `voicsh config migrate`)\n",
+            crate::config_migration::CURRENT_VERSION
+        ));
+        out.push('\n');
 
         out.push_str("[audio]\n");
         out.push_str("# device = \"hw:0,0\"  # Audio input device (default: system default)\n");
+        out.push_str("# device_priority = [\"USB Headset\", \"default\"]  # Fallbacks when the device is unplugged; switches back when a preferred one returns\n");
         out.push_str(&format!(
             "# sample_rate = {}  # Sample rate in Hz\n",
             defaults::SAMPLE_RATE
@@ -533,6 +1124,28 @@ impl Config {
             "# silence_duration_ms = {}  # Silence before speech end (ms)\n",
             defaults::SILENCE_DURATION_MS
         ));
+        out.push_str(
+            "# vad_backend = \"rms\"  # Speech detector: rms (level only), spectral (ignores fans, typing), neural\n",
+        );
+        out.push_str(&format!(
+            "# vad_model = \"{}\"  # Neural VAD model (downloaded on first use)\n",
+            defaults::DEFAULT_VAD_MODEL
+        ));
+        out.push('\n');
+
+        out.push_str("[audio.denoise]\n");
+        out.push_str(
+            "# enabled = false  # Spectral noise suppression before VAD (switchable live)\n",
+        );
+        out.push_str("# strength = 1.0  # Over-subtraction factor (0.0-2.0)\n");
+        out.push_str("# min_gain_db = -15.0  # Maximum attenuation in dB (-60.0-0.0)\n");
+        out.push('\n');
+
+        out.push_str("# [audio.device_thresholds]  # Per-device VAD thresholds, written by `voicsh calibrate`\n");
+        out.push_str(
+            "# default = 0.015  # Overrides vad_threshold and auto-leveling for this device\n",
+        );
+        out.push_str("# \"hw:1,0\" = 0.03\n");
         out.push('\n');
 
         out.push_str("[stt]\n");
@@ -545,12 +1158,76 @@ impl Config {
             defaults::DEFAULT_LANGUAGE
         ));
         out.push_str("# fan_out = false  # Run multilingual + English models in parallel\n");
+        out.push_str("# fan_out_strategy = \"best\"  # Combine fan-out results: best (highest confidence) or vote (per word)\n");
         out.push_str(
             "# allowed_languages = [\"en\", \"de\"]  # Only accept these languages (empty = all)\n",
         );
         out.push_str(
             "# min_confidence = 0.0  # Minimum confidence threshold (0.0-1.0, 0 = accept all)\n",
         );
+        out.push_str(
+            "# backend = \"local\"  # Transcription engine: local (whisper.cpp), http (remote server), deepgram (streaming cloud) or candle (pure Rust)\n",
+        );
+        out.push_str("# unload_after = \"15m\"  # Daemon: unload the model when idle this long, reload on the next recording\n");
+        out.push_str("# language_tracking = true  # With language = \"auto\", stick to the session language unless clearly switched\n");
+        out.push_str("#   (costs a detection pass per chunk: an extra encoder run with whisper.cpp, one decoder step with candle)\n");
+        out.push_str(&format!(
+            "# language_models_budget_mb = {}  # Memory for models loaded via [stt.language_models]\n",
+            defaults::LANGUAGE_MODELS_BUDGET_MB
+        ));
+        out.push('\n');
+
+        out.push_str("# [stt.language_models]  # With language = \"auto\", transcribe each detected language with its own model\n");
+        out.push_str("# de = \"large-v3-turbo-q5_0\"\n");
+        out.push_str("# en = \"small.en\"\n");
+        out.push('\n');
+
+        out.push_str("[stt.http]\n");
+        out.push_str(&format!(
+            "# url = \"{}\"  # OpenAI-compatible transcription endpoint\n",
+            defaults::HTTP_STT_URL
+        ));
+        out.push_str(&format!(
+            "# model = \"{}\"  # Model name sent to the server\n",
+            defaults::HTTP_STT_MODEL
+        ));
+        out.push_str("# api_key = \"sk-...\"  # Bearer token (or set VOICSH_STT_API_KEY)\n");
+        out.push_str(&format!(
+            "# timeout_secs = {}  # Per-request timeout\n",
+            defaults::HTTP_STT_TIMEOUT_SECS
+        ));
+        out.push_str(&format!(
+            "# retries = {}  # Retries after timeouts, connection errors and 5xx responses\n",
+            defaults::HTTP_STT_RETRIES
+        ));
+        out.push_str("# fallback = true  # Use the installed local stt.model while the server is unreachable\n");
+        out.push('\n');
+
+        out.push_str("[stt.deepgram]\n");
+        out.push_str(
+            "# Results are filtered like local ones; session archives keep only audio and VAD\n",
+        );
+        out.push_str(&format!(
+            "# url = \"{}\"  # Deepgram-compatible streaming endpoint\n",
+            defaults::DEEPGRAM_URL
+        ));
+        out.push_str(&format!(
+            "# model = \"{}\"  # Model requested from the service\n",
+            defaults::DEEPGRAM_MODEL
+        ));
+        out.push_str("# api_key_file = \"/run/secrets/deepgram\"  # API key file (or set VOICSH_DEEPGRAM_API_KEY / DEEPGRAM_API_KEY)\n");
+        out.push_str("# interim_results = true  # Show interim hypotheses while speaking\n");
+        out.push('\n');
+
+        out.push_str("[stt.candle]\n");
+        out.push_str(&format!(
+            "# repo = \"{}\"  # HuggingFace repo with a safetensors Whisper model\n",
+            defaults::CANDLE_WHISPER_REPO
+        ));
+        out.push_str(&format!(
+            "# beam_size = {}  # Beam width and number of hypotheses kept\n",
+            defaults::CANDLE_WHISPER_BEAM_SIZE
+        ));
         out.push('\n');
 
         out.push_str("[injection]\n");
@@ -629,11 +1306,67 @@ impl Config {
             let quoted: Vec<String> = phrases.iter().map(|p| format!("\"{}\"", p)).collect();
             out.push_str(&format!("# {} = [{}]\n", lang, quoted.join(", ")));
         }
+        out.push('\n');
+
+        out.push_str("[debug]\n");
+        out.push_str("# archive_audio = false  # Save each session's audio, chunks and results (see `voicsh sessions`)\n");
+        out.push_str("# archive_dir = \"~/.local/share/voicsh/sessions\"  # Where archived sessions are stored\n");
+        out.push_str(&format!(
+            "# max_sessions = {}  # Keep at most this many sessions (0 = unlimited)\n",
+            defaults::ARCHIVE_MAX_SESSIONS
+        ));
+        out.push_str(&format!(
+            "# max_archive_mb = {}  # Keep at most this many MB of archived audio (0 = unlimited)\n",
+            defaults::ARCHIVE_MAX_MB
+        ));
+        out.push('\n');
+
+        out.push_str("[api]\n");
+        out.push_str("# enabled = false  # Serve the daemon's model as an OpenAI-compatible API\n");
+        out.push_str(&format!(
+            "# listen = \"{}\"  # Loopback host:port, or an absolute Unix socket path\n",
+            defaults::API_LISTEN
+        ));
+        out.push_str("# api_key = \"...\"  # Require this bearer token from clients\n");
+        out.push_str(&format!(
+            "# max_upload_mb = {}  # Largest accepted audio upload\n",
+            defaults::API_MAX_UPLOAD_MB
+        ));
 
         out
     }
 }
 
+/// Parse a duration such as `90s`, `15m`, `2h` or `1h30m`.
+///
+/// A bare number is seconds. Returns `None` for anything else.
+pub fn parse_duration(s: &str) -> Option<std::time::Duration> {
+    let s = s.trim();
+    if let Ok(secs) = s.parse::<u64>() {
+        return Some(std::time::Duration::from_secs(secs));
+    }
+    let mut total: u64 = 0;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let value: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit_secs = match &rest[..unit_len] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            "d" => 86_400,
+            _ => return None,
+        };
+        total = total.checked_add(value.checked_mul(unit_secs)?)?;
+        rest = &rest[unit_len..];
+    }
+    (!s.is_empty()).then(|| std::time::Duration::from_secs(total))
+}
+
 /// Map a language code to a human-readable name.
 fn language_name(code: &str) -> &'static str {
     match code {
@@ -782,6 +1515,30 @@ fn navigate_toml_path<'a>(
     Ok(current)
 }
 
+/// Recursively collect dotted keys whose leaf values differ between two TOML trees.
+fn collect_changed_keys(
+    old: Option<&toml::Value>,
+    new: Option<&toml::Value>,
+    prefix: &str,
+    out: &mut Vec<String>,
+) {
+    match (old, new) {
+        (Some(toml::Value::Table(a)), Some(toml::Value::Table(b))) => {
+            let keys: std::collections::BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                collect_changed_keys(a.get(key), b.get(key), &path, out);
+            }
+        }
+        (a, b) if a != b => out.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
 /// Set a value in a TOML tree at a dotted path, creating intermediate tables as needed.
 fn set_toml_path(
     root: &mut toml::Value,
@@ -817,6 +1574,74 @@ fn set_toml_path(
     Ok(())
 }
 
+/// Set (or, for `None`, remove) the value at a dotted path in TOML text,
+/// leaving everything else in the document untouched.
+fn set_value_in_document(
+    contents: &str,
+    path: &str,
+    value: Option<&toml::Value>,
+) -> crate::error::Result<String> {
+    let invalid = |message: String| crate::error::VoicshError::ConfigInvalidValue {
+        key: path.to_string(),
+        message,
+    };
+    let mut doc: toml_edit::DocumentMut = contents
+        .parse()
+        .map_err(|e: toml_edit::TomlError| invalid(e.to_string()))?;
+    let (parents, leaf) = match path.rsplit_once('.') {
+        Some((parents, leaf)) => (parents.split('.').collect::<Vec<_>>(), leaf),
+        None => (Vec::new(), path),
+    };
+
+    let mut current: &mut dyn toml_edit::TableLike = doc.as_table_mut();
+    for (i, part) in parents.iter().enumerate() {
+        if value.is_none() && current.get(part).is_none() {
+            return Ok(doc.to_string());
+        }
+        current = current
+            .entry(part)
+            .or_insert_with(|| {
+                let mut table = toml_edit::Table::new();
+                table.set_implicit(true);
+                toml_edit::Item::Table(table)
+            })
+            .as_table_like_mut()
+            .ok_or_else(|| invalid(format!("'{}' is not a table", parents[..=i].join("."))))?;
+    }
+
+    match value {
+        Some(value) => {
+            let value: toml_edit::Value = value
+                .to_string()
+                .parse()
+                .map_err(|e: toml_edit::TomlError| invalid(e.to_string()))?;
+            current.insert(leaf, toml_edit::Item::Value(value));
+        }
+        None => {
+            current.remove(leaf);
+        }
+    }
+    Ok(doc.to_string())
+}
+
+/// Remove the value at a dotted path from a TOML tree, if present.
+fn remove_toml_path(root: &mut toml::Value, path: &str) {
+    let (parent, leaf) = match path.rsplit_once('.') {
+        Some((parent, leaf)) => (Some(parent), leaf),
+        None => (None, path),
+    };
+    let mut current = root;
+    for part in parent.into_iter().flat_map(|p| p.split('.')) {
+        match current.get_mut(part) {
+            Some(next) => current = next,
+            None => return,
+        }
+    }
+    if let toml::Value::Table(table) = current {
+        table.remove(leaf);
+    }
+}
+
 /// Parse a string into a TOML value, trying integer, float, bool, then string.
 fn parse_toml_value(s: &str) -> toml::Value {
     if let Ok(i) = s.parse::<i64>() {
@@ -1490,6 +2315,7 @@ mod tests {
                 fan_out: false,
                 allowed_languages: Vec::new(),
                 min_confidence: 0.0,
+                ..SttConfig::default()
             },
             ..Config::default()
         };
@@ -1514,6 +2340,7 @@ mod tests {
                 fan_out: true,
                 allowed_languages: Vec::new(),
                 min_confidence: 0.0,
+                ..SttConfig::default()
             },
             audio: AudioConfig {
                 vad_threshold: 0.05,
@@ -1547,6 +2374,217 @@ mod tests {
         assert_eq!(reloaded.stt.language, "auto"); // default
     }
 
+    #[test]
+    fn test_device_chain_order_and_dedup() {
+        let mut audio = AudioConfig::default();
+        assert_eq!(audio.device_chain(), vec!["default"]);
+
+        audio.device_priority = vec!["usb".to_string(), "default".to_string(), "hw:1".to_string()];
+        assert_eq!(audio.device_chain(), vec!["usb", "default", "hw:1"]);
+
+        audio.device = Some("hw:1".to_string());
+        assert_eq!(audio.device_chain(), vec!["hw:1", "usb", "default"]);
+    }
+
+    #[test]
+    fn test_stt_http_section_parses() {
+        let config: Config = toml::from_str(
+            r#"
+            [stt]
+            backend = "http"
+
+            [stt.http]
+            url = "http://gpu-box:8000/v1/audio/transcriptions"
+            retries = 0
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.stt.backend, SttBackend::Http);
+        assert_eq!(
+            config.stt.http.url,
+            "http://gpu-box:8000/v1/audio/transcriptions"
+        );
+        assert_eq!(config.stt.http.retries, 0);
+        assert_eq!(config.stt.http.model, defaults::HTTP_STT_MODEL);
+        assert!(config.stt.http.fallback);
+        assert_eq!(Config::default().stt.backend, SttBackend::Local);
+        assert_eq!("HTTP".parse::<SttBackend>(), Ok(SttBackend::Http));
+        assert!("grpc".parse::<SttBackend>().is_err());
+    }
+
+    #[test]
+    fn test_stt_deepgram_section_parses() {
+        let config: Config = toml::from_str(
+            r#"
+            [stt]
+            backend = "deepgram"
+
+            [stt.deepgram]
+            api_key_file = "/run/secrets/deepgram"
+            interim_results = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.stt.backend, SttBackend::Deepgram);
+        assert_eq!(
+            config.stt.deepgram.api_key_file.as_deref(),
+            Some("/run/secrets/deepgram")
+        );
+        assert!(!config.stt.deepgram.interim_results);
+        assert_eq!(config.stt.deepgram.url, defaults::DEEPGRAM_URL);
+        assert_eq!(SttBackend::Deepgram.to_string(), "deepgram");
+    }
+
+    #[test]
+    fn test_fan_out_strategy_parses() {
+        let config: Config = toml::from_str(
+            r#"
+            [stt]
+            fan_out = true
+            fan_out_strategy = "vote"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.stt.fan_out_strategy, FanOutStrategy::Vote);
+        assert_eq!(Config::default().stt.fan_out_strategy, FanOutStrategy::Best);
+        assert_eq!("BEST".parse::<FanOutStrategy>(), Ok(FanOutStrategy::Best));
+        assert!("rover".parse::<FanOutStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_stt_candle_section_parses() {
+        let config: Config = toml::from_str(
+            r#"
+            [stt]
+            backend = "candle"
+
+            [stt.candle]
+            repo = "distil-whisper/distil-small.en"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.stt.backend, SttBackend::Candle);
+        assert_eq!(config.stt.candle.repo, "distil-whisper/distil-small.en");
+        assert_eq!(
+            config.stt.candle.beam_size,
+            defaults::CANDLE_WHISPER_BEAM_SIZE
+        );
+        assert_eq!("candle".parse::<SttBackend>(), Ok(SttBackend::Candle));
+    }
+
+    #[test]
+    fn test_stt_language_models_parse() {
+        let config: Config = toml::from_str(
+            r#"
+            [stt]
+            language_models_budget_mb = 1024
+
+            [stt.language_models]
+            de = "large-v3-turbo-q5_0"
+            en = "small.en"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.stt.language_models.len(), 2);
+        assert_eq!(config.stt.language_models["de"], "large-v3-turbo-q5_0");
+        assert_eq!(config.stt.language_models["en"], "small.en");
+        assert_eq!(config.stt.language_models_budget_mb, 1024);
+        assert!(Config::default().stt.language_models.is_empty());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        use std::time::Duration;
+        assert_eq!(parse_duration("15m"), Some(Duration::from_secs(900)));
+        assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration(" 2d "), Some(Duration::from_secs(172_800)));
+        assert_eq!(parse_duration("0s"), Some(Duration::ZERO));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("15 minutes"), None);
+        assert_eq!(parse_duration("-5m"), None);
+    }
+
+    #[test]
+    fn test_stt_unload_after() {
+        assert_eq!(Config::default().stt.unload_after(), None);
+        let config: Config = toml::from_str("[stt]\nunload_after = \"15m\"\n").unwrap();
+        assert_eq!(
+            config.stt.unload_after(),
+            Some(std::time::Duration::from_secs(900))
+        );
+        let zero: Config = toml::from_str("[stt]\nunload_after = \"0\"\n").unwrap();
+        assert_eq!(zero.stt.unload_after(), None);
+    }
+
+    #[test]
+    fn test_stt_language_tracking_defaults_on() {
+        assert!(Config::default().stt.language_tracking);
+        let config: Config = toml::from_str("[stt]\nlanguage_tracking = false\n").unwrap();
+        assert!(!config.stt.language_tracking);
+        assert!(Config::default().stt.tracks_language());
+        let fixed: Config = toml::from_str("[stt]\nlanguage = \"de\"\n").unwrap();
+        assert!(!fixed.stt.tracks_language());
+    }
+
+    #[test]
+    fn test_debug_section_parses_and_resolves_archive_dir() {
+        let config: Config = toml::from_str(
+            r#"
+            [debug]
+            archive_audio = true
+            archive_dir = "/tmp/voicsh-sessions"
+            max_sessions = 5
+            "#,
+        )
+        .unwrap();
+        assert!(config.debug.archive_audio);
+        assert_eq!(config.debug.max_sessions, 5);
+        assert_eq!(config.debug.max_archive_mb, defaults::ARCHIVE_MAX_MB);
+        assert_eq!(
+            config.debug.archive_path(),
+            std::path::PathBuf::from("/tmp/voicsh-sessions")
+        );
+
+        let debug = DebugConfig {
+            archive_dir: Some("~/debug/sessions".to_string()),
+            ..Default::default()
+        };
+        let path = debug.archive_path();
+        assert!(path.ends_with("debug/sessions"), "{path:?}");
+        assert!(!path.starts_with("~"), "{path:?}");
+
+        let default_path = DebugConfig::default().archive_path();
+        assert!(
+            default_path.ends_with("voicsh/sessions"),
+            "{default_path:?}"
+        );
+        assert!(!Config::default().debug.archive_audio);
+    }
+
+    #[test]
+    fn test_update_device_threshold_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        Config::update_model(&path, "tiny").unwrap();
+
+        Config::update_device_threshold(&path, "default", 0.012).unwrap();
+        Config::update_device_threshold(&path, "hw:1,0", 0.034).unwrap();
+
+        let mut reloaded = Config::load(&path).unwrap();
+        assert_eq!(reloaded.stt.model, "tiny"); // preserved
+        assert_eq!(reloaded.audio.calibrated_threshold(), Some(0.012));
+        reloaded.audio.device = Some("hw:1,0".to_string());
+        assert_eq!(reloaded.audio.effective_vad_threshold(), 0.034);
+        reloaded.audio.device = Some("usb".to_string());
+        assert_eq!(reloaded.audio.calibrated_threshold(), None);
+        assert_eq!(
+            reloaded.audio.effective_vad_threshold(),
+            reloaded.audio.vad_threshold
+        );
+    }
+
     #[test]
     fn test_save_creates_parent_directories() {
         let dir = tempfile::tempdir().unwrap();
@@ -1726,6 +2764,124 @@ mod tests {
         assert_eq!(reloaded.audio.sample_rate, 16000);
     }
 
+    #[test]
+    fn test_set_value_by_path_keeps_rest_of_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let original = format!(
+            "config_version = {}\n\n# my model\n[stt]\nmodel = \"base\"  # fast enough\n\n[audio]\ndevice = \"usb\"\n",
+            crate::config_migration::CURRENT_VERSION
+        );
+        std::fs::write(&path, &original).unwrap();
+
+        Config::set_value_by_path(&path, "audio.vad_threshold", "0.2").unwrap();
+        Config::set_value_by_path(&path, "stt.deepgram.model", "nova-3").unwrap();
+        Config::set_value_by_path(&path, "audio.device", "").unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            written.starts_with(&original[..original.find("[audio]").unwrap()]),
+            "got: {written}"
+        );
+        assert!(!written.contains("device"), "got: {written}");
+        let reloaded = Config::load(&path).unwrap();
+        assert_eq!(reloaded.audio.vad_threshold, 0.2);
+        assert_eq!(reloaded.stt.deepgram.model, "nova-3");
+        assert_eq!(reloaded.audio.device, None);
+    }
+
+    #[test]
+    fn test_set_value_by_path_rejects_invalid_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        Config::default().save(&path).unwrap();
+
+        let result = Config::set_value_by_path(&path, "audio.vad_threshold", "2.0");
+        assert!(result.is_err(), "out-of-range value should be rejected");
+        let reloaded = Config::load(&path).unwrap();
+        assert_eq!(reloaded, Config::default(), "file should be untouched");
+    }
+
+    #[test]
+    fn test_set_value_by_path_accepts_key_outside_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        Config::default().save(&path).unwrap();
+
+        // Offline edits warn about unknown keys instead of failing
+        Config::set_value_by_path(&path, "stt.not_a_key", "42").unwrap();
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+        assert!(Config::default().with_value("stt.not_a_key", "42").is_err());
+    }
+
+    #[test]
+    fn test_with_value_sets_list_and_enum() {
+        let config = Config::default()
+            .with_value("stt.allowed_languages", "en,de")
+            .unwrap()
+            .with_value("injection.method", "Clipboard")
+            .unwrap();
+        assert_eq!(config.stt.allowed_languages, vec!["en", "de"]);
+        assert_eq!(config.injection.method, InjectionMethod::Clipboard);
+    }
+
+    #[test]
+    fn test_redacted_hides_api_keys() {
+        let mut config = Config::default();
+        config.stt.http.api_key = Some("sk-secret".to_string());
+        config.api.api_key = Some("local-secret".to_string());
+        let redacted = config.redacted();
+        assert_eq!(redacted.stt.http.api_key.as_deref(), Some(REDACTED));
+        assert_eq!(redacted.api.api_key.as_deref(), Some(REDACTED));
+        assert!(!redacted.to_display_toml().unwrap().contains("secret"));
+        assert_eq!(Config::default().redacted(), Config::default());
+    }
+
+    #[test]
+    fn test_with_value_unknown_key() {
+        let err = Config::default()
+            .with_value("stt.modle", "base")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown config key"), "got: {err}");
+    }
+
+    #[test]
+    fn test_with_value_empty_unsets_optional() {
+        let config = Config::default()
+            .with_value("audio.device", "hw:0")
+            .unwrap();
+        assert_eq!(config.audio.device.as_deref(), Some("hw:0"));
+        let cleared = config.with_value("audio.device", "").unwrap();
+        assert_eq!(cleared.audio.device, None);
+    }
+
+    #[test]
+    fn test_with_value_custom_voice_command() {
+        let config = Config::default()
+            .with_value("voice_commands.commands.shrug", "¯\\_(ツ)_/¯")
+            .unwrap();
+        assert_eq!(
+            config
+                .voice_commands
+                .commands
+                .get("shrug")
+                .map(String::as_str),
+            Some("¯\\_(ツ)_/¯")
+        );
+    }
+
+    #[test]
+    fn test_get_toml_value_list() {
+        let mut config = Config::default();
+        config.stt.allowed_languages = vec!["en".to_string()];
+        let value = config.get_toml_value("stt.allowed_languages").unwrap();
+        assert_eq!(
+            value,
+            toml::Value::Array(vec![toml::Value::String("en".to_string())])
+        );
+    }
+
     #[test]
     fn test_to_display_toml_roundtrip() {
         let config = Config::default();
@@ -2294,6 +3450,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vad_backend_from_toml() {
+        let toml_content = r#"
+            [audio]
+            vad_backend = "spectral"
+        "#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(toml_content.as_bytes()).unwrap();
+
+        let config = Config::load(temp_file.path()).unwrap();
+        assert_eq!(config.audio.vad_backend, VadBackend::Spectral);
+        assert_eq!(Config::default().audio.vad_backend, VadBackend::Rms);
+    }
+
+    #[test]
+    fn test_vad_backend_from_str_and_display() {
+        assert_eq!("Spectral".parse::<VadBackend>(), Ok(VadBackend::Spectral));
+        assert_eq!(VadBackend::Rms.to_string(), "rms");
+        assert_eq!("neural".parse::<VadBackend>(), Ok(VadBackend::Neural));
+        let err = "webrtc".parse::<VadBackend>().unwrap_err();
+        assert!(err.contains("Valid options"), "{err}");
+    }
+
     #[test]
     fn test_error_correction_config_dictionary_language_default() {
         let config = ErrorCorrectionConfig::default();
@@ -2398,4 +3578,80 @@ mod tests {
             toml::from_str("method = \"clipboard\"").expect("deserialize clipboard");
         assert_eq!(clipboard.method, InjectionMethod::Clipboard);
     }
+
+    #[test]
+    fn test_diff_keys_identical_configs_is_empty() {
+        let config = Config::default();
+        let keys = config.diff_keys(&config.clone()).unwrap();
+        assert!(keys.is_empty(), "Expected no changes, got: {:?}", keys);
+    }
+
+    #[test]
+    fn test_diff_keys_reports_changed_leaves_sorted() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.stt.model = "small".to_string();
+        new.audio.vad_threshold = 0.05;
+        new.transcription.error_correction.enabled = false;
+
+        let keys = old.diff_keys(&new).unwrap();
+        assert_eq!(
+            keys,
+            vec![
+                "audio.vad_threshold",
+                "stt.model",
+                "transcription.error_correction.enabled",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_values_from_copies_and_removes_keys() {
+        let old = Config::default();
+        let mut new = Config::default();
+        new.stt.fan_out = true;
+        new.stt.backend = SttBackend::Http;
+        new.audio.vad_threshold = 0.05;
+        new.stt
+            .language_models
+            .insert("de".to_string(), "large-v3-turbo".to_string());
+
+        let keys = vec![
+            "stt.backend".to_string(),
+            "stt.fan_out".to_string(),
+            "stt.language_models.de".to_string(),
+        ];
+        let merged = new.with_values_from(&old, &keys).unwrap();
+        assert_eq!(merged.stt.backend, old.stt.backend);
+        assert!(!merged.stt.fan_out);
+        assert!(merged.stt.language_models.is_empty());
+        assert_eq!(merged.audio.vad_threshold, 0.05);
+    }
+
+    #[test]
+    fn test_diff_keys_reports_added_and_removed_entries() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.audio.device = Some("hw:1,0".to_string());
+        new.voice_commands
+            .commands
+            .insert("smiley".to_string(), ":)".to_string());
+
+        let keys = old.diff_keys(&new).unwrap();
+        assert_eq!(keys, vec!["audio.device", "voice_commands.commands.smiley"]);
+
+        // Reverse direction reports the same keys as removed
+        let keys = new.diff_keys(&old).unwrap();
+        assert_eq!(keys, vec!["audio.device", "voice_commands.commands.smiley"]);
+    }
+
+    #[test]
+    fn test_diff_keys_treats_arrays_as_leaves() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.stt.allowed_languages = vec!["en".to_string(), "de".to_string()];
+
+        let keys = old.diff_keys(&new).unwrap();
+        assert_eq!(keys, vec!["stt.allowed_languages"]);
+    }
 }
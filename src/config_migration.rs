@@ -0,0 +1,419 @@
+//! Versioned config file migrations.
+//!
+//! Each config file records its format in `config_version`. Files without it
+//! are version 0. [`MIGRATIONS`] is an ordered chain: migration `n` rewrites a
+//! version-`n` document into version `n + 1`. Edits go through `toml_edit` so
+//! comments and formatting survive wherever the structure allows.
+
+use crate::error::{Result, VoicshError};
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+
+/// Config format version written by this build.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// One step in the migration chain.
+pub struct Migration {
+    /// Version this migration upgrades from (result is `from + 1`)
+    pub from: u32,
+    /// Short description shown by `voicsh config migrate`
+    pub description: &'static str,
+    apply: fn(&mut DocumentMut),
+}
+
+/// Ordered migration chain; `MIGRATIONS[n].from == n`.
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 0,
+    description: "rename [input] section to [injection]",
+    apply: rename_input_section,
+}];
+
+/// Result of migrating a config document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migrated {
+    /// Version the document was at before migrating
+    pub from: u32,
+    /// Version after migrating
+    pub to: u32,
+    /// Descriptions of the migrations that ran, in order
+    pub applied: Vec<&'static str>,
+    /// Migrated TOML text
+    pub text: String,
+}
+
+impl Migrated {
+    /// Whether the document was already at the current version.
+    pub fn is_noop(&self) -> bool {
+        self.from == self.to
+    }
+}
+
+/// Outcome of [`migrate_file`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationReport {
+    pub migrated: Migrated,
+    /// Line diff between the original and migrated file
+    pub diff: String,
+    /// Where the original was copied to (`None` for dry runs and no-ops)
+    pub backup: Option<PathBuf>,
+}
+
+/// Read `config_version` from a TOML document (0 when absent).
+pub fn document_version(doc: &DocumentMut) -> Result<u32> {
+    match doc.get("config_version") {
+        None => Ok(0),
+        Some(item) => item
+            .as_integer()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| VoicshError::ConfigInvalidValue {
+                key: "config_version".to_string(),
+                message: "expected a non-negative integer".to_string(),
+            }),
+    }
+}
+
+/// Read `config_version` from TOML text (0 when absent or unparsable).
+pub fn text_version(contents: &str) -> u32 {
+    contents
+        .parse::<DocumentMut>()
+        .ok()
+        .and_then(|doc| document_version(&doc).ok())
+        .unwrap_or(0)
+}
+
+/// Run every pending migration on `contents` and stamp the current version.
+pub fn migrate_str(contents: &str) -> Result<Migrated> {
+    let mut doc = parse_document(contents)?;
+    let from = document_version(&doc)?;
+    if from > CURRENT_VERSION {
+        return Err(VoicshError::ConfigInvalidValue {
+            key: "config_version".to_string(),
+            message: format!(
+                "config version {from} is newer than this voicsh supports ({CURRENT_VERSION}); please upgrade voicsh"
+            ),
+        });
+    }
+    if from == CURRENT_VERSION {
+        return Ok(Migrated {
+            from,
+            to: from,
+            applied: Vec::new(),
+            text: contents.to_string(),
+        });
+    }
+
+    let applied = apply_pending(&mut doc, from);
+    doc.insert(
+        "config_version",
+        toml_edit::value(i64::from(CURRENT_VERSION)),
+    );
+
+    Ok(Migrated {
+        from,
+        to: CURRENT_VERSION,
+        applied,
+        text: doc.to_string(),
+    })
+}
+
+/// Apply pending structural migrations without stamping the version.
+///
+/// Used by validation, which needs the new structure but wants line numbers
+/// to keep matching the file on disk.
+pub(crate) fn migrate_structure(contents: &str) -> Result<String> {
+    let mut doc = parse_document(contents)?;
+    let from = document_version(&doc)?;
+    if from >= CURRENT_VERSION {
+        return Ok(contents.to_string());
+    }
+    apply_pending(&mut doc, from);
+    Ok(doc.to_string())
+}
+
+/// Migrate a config file in place, keeping a backup of the original.
+///
+/// With `dry_run`, nothing is written; the report still carries the diff.
+pub fn migrate_file(path: &Path, dry_run: bool) -> Result<MigrationReport> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            VoicshError::ConfigFileNotFound {
+                path: path.display().to_string(),
+            }
+        } else {
+            VoicshError::Io(e)
+        }
+    })?;
+
+    let migrated = migrate_str(&contents)?;
+    let diff = line_diff(&contents, &migrated.text);
+
+    if migrated.is_noop() || dry_run {
+        return Ok(MigrationReport {
+            migrated,
+            diff,
+            backup: None,
+        });
+    }
+
+    // Make sure the result still loads before touching the file
+    let _validated: crate::config::Config = toml::from_str(&migrated.text)?;
+
+    let backup = backup_path(path, migrated.from);
+    std::fs::copy(path, &backup).map_err(|e| VoicshError::ConfigSerialize {
+        message: format!("Failed to back up config to '{}': {e}", backup.display()),
+    })?;
+    std::fs::write(path, &migrated.text).map_err(|e| VoicshError::ConfigSerialize {
+        message: format!("Failed to write config to '{}': {e}", path.display()),
+    })?;
+
+    Ok(MigrationReport {
+        migrated,
+        diff,
+        backup: Some(backup),
+    })
+}
+
+/// Backup location for a config file at `version`: `config.toml.v0.bak`.
+pub fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "config.toml".to_string());
+    path.with_file_name(format!("{name}.v{version}.bak"))
+}
+
+fn parse_document(contents: &str) -> Result<DocumentMut> {
+    contents
+        .parse::<DocumentMut>()
+        .map_err(|e| VoicshError::ConfigParse {
+            message: e.to_string(),
+        })
+}
+
+fn apply_pending(doc: &mut DocumentMut, from: u32) -> Vec<&'static str> {
+    MIGRATIONS
+        .iter()
+        .filter(|m| m.from >= from)
+        .map(|m| {
+            (m.apply)(doc);
+            m.description
+        })
+        .collect()
+}
+
+/// v0 → v1: the `[input]` section was renamed to `[injection]`.
+///
+/// If both exist, keys already set in `[injection]` win.
+fn rename_input_section(doc: &mut DocumentMut) {
+    let Some(input) = doc.remove("input") else {
+        return;
+    };
+    match doc.get_mut("injection").and_then(Item::as_table_like_mut) {
+        Some(injection) => {
+            if let Some(old) = input.as_table_like() {
+                for (key, value) in old.iter() {
+                    if !injection.contains_key(key) {
+                        injection.insert(key, value.clone());
+                    }
+                }
+            }
+        }
+        None => {
+            doc.insert("injection", input);
+        }
+    }
+}
+
+/// Line diff of two texts: changed lines prefixed with `-`/`+`, two lines
+/// of unchanged context around each change, `@@` separating hunks.
+pub fn line_diff(old: &str, new: &str) -> String {
+    const CONTEXT: usize = 2;
+
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence table, filled from the end
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<(char, &str)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', a[i]));
+            i += 1;
+        } else {
+            ops.push(('+', b[j]));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != ' ')
+        .map(|(idx, _)| idx)
+        .collect();
+
+    let mut out = String::new();
+    let mut last_printed: Option<usize> = None;
+    for &idx in &changed {
+        let start = idx.saturating_sub(CONTEXT);
+        let start = last_printed.map_or(start, |last| start.max(last + 1));
+        if last_printed.is_some_and(|last| start > last + 1) {
+            out.push_str("@@\n");
+        }
+        let end = (idx + CONTEXT).min(ops.len() - 1);
+        for (tag, line) in &ops[start..=end] {
+            out.push(*tag);
+            out.push_str(line);
+            out.push('\n');
+        }
+        last_printed = Some(end);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_are_ordered_and_complete() {
+        for (i, m) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(m.from as usize, i, "migration {i} has wrong 'from'");
+        }
+        assert_eq!(MIGRATIONS.len() as u32, CURRENT_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_renames_input_and_keeps_comments() {
+        let old = "# my voicsh config\n\n[stt]\nmodel = \"base\"\n\n# typing settings\n[input]\nmethod = \"direct\" # no clipboard\n";
+        let migrated = migrate_str(old).unwrap();
+        assert_eq!(migrated.from, 0);
+        assert_eq!(migrated.to, CURRENT_VERSION);
+        assert_eq!(
+            migrated.applied,
+            vec!["rename [input] section to [injection]"]
+        );
+        assert!(migrated.text.contains("# my voicsh config"));
+        assert!(migrated.text.contains("# typing settings\n[injection]"));
+        assert!(migrated.text.contains("# no clipboard"));
+        assert!(!migrated.text.contains("[input]"));
+        assert!(migrated.text.contains("config_version = 1"));
+
+        let config: crate::config::Config = toml::from_str(&migrated.text).unwrap();
+        assert_eq!(
+            config.injection.method,
+            crate::config::InjectionMethod::Direct
+        );
+        assert_eq!(config.config_version, CURRENT_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_merges_input_into_existing_injection() {
+        let old = "[injection]\nbackend = \"wtype\"\n\n[input]\nbackend = \"portal\"\npaste_key = \"ctrl+v\"\n";
+        let migrated = migrate_str(old).unwrap();
+        let config: crate::config::Config = toml::from_str(&migrated.text).unwrap();
+        assert_eq!(
+            config.injection.backend,
+            crate::config::InjectionBackend::Wtype
+        );
+        assert_eq!(config.injection.paste_key, "ctrl+v");
+    }
+
+    #[test]
+    fn test_migrate_current_version_is_noop() {
+        let text = "config_version = 1\n\n[stt]\nmodel = \"base\"\n";
+        let migrated = migrate_str(text).unwrap();
+        assert!(migrated.is_noop());
+        assert_eq!(migrated.text, text);
+    }
+
+    #[test]
+    fn test_migrate_newer_version_errors() {
+        let err = migrate_str("config_version = 99\n")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("newer than this voicsh supports"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn test_migrate_structure_keeps_line_numbers() {
+        let old = "[stt]\nmodel = \"base\"\n[input]\nmethod = \"direct\"\n";
+        let migrated = migrate_structure(old).unwrap();
+        assert_eq!(migrated.lines().count(), old.lines().count());
+        assert!(!migrated.contains("config_version"));
+    }
+
+    #[test]
+    fn test_text_version() {
+        assert_eq!(text_version("[stt]\nmodel = \"base\"\n"), 0);
+        assert_eq!(text_version("config_version = 1\n"), 1);
+    }
+
+    #[test]
+    fn test_migrate_file_writes_backup() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let old = "[input]\nmethod = \"direct\"\n";
+        std::fs::write(&path, old).unwrap();
+
+        let report = migrate_file(&path, false).unwrap();
+        let backup = report.backup.unwrap();
+        assert_eq!(backup, dir.path().join("config.toml.v0.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), old);
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("[injection]")
+        );
+    }
+
+    #[test]
+    fn test_migrate_file_dry_run_leaves_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let old = "[input]\nmethod = \"direct\"\n";
+        std::fs::write(&path, old).unwrap();
+
+        let report = migrate_file(&path, true).unwrap();
+        assert!(report.backup.is_none());
+        assert!(report.diff.contains("-[input]"), "diff: {}", report.diff);
+        assert!(
+            report.diff.contains("+[injection]"),
+            "diff: {}",
+            report.diff
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), old);
+        assert!(!dir.path().join("config.toml.v0.bak").exists());
+    }
+
+    #[test]
+    fn test_line_diff_context_and_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nJ\n";
+        let diff = line_diff(old, new);
+        assert_eq!(diff, " a\n-b\n+B\n c\n d\n@@\n h\n i\n-j\n+J\n");
+    }
+
+    #[test]
+    fn test_line_diff_identical_is_empty() {
+        assert_eq!(line_diff("a\nb\n", "a\nb\n"), "");
+    }
+}
@@ -0,0 +1,1364 @@
+//! Typed schema for configuration keys.
+//!
+//! Every settable leaf of [`Config`](crate::config::Config) is listed in
+//! [`SCHEMA`] with its type, allowed range or values, and a short description.
+//! Values set through `voicsh config set` or the `SetConfig` IPC command are
+//! parsed and validated here before they reach the config.
+
+use crate::error::{Result, VoicshError};
+
+/// Type and constraints of a single config key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueKind {
+    /// `true` or `false`
+    Bool,
+    /// Whole number within an inclusive range
+    Integer { min: i64, max: i64 },
+    /// Number within an inclusive range
+    Float { min: f64, max: f64 },
+    /// Free-form string
+    String,
+    /// String that may be unset (empty value clears it)
+    OptionalString,
+    /// One of a fixed set of lowercase values
+    Enum(&'static [&'static str]),
+    /// `"auto"` or a supported transcription language code
+    Language,
+    /// List of supported transcription language codes
+    LanguageList,
+    /// `"auto"` or a language with a SymSpell dictionary
+    DictionaryLanguage,
+    /// List of free-form strings
+    StringList,
+    /// Table of string → string entries
+    StringMap,
+    /// Table of string → number entries, each within an inclusive range
+    FloatMap { min: f64, max: f64 },
+    /// Duration such as `15m` or `1h30m` that may be unset (empty value clears it)
+    Duration,
+}
+
+/// Schema entry for one config key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeySpec {
+    /// Dotted key path (e.g. `audio.vad_threshold`)
+    pub key: &'static str,
+    /// Value type and constraints
+    pub kind: ValueKind,
+    /// One-line description
+    pub description: &'static str,
+}
+
+/// Prefix for user-defined voice command entries.
+const VOICE_COMMAND_PREFIX: &str = "voice_commands.commands.";
+
+/// Prefix for per-language model entries.
+const LANGUAGE_MODEL_PREFIX: &str = "stt.language_models.";
+
+/// Prefix for calibrated per-device VAD thresholds.
+const DEVICE_THRESHOLD_PREFIX: &str = "audio.device_thresholds.";
+
+/// Prefix for per-language hallucination filter overrides.
+const FILTER_OVERRIDE_PREFIX: &str = "transcription.hallucination_filters.";
+
+/// All settable config keys.
+pub const SCHEMA: &[KeySpec] = &[
+    KeySpec {
+        key: "config_version",
+        kind: ValueKind::Integer {
+            min: 0,
+            max: crate::config_migration::CURRENT_VERSION as i64,
+        },
+        description: "Config file format version (managed by `voicsh config migrate`)",
+    },
+    KeySpec {
+        key: "audio.device",
+        kind: ValueKind::OptionalString,
+        description: "Audio input device (unset = system default)",
+    },
+    KeySpec {
+        key: "audio.device_priority",
+        kind: ValueKind::StringList,
+        description: "Fallback input devices, most preferred first",
+    },
+    KeySpec {
+        key: "audio.sample_rate",
+        kind: ValueKind::Integer {
+            min: 8000,
+            max: 192_000,
+        },
+        description: "Sample rate in Hz",
+    },
+    KeySpec {
+        key: "audio.vad_threshold",
+        kind: ValueKind::Float { min: 0.0, max: 1.0 },
+        description: "Voice activity detection threshold",
+    },
+    KeySpec {
+        key: "audio.silence_duration_ms",
+        kind: ValueKind::Integer {
+            min: 100,
+            max: 30_000,
+        },
+        description: "Silence before speech end (ms)",
+    },
+    KeySpec {
+        key: "audio.vad_backend",
+        kind: ValueKind::Enum(&["rms", "spectral", "neural"]),
+        description: "Voice activity detector (rms, spectral or neural)",
+    },
+    KeySpec {
+        key: "audio.vad_model",
+        kind: ValueKind::Enum(&["silero-v5.1.2"]),
+        description: "Neural VAD model",
+    },
+    KeySpec {
+        key: "audio.denoise.enabled",
+        kind: ValueKind::Bool,
+        description: "Spectral noise suppression before VAD",
+    },
+    KeySpec {
+        key: "audio.denoise.strength",
+        kind: ValueKind::Float { min: 0.0, max: 2.0 },
+        description: "Noise over-subtraction factor",
+    },
+    KeySpec {
+        key: "audio.device_thresholds",
+        kind: ValueKind::FloatMap { min: 0.0, max: 1.0 },
+        description: "Calibrated VAD thresholds by device name (see `voicsh calibrate`)",
+    },
+    KeySpec {
+        key: "audio.denoise.min_gain_db",
+        kind: ValueKind::Float {
+            min: -60.0,
+            max: 0.0,
+        },
+        description: "Maximum noise attenuation (dB)",
+    },
+    KeySpec {
+        key: "stt.model",
+        kind: ValueKind::String,
+        description: "Whisper model name",
+    },
+    KeySpec {
+        key: "stt.language",
+        kind: ValueKind::Language,
+        description: "Transcription language (auto = detect)",
+    },
+    KeySpec {
+        key: "stt.fan_out",
+        kind: ValueKind::Bool,
+        description: "Run multilingual + English models in parallel",
+    },
+    KeySpec {
+        key: "stt.fan_out_strategy",
+        kind: ValueKind::Enum(&["best", "vote"]),
+        description: "Combine fan-out results: best result or per-word vote",
+    },
+    KeySpec {
+        key: "stt.allowed_languages",
+        kind: ValueKind::LanguageList,
+        description: "Only accept these languages in auto mode (empty = all)",
+    },
+    KeySpec {
+        key: "stt.min_confidence",
+        kind: ValueKind::Float { min: 0.0, max: 1.0 },
+        description: "Drop transcriptions below this confidence",
+    },
+    KeySpec {
+        key: "stt.unload_after",
+        kind: ValueKind::Duration,
+        description: "Unload the daemon's model after this idle time (e.g. 15m)",
+    },
+    KeySpec {
+        key: "stt.language_tracking",
+        kind: ValueKind::Bool,
+        description: "Track the session language in auto mode instead of detecting per chunk",
+    },
+    KeySpec {
+        key: "stt.language_models",
+        kind: ValueKind::StringMap,
+        description: "Per-language models in auto mode: language → model",
+    },
+    KeySpec {
+        key: "stt.language_models_budget_mb",
+        kind: ValueKind::Integer {
+            min: 0,
+            max: 1_048_576,
+        },
+        description: "Memory budget for per-language models (MB)",
+    },
+    KeySpec {
+        key: "stt.backend",
+        kind: ValueKind::Enum(&["local", "http", "deepgram", "candle"]),
+        description: "Transcription engine (local, http, deepgram or candle)",
+    },
+    KeySpec {
+        key: "stt.http.url",
+        kind: ValueKind::String,
+        description: "OpenAI-compatible transcription endpoint",
+    },
+    KeySpec {
+        key: "stt.http.model",
+        kind: ValueKind::String,
+        description: "Model name sent to the transcription server",
+    },
+    KeySpec {
+        key: "stt.http.api_key",
+        kind: ValueKind::OptionalString,
+        description: "Bearer token for the transcription server",
+    },
+    KeySpec {
+        key: "stt.http.timeout_secs",
+        kind: ValueKind::Integer { min: 1, max: 600 },
+        description: "Transcription request timeout (s)",
+    },
+    KeySpec {
+        key: "stt.http.retries",
+        kind: ValueKind::Integer { min: 0, max: 10 },
+        description: "Retries after a failed transcription request",
+    },
+    KeySpec {
+        key: "stt.http.fallback",
+        kind: ValueKind::Bool,
+        description: "Use the local model while the server is unreachable",
+    },
+    KeySpec {
+        key: "stt.deepgram.url",
+        kind: ValueKind::String,
+        description: "Deepgram-compatible streaming endpoint",
+    },
+    KeySpec {
+        key: "stt.deepgram.model",
+        kind: ValueKind::String,
+        description: "Model requested from the streaming service",
+    },
+    KeySpec {
+        key: "stt.deepgram.api_key_file",
+        kind: ValueKind::OptionalString,
+        description: "File holding the streaming service API key",
+    },
+    KeySpec {
+        key: "stt.deepgram.interim_results",
+        kind: ValueKind::Bool,
+        description: "Show interim hypotheses while speaking",
+    },
+    KeySpec {
+        key: "stt.candle.repo",
+        kind: ValueKind::String,
+        description: "HuggingFace repo with a safetensors Whisper model",
+    },
+    KeySpec {
+        key: "stt.candle.beam_size",
+        kind: ValueKind::Integer { min: 1, max: 16 },
+        description: "Beam width of the candle Whisper decoder",
+    },
+    KeySpec {
+        key: "injection.method",
+        kind: ValueKind::Enum(&["direct", "clipboard"]),
+        description: "Injection method",
+    },
+    KeySpec {
+        key: "injection.paste_key",
+        kind: ValueKind::String,
+        description: "Paste key combo (auto, ctrl+v, ctrl+shift+v)",
+    },
+    KeySpec {
+        key: "injection.backend",
+        kind: ValueKind::Enum(&["auto", "portal", "wtype", "ydotool"]),
+        description: "Injection backend",
+    },
+    KeySpec {
+        key: "voice_commands.enabled",
+        kind: ValueKind::Bool,
+        description: "Enable voice command processing",
+    },
+    KeySpec {
+        key: "voice_commands.disable_defaults",
+        kind: ValueKind::Bool,
+        description: "Disable all built-in voice commands",
+    },
+    KeySpec {
+        key: "voice_commands.commands",
+        kind: ValueKind::StringMap,
+        description: "Custom voice commands: spoken phrase → replacement text",
+    },
+    KeySpec {
+        key: "transcription.hallucination_filters.add",
+        kind: ValueKind::StringList,
+        description: "Extra phrases to filter",
+    },
+    KeySpec {
+        key: "transcription.hallucination_filters.suspect_add",
+        kind: ValueKind::StringList,
+        description: "Extra suspect phrases to soft-filter",
+    },
+    KeySpec {
+        key: "transcription.error_correction.enabled",
+        kind: ValueKind::Bool,
+        description: "Post-ASR error correction",
+    },
+    KeySpec {
+        key: "transcription.error_correction.backend",
+        kind: ValueKind::Enum(&["symspell", "t5", "hybrid"]),
+        description: "Correction backend",
+    },
+    KeySpec {
+        key: "transcription.error_correction.model",
+        kind: ValueKind::String,
+        description: "T5 correction model",
+    },
+    KeySpec {
+        key: "transcription.error_correction.confidence_threshold",
+        kind: ValueKind::Float { min: 0.0, max: 1.0 },
+        description: "Only correct tokens below this probability",
+    },
+    KeySpec {
+        key: "transcription.error_correction.dictionary_language",
+        kind: ValueKind::DictionaryLanguage,
+        description: "SymSpell dictionary language",
+    },
+    KeySpec {
+        key: "transcription.error_correction.symspell_languages",
+        kind: ValueKind::StringList,
+        description: "Languages for SymSpell in hybrid mode",
+    },
+    KeySpec {
+        key: "debug.archive_audio",
+        kind: ValueKind::Bool,
+        description: "Save each session's audio, chunks and results",
+    },
+    KeySpec {
+        key: "debug.archive_dir",
+        kind: ValueKind::OptionalString,
+        description: "Session archive directory (default: ~/.local/share/voicsh/sessions)",
+    },
+    KeySpec {
+        key: "debug.max_sessions",
+        kind: ValueKind::Integer {
+            min: 0,
+            max: 100_000,
+        },
+        description: "Archived sessions to keep (0 = unlimited)",
+    },
+    KeySpec {
+        key: "debug.max_archive_mb",
+        kind: ValueKind::Integer {
+            min: 0,
+            max: 1_000_000,
+        },
+        description: "Session archive size limit in MB (0 = unlimited)",
+    },
+    KeySpec {
+        key: "api.enabled",
+        kind: ValueKind::Bool,
+        description: "Serve the loaded model as an OpenAI-compatible API",
+    },
+    KeySpec {
+        key: "api.listen",
+        kind: ValueKind::String,
+        description: "API address: loopback host:port or Unix socket path",
+    },
+    KeySpec {
+        key: "api.api_key",
+        kind: ValueKind::OptionalString,
+        description: "Bearer token required by the API",
+    },
+    KeySpec {
+        key: "api.max_upload_mb",
+        kind: ValueKind::Integer { min: 1, max: 4096 },
+        description: "Largest accepted API upload (MB)",
+    },
+];
+
+/// Schema entry for a voice command entry (`voice_commands.commands.<phrase>`).
+const VOICE_COMMAND_ENTRY: KeySpec = KeySpec {
+    key: "voice_commands.commands.<phrase>",
+    kind: ValueKind::String,
+    description: "Replacement text for a custom voice command",
+};
+
+/// Schema entry for a per-language model (`stt.language_models.<lang>`).
+const LANGUAGE_MODEL_ENTRY: KeySpec = KeySpec {
+    key: "stt.language_models.<lang>",
+    kind: ValueKind::String,
+    description: "Model that transcribes one detected language",
+};
+
+/// Schema entry for a calibrated device threshold (`audio.device_thresholds.<device>`).
+const DEVICE_THRESHOLD_ENTRY: KeySpec = KeySpec {
+    key: "audio.device_thresholds.<device>",
+    kind: ValueKind::Float { min: 0.0, max: 1.0 },
+    description: "Calibrated VAD threshold for one input device",
+};
+
+/// Schema entry for a per-language filter override (`transcription.hallucination_filters.<lang>`).
+const FILTER_OVERRIDE_ENTRY: KeySpec = KeySpec {
+    key: "transcription.hallucination_filters.<lang>",
+    kind: ValueKind::StringList,
+    description: "Replaces the built-in hallucination filters for one language",
+};
+
+/// Look up the schema entry for a dotted key.
+///
+/// Entries inside `voice_commands.commands`, per-device thresholds and
+/// per-language hallucination filter overrides match their wildcard specs.
+pub fn find_key(key: &str) -> Option<&'static KeySpec> {
+    if let Some(spec) = SCHEMA.iter().find(|s| s.key == key) {
+        return Some(spec);
+    }
+    if key
+        .strip_prefix(VOICE_COMMAND_PREFIX)
+        .is_some_and(|phrase| !phrase.is_empty())
+    {
+        return Some(&VOICE_COMMAND_ENTRY);
+    }
+    if key
+        .strip_prefix(LANGUAGE_MODEL_PREFIX)
+        .is_some_and(|lang| !lang.is_empty() && !lang.contains('.'))
+    {
+        return Some(&LANGUAGE_MODEL_ENTRY);
+    }
+    if key
+        .strip_prefix(DEVICE_THRESHOLD_PREFIX)
+        .is_some_and(|device| !device.is_empty())
+    {
+        return Some(&DEVICE_THRESHOLD_ENTRY);
+    }
+    if key
+        .strip_prefix(FILTER_OVERRIDE_PREFIX)
+        .is_some_and(|lang| !lang.is_empty() && !lang.contains('.'))
+    {
+        return Some(&FILTER_OVERRIDE_ENTRY);
+    }
+    None
+}
+
+impl KeySpec {
+    /// Parse a user-supplied string into a validated TOML value.
+    ///
+    /// Lists accept either a TOML array (`["en", "de"]`) or a comma-separated
+    /// string (`en,de`). Returns `None` when the value clears an optional key.
+    pub fn parse(&self, raw: &str, key: &str) -> Result<Option<toml::Value>> {
+        let raw = raw.trim();
+        let value = match self.kind {
+            ValueKind::Bool => match raw {
+                "true" => toml::Value::Boolean(true),
+                "false" => toml::Value::Boolean(false),
+                _ => return Err(invalid(key, format!("expected true or false, got '{raw}'"))),
+            },
+            ValueKind::Integer { .. } => raw
+                .parse::<i64>()
+                .map(toml::Value::Integer)
+                .map_err(|_| invalid(key, format!("expected an integer, got '{raw}'")))?,
+            ValueKind::Float { .. } => raw
+                .parse::<f64>()
+                .map(toml::Value::Float)
+                .map_err(|_| invalid(key, format!("expected a number, got '{raw}'")))?,
+            ValueKind::OptionalString | ValueKind::Duration if raw.is_empty() => return Ok(None),
+            ValueKind::Duration => toml::Value::String(raw.to_string()),
+            ValueKind::String | ValueKind::OptionalString => toml::Value::String(raw.to_string()),
+            ValueKind::Enum(_) | ValueKind::Language | ValueKind::DictionaryLanguage => {
+                toml::Value::String(raw.to_lowercase())
+            }
+            ValueKind::LanguageList | ValueKind::StringList => parse_list(raw, key)?,
+            ValueKind::StringMap => {
+                toml::Value::Table(parse_inline_table(raw, key, "{ \"phrase\" = \"text\" }")?)
+            }
+            ValueKind::FloatMap { .. } => {
+                toml::Value::Table(parse_inline_table(raw, key, "{ \"hw:0\" = 0.03 }")?)
+            }
+        };
+        self.validate(&value, key)?;
+        Ok(Some(value))
+    }
+
+    /// Check that a TOML value has the right type and is within range.
+    pub fn validate(&self, value: &toml::Value, key: &str) -> Result<()> {
+        match (self.kind, value) {
+            (ValueKind::Bool, toml::Value::Boolean(_)) => Ok(()),
+            (ValueKind::Integer { min, max }, toml::Value::Integer(i)) => {
+                if (min..=max).contains(i) {
+                    Ok(())
+                } else {
+                    Err(invalid(key, format!("{i} is out of range ({min}-{max})")))
+                }
+            }
+            (ValueKind::Float { min, max }, toml::Value::Float(f)) => {
+                check_float_range(*f, min, max, key)
+            }
+            (ValueKind::Float { min, max }, toml::Value::Integer(i)) => {
+                check_float_range(*i as f64, min, max, key)
+            }
+            (ValueKind::String | ValueKind::OptionalString, toml::Value::String(_)) => Ok(()),
+            (ValueKind::Duration, toml::Value::String(s)) => {
+                if crate::config::parse_duration(s).is_some() {
+                    Ok(())
+                } else {
+                    Err(invalid(
+                        key,
+                        format!("'{s}' is not a duration like 90s, 15m or 1h30m"),
+                    ))
+                }
+            }
+            (ValueKind::Enum(allowed), toml::Value::String(s)) => {
+                if allowed.iter().any(|a| a.eq_ignore_ascii_case(s)) {
+                    Ok(())
+                } else {
+                    Err(invalid(
+                        key,
+                        format!("'{s}' is not one of: {}", allowed.join(", ")),
+                    ))
+                }
+            }
+            (ValueKind::Language, toml::Value::String(s)) => check_language(s, true, key),
+            (ValueKind::DictionaryLanguage, toml::Value::String(s)) => {
+                if s == crate::defaults::AUTO_LANGUAGE || crate::dictionary::has_dictionary(s) {
+                    Ok(())
+                } else {
+                    let available: Vec<&str> = crate::dictionary::list_dictionaries()
+                        .iter()
+                        .map(|d| d.language)
+                        .collect();
+                    Err(invalid(
+                        key,
+                        format!(
+                            "no dictionary for '{s}'. Available: auto, {}",
+                            available.join(", ")
+                        ),
+                    ))
+                }
+            }
+            (ValueKind::LanguageList, toml::Value::Array(items)) => {
+                for item in items {
+                    match item {
+                        toml::Value::String(s) => check_language(s, false, key)?,
+                        other => return Err(type_mismatch(key, "a list of strings", other)),
+                    }
+                }
+                Ok(())
+            }
+            (ValueKind::StringList, toml::Value::Array(items)) => {
+                match items.iter().find(|v| !v.is_str()) {
+                    Some(other) => Err(type_mismatch(key, "a list of strings", other)),
+                    None => Ok(()),
+                }
+            }
+            (ValueKind::StringMap, toml::Value::Table(table)) => {
+                match table.values().find(|v| !v.is_str()) {
+                    Some(other) => Err(type_mismatch(key, "a table of strings", other)),
+                    None => Ok(()),
+                }
+            }
+            (ValueKind::FloatMap { min, max }, toml::Value::Table(table)) => {
+                for (name, value) in table {
+                    let entry_key = format!("{key}.{name}");
+                    match value {
+                        toml::Value::Float(f) => check_float_range(*f, min, max, &entry_key)?,
+                        toml::Value::Integer(i) => {
+                            check_float_range(*i as f64, min, max, &entry_key)?
+                        }
+                        other => return Err(type_mismatch(&entry_key, "a number", other)),
+                    }
+                }
+                Ok(())
+            }
+            (kind, other) => Err(type_mismatch(key, kind.type_name(), other)),
+        }
+    }
+}
+
+impl ValueKind {
+    /// Human-readable type name for error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Bool => "a boolean",
+            Self::Integer { .. } => "an integer",
+            Self::Float { .. } => "a number",
+            Self::String | Self::OptionalString => "a string",
+            Self::Enum(_) => "a string",
+            Self::Language | Self::DictionaryLanguage => "a language code",
+            Self::LanguageList => "a list of language codes",
+            Self::StringList => "a list of strings",
+            Self::StringMap => "a table of strings",
+            Self::FloatMap { .. } => "a table of numbers",
+            Self::Duration => "a duration",
+        }
+    }
+}
+
+/// Parse an inline TOML table value such as `{ "a" = "b" }`.
+fn parse_inline_table(raw: &str, key: &str, example: &str) -> Result<toml::Table> {
+    toml::from_str(&format!("v = {raw}"))
+        .ok()
+        .and_then(|mut t: toml::Table| t.remove("v"))
+        .and_then(|v| v.as_table().cloned())
+        .ok_or_else(|| {
+            invalid(
+                key,
+                format!("expected an inline table like {example}, got '{raw}'"),
+            )
+        })
+}
+
+/// Parse a list value from either TOML array syntax or comma-separated text.
+fn parse_list(raw: &str, key: &str) -> Result<toml::Value> {
+    if raw.starts_with('[') {
+        return toml::from_str::<toml::Table>(&format!("v = {raw}"))
+            .ok()
+            .and_then(|mut t| t.remove("v"))
+            .ok_or_else(|| invalid(key, format!("invalid list '{raw}'")));
+    }
+    let items = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| toml::Value::String(s.to_string()))
+        .collect();
+    Ok(toml::Value::Array(items))
+}
+
+fn check_float_range(f: f64, min: f64, max: f64, key: &str) -> Result<()> {
+    if (min..=max).contains(&f) {
+        Ok(())
+    } else {
+        Err(invalid(key, format!("{f} is out of range ({min}-{max})")))
+    }
+}
+
+fn check_language(code: &str, allow_auto: bool, key: &str) -> Result<()> {
+    use crate::pipeline::post_processor::SUPPORTED_LANGUAGES;
+    if (allow_auto && code == crate::defaults::AUTO_LANGUAGE) || SUPPORTED_LANGUAGES.contains(&code)
+    {
+        return Ok(());
+    }
+    let prefix = if allow_auto { "auto, " } else { "" };
+    Err(invalid(
+        key,
+        format!(
+            "unsupported language '{code}'. Supported: {prefix}{}",
+            SUPPORTED_LANGUAGES.join(", ")
+        ),
+    ))
+}
+
+fn invalid(key: &str, message: String) -> VoicshError {
+    VoicshError::ConfigInvalidValue {
+        key: key.to_string(),
+        message,
+    }
+}
+
+fn type_mismatch(key: &str, expected: &str, actual: &toml::Value) -> VoicshError {
+    invalid(
+        key,
+        format!("expected {expected}, got {}", actual.type_str()),
+    )
+}
+
+/// How serious a config file problem is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The value is invalid (wrong type, out of range, bad language code)
+    Error,
+    /// The key is ignored (unknown key or section)
+    Warning,
+}
+
+/// A problem found in a config file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// 1-based line number, if known
+    pub line: Option<usize>,
+    /// Dotted key the problem refers to, if any
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let level = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match self.line {
+            Some(line) => write!(f, "line {line}: {level}: ")?,
+            None => write!(f, "{level}: ")?,
+        }
+        match &self.key {
+            Some(key) => write!(f, "{key}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Check a config file's contents against the schema.
+///
+/// Reports TOML syntax errors, unknown keys and sections (which serde would
+/// otherwise silently ignore), type mismatches, out-of-range values and
+/// invalid language codes, each with the line it appears on.
+pub fn check_toml(contents: &str) -> Vec<Diagnostic> {
+    let mut out = Vec::new();
+
+    // Validate the structure the loader will actually see. Structural
+    // migrations keep lines in place, so line numbers still match the file.
+    let migrated = crate::config_migration::migrate_structure(contents)
+        .unwrap_or_else(|_| contents.to_string());
+    if migrated != contents {
+        out.push(Diagnostic {
+            severity: Severity::Warning,
+            line: None,
+            key: Some("config_version".to_string()),
+            message: format!(
+                "config uses format version {}; run `voicsh config migrate` to update it",
+                crate::config_migration::text_version(contents)
+            ),
+        });
+    }
+    let contents = migrated.as_str();
+
+    let spanned = match toml::de::DeTable::parse(contents) {
+        Ok(table) => table,
+        Err(e) => {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                line: e.span().map(|span| line_of(contents, span.start)),
+                key: None,
+                message: e.message().to_string(),
+            });
+            return out;
+        }
+    };
+    let values: toml::Table = match toml::from_str(contents) {
+        Ok(table) => table,
+        Err(e) => {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                line: e.span().map(|span| line_of(contents, span.start)),
+                key: None,
+                message: e.message().to_string(),
+            });
+            return out;
+        }
+    };
+
+    check_table(spanned.get_ref(), &values, "", contents, &mut out);
+
+    // Anything the schema did not catch but serde still rejects
+    if !out.iter().any(|d| d.severity == Severity::Error)
+        && let Err(e) = toml::from_str::<crate::config::Config>(contents)
+    {
+        out.push(Diagnostic {
+            severity: Severity::Error,
+            line: e.span().map(|span| line_of(contents, span.start)),
+            key: None,
+            message: e.message().to_string(),
+        });
+    }
+
+    out.sort_by_key(|d| d.line);
+    out
+}
+
+/// Read and check a config file. Returns an empty list if the file does not exist.
+pub fn check_file(path: &std::path::Path) -> Result<Vec<Diagnostic>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(check_toml(&contents)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(VoicshError::Io(e)),
+    }
+}
+
+fn check_table(
+    spanned: &toml::de::DeTable<'_>,
+    values: &toml::Table,
+    prefix: &str,
+    contents: &str,
+    out: &mut Vec<Diagnostic>,
+) {
+    for (name, de_value) in spanned {
+        let name: &str = name.get_ref();
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        let line = Some(line_of(contents, de_value.span().start));
+        let Some(value) = values.get(name) else {
+            continue;
+        };
+
+        if let Some(spec) = find_key(&path) {
+            match (spec.kind, de_value.get_ref(), value) {
+                // Check map entries one by one so each gets its own line number
+                (
+                    ValueKind::StringMap | ValueKind::FloatMap { .. },
+                    toml::de::DeValue::Table(inner),
+                    toml::Value::Table(v),
+                ) => {
+                    check_table(inner, v, &path, contents, out);
+                }
+                _ => {
+                    if let Err(e) = spec.validate(value, &path) {
+                        out.push(Diagnostic {
+                            severity: Severity::Error,
+                            line,
+                            key: Some(path),
+                            message: invalid_message(e),
+                        });
+                    }
+                }
+            }
+        } else if is_section(&path) {
+            match (de_value.get_ref(), value) {
+                (toml::de::DeValue::Table(inner), toml::Value::Table(v)) => {
+                    check_table(inner, v, &path, contents, out);
+                }
+                (_, other) => out.push(Diagnostic {
+                    severity: Severity::Error,
+                    line,
+                    key: Some(path),
+                    message: format!("expected a table, got {}", other.type_str()),
+                }),
+            }
+        } else {
+            let mut message = format!("unknown key '{name}' is ignored");
+            if let Some(suggestion) = suggest(prefix, name) {
+                message.push_str(&format!(" (did you mean '{suggestion}'?)"));
+            }
+            out.push(Diagnostic {
+                severity: Severity::Warning,
+                line,
+                key: Some(path),
+                message,
+            });
+        }
+    }
+}
+
+/// Whether `path` is a table that contains schema keys.
+fn is_section(path: &str) -> bool {
+    SCHEMA.iter().any(|s| {
+        s.key.len() > path.len() && s.key.starts_with(path) && s.key[path.len()..].starts_with('.')
+    })
+}
+
+/// Closest known key name under `prefix`, if `name` looks like a typo of one.
+fn suggest(prefix: &str, name: &str) -> Option<String> {
+    let candidates = SCHEMA.iter().filter_map(|s| {
+        let rest = if prefix.is_empty() {
+            s.key
+        } else {
+            s.key.strip_prefix(prefix)?.strip_prefix('.')?
+        };
+        rest.split('.').next()
+    });
+    candidates
+        .map(|c| (crate::correction::prompt::edit_distance(name, c), c))
+        .filter(|(dist, _)| *dist <= 2)
+        .min_by_key(|(dist, _)| *dist)
+        .map(|(_, c)| c.to_string())
+}
+
+/// Strip the "Invalid configuration value for <key>:" wrapper; diagnostics print the key themselves.
+fn invalid_message(e: VoicshError) -> String {
+    match e {
+        VoicshError::ConfigInvalidValue { message, .. } => message,
+        other => other.to_string(),
+    }
+}
+
+/// 1-based line number of a byte offset.
+fn line_of(contents: &str, offset: usize) -> usize {
+    contents
+        .get(..offset)
+        .unwrap_or(contents)
+        .bytes()
+        .filter(|&b| b == b'\n')
+        .count()
+        + 1
+}
+
+/// JSON Schema (draft-07) describing `config.toml`.
+///
+/// Editors with TOML language servers (e.g. taplo) use it for completion
+/// and inline validation.
+pub fn json_schema() -> serde_json::Value {
+    use serde_json::{Map, Value, json};
+
+    let defaults = toml::Value::try_from(crate::config::Config::default())
+        .ok()
+        .and_then(|v| serde_json::to_value(v).ok())
+        .unwrap_or(Value::Null);
+
+    let mut root = section_schema();
+    for spec in SCHEMA {
+        let parts: Vec<&str> = spec.key.split('.').collect();
+        let mut leaf = kind_schema(spec.kind);
+        if let Some(obj) = leaf.as_object_mut() {
+            obj.insert("description".to_string(), json!(spec.description));
+            let default = parts.iter().try_fold(&defaults, |v, p| v.get(p));
+            if let Some(default) = default {
+                obj.insert("default".to_string(), default.clone());
+            }
+        }
+        insert_property(&mut root, &parts, leaf);
+    }
+
+    // Per-language hallucination filter overrides: any other key is a language code
+    if let Some(filters) = root
+        .pointer_mut("/properties/transcription/properties/hallucination_filters")
+        .and_then(Value::as_object_mut)
+    {
+        filters.insert(
+            "additionalProperties".to_string(),
+            json!({
+                "type": "array",
+                "items": { "type": "string" },
+                "description": FILTER_OVERRIDE_ENTRY.description,
+            }),
+        );
+    }
+
+    if let Some(obj) = root.as_object_mut() {
+        let mut header = Map::new();
+        header.insert(
+            "$schema".to_string(),
+            json!("http://json-schema.org/draft-07/schema#"),
+        );
+        header.insert("title".to_string(), json!("voicsh config.toml"));
+        header.extend(std::mem::take(obj));
+        *obj = header;
+    }
+    root
+}
+
+/// Insert `leaf` at the nested property path, creating section objects on the way.
+fn insert_property(node: &mut serde_json::Value, parts: &[&str], leaf: serde_json::Value) {
+    let Some(properties) = node
+        .get_mut("properties")
+        .and_then(serde_json::Value::as_object_mut)
+    else {
+        return;
+    };
+    match parts {
+        [] => {}
+        [last] => {
+            properties.insert(last.to_string(), leaf);
+        }
+        [first, rest @ ..] => {
+            let child = properties
+                .entry(first.to_string())
+                .or_insert_with(section_schema);
+            insert_property(child, rest, leaf);
+        }
+    }
+}
+
+fn section_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {},
+        "additionalProperties": false,
+    })
+}
+
+fn kind_schema(kind: ValueKind) -> serde_json::Value {
+    use crate::pipeline::post_processor::SUPPORTED_LANGUAGES;
+    use serde_json::json;
+
+    match kind {
+        ValueKind::Bool => json!({ "type": "boolean" }),
+        ValueKind::Integer { min, max } => {
+            json!({ "type": "integer", "minimum": min, "maximum": max })
+        }
+        ValueKind::Float { min, max } => {
+            json!({ "type": "number", "minimum": min, "maximum": max })
+        }
+        ValueKind::String | ValueKind::OptionalString => json!({ "type": "string" }),
+        ValueKind::Enum(values) => json!({ "type": "string", "enum": values }),
+        ValueKind::Language => {
+            let mut codes = vec![crate::defaults::AUTO_LANGUAGE];
+            codes.extend_from_slice(SUPPORTED_LANGUAGES);
+            json!({ "type": "string", "enum": codes })
+        }
+        ValueKind::LanguageList => json!({
+            "type": "array",
+            "items": { "type": "string", "enum": SUPPORTED_LANGUAGES },
+        }),
+        ValueKind::DictionaryLanguage => {
+            let mut codes = vec![crate::defaults::AUTO_LANGUAGE];
+            codes.extend(
+                crate::dictionary::list_dictionaries()
+                    .iter()
+                    .map(|d| d.language),
+            );
+            json!({ "type": "string", "enum": codes })
+        }
+        ValueKind::StringList => json!({ "type": "array", "items": { "type": "string" } }),
+        ValueKind::StringMap => json!({
+            "type": "object",
+            "additionalProperties": { "type": "string" },
+        }),
+        ValueKind::FloatMap { min, max } => json!({
+            "type": "object",
+            "additionalProperties": { "type": "number", "minimum": min, "maximum": max },
+        }),
+        ValueKind::Duration => json!({
+            "type": "string",
+            "pattern": "^\\s*([0-9]+|([0-9]+[smhd])+)\\s*$",
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_schema_covers_every_config_leaf() {
+        let value = toml::Value::try_from(Config::default()).unwrap();
+        let mut leaves = Vec::new();
+        collect_leaves(&value, "", &mut leaves);
+        for leaf in leaves {
+            assert!(find_key(&leaf).is_some(), "Schema is missing key '{leaf}'");
+        }
+    }
+
+    fn collect_leaves(value: &toml::Value, prefix: &str, out: &mut Vec<String>) {
+        match value {
+            toml::Value::Table(t) if prefix != "voice_commands.commands" => {
+                for (k, v) in t {
+                    let path = if prefix.is_empty() {
+                        k.clone()
+                    } else {
+                        format!("{prefix}.{k}")
+                    };
+                    collect_leaves(v, &path, out);
+                }
+            }
+            _ => out.push(prefix.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_schema_defaults_validate() {
+        let value = toml::Value::try_from(Config::default()).unwrap();
+        for spec in SCHEMA {
+            let mut current = Some(&value);
+            for part in spec.key.split('.') {
+                current = current.and_then(|v| v.get(part));
+            }
+            if let Some(v) = current {
+                spec.validate(v, spec.key)
+                    .unwrap_or_else(|e| panic!("Default for '{}' is invalid: {e}", spec.key));
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_key_wildcards() {
+        assert_eq!(
+            find_key("voice_commands.commands.smiley").map(|s| s.kind),
+            Some(ValueKind::String)
+        );
+        assert_eq!(
+            find_key("transcription.hallucination_filters.en").map(|s| s.kind),
+            Some(ValueKind::StringList)
+        );
+        assert!(find_key("voice_commands.commands.").is_none());
+        assert!(find_key("voice_command.enabled").is_none());
+        assert!(find_key("audio.unknown").is_none());
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        let spec = find_key("stt.fan_out").unwrap();
+        assert_eq!(
+            spec.parse("true", spec.key).unwrap(),
+            Some(toml::Value::Boolean(true))
+        );
+        assert!(spec.parse("yes", spec.key).is_err());
+    }
+
+    #[test]
+    fn test_parse_float_range() {
+        let spec = find_key("audio.vad_threshold").unwrap();
+        assert_eq!(
+            spec.parse("0.05", spec.key).unwrap(),
+            Some(toml::Value::Float(0.05))
+        );
+        let err = spec.parse("1.5", spec.key).unwrap_err().to_string();
+        assert!(err.contains("out of range"), "got: {err}");
+        assert!(spec.parse("loud", spec.key).is_err());
+    }
+
+    #[test]
+    fn test_parse_integer_range() {
+        let spec = find_key("audio.silence_duration_ms").unwrap();
+        assert_eq!(
+            spec.parse("800", spec.key).unwrap(),
+            Some(toml::Value::Integer(800))
+        );
+        assert!(spec.parse("0", spec.key).is_err());
+        assert!(spec.parse("1.5", spec.key).is_err());
+    }
+
+    #[test]
+    fn test_parse_enum_is_case_insensitive() {
+        let spec = find_key("injection.backend").unwrap();
+        assert_eq!(
+            spec.parse("Portal", spec.key).unwrap(),
+            Some(toml::Value::String("portal".to_string()))
+        );
+        let err = spec.parse("xdotool", spec.key).unwrap_err().to_string();
+        assert!(err.contains("auto, portal, wtype, ydotool"), "got: {err}");
+    }
+
+    #[test]
+    fn test_parse_language() {
+        let spec = find_key("stt.language").unwrap();
+        assert!(spec.parse("auto", spec.key).is_ok());
+        assert!(spec.parse("de", spec.key).is_ok());
+        assert!(spec.parse("xx", spec.key).is_err());
+    }
+
+    #[test]
+    fn test_parse_language_list_comma_and_array() {
+        let spec = find_key("stt.allowed_languages").unwrap();
+        let expected = Some(toml::Value::Array(vec![
+            toml::Value::String("en".to_string()),
+            toml::Value::String("de".to_string()),
+        ]));
+        assert_eq!(spec.parse("en, de", spec.key).unwrap(), expected);
+        assert_eq!(spec.parse(r#"["en", "de"]"#, spec.key).unwrap(), expected);
+        assert_eq!(
+            spec.parse("", spec.key).unwrap(),
+            Some(toml::Value::Array(vec![]))
+        );
+        assert!(spec.parse("en,auto", spec.key).is_err());
+    }
+
+    #[test]
+    fn test_parse_optional_string_empty_clears() {
+        let spec = find_key("audio.device").unwrap();
+        assert_eq!(spec.parse("", spec.key).unwrap(), None);
+        assert_eq!(
+            spec.parse("hw:0,0", spec.key).unwrap(),
+            Some(toml::Value::String("hw:0,0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_value() {
+        let spec = find_key("stt.unload_after").unwrap();
+        assert_eq!(spec.parse("", spec.key).unwrap(), None);
+        assert_eq!(
+            spec.parse("15m", spec.key).unwrap(),
+            Some(toml::Value::String("15m".to_string()))
+        );
+        let err = spec.parse("soon", spec.key).unwrap_err().to_string();
+        assert!(err.contains("not a duration"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_dictionary_language() {
+        let spec = find_key("transcription.error_correction.dictionary_language").unwrap();
+        assert!(spec.parse("auto", spec.key).is_ok());
+        assert!(spec.parse("he", spec.key).is_ok());
+        assert!(spec.parse("ko", spec.key).is_err());
+    }
+
+    #[test]
+    fn test_parse_string_map() {
+        let spec = find_key("voice_commands.commands").unwrap();
+        let value = spec
+            .parse(r#"{ "smiley" = ":)" }"#, spec.key)
+            .unwrap()
+            .unwrap();
+        assert_eq!(value.get("smiley").and_then(|v| v.as_str()), Some(":)"));
+        assert!(spec.parse("smiley", spec.key).is_err());
+    }
+
+    #[test]
+    fn test_validate_type_mismatch_message() {
+        let spec = find_key("stt.min_confidence").unwrap();
+        let err = spec
+            .validate(&toml::Value::String("high".to_string()), spec.key)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expected a number, got string"), "got: {err}");
+    }
+
+    #[test]
+    fn test_check_toml_valid_file_has_no_diagnostics() {
+        let contents =
+            "[stt]\nmodel = \"small\"\nlanguage = \"de\"\n\n[audio]\nvad_threshold = 0.05\n";
+        assert_eq!(check_toml(contents), vec![]);
+    }
+
+    #[test]
+    fn test_check_toml_default_template_is_clean() {
+        let diagnostics = check_toml(&Config::dump_template());
+        assert_eq!(diagnostics, vec![], "dump template should validate");
+    }
+
+    #[test]
+    fn test_check_toml_unknown_section_with_suggestion() {
+        let contents = "[stt]\nmodel = \"base\"\n\n[voice_command]\nenabled = false\n";
+        let diagnostics = check_toml(contents);
+        assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+        let d = &diagnostics[0];
+        assert_eq!(d.severity, Severity::Warning);
+        assert_eq!(d.line, Some(4));
+        assert_eq!(d.key.as_deref(), Some("voice_command"));
+        assert!(
+            d.message.contains("did you mean 'voice_commands'"),
+            "got: {}",
+            d.message
+        );
+    }
+
+    #[test]
+    fn test_check_toml_unknown_nested_key() {
+        let contents = "[audio]\nvad_treshold = 0.05\n";
+        let diagnostics = check_toml(contents);
+        assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert_eq!(diagnostics[0].key.as_deref(), Some("audio.vad_treshold"));
+        assert!(diagnostics[0].message.contains("'vad_threshold'"));
+    }
+
+    #[test]
+    fn test_check_toml_out_of_range_and_bad_language() {
+        let contents =
+            "[audio]\nvad_threshold = 3.0\n\n[stt]\nallowed_languages = [\"en\", \"xx\"]\n";
+        let diagnostics = check_toml(contents);
+        assert_eq!(diagnostics.len(), 2, "got: {diagnostics:?}");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert!(diagnostics[0].message.contains("out of range"));
+        assert_eq!(diagnostics[1].line, Some(5));
+        assert!(diagnostics[1].message.contains("unsupported language 'xx'"));
+    }
+
+    #[test]
+    fn test_check_toml_device_thresholds() {
+        let contents =
+            "[audio.device_thresholds]\ndefault = 0.015\n\"hw:1,0\" = 1.5\nusb = \"loud\"\n";
+        let diagnostics = check_toml(contents);
+        assert_eq!(diagnostics.len(), 2, "got: {diagnostics:?}");
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert_eq!(
+            diagnostics[0].key.as_deref(),
+            Some("audio.device_thresholds.hw:1,0")
+        );
+        assert!(diagnostics[0].message.contains("out of range"));
+        assert_eq!(diagnostics[1].line, Some(4));
+        assert!(diagnostics[1].message.contains("expected a number"));
+    }
+
+    #[test]
+    fn test_parse_device_thresholds() {
+        let spec = find_key("audio.device_thresholds").unwrap();
+        let parsed = spec.parse(r#"{ "hw:0" = 0.03 }"#, spec.key).unwrap();
+        assert_eq!(
+            parsed.and_then(|v| v.get("hw:0").and_then(toml::Value::as_float)),
+            Some(0.03)
+        );
+        assert!(spec.parse(r#"{ "hw:0" = 2.0 }"#, spec.key).is_err());
+        assert!(spec.parse("0.03", spec.key).is_err());
+        assert_eq!(
+            find_key("audio.device_thresholds.hw:0").map(|s| s.kind),
+            Some(ValueKind::Float { min: 0.0, max: 1.0 })
+        );
+    }
+
+    #[test]
+    fn test_check_toml_type_mismatch() {
+        let contents = "[stt]\nfan_out = \"yes\"\n";
+        let diagnostics = check_toml(contents);
+        assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+        assert_eq!(diagnostics[0].line, Some(2));
+        assert_eq!(diagnostics[0].message, "expected a boolean, got string");
+    }
+
+    #[test]
+    fn test_check_toml_voice_command_entries_checked_individually() {
+        let contents = "[voice_commands.commands]\nsmiley = \":)\"\nbad = 3\n";
+        let diagnostics = check_toml(contents);
+        assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert_eq!(
+            diagnostics[0].key.as_deref(),
+            Some("voice_commands.commands.bad")
+        );
+    }
+
+    #[test]
+    fn test_check_toml_syntax_error_has_line() {
+        let contents = "[stt]\nmodel = \n";
+        let diagnostics = check_toml(contents);
+        assert_eq!(diagnostics.len(), 1, "got: {diagnostics:?}");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_diagnostic_display() {
+        let d = Diagnostic {
+            severity: Severity::Warning,
+            line: Some(4),
+            key: Some("voice_command".to_string()),
+            message: "unknown key 'voice_command' is ignored".to_string(),
+        };
+        assert_eq!(
+            d.to_string(),
+            "line 4: warning: voice_command: unknown key 'voice_command' is ignored"
+        );
+    }
+
+    #[test]
+    fn test_check_file_missing_is_empty() {
+        let diagnostics = check_file(std::path::Path::new("/nonexistent/voicsh.toml")).unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_json_schema_structure() {
+        let schema = json_schema();
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+        let threshold = &schema["properties"]["audio"]["properties"]["vad_threshold"];
+        assert_eq!(threshold["type"], "number");
+        assert_eq!(threshold["maximum"], 1.0);
+        assert!(threshold["default"].is_number());
+        assert_eq!(
+            schema["properties"]["injection"]["properties"]["backend"]["enum"],
+            serde_json::json!(["auto", "portal", "wtype", "ydotool"])
+        );
+        assert_eq!(schema["properties"]["stt"]["additionalProperties"], false);
+        assert_eq!(
+            schema["properties"]["voice_commands"]["properties"]["commands"]["additionalProperties"]
+                ["type"],
+            "string"
+        );
+        assert_eq!(
+            schema["properties"]["transcription"]["properties"]["hallucination_filters"]["additionalProperties"]
+                ["type"],
+            "array"
+        );
+    }
+
+    #[test]
+    fn test_check_toml_legacy_input_section_suggests_migrate() {
+        let contents = "[input]\nmethod = \"direct\"\nbackend = \"xdotool\"\n";
+        let diagnostics = check_toml(contents);
+        assert_eq!(diagnostics.len(), 2, "got: {diagnostics:?}");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0].message.contains("voicsh config migrate"));
+        assert_eq!(diagnostics[1].line, Some(3));
+        assert_eq!(diagnostics[1].key.as_deref(), Some("injection.backend"));
+    }
+}
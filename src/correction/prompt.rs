@@ -1,6 +1,6 @@
 //! Generic utilities for correction pipeline.
 
-use crate::stt::transcriber::TokenProbability;
+use crate::stt::transcriber::{TokenProbability, Word};
 
 /// Check whether any tokens need correction (below threshold).
 ///
@@ -49,6 +49,18 @@ pub fn build_confidence_prompt(tokens: &[TokenProbability]) -> String {
     parts.join(" ")
 }
 
+/// Build a confidence-annotated prompt from whole words.
+///
+/// Same format as [`build_confidence_prompt`], but sub-word tokens are already
+/// merged, so T5 sees `quickly[0.41]` instead of `quick[0.41] ly[0.88]`.
+pub fn build_word_confidence_prompt(words: &[Word]) -> String {
+    words
+        .iter()
+        .map(|w| format!("{}[{:.2}]", w.text, w.probability))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Strip confidence score annotations `[0.XX]` from text.
 ///
 /// Used to clean T5 output that might echo back confidence scores.
@@ -437,6 +449,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_word_confidence_prompt_formats_words() {
+        let words = vec![
+            Word {
+                text: "quickly".into(),
+                start_ms: 0,
+                end_ms: 400,
+                probability: 0.41,
+                model: None,
+            },
+            Word {
+                text: "ran.".into(),
+                start_ms: 400,
+                end_ms: 700,
+                probability: 0.875,
+                model: None,
+            },
+        ];
+        assert_eq!(
+            build_word_confidence_prompt(&words),
+            "quickly[0.41] ran.[0.88]"
+        );
+        assert_eq!(build_word_confidence_prompt(&[]), "");
+    }
+
     #[test]
     fn build_confidence_prompt_empty_returns_empty() {
         assert_eq!(build_confidence_prompt(&[]), "");
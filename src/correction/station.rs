@@ -76,7 +76,9 @@ impl Station for CorrectionStation {
             return Ok(Some(input));
         }
 
-        let correction_text = if uses_t5 {
+        let correction_text = if uses_t5 && !input.words.is_empty() {
+            prompt::build_word_confidence_prompt(&input.words)
+        } else if uses_t5 {
             prompt::build_confidence_prompt(&input.token_probabilities)
         } else {
             prompt::extract_raw_text(&input.token_probabilities)
@@ -181,6 +183,8 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::default(),
             corrector_name: None,
+            segments: Vec::new(),
+            words: Vec::new(),
         }
     }
 
@@ -262,6 +266,49 @@ mod tests {
         assert_eq!(result.text, "the quick brown");
     }
 
+    /// Corrector that records the prompts it receives.
+    struct RecordingCorrectorForTest {
+        prompts: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl Corrector for RecordingCorrectorForTest {
+        fn correct(&mut self, prompt: &str) -> crate::error::Result<String> {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+            Ok(String::new())
+        }
+        fn name(&self) -> &str {
+            "recording-corrector"
+        }
+    }
+
+    #[test]
+    fn t5_prompt_uses_whole_words_when_available() {
+        let prompts = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let corrector = Box::new(RecordingCorrectorForTest {
+            prompts: prompts.clone(),
+        });
+        let mut station = CorrectionStation::new(corrector, enabled_config(0.7));
+        let mut input = make_input("the quik", "en", low_confidence_tokens());
+        input.words = vec![
+            crate::stt::transcriber::Word {
+                text: "the".into(),
+                start_ms: 0,
+                end_ms: 200,
+                probability: 0.25,
+                model: None,
+            },
+            crate::stt::transcriber::Word {
+                text: "quik".into(),
+                start_ms: 200,
+                end_ms: 500,
+                probability: 0.5,
+                model: None,
+            },
+        ];
+        station.process(input).unwrap();
+        assert_eq!(*prompts.lock().unwrap(), vec!["the[0.25] quik[0.50]"]);
+    }
+
     #[test]
     fn corrector_error_falls_back_to_raw_text() {
         let corrector = Box::new(FixedCorrectorForTest {
@@ -305,6 +352,8 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::default(),
             corrector_name: None,
+            segments: Vec::new(),
+            words: Vec::new(),
         };
         let result = station.process(input).unwrap().unwrap();
         assert_eq!(result.text, "the quick brown");
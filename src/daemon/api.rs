@@ -0,0 +1,851 @@
+//! Local OpenAI-compatible transcription API.
+//!
+//! Serves `POST /v1/audio/transcriptions` and `GET /v1/models` on a loopback
+//! TCP port or a Unix socket, so other local tools can use the daemon's warm
+//! model. Uploads run through the same pipeline as `voicsh transcribe` and
+//! queue behind live dictation (see [`crate::stt::queue`]). Upload bodies
+//! are charged against the same audio budget as IPC uploads.
+//!
+//! Only the parts of HTTP/1.1 these endpoints need are implemented: one
+//! request per connection, bodies with `Content-Length`, `Expect: 100-continue`.
+
+use crate::audio::decode;
+use crate::config::ApiConfig;
+use crate::daemon::handler::DaemonCommandHandler;
+use crate::error::{Result, VoicshError};
+use crate::pipeline::sink::TextSink;
+use crate::pipeline::types::TranscribedText;
+use crate::transcript::{Transcript, TranscriptFormat};
+use serde_json::json;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, UnixListener};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Endpoint for transcription uploads.
+const TRANSCRIPTIONS_PATH: &str = "/v1/audio/transcriptions";
+
+/// Endpoint listing the loaded model.
+const MODELS_PATH: &str = "/v1/models";
+
+/// Largest accepted request head (request line and headers).
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Time a client gets to send its complete request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Maximum number of concurrent API connections.
+const MAX_CONNECTIONS: usize = 8;
+
+/// Where the API listens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiListen {
+    /// Loopback TCP address.
+    Tcp(SocketAddr),
+    /// Unix socket path.
+    Unix(PathBuf),
+}
+
+impl std::str::FromStr for ApiListen {
+    type Err = VoicshError;
+
+    /// Parse `host:port` (loopback only) or an absolute Unix socket path.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |message: String| VoicshError::ConfigInvalidValue {
+            key: "api.listen".to_string(),
+            message,
+        };
+        if s.starts_with('/') {
+            return Ok(Self::Unix(PathBuf::from(s)));
+        }
+        let addr: SocketAddr = s.parse().map_err(|_| {
+            invalid(format!(
+                "'{s}' is neither host:port nor an absolute socket path"
+            ))
+        })?;
+        if !addr.ip().is_loopback() {
+            return Err(invalid(format!(
+                "'{s}' is not a loopback address; the API is only served locally"
+            )));
+        }
+        Ok(Self::Tcp(addr))
+    }
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    Unix(UnixListener, PathBuf),
+}
+
+/// Bound API listener, ready to [`serve`](Self::serve).
+pub struct ApiServer {
+    listener: Listener,
+    api_key: Option<String>,
+    max_upload_bytes: usize,
+    audio_budget: Arc<Semaphore>,
+}
+
+impl ApiServer {
+    /// Bind the listener configured in `[api]`.
+    pub async fn bind(config: &ApiConfig) -> Result<Self> {
+        let api_error = |message: String| VoicshError::IpcSocket { message };
+        let listener = match config.listen.parse::<ApiListen>()? {
+            ApiListen::Tcp(addr) => Listener::Tcp(
+                TcpListener::bind(addr)
+                    .await
+                    .map_err(|e| api_error(format!("Failed to bind API to {addr}: {e}")))?,
+            ),
+            ApiListen::Unix(path) => {
+                if let Err(e) = std::fs::remove_file(&path)
+                    && e.kind() != std::io::ErrorKind::NotFound
+                {
+                    return Err(api_error(format!(
+                        "Failed to remove existing socket {}: {e}",
+                        path.display()
+                    )));
+                }
+                let listener = UnixListener::bind(&path).map_err(|e| {
+                    api_error(format!("Failed to bind API to {}: {e}", path.display()))
+                })?;
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+                    .map_err(|e| api_error(format!("Failed to set socket permissions: {e}")))?;
+                Listener::Unix(listener, path)
+            }
+        };
+        Ok(Self {
+            listener,
+            api_key: config.api_key.clone().filter(|k| !k.is_empty()),
+            max_upload_bytes: config.max_upload_mb as usize * 1024 * 1024,
+            audio_budget: Arc::new(Semaphore::new(crate::ipc::server::AUDIO_BUDGET_BYTES)),
+        })
+    }
+
+    /// Charge uploads against `budget`, shared with the IPC server, instead
+    /// of a budget of their own.
+    pub fn with_audio_budget(mut self, budget: Arc<Semaphore>) -> Self {
+        self.audio_budget = budget;
+        self
+    }
+
+    /// Where clients reach the API.
+    pub fn address(&self) -> String {
+        match &self.listener {
+            Listener::Tcp(listener) => listener
+                .local_addr()
+                .map_or_else(|_| "tcp".to_string(), |addr| format!("http://{addr}")),
+            Listener::Unix(_, path) => path.display().to_string(),
+        }
+    }
+
+    /// Accept connections until the task is dropped (the Unix socket file is
+    /// removed then).
+    pub async fn serve(self, handler: DaemonCommandHandler) {
+        let semaphore = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+        let context = Arc::new(RequestContext {
+            handler,
+            api_key: self.api_key.clone(),
+            max_upload_bytes: self.max_upload_bytes,
+            audio_budget: Arc::clone(&self.audio_budget),
+        });
+        loop {
+            let permit = match semaphore.clone().acquire_owned().await {
+                Ok(permit) => permit,
+                Err(_) => return,
+            };
+            let context = Arc::clone(&context);
+            let accepted = match &self.listener {
+                Listener::Tcp(listener) => listener.accept().await.map(|(stream, _)| {
+                    tokio::spawn(async move {
+                        handle_connection(stream, &context).await;
+                        drop(permit);
+                    });
+                }),
+                Listener::Unix(listener, _) => listener.accept().await.map(|(stream, _)| {
+                    tokio::spawn(async move {
+                        handle_connection(stream, &context).await;
+                        drop(permit);
+                    });
+                }),
+            };
+            if let Err(e) = accepted {
+                eprintln!("voicsh: API failed to accept connection: {e}");
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        if let Listener::Unix(_, path) = &self.listener {
+            std::fs::remove_file(path).ok();
+        }
+    }
+}
+
+/// Shared by all connections of one server.
+struct RequestContext {
+    handler: DaemonCommandHandler,
+    api_key: Option<String>,
+    max_upload_bytes: usize,
+    audio_budget: Arc<Semaphore>,
+}
+
+/// Failed request, rendered as an OpenAI-style error body.
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn body(&self) -> String {
+        let kind = if self.status >= 500 {
+            "server_error"
+        } else {
+            "invalid_request_error"
+        };
+        json!({"error": {"message": self.message, "type": kind, "code": null}}).to_string()
+    }
+}
+
+/// Parsed request.
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    /// Share of the audio budget, held until the request is answered
+    _charge: Option<OwnedSemaphorePermit>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Successful response.
+struct Reply {
+    content_type: &'static str,
+    body: String,
+}
+
+async fn handle_connection<S>(mut stream: S, context: &RequestContext)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let request =
+        match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream, context)).await {
+            Ok(result) => result,
+            Err(_) => Err(ApiError::new(408, "Request timed out")),
+        };
+    let (status, content_type, body) = match request {
+        Ok(request) => match route(request, context).await {
+            Ok(reply) => (200, reply.content_type, reply.body),
+            Err(e) => (e.status, "application/json", e.body()),
+        },
+        Err(e) => (e.status, "application/json", e.body()),
+    };
+    let head = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        reason(status),
+        body.len()
+    );
+    if stream.write_all(head.as_bytes()).await.is_ok()
+        && stream.write_all(body.as_bytes()).await.is_ok()
+    {
+        stream.shutdown().await.ok();
+    }
+}
+
+/// Read the request head and its `Content-Length` body.
+async fn read_request<S>(
+    stream: &mut S,
+    context: &RequestContext,
+) -> std::result::Result<Request, ApiError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let closed = |e: std::io::Error| ApiError::new(400, format!("Failed to read request: {e}"));
+    let mut buf = Vec::new();
+    let head_end = loop {
+        if let Some(pos) = find(&buf, b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err(ApiError::new(431, "Request headers too large"));
+        }
+        let mut chunk = [0u8; 4096];
+        let n = stream.read(&mut chunk).await.map_err(closed)?;
+        if n == 0 {
+            return Err(ApiError::new(400, "Connection closed before request ended"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let body = buf.split_off(head_end + 4);
+    let head = String::from_utf8_lossy(&buf[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err(ApiError::new(400, "Malformed request line"));
+    };
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    let mut request = Request {
+        method: method.to_string(),
+        path: target.split('?').next().unwrap_or(target).to_string(),
+        headers,
+        body,
+        _charge: None,
+    };
+
+    if request
+        .header("transfer-encoding")
+        .is_some_and(|v| !v.eq_ignore_ascii_case("identity"))
+    {
+        return Err(ApiError::new(
+            411,
+            "Chunked uploads are not supported, send Content-Length",
+        ));
+    }
+    let length = match request.header("content-length") {
+        Some(v) => v
+            .parse::<usize>()
+            .map_err(|_| ApiError::new(400, "Invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > context.max_upload_bytes {
+        return Err(ApiError::new(
+            413,
+            format!(
+                "Upload of {length} bytes exceeds the {} MB limit (api.max_upload_mb)",
+                context.max_upload_bytes / (1024 * 1024)
+            ),
+        ));
+    }
+    let mut charge = None;
+    crate::ipc::server::charge_audio_budget(&context.audio_budget, &mut charge, length)
+        .map_err(|e| ApiError::new(503, e.to_string()))?;
+    request._charge = charge;
+    if length > request.body.len() {
+        if request
+            .header("expect")
+            .is_some_and(|v| v.eq_ignore_ascii_case("100-continue"))
+        {
+            stream
+                .write_all(b"HTTP/1.1 100 Continue\r\n\r\n")
+                .await
+                .map_err(closed)?;
+        }
+        let start = request.body.len();
+        request.body.resize(length, 0);
+        stream
+            .read_exact(&mut request.body[start..])
+            .await
+            .map_err(closed)?;
+    }
+    request.body.truncate(length);
+    Ok(request)
+}
+
+async fn route(request: Request, context: &RequestContext) -> std::result::Result<Reply, ApiError> {
+    if let Some(key) = &context.api_key {
+        let expected = format!("Bearer {key}");
+        if request.header("authorization") != Some(expected.as_str()) {
+            return Err(ApiError::new(401, "Invalid or missing API key"));
+        }
+    }
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", MODELS_PATH) => {
+            let model = context.handler.model_name().await;
+            Ok(Reply {
+                content_type: "application/json",
+                body: json!({
+                    "object": "list",
+                    "data": [{"id": model, "object": "model", "created": 0, "owned_by": "voicsh"}],
+                })
+                .to_string(),
+            })
+        }
+        ("POST", TRANSCRIPTIONS_PATH) => transcribe(request, context).await,
+        (_, MODELS_PATH | TRANSCRIPTIONS_PATH) => Err(ApiError::new(
+            405,
+            format!("{} is not allowed on {}", request.method, request.path),
+        )),
+        _ => Err(ApiError::new(
+            404,
+            format!("Unknown endpoint {}", request.path),
+        )),
+    }
+}
+
+/// Handle an upload: decode the `file` part and run it through the pipeline.
+///
+/// `model`, `language`, `prompt` and `temperature` are accepted for client
+/// compatibility; the daemon's loaded model and language settings apply.
+async fn transcribe(
+    request: Request,
+    context: &RequestContext,
+) -> std::result::Result<Reply, ApiError> {
+    let content_type = request.header("content-type").unwrap_or_default();
+    let parts = parse_multipart(content_type, &request.body)?;
+    let field = |name: &str| {
+        parts
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| String::from_utf8_lossy(p.data).trim().to_string())
+    };
+    let format: ResponseFormat = match field("response_format") {
+        Some(f) => f.parse()?,
+        None => ResponseFormat::Json,
+    };
+    let file = parts
+        .iter()
+        .find(|p| p.name == "file")
+        .ok_or_else(|| ApiError::new(400, "Missing 'file' field"))?;
+    // Cut the file out of the request buffer rather than copying it
+    let start = file.data.as_ptr() as usize - request.body.as_ptr() as usize;
+    let end = start + file.data.len();
+
+    let mut audio = request.body;
+    audio.truncate(end);
+    audio.drain(..start);
+    let source = decode::open_bytes(audio, None).map_err(|e| ApiError::new(400, e.to_string()))?;
+    let body = context
+        .handler
+        .transcribe_to_sink(source, Box::new(ApiSink::new(format)))
+        .await
+        .map_err(|message| ApiError::new(500, message))?
+        .unwrap_or_default();
+    Ok(Reply {
+        content_type: format.content_type(),
+        body,
+    })
+}
+
+/// `response_format` of the transcription endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseFormat {
+    Json,
+    Text,
+    Srt,
+    Vtt,
+    VerboseJson,
+}
+
+impl std::str::FromStr for ResponseFormat {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            "srt" => Ok(Self::Srt),
+            "vtt" => Ok(Self::Vtt),
+            "verbose_json" => Ok(Self::VerboseJson),
+            other => Err(ApiError::new(
+                400,
+                format!(
+                    "Unsupported response_format '{other}'. Valid options: json, text, srt, vtt, verbose_json"
+                ),
+            )),
+        }
+    }
+}
+
+impl ResponseFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Json | Self::VerboseJson => "application/json",
+            Self::Text | Self::Srt => "text/plain; charset=utf-8",
+            Self::Vtt => "text/vtt; charset=utf-8",
+        }
+    }
+}
+
+/// Collects the transcript of one upload and renders the response body.
+struct ApiSink {
+    transcript: Transcript,
+    format: ResponseFormat,
+}
+
+impl ApiSink {
+    fn new(format: ResponseFormat) -> Self {
+        Self {
+            transcript: Transcript::new(),
+            format,
+        }
+    }
+
+    /// OpenAI `verbose_json`: language, duration, timed segments and words.
+    fn render_verbose_json(&self) -> String {
+        let segments = self.transcript.segments();
+        let ms_to_s = |ms: u64| ms as f64 / 1000.0;
+        let segment_values: Vec<_> = segments
+            .iter()
+            .enumerate()
+            .map(|(id, s)| {
+                json!({
+                    "id": id,
+                    "start": ms_to_s(s.start_ms),
+                    "end": ms_to_s(s.end_ms),
+                    "text": s.text,
+                    "avg_logprob": f64::from(s.confidence.max(1e-6)).ln(),
+                })
+            })
+            .collect();
+        let words: Vec<_> = segments
+            .iter()
+            .flat_map(|s| &s.words)
+            .map(|w| {
+                json!({
+                    "word": w.text,
+                    "start": ms_to_s(w.start_ms),
+                    "end": ms_to_s(w.end_ms),
+                    "probability": w.probability,
+                })
+            })
+            .collect();
+        json!({
+            "task": "transcribe",
+            "language": self.transcript.language().unwrap_or_default(),
+            "duration": segments.last().map_or(0.0, |s| ms_to_s(s.end_ms)),
+            "text": self.transcript.text(),
+            "segments": segment_values,
+            "words": words,
+        })
+        .to_string()
+    }
+}
+
+impl TextSink for ApiSink {
+    fn handle(&mut self, text: &str) -> Result<()> {
+        self.transcript
+            .push(&TranscribedText::new(text.to_string()));
+        Ok(())
+    }
+
+    fn handle_transcription(&mut self, text: &TranscribedText) -> Result<()> {
+        self.transcript.push(text);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Option<String> {
+        Some(match self.format {
+            ResponseFormat::Json => json!({"text": self.transcript.text()}).to_string(),
+            ResponseFormat::Text => self.transcript.text(),
+            ResponseFormat::Srt => self.transcript.render(TranscriptFormat::Srt),
+            ResponseFormat::Vtt => self.transcript.render(TranscriptFormat::Vtt),
+            ResponseFormat::VerboseJson => self.render_verbose_json(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "api"
+    }
+}
+
+/// One part of a `multipart/form-data` body.
+#[derive(Debug, PartialEq)]
+struct FormPart<'a> {
+    name: String,
+    data: &'a [u8],
+}
+
+/// Split a `multipart/form-data` body into its parts.
+fn parse_multipart<'a>(
+    content_type: &str,
+    body: &'a [u8],
+) -> std::result::Result<Vec<FormPart<'a>>, ApiError> {
+    let boundary = content_type
+        .split(';')
+        .map(str::trim)
+        .find_map(|param| param.strip_prefix("boundary="))
+        .map(|b| b.trim_matches('"'))
+        .filter(|_| content_type.trim_start().starts_with("multipart/form-data"))
+        .ok_or_else(|| ApiError::new(400, "Expected a multipart/form-data upload"))?;
+    let delimiter = format!("--{boundary}").into_bytes();
+    let malformed = || ApiError::new(400, "Malformed multipart body");
+
+    let mut rest = &body[find(body, &delimiter).ok_or_else(malformed)? + delimiter.len()..];
+    let mut parts = Vec::new();
+    while !rest.starts_with(b"--") {
+        rest = rest.strip_prefix(b"\r\n").ok_or_else(malformed)?;
+        let headers_end = find(rest, b"\r\n\r\n").ok_or_else(malformed)?;
+        let headers = String::from_utf8_lossy(&rest[..headers_end]);
+        let content = &rest[headers_end + 4..];
+        let mut closing = b"\r\n".to_vec();
+        closing.extend_from_slice(&delimiter);
+        let end = find(content, &closing).ok_or_else(malformed)?;
+
+        let name = headers
+            .split("\r\n")
+            .find(|h| h.to_ascii_lowercase().starts_with("content-disposition:"))
+            .and_then(|h| {
+                h.split(';')
+                    .map(str::trim)
+                    .find_map(|p| p.strip_prefix("name="))
+            })
+            .map(|n| n.trim_matches('"').to_string())
+            .ok_or_else(malformed)?;
+        parts.push(FormPart {
+            name,
+            data: &content[..end],
+        });
+        rest = &content[end + closing.len()..];
+    }
+    Ok(parts)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::daemon::DaemonState;
+    use crate::stt::http::multipart_body;
+    use crate::stt::transcriber::{MockTranscriber, Transcriber};
+
+    fn loud_wav_bytes() -> Vec<u8> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        // 2 seconds of loud audio so the VAD detects speech
+        for _ in 0..32000 {
+            writer.write_sample(10000i16).unwrap();
+        }
+        writer.finalize().unwrap();
+        cursor.into_inner()
+    }
+
+    fn handler() -> DaemonCommandHandler {
+        let transcriber: Arc<dyn Transcriber> =
+            Arc::new(MockTranscriber::new("mock-api-model").with_response("hello from the api"));
+        let state = DaemonState::new(
+            Config::default(),
+            transcriber,
+            #[cfg(feature = "portal")]
+            None,
+        );
+        DaemonCommandHandler::new(state, true, 0)
+    }
+
+    /// Serve on an ephemeral loopback port and return its base URL.
+    async fn start(api_key: Option<&str>) -> String {
+        let config = ApiConfig {
+            enabled: true,
+            listen: "127.0.0.1:0".to_string(),
+            api_key: api_key.map(str::to_string),
+            ..ApiConfig::default()
+        };
+        let server = ApiServer::bind(&config).await.unwrap();
+        let url = server.address();
+        tokio::spawn(server.serve(handler()));
+        url
+    }
+
+    async fn upload(url: &str, fields: &[(&str, &str)]) -> reqwest::Response {
+        let (content_type, body) = multipart_body(fields, &loud_wav_bytes());
+        reqwest::Client::new()
+            .post(format!("{url}{TRANSCRIPTIONS_PATH}"))
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    async fn json_body(response: reqwest::Response) -> serde_json::Value {
+        serde_json::from_str(&response.text().await.unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_listen_address_parsing() {
+        assert_eq!(
+            "127.0.0.1:8765".parse::<ApiListen>().unwrap(),
+            ApiListen::Tcp("127.0.0.1:8765".parse().unwrap())
+        );
+        assert!("[::1]:8765".parse::<ApiListen>().is_ok());
+        assert_eq!(
+            "/run/user/1000/voicsh-api.sock"
+                .parse::<ApiListen>()
+                .unwrap(),
+            ApiListen::Unix(PathBuf::from("/run/user/1000/voicsh-api.sock"))
+        );
+        let err = "0.0.0.0:8765".parse::<ApiListen>().unwrap_err();
+        assert!(err.to_string().contains("loopback"), "{err}");
+        assert!("localhost".parse::<ApiListen>().is_err());
+    }
+
+    #[test]
+    fn test_parse_multipart_fields_and_binary_file() {
+        let wav = [0u8, 13, 10, 45, 45, 255];
+        let (content_type, body) = multipart_body(&[("model", "whisper-1")], &wav);
+        let parts = parse_multipart(&content_type, &body).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].name, "model");
+        assert_eq!(parts[0].data, b"whisper-1");
+        assert_eq!(parts[1].name, "file");
+        assert_eq!(parts[1].data, wav);
+
+        assert!(parse_multipart("application/json", &body).is_err());
+        assert!(parse_multipart(&content_type, b"garbage").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_models_lists_loaded_model() {
+        let url = start(None).await;
+        let body = json_body(reqwest::get(format!("{url}{MODELS_PATH}")).await.unwrap()).await;
+        assert_eq!(body["data"][0]["id"], "mock-api-model");
+    }
+
+    #[tokio::test]
+    async fn test_transcription_json_and_verbose_json() {
+        let url = start(None).await;
+
+        let response = upload(&url, &[("model", "whisper-1")]).await;
+        assert_eq!(response.status(), 200);
+        let body = json_body(response).await;
+        assert_eq!(body["text"], "hello from the api");
+
+        let response = upload(&url, &[("response_format", "verbose_json")]).await;
+        let body = json_body(response).await;
+        assert_eq!(body["task"], "transcribe");
+        assert_eq!(body["text"], "hello from the api");
+        assert!(body["segments"].is_array());
+
+        let response = upload(&url, &[("response_format", "text")]).await;
+        assert_eq!(response.text().await.unwrap(), "hello from the api");
+    }
+
+    #[tokio::test]
+    async fn test_request_errors() {
+        let url = start(Some("secret")).await;
+        let client = reqwest::Client::new();
+
+        let response = upload(&url, &[]).await;
+        assert_eq!(response.status(), 401);
+        let body = json_body(response).await;
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+
+        let response = client
+            .get(format!("{url}/v1/unknown"))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+
+        let response = client
+            .get(format!("{url}{TRANSCRIPTIONS_PATH}"))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 405);
+
+        let (content_type, body) = multipart_body(&[("response_format", "docx")], b"RIFF");
+        let response = client
+            .post(format!("{url}{TRANSCRIPTIONS_PATH}"))
+            .bearer_auth("secret")
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+        assert!(response.text().await.unwrap().contains("docx"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_over_shared_budget_is_rejected() {
+        let config = ApiConfig {
+            enabled: true,
+            listen: "127.0.0.1:0".to_string(),
+            ..ApiConfig::default()
+        };
+        let budget = Arc::new(Semaphore::new(1024));
+        let server = ApiServer::bind(&config)
+            .await
+            .unwrap()
+            .with_audio_budget(budget.clone());
+        let url = server.address();
+        tokio::spawn(server.serve(handler()));
+
+        let response = upload(&url, &[]).await;
+        assert_eq!(response.status(), 503);
+        assert!(response.text().await.unwrap().contains("busy"));
+        assert_eq!(budget.available_permits(), 1024);
+    }
+
+    #[tokio::test]
+    async fn test_serves_on_unix_socket_and_removes_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api.sock");
+        let config = ApiConfig {
+            enabled: true,
+            listen: path.to_string_lossy().into_owned(),
+            ..ApiConfig::default()
+        };
+        let server = ApiServer::bind(&config).await.unwrap();
+        assert_eq!(server.address(), path.display().to_string());
+        let task = tokio::spawn(server.serve(handler()));
+
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        stream
+            .write_all(b"GET /v1/models HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.contains("mock-api-model"), "{response}");
+
+        task.abort();
+        assert!(task.await.is_err());
+        assert!(!path.exists(), "socket file removed on shutdown");
+    }
+}
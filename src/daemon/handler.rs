@@ -1,6 +1,8 @@
 //! Command handler implementation for the daemon.
 
-use crate::audio::capture::CpalAudioSource;
+use crate::audio::capture::CpalDevices;
+use crate::audio::decode;
+use crate::audio::hotplug::HotplugSource;
 use crate::audio::recorder::AudioSource;
 use crate::audio::vad::VadConfig;
 use crate::config::{Config, resolve_hallucination_filters, resolve_suspect_phrases};
@@ -9,12 +11,47 @@ use crate::inject::focused_window::reset_detection_cache;
 use crate::ipc::protocol::{Command, DaemonEvent, Response, TextOrigin};
 use crate::ipc::server::CommandHandler;
 use crate::pipeline::adaptive_chunker::AdaptiveChunkerConfig;
+use crate::pipeline::archive::ArchiveSettings;
+use crate::pipeline::live::{PhraseFilters, VadThreshold};
 use crate::pipeline::orchestrator::{Pipeline, PipelineConfig};
 use crate::pipeline::post_processor::build_post_processors;
-use crate::pipeline::sink::InjectorSink;
+use crate::pipeline::sink::{InjectorSink, TextSink, TranscriptSink};
+use crate::stt::deferred::DeferredTranscriber;
+use crate::stt::queue::Priority;
+use crate::stt::transcriber::Transcriber;
+use crate::transcript::TranscriptFormat;
+use std::path::Path;
 use std::sync::Arc;
 
+/// Config keys that only take effect after a daemon restart.
+///
+/// These change how the transcriber is built; they are applied anyway when
+/// the same reload also switches `stt.model`.
+const RESTART_REQUIRED_KEYS: &[&str] = &[
+    "stt.fan_out",
+    "stt.fan_out_strategy",
+    "stt.backend",
+    "stt.http.url",
+    "stt.http.model",
+    "stt.http.api_key",
+    "stt.http.timeout_secs",
+    "stt.http.retries",
+    "stt.http.fallback",
+    "stt.deepgram.url",
+    "stt.deepgram.model",
+    "stt.deepgram.api_key_file",
+    "stt.deepgram.interim_results",
+    "stt.candle.repo",
+    "stt.candle.beam_size",
+    "stt.language_models_budget_mb",
+];
+
+/// Config sections bound once at daemon startup: the local API listener and
+/// the per-language model routes.
+const RESTART_REQUIRED_PREFIXES: &[&str] = &["api.", "stt.language_models."];
+
 /// Command handler for daemon IPC commands.
+#[derive(Clone)]
 pub struct DaemonCommandHandler {
     state: Arc<DaemonState>,
     quiet: bool,
@@ -36,6 +73,144 @@ impl DaemonCommandHandler {
         self.state.subscribe()
     }
 
+    /// Name of the loaded model (the configured one while unloaded).
+    pub(crate) async fn model_name(&self) -> String {
+        let loaded = self
+            .state
+            .transcriber
+            .read()
+            .await
+            .as_ref()
+            .map(|t| t.model_name().to_string());
+        match loaded {
+            Some(name) => name,
+            None => self.state.model_name().await,
+        }
+    }
+
+    /// The transcriber for a new pipeline, reloading an unloaded model.
+    ///
+    /// While unloaded, a [`DeferredTranscriber`] is returned at once and the
+    /// model loads in the background, so the pipeline already buffers audio
+    /// and transcribes it once the model is ready.
+    async fn current_transcriber(&self) -> Arc<dyn Transcriber> {
+        if let Some(transcriber) = self.state.transcriber.read().await.as_ref() {
+            return transcriber.clone();
+        }
+        let mut slot = self.state.transcriber.write().await;
+        // Another request may have started the reload meanwhile
+        if let Some(transcriber) = slot.as_ref() {
+            return transcriber.clone();
+        }
+        let config = self.state.config.lock().await.clone();
+        let deferred = Arc::new(DeferredTranscriber::new(&config.stt.model));
+        *slot = Some(deferred.clone());
+        drop(slot);
+
+        let state = self.state.clone();
+        let verbosity = self.verbosity;
+        let quiet = self.quiet;
+        let no_download = self.state.no_download;
+        let placeholder = deferred.clone();
+        tokio::spawn(async move {
+            let model = config.stt.model.clone();
+            state.emit(DaemonEvent::ModelLoading {
+                model: model.clone(),
+                progress: "loading".to_string(),
+            });
+            // Separate task, so even a panic completes the placeholder and
+            // releases the waiting pipeline
+            let loaded = match tokio::spawn(async move {
+                crate::daemon::create_transcriber(&config, quiet, verbosity, no_download).await
+            })
+            .await
+            {
+                Ok(result) => result,
+                Err(e) => Err(crate::error::VoicshError::Other(format!(
+                    "Model loading task failed: {e}"
+                ))),
+            };
+
+            let mut slot = state.transcriber.write().await;
+            // A model switch during loading keeps its own transcriber
+            let pending = slot
+                .as_ref()
+                .is_some_and(|t| std::ptr::addr_eq(Arc::as_ptr(t), Arc::as_ptr(&placeholder)));
+            match loaded {
+                Ok(transcriber) => {
+                    if pending {
+                        *slot = Some(transcriber.clone());
+                    }
+                    drop(slot);
+                    placeholder.complete(Ok(transcriber));
+                    if !quiet {
+                        eprintln!("Model '{model}' reloaded.");
+                    }
+                    state.emit(DaemonEvent::ModelLoaded { model });
+                }
+                Err(e) => {
+                    if pending {
+                        *slot = None;
+                    }
+                    drop(slot);
+                    eprintln!("voicsh: failed to reload model '{model}': {e}");
+                    state.emit(DaemonEvent::ModelLoadFailed {
+                        model,
+                        error: e.to_string(),
+                    });
+                    placeholder.complete(Err(e));
+                }
+            }
+        });
+        deferred
+    }
+
+    /// Unload the model once nothing was recorded or transcribed for
+    /// `stt.unload_after`. Returns true when it was unloaded.
+    ///
+    /// The next recording or transcription reloads it.
+    pub(crate) async fn unload_if_idle(&self) -> bool {
+        let config = self.state.config.lock().await;
+        let Some(unload_after) = config.stt.unload_after() else {
+            return false;
+        };
+        let idle_setting = config.stt.unload_after.clone().unwrap_or_default();
+        drop(config);
+        if self.state.idle_for() < unload_after || self.state.is_recording().await {
+            return false;
+        }
+
+        let mut slot = self.state.transcriber.write().await;
+        let Some(transcriber) = slot.as_ref() else {
+            return false;
+        };
+        // Still loading, or a streaming backend that holds no local model
+        if !transcriber.is_ready() || transcriber.streaming().is_some() {
+            return false;
+        }
+        // A recording or transcription that took the model after the checks
+        // above still holds a clone
+        if Arc::strong_count(transcriber) > 1 {
+            return false;
+        }
+        let model = transcriber.model_name().to_string();
+        *slot = None;
+        drop(slot);
+
+        if !self.quiet {
+            eprintln!("Model '{model}' unloaded after {idle_setting} idle.");
+        }
+        self.state.emit(DaemonEvent::Log {
+            message: format!("Model {model} unloaded after {idle_setting} idle"),
+        });
+        true
+    }
+
+    /// Configured transcription language ("auto" for detection).
+    pub(crate) async fn language(&self) -> String {
+        self.state.language().await
+    }
+
     /// Update a config field and emit a ConfigChanged event.
     ///
     /// Locks the config mutex, applies the mutation, drops the lock,
@@ -66,7 +241,8 @@ impl DaemonCommandHandler {
             };
         }
 
-        // Get config
+        // Get config; updates posted for an earlier recording are superseded by it
+        self.state.live.clear();
         let config = self.state.config.lock().await.clone();
 
         // Create audio source
@@ -92,7 +268,11 @@ impl DaemonCommandHandler {
         reset_detection_cache();
 
         // Start pipeline
-        let transcriber = self.state.transcriber.read().await.clone();
+        self.state.touch();
+        let transcriber = self
+            .state
+            .transcription_queue
+            .wrap(self.current_transcriber().await, Priority::Live);
         let mut pipeline = Pipeline::new(pipeline_config);
 
         // Wire error correction when enabled
@@ -122,10 +302,50 @@ impl DaemonCommandHandler {
     }
 
     /// Create audio source from config.
+    ///
+    /// The source falls back along `audio.device_priority` when the device is
+    /// unplugged and reports each switch as [`DaemonEvent::AudioDeviceChanged`].
     fn create_audio_source(&self, config: &Config) -> Result<Box<dyn AudioSource>, Box<Response>> {
         let device_name = config.audio.device.as_deref();
-        match CpalAudioSource::new(device_name) {
-            Ok(source) => Ok(Box::new(source)),
+        match HotplugSource::open(CpalDevices, config.audio.device_chain()) {
+            Ok(source) => {
+                let opened = source.device().unwrap_or(config.audio.device_key());
+                {
+                    let mut active = self
+                        .state
+                        .audio_device
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner());
+                    // A fallback device gets its own calibrated threshold
+                    if opened != config.audio.device_key() {
+                        self.state
+                            .live
+                            .vad_threshold
+                            .set(VadThreshold::for_device(&config.audio, opened));
+                    }
+                    *active = Some(opened.to_string());
+                }
+
+                let event_tx = self.state.pipeline_event_tx.clone();
+                let audio = self.state.audio.clone();
+                let audio_device = self.state.audio_device.clone();
+                let vad_threshold = self.state.live.vad_threshold.clone();
+                Ok(Box::new(source.with_notifier(move |change| {
+                    {
+                        let mut active = audio_device.lock().unwrap_or_else(|e| e.into_inner());
+                        let audio = audio.read().unwrap_or_else(|e| e.into_inner());
+                        vad_threshold.set(VadThreshold::for_device(&audio, &change.device));
+                        *active = Some(change.device.clone());
+                    }
+                    event_tx
+                        .try_send(DaemonEvent::AudioDeviceChanged {
+                            device: change.device.clone(),
+                            previous: change.previous.clone(),
+                            reason: change.reason.to_string(),
+                        })
+                        .ok();
+                })))
+            }
             Err(e) => {
                 let device_info = device_name.unwrap_or("default");
                 Err(Box::new(Response::Error {
@@ -148,13 +368,16 @@ impl DaemonCommandHandler {
         let suspect_phrases = resolve_suspect_phrases(&config.transcription.hallucination_filters);
         PipelineConfig {
             vad: VadConfig {
-                speech_threshold: config.audio.vad_threshold,
+                speech_threshold: config.audio.effective_vad_threshold(),
                 silence_duration_ms: config.audio.silence_duration_ms,
+                backend: config.audio.vad_backend,
+                model: crate::models::vad_catalog::resolve_vad_model(&config.audio.vad_model),
                 ..Default::default()
             },
             chunker: AdaptiveChunkerConfig::default(),
             verbosity: self.verbosity,
-            auto_level: true,
+            // A calibrated device keeps its measured threshold
+            auto_level: config.audio.calibrated_threshold().is_none(),
             quiet: self.quiet,
             sample_rate: WHISPER_SAMPLE_RATE,
             hallucination_filters,
@@ -162,10 +385,87 @@ impl DaemonCommandHandler {
             event_tx: Some(self.state.pipeline_event_tx.clone()),
             allowed_languages: self.state.allowed_languages.clone(),
             min_confidence: self.state.min_confidence.clone(),
+            language_tracking: config.stt.tracks_language(),
+            denoise: self.state.denoise.clone(),
+            archive: ArchiveSettings::from_config(&config.debug),
+            live: Some(self.state.live.clone()),
             ..Default::default()
         }
     }
 
+    /// Transcribe a finite audio source without touching the recording pipeline.
+    async fn transcribe_source(
+        &self,
+        audio_source: Box<dyn AudioSource>,
+        output_format: TranscriptFormat,
+    ) -> Response {
+        match self
+            .transcribe_to_sink(audio_source, Box::new(TranscriptSink::new(output_format)))
+            .await
+        {
+            Ok(text) => Response::Transcription {
+                text: text.unwrap_or_default(),
+            },
+            Err(message) => Response::Error { message },
+        }
+    }
+
+    /// Run a finite audio source through a separate pipeline and return what
+    /// `sink` produced.
+    ///
+    /// Uses the loaded model, so nothing is injected and a live recording is
+    /// not interrupted; its chunks queue behind any waiting dictation chunk.
+    pub(crate) async fn transcribe_to_sink(
+        &self,
+        audio_source: Box<dyn AudioSource>,
+        sink: Box<dyn TextSink>,
+    ) -> std::result::Result<Option<String>, String> {
+        let config = self.state.config.lock().await.clone();
+        let pipeline_config = PipelineConfig {
+            auto_level: false, // No auto-level for file input
+            quiet: true,       // No meter display for file input
+            event_tx: None,    // Keep file results out of the live event stream
+            archive: None,     // Only live recordings are archived
+            live: None,        // Config reloads apply to the live recording
+            ..self.build_pipeline_config(&config)
+        };
+        let post_processors = build_post_processors(&config);
+        self.state.touch();
+        let transcriber = self
+            .state
+            .transcription_queue
+            .wrap(self.current_transcriber().await, Priority::Background);
+
+        let result = tokio::task::spawn_blocking(move || {
+            Pipeline::new(pipeline_config)
+                .start_with_post_processors(audio_source, transcriber, sink, post_processors)
+                .map(|handle| handle.wait())
+        })
+        .await;
+        self.state.touch();
+
+        match result {
+            Ok(Ok(text)) => Ok(text),
+            Ok(Err(e)) => Err(format!("Failed to start pipeline: {}", e)),
+            Err(e) => Err(format!("Transcription task failed: {}", e)),
+        }
+    }
+
+    /// Transcribe an audio file readable by the daemon.
+    async fn handle_transcribe_file(
+        &self,
+        path: String,
+        format: Option<String>,
+        output_format: TranscriptFormat,
+    ) -> Response {
+        match decode::open_file(Path::new(&path), format.as_deref()) {
+            Ok(source) => self.transcribe_source(source, output_format).await,
+            Err(e) => Response::Error {
+                message: e.to_string(),
+            },
+        }
+    }
+
     /// Create sink with portal support based on config.
     #[cfg(feature = "portal")]
     fn create_sink(
@@ -201,6 +501,7 @@ impl DaemonCommandHandler {
         if let Some(handle) = pipeline_guard.take() {
             // Stop pipeline and get result
             let result = handle.stop();
+            self.state.touch();
 
             self.state
                 .emit(DaemonEvent::RecordingStateChanged { recording: false });
@@ -214,6 +515,7 @@ impl DaemonCommandHandler {
                     raw_text: None,
                     text_origin: TextOrigin::default(),
                     corrector_name: None,
+                    words: vec![],
                 });
                 Response::Transcription { text }
             } else {
@@ -236,6 +538,7 @@ impl DaemonCommandHandler {
         if let Some(handle) = pipeline_guard.take() {
             // Signal shutdown and wait for threads to finish before emitting the state change.
             handle.stop();
+            self.state.touch();
             self.state
                 .emit(DaemonEvent::RecordingStateChanged { recording: false });
             Response::Ok {
@@ -260,6 +563,7 @@ impl DaemonCommandHandler {
     /// Get daemon status.
     async fn get_status(&self) -> Response {
         let recording = self.state.is_recording().await;
+        let model_loaded = self.state.is_model_loaded().await;
         let model_name = Some(self.state.model_name().await);
         let language = Some(self.state.language().await);
         let config = self.state.config.lock().await;
@@ -278,7 +582,7 @@ impl DaemonCommandHandler {
 
         Response::Status {
             recording,
-            model_loaded: true, // Model is always loaded in daemon
+            model_loaded,
             model_name,
             language,
             daemon_version: crate::version_string(),
@@ -396,12 +700,19 @@ impl DaemonCommandHandler {
         let mut new_config = self.state.config.lock().await.clone();
         new_config.stt.model = model.clone();
 
-        match crate::daemon::create_transcriber(&new_config, true, self.verbosity, false).await {
+        match crate::daemon::create_transcriber(
+            &new_config,
+            true,
+            self.verbosity,
+            self.state.no_download,
+        )
+        .await
+        {
             Ok(new_transcriber) => {
                 // Swap transcriber — safe during recording because the pipeline
                 // holds its own Arc clone (taken at start_recording). The old model
                 // stays alive via refcount until the pipeline finishes.
-                *self.state.transcriber.write().await = new_transcriber;
+                *self.state.transcriber.write().await = Some(new_transcriber);
 
                 // Update config
                 self.state.config.lock().await.stt.model = model.clone();
@@ -821,6 +1132,251 @@ impl DaemonCommandHandler {
         None
     }
 
+    /// Re-read the config file and apply changes.
+    async fn reload_config(&self) -> Response {
+        let Some(path) = self.state.config_path.clone() else {
+            return Response::Error {
+                message: "Daemon was started without a config file path".to_string(),
+            };
+        };
+
+        match Config::load_or_default(&path) {
+            Ok(config) => self.apply_config(config.with_env_overrides()).await,
+            Err(e) => Response::Error {
+                message: format!("Config reload failed, keeping current settings: {}", e),
+            },
+        }
+    }
+
+    /// Apply a freshly loaded config to the running daemon.
+    ///
+    /// Language filters and noise suppression update the live pipeline
+    /// immediately. Other audio, injection, voice command and correction
+    /// settings are read at the start of each recording, so they apply from
+    /// the next one. The model is reloaded only when `stt.model` changed.
+    async fn apply_config(&self, new_config: Config) -> Response {
+        let old_config = self.state.config.lock().await.clone();
+        let changed = match old_config.diff_keys(&new_config) {
+            Ok(keys) => keys,
+            Err(e) => {
+                return Response::Error {
+                    message: format!("Config reload failed: {}", e),
+                };
+            }
+        };
+
+        let model_changed = changed.iter().any(|k| k == "stt.model");
+        let (restart_required, applied): (Vec<String>, Vec<String>) =
+            changed.into_iter().partition(|k| {
+                RESTART_REQUIRED_PREFIXES.iter().any(|p| k.starts_with(p))
+                    || (!model_changed && RESTART_REQUIRED_KEYS.contains(&k.as_str()))
+            });
+
+        // Keep the running model until the new one has loaded successfully, and
+        // the running values of keys that need a restart, so the next reload
+        // still reports them as pending
+        let mut staged = match new_config.with_values_from(&old_config, &restart_required) {
+            Ok(staged) => staged,
+            Err(e) => {
+                return Response::Error {
+                    message: format!("Config reload failed: {}", e),
+                };
+            }
+        };
+        staged.stt.model = old_config.stt.model.clone();
+        *self.state.config.lock().await = staged;
+
+        // Same write-side policy as handle_set_language: propagate lock poisoning loudly.
+        #[allow(clippy::expect_used)]
+        {
+            *self
+                .state
+                .allowed_languages
+                .write()
+                .expect("allowed_languages RwLock poisoned") =
+                new_config.stt.allowed_languages.clone();
+            *self
+                .state
+                .min_confidence
+                .write()
+                .expect("min_confidence RwLock poisoned") = new_config.stt.min_confidence;
+            *self.state.denoise.write().expect("denoise RwLock poisoned") =
+                new_config.audio.denoise.clone();
+            *self.state.audio.write().expect("audio RwLock poisoned") = new_config.audio.clone();
+        }
+
+        for key in applied.iter().filter(|k| *k != "stt.model") {
+            self.state.emit(DaemonEvent::ConfigChanged {
+                key: key.clone(),
+                value: new_config.get_value_by_path(key).unwrap_or_default(),
+            });
+        }
+
+        if self.state.is_recording().await {
+            self.post_live_updates(&new_config, &applied).await;
+        }
+
+        if new_config.audio.vad_backend == crate::config::VadBackend::Neural {
+            // Fetch the model now so the next recording does not fall back
+            crate::audio::neural_vad::prepare_model(
+                &new_config.audio.vad_model,
+                true,
+                self.state.no_download,
+            )
+            .await;
+        }
+
+        let mut model_reloaded = false;
+        if model_changed {
+            match self.handle_set_model(new_config.stt.model.clone()).await {
+                Response::Error { message } => {
+                    return Response::Error {
+                        message: format!(
+                            "Config reloaded, but switching to model '{}' failed: {}",
+                            new_config.stt.model, message
+                        ),
+                    };
+                }
+                _ => model_reloaded = true,
+            }
+        }
+
+        Response::ConfigReloaded {
+            changed: applied,
+            restart_required,
+            model_reloaded,
+        }
+    }
+
+    /// Hand the settings among `changed` to the running recording.
+    ///
+    /// Denoise, allowed languages and minimum confidence are shared with the
+    /// pipeline directly; everything else is rebuilt here and picked up by
+    /// its station before the next item.
+    async fn post_live_updates(&self, config: &Config, changed: &[String]) {
+        let touches = |prefix: &str| changed.iter().any(|k| k.starts_with(prefix));
+        let live = &self.state.live;
+
+        if touches("audio.vad_threshold") || touches("audio.device_thresholds.") {
+            let active = self
+                .state
+                .audio_device
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            let device = active.as_deref().unwrap_or(config.audio.device_key());
+            live.vad_threshold
+                .set(VadThreshold::for_device(&config.audio, device));
+        }
+        if touches("transcription.hallucination_filters.") {
+            let filters = &config.transcription.hallucination_filters;
+            live.filters.set(PhraseFilters {
+                hallucination_filters: resolve_hallucination_filters(filters),
+                suspect_phrases: resolve_suspect_phrases(filters),
+            });
+        }
+        if touches("transcription.error_correction.") {
+            let station = if config.transcription.error_correction.enabled {
+                self.build_correction_station(&config.transcription.error_correction)
+                    .await
+            } else {
+                None
+            };
+            live.correction.set(station);
+        }
+        if touches("voice_commands.") || changed.iter().any(|k| k == "stt.language") {
+            live.post_processors.set(build_post_processors(config));
+        }
+        if touches("injection.") {
+            #[cfg(feature = "portal")]
+            let sink = self.create_sink(config, self.state.portal.clone());
+            #[cfg(not(feature = "portal"))]
+            let sink = self.create_sink(config);
+            live.sink.set(sink);
+        }
+    }
+
+    /// Handle get config command: read a value from the running config.
+    ///
+    /// API keys are redacted, also inside a returned section.
+    async fn handle_get_config(&self, key: String) -> Response {
+        let config = self.state.config.lock().await.redacted();
+        let value = match config.get_toml_value(&key) {
+            Ok(v) => v,
+            Err(e) => {
+                return Response::Error {
+                    message: e.to_string(),
+                };
+            }
+        };
+        match serde_json::to_value(&value) {
+            Ok(value) => Response::ConfigValue { key, value },
+            Err(e) => Response::Error {
+                message: format!("Failed to encode value for '{}': {}", key, e),
+            },
+        }
+    }
+
+    /// Handle set config command: validate, apply live, optionally persist.
+    ///
+    /// The value is validated against the config schema before anything is
+    /// touched. With `persist`, the same change is written to the config file
+    /// the daemon was started with; only that key is rewritten, so comments
+    /// and other settings in the file stay as they are.
+    async fn handle_set_config(&self, key: String, value: String, persist: bool) -> Response {
+        let current = self.state.config.lock().await.clone();
+        let new_config = match current.with_value(&key, &value) {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                return Response::Error {
+                    message: e.to_string(),
+                };
+            }
+        };
+
+        let persist_path = if persist {
+            match self.state.config_path.clone() {
+                Some(path) => Some(path),
+                None => {
+                    return Response::Error {
+                        message: "Cannot persist: daemon was started without a config file path"
+                            .to_string(),
+                    };
+                }
+            }
+        } else {
+            None
+        };
+
+        let restart_required = match self.apply_config(new_config).await {
+            Response::ConfigReloaded {
+                restart_required, ..
+            } => !restart_required.is_empty(),
+            other => return other,
+        };
+
+        if let Some(path) = persist_path
+            && let Err(e) = Config::set_value_by_path(&path, &key, &value)
+        {
+            return Response::Error {
+                message: format!(
+                    "{} applied, but saving to {} failed: {}",
+                    key,
+                    path.display(),
+                    e
+                ),
+            };
+        }
+
+        let mut message = format!("{} updated", key);
+        if persist {
+            message.push_str(" and saved");
+        }
+        if restart_required {
+            message.push_str(" (takes effect after daemon restart)");
+        }
+        Response::Ok { message }
+    }
+
     /// Handle list models command.
     async fn handle_list_models(&self) -> Response {
         use crate::ipc::protocol::ModelInfoResponse;
@@ -877,6 +1433,41 @@ impl CommandHandler for DaemonCommandHandler {
             }
             Command::SetCorrectionModel { model } => self.handle_set_correction_model(model).await,
             Command::ListCorrectionModels => self.handle_list_correction_models().await,
+            Command::ReloadConfig => self.reload_config().await,
+            Command::GetConfig { key } => self.handle_get_config(key).await,
+            Command::SetConfig {
+                key,
+                value,
+                persist,
+            } => self.handle_set_config(key, value, persist).await,
+            Command::TranscribeFile {
+                path,
+                format,
+                output_format,
+            } => {
+                self.handle_transcribe_file(path, format, output_format)
+                    .await
+            }
+            Command::TranscribeStream { .. } => {
+                // Audio bytes are read by the server and passed to handle_audio
+                Response::Error {
+                    message: "TranscribeStream requires audio data on the connection".to_string(),
+                }
+            }
+        }
+    }
+
+    async fn handle_audio(
+        &self,
+        format: Option<String>,
+        output_format: TranscriptFormat,
+        audio: Vec<u8>,
+    ) -> Response {
+        match decode::open_bytes(audio, format.as_deref()) {
+            Ok(source) => self.transcribe_source(source, output_format).await,
+            Err(e) => Response::Error {
+                message: e.to_string(),
+            },
         }
     }
 
@@ -963,6 +1554,79 @@ mod tests {
         }
     }
 
+    /// Pretend the last recording ended an hour ago.
+    fn make_idle(handler: &DaemonCommandHandler) {
+        *handler.state.last_activity.lock().unwrap() = std::time::Instant::now()
+            .checked_sub(std::time::Duration::from_secs(3600))
+            .expect("monotonic clock should be past one hour");
+    }
+
+    #[tokio::test]
+    async fn test_unload_if_idle_unloads_after_setting() {
+        let handler = create_test_handler();
+        make_idle(&handler);
+        assert!(
+            !handler.unload_if_idle().await,
+            "no unload_after configured"
+        );
+
+        handler.state.config.lock().await.stt.unload_after = Some("2h".to_string());
+        assert!(!handler.unload_if_idle().await, "not idle long enough");
+
+        handler.state.config.lock().await.stt.unload_after = Some("15m".to_string());
+        let mut events = handler.subscribe();
+        assert!(handler.unload_if_idle().await);
+        assert!(!handler.state.is_model_loaded().await);
+        assert!(!handler.unload_if_idle().await, "already unloaded");
+        match events.try_recv().expect("should emit a log event") {
+            DaemonEvent::Log { message } => {
+                assert!(message.contains("unloaded after 15m idle"), "{message}")
+            }
+            other => panic!("Expected Log, got {other:?}"),
+        }
+
+        // Unloaded: the configured model is reported
+        assert_eq!(handler.model_name().await, "base");
+        match handler.handle(Command::Status).await {
+            Response::Status { model_loaded, .. } => assert!(!model_loaded),
+            other => panic!("Expected Status, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unload_if_idle_skips_recent_activity_and_loading_model() {
+        let handler = create_test_handler();
+        handler.state.config.lock().await.stt.unload_after = Some("15m".to_string());
+        make_idle(&handler);
+        handler.state.touch();
+        assert!(!handler.unload_if_idle().await, "activity just now");
+
+        make_idle(&handler);
+        let in_use = handler.state.transcriber.read().await.clone();
+        assert!(
+            !handler.unload_if_idle().await,
+            "model taken by a recording"
+        );
+        drop(in_use);
+
+        make_idle(&handler);
+        let loading = Arc::new(DeferredTranscriber::new("base"));
+        *handler.state.transcriber.write().await = Some(loading.clone());
+        assert!(!handler.unload_if_idle().await, "model still loading");
+        match handler.handle(Command::Status).await {
+            Response::Status { model_loaded, .. } => {
+                assert!(
+                    !model_loaded,
+                    "a model still loading is not reported as loaded"
+                )
+            }
+            other => panic!("Expected Status, got {other:?}"),
+        }
+
+        loading.complete(Ok(Arc::new(MockTranscriber::new("base"))));
+        assert!(handler.state.is_model_loaded().await);
+    }
+
     #[tokio::test]
     async fn test_handler_stop_when_not_recording() {
         let handler = create_test_handler();
@@ -1204,6 +1868,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::default(),
             corrector_name: None,
+            words: vec![],
         });
 
         // Should receive the event
@@ -1312,6 +1977,31 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_build_pipeline_config_uses_device_threshold() {
+        let handler = create_test_handler();
+        let mut config = handler.state.config.lock().await.clone();
+        config
+            .audio
+            .device_thresholds
+            .insert("usb-mic".to_string(), 0.045);
+
+        let pipeline_config = handler.build_pipeline_config(&config);
+        assert_eq!(
+            pipeline_config.vad.speech_threshold, config.audio.vad_threshold,
+            "Uncalibrated device should use audio.vad_threshold"
+        );
+        assert!(pipeline_config.auto_level);
+
+        config.audio.device = Some("usb-mic".to_string());
+        let pipeline_config = handler.build_pipeline_config(&config);
+        assert_eq!(pipeline_config.vad.speech_threshold, 0.045);
+        assert!(
+            !pipeline_config.auto_level,
+            "Calibrated device should keep its threshold"
+        );
+    }
+
     #[tokio::test]
     async fn test_handler_new_with_different_verbosity() {
         let config = Config::default();
@@ -1596,6 +2286,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::default(),
             corrector_name: None,
+            words: vec![],
         });
 
         // Should receive
@@ -1887,4 +2578,564 @@ mod tests {
             "ListCorrectionModels should return CorrectionModels"
         );
     }
+
+    fn create_test_handler_with_config_file(
+        contents: &str,
+    ) -> (DaemonCommandHandler, tempfile::TempDir) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, contents).unwrap();
+        let transcriber: Arc<dyn crate::stt::transcriber::Transcriber> =
+            Arc::new(MockTranscriber::new("mock-test-model"));
+        let state = DaemonState::new(
+            Config::default(),
+            transcriber,
+            #[cfg(feature = "portal")]
+            None,
+        )
+        .with_config_path(path);
+        (DaemonCommandHandler::new(state, true, 0), dir)
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_without_path_returns_error() {
+        let handler = create_test_handler();
+        let response = handler.handle(Command::ReloadConfig).await;
+        match response {
+            Response::Error { message } => {
+                assert!(message.contains("config file path"), "got: {message}");
+            }
+            other => panic!("Expected Error response, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_invalid_toml_keeps_current_config() {
+        let (handler, _dir) = create_test_handler_with_config_file("[audio\nbroken");
+        let response = handler.handle(Command::ReloadConfig).await;
+        match response {
+            Response::Error { message } => {
+                assert!(
+                    message.contains("keeping current settings"),
+                    "got: {message}"
+                );
+            }
+            other => panic!("Expected Error response, got {:?}", other),
+        }
+        assert_eq!(*handler.state.config.lock().await, Config::default());
+    }
+
+    #[tokio::test]
+    async fn test_reload_config_from_file_applies_changes() {
+        let (handler, _dir) = create_test_handler_with_config_file(
+            "[audio]\nvad_threshold = 0.05\n[voice_commands]\nenabled = false\n",
+        );
+        // The response may also mention a model switch if VOICSH_MODEL is set
+        // in the environment, so only the applied config is asserted here.
+        handler.handle(Command::ReloadConfig).await;
+        let config = handler.state.config.lock().await;
+        assert_eq!(config.audio.vad_threshold, 0.05);
+        assert!(!config.voice_commands.enabled);
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_unchanged_reports_nothing() {
+        let handler = create_test_handler();
+        let response = handler.apply_config(Config::default()).await;
+        assert_eq!(
+            response,
+            Response::ConfigReloaded {
+                changed: vec![],
+                restart_required: vec![],
+                model_reloaded: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_switches_denoise_live() {
+        let handler = create_test_handler();
+        let pipeline_denoise = handler.build_pipeline_config(&Config::default()).denoise;
+
+        let mut new_config = Config::default();
+        new_config.audio.denoise.enabled = true;
+        new_config.audio.denoise.strength = 1.5;
+
+        let response = handler.apply_config(new_config).await;
+        assert_eq!(
+            response,
+            Response::ConfigReloaded {
+                changed: vec![
+                    "audio.denoise.enabled".to_string(),
+                    "audio.denoise.strength".to_string(),
+                ],
+                restart_required: vec![],
+                model_reloaded: false,
+            }
+        );
+
+        // The running pipeline shares the same settings
+        let denoise = pipeline_denoise.read().unwrap();
+        assert!(denoise.enabled);
+        assert_eq!(denoise.strength, 1.5);
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_updates_live_filters_and_emits_events() {
+        let handler = create_test_handler();
+        let mut rx = handler.subscribe();
+
+        let mut new_config = Config::default();
+        new_config.stt.min_confidence = 0.4;
+        new_config.stt.allowed_languages = vec!["de".to_string()];
+        new_config.injection.paste_key = "ctrl+shift+v".to_string();
+
+        let response = handler.apply_config(new_config).await;
+        assert_eq!(
+            response,
+            Response::ConfigReloaded {
+                changed: vec![
+                    "injection.paste_key".to_string(),
+                    "stt.allowed_languages".to_string(),
+                    "stt.min_confidence".to_string(),
+                ],
+                restart_required: vec![],
+                model_reloaded: false,
+            }
+        );
+
+        assert_eq!(*handler.state.min_confidence.read().unwrap(), 0.4);
+        assert!(!handler.state.denoise.read().unwrap().enabled);
+        assert_eq!(
+            *handler.state.allowed_languages.read().unwrap(),
+            vec!["de".to_string()]
+        );
+        assert_eq!(
+            handler.state.config.lock().await.injection.paste_key,
+            "ctrl+shift+v"
+        );
+
+        let mut keys = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let DaemonEvent::ConfigChanged { key, .. } = event {
+                keys.push(key);
+            }
+        }
+        assert_eq!(
+            keys,
+            vec![
+                "injection.paste_key",
+                "stt.allowed_languages",
+                "stt.min_confidence"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_fan_out_requires_restart() {
+        let handler = create_test_handler();
+        let mut new_config = Config::default();
+        new_config.stt.fan_out = true;
+
+        let response = handler.apply_config(new_config).await;
+        assert_eq!(
+            response,
+            Response::ConfigReloaded {
+                changed: vec![],
+                restart_required: vec!["stt.fan_out".to_string()],
+                model_reloaded: false,
+            }
+        );
+        assert!(!handler.state.config.lock().await.stt.fan_out);
+    }
+
+    #[tokio::test]
+    async fn test_post_live_updates_rebuilds_changed_parts() {
+        let handler = create_test_handler();
+        let mut config = Config::default();
+        config.audio.vad_threshold = 0.05;
+        config.voice_commands.enabled = false;
+        let changed = vec![
+            "audio.vad_threshold".to_string(),
+            "injection.method".to_string(),
+            "voice_commands.enabled".to_string(),
+        ];
+        handler.post_live_updates(&config, &changed).await;
+
+        let live = &handler.state.live;
+        assert_eq!(
+            live.vad_threshold.take(),
+            Some(VadThreshold {
+                threshold: 0.05,
+                auto_level: true,
+            })
+        );
+        assert!(live.post_processors.take().unwrap().is_empty());
+        assert!(live.sink.take().is_some());
+        assert!(live.filters.take().is_none(), "filters did not change");
+        assert!(
+            live.correction.take().is_none(),
+            "correction did not change"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_live_updates_uses_threshold_of_active_device() {
+        let handler = create_test_handler();
+        *handler.state.audio_device.lock().unwrap() = Some("usb".to_string());
+        let mut config = Config::default();
+        config
+            .audio
+            .device_thresholds
+            .insert("usb".to_string(), 0.3);
+        let changed = vec!["audio.device_thresholds.usb".to_string()];
+        handler.post_live_updates(&config, &changed).await;
+
+        assert_eq!(
+            handler.state.live.vad_threshold.take(),
+            Some(VadThreshold {
+                threshold: 0.3,
+                auto_level: false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_config_restart_required_key_stays_pending() {
+        let handler = create_test_handler();
+        let mut new_config = Config::default();
+        new_config.stt.fan_out = true;
+        handler.apply_config(new_config.clone()).await;
+
+        // A later reload of the same file still reports the pending restart
+        new_config.audio.vad_threshold = 0.05;
+        let response = handler.apply_config(new_config).await;
+        assert_eq!(
+            response,
+            Response::ConfigReloaded {
+                changed: vec!["audio.vad_threshold".to_string()],
+                restart_required: vec!["stt.fan_out".to_string()],
+                model_reloaded: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "whisper"))]
+    async fn test_apply_config_model_change_keeps_old_model_on_failure() {
+        let handler = create_test_handler();
+        let mut new_config = Config::default();
+        new_config.stt.model = "small".to_string();
+        new_config.audio.vad_threshold = 0.05;
+
+        let response = handler.apply_config(new_config).await;
+        match response {
+            Response::Error { message } => {
+                assert!(
+                    message.contains("switching to model 'small' failed"),
+                    "got: {message}"
+                );
+            }
+            other => panic!("Expected Error response, got {:?}", other),
+        }
+
+        let config = handler.state.config.lock().await;
+        assert_eq!(config.stt.model, "base", "Model should stay on the old one");
+        assert_eq!(
+            config.audio.vad_threshold, 0.05,
+            "Other settings should still be applied"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_config_returns_json_value() {
+        let handler = create_test_handler();
+        let response = handler
+            .handle(Command::GetConfig {
+                key: "audio.sample_rate".to_string(),
+            })
+            .await;
+        assert_eq!(
+            response,
+            Response::ConfigValue {
+                key: "audio.sample_rate".to_string(),
+                value: serde_json::json!(16000),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_config_redacts_api_keys() {
+        let handler = create_test_handler();
+        handler.state.config.lock().await.stt.http.api_key = Some("sk-secret".to_string());
+        let response = handler
+            .handle(Command::GetConfig {
+                key: "stt.http".to_string(),
+            })
+            .await;
+        match response {
+            Response::ConfigValue { value, .. } => {
+                assert_eq!(value["api_key"], serde_json::json!(crate::config::REDACTED));
+            }
+            other => panic!("Expected ConfigValue response, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_config_unknown_key_returns_error() {
+        let handler = create_test_handler();
+        let response = handler
+            .handle(Command::GetConfig {
+                key: "audio.nope".to_string(),
+            })
+            .await;
+        assert!(
+            matches!(response, Response::Error { .. }),
+            "got {response:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_config_applies_live_and_emits_event() {
+        let handler = create_test_handler();
+        let mut events = handler.subscribe();
+
+        let response = handler
+            .handle(Command::SetConfig {
+                key: "stt.allowed_languages".to_string(),
+                value: "en,de".to_string(),
+                persist: false,
+            })
+            .await;
+        assert_eq!(
+            response,
+            Response::Ok {
+                message: "stt.allowed_languages updated".to_string(),
+            }
+        );
+
+        let allowed = handler.state.allowed_languages.read().unwrap().clone();
+        assert_eq!(allowed, vec!["en", "de"]);
+        match events.try_recv() {
+            Ok(DaemonEvent::ConfigChanged { key, .. }) => {
+                assert_eq!(key, "stt.allowed_languages");
+            }
+            other => panic!("Expected ConfigChanged event, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_config_invalid_value_leaves_config_untouched() {
+        let handler = create_test_handler();
+        let response = handler
+            .handle(Command::SetConfig {
+                key: "stt.min_confidence".to_string(),
+                value: "1.5".to_string(),
+                persist: false,
+            })
+            .await;
+        match response {
+            Response::Error { message } => {
+                assert!(message.contains("out of range"), "got: {message}");
+            }
+            other => panic!("Expected Error response, got {:?}", other),
+        }
+        assert_eq!(*handler.state.config.lock().await, Config::default());
+    }
+
+    #[tokio::test]
+    async fn test_set_config_persist_writes_config_file() {
+        let (handler, dir) = create_test_handler_with_config_file("[stt]\nmodel = \"base\"\n");
+        let response = handler
+            .handle(Command::SetConfig {
+                key: "audio.vad_threshold".to_string(),
+                value: "0.5".to_string(),
+                persist: true,
+            })
+            .await;
+        assert_eq!(
+            response,
+            Response::Ok {
+                message: "audio.vad_threshold updated and saved".to_string(),
+            }
+        );
+        let saved = Config::load(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(saved.audio.vad_threshold, 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_set_config_persist_without_path_returns_error() {
+        let handler = create_test_handler();
+        let response = handler
+            .handle(Command::SetConfig {
+                key: "audio.vad_threshold".to_string(),
+                value: "0.5".to_string(),
+                persist: true,
+            })
+            .await;
+        assert!(
+            matches!(response, Response::Error { .. }),
+            "got {response:?}"
+        );
+        assert_eq!(
+            handler.state.config.lock().await.audio.vad_threshold,
+            Config::default().audio.vad_threshold
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_config_restart_required_key_is_reported() {
+        let handler = create_test_handler();
+        let response = handler
+            .handle(Command::SetConfig {
+                key: "stt.fan_out".to_string(),
+                value: "true".to_string(),
+                persist: false,
+            })
+            .await;
+        assert_eq!(
+            response,
+            Response::Ok {
+                message: "stt.fan_out updated (takes effect after daemon restart)".to_string(),
+            }
+        );
+    }
+
+    fn loud_wav_bytes() -> Vec<u8> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        // 2 seconds of loud audio so the VAD detects speech
+        for _ in 0..32000 {
+            writer.write_sample(10000i16).unwrap();
+        }
+        writer.finalize().unwrap();
+        cursor.into_inner()
+    }
+
+    fn create_handler_with_response(text: &str) -> DaemonCommandHandler {
+        let transcriber: Arc<dyn crate::stt::transcriber::Transcriber> =
+            Arc::new(MockTranscriber::new("mock-test-model").with_response(text));
+        let state = DaemonState::new(
+            Config::default(),
+            transcriber,
+            #[cfg(feature = "portal")]
+            None,
+        );
+        DaemonCommandHandler::new(state, true, 0)
+    }
+
+    #[tokio::test]
+    async fn test_handler_transcribe_file() {
+        let handler = create_handler_with_response("hello from file");
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("clip.wav");
+        std::fs::write(&path, loud_wav_bytes()).unwrap();
+
+        let response = handler
+            .handle(Command::TranscribeFile {
+                path: path.to_string_lossy().into_owned(),
+                format: None,
+                output_format: TranscriptFormat::Txt,
+            })
+            .await;
+        match response {
+            Response::Transcription { text } => {
+                assert!(
+                    text.to_lowercase().contains("hello from file"),
+                    "got: {text}"
+                );
+            }
+            other => panic!("Expected Transcription response, got: {:?}", other),
+        }
+        // The file pipeline must not start a recording session
+        assert!(handler.state.pipeline.lock().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handler_transcribe_file_missing() {
+        let handler = create_test_handler();
+        let response = handler
+            .handle(Command::TranscribeFile {
+                path: "/nonexistent/voicsh.wav".to_string(),
+                format: None,
+                output_format: TranscriptFormat::Txt,
+            })
+            .await;
+        match response {
+            Response::Error { message } => {
+                assert!(
+                    message.contains("/nonexistent/voicsh.wav"),
+                    "got: {message}"
+                );
+            }
+            other => panic!("Expected Error response, got: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handler_audio_bytes_transcribed() {
+        let handler = create_handler_with_response("hello from stream");
+        let response = handler
+            .handle_audio(
+                Some("wav".to_string()),
+                TranscriptFormat::Txt,
+                loud_wav_bytes(),
+            )
+            .await;
+        match response {
+            Response::Transcription { text } => {
+                assert!(
+                    text.to_lowercase().contains("hello from stream"),
+                    "got: {text}"
+                );
+            }
+            other => panic!("Expected Transcription response, got: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handler_audio_bytes_as_srt() {
+        let handler = create_handler_with_response("stand-up notes");
+        let response = handler
+            .handle_audio(None, TranscriptFormat::Srt, loud_wav_bytes())
+            .await;
+        match response {
+            Response::Transcription { text } => {
+                assert!(text.starts_with("1\n00:00:0"), "got: {text}");
+                assert!(text.contains(" --> "), "got: {text}");
+                assert!(
+                    text.to_lowercase().contains("stand-up notes"),
+                    "got: {text}"
+                );
+            }
+            other => panic!("Expected Transcription response, got: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handler_audio_unsupported_format() {
+        let handler = create_test_handler();
+        let response = handler
+            .handle_audio(Some("mp4".to_string()), TranscriptFormat::Txt, vec![0; 16])
+            .await;
+        assert!(matches!(response, Response::Error { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_handler_transcribe_stream_without_audio_is_error() {
+        let handler = create_test_handler();
+        let response = handler
+            .handle(Command::TranscribeStream {
+                format: None,
+                output_format: TranscriptFormat::Txt,
+            })
+            .await;
+        assert!(matches!(response, Response::Error { .. }));
+    }
 }
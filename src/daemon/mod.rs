@@ -1,16 +1,23 @@
 //! Daemon mode for voicsh - manages recording state and IPC server.
 
+pub mod api;
 pub mod handler;
+pub mod watcher;
+pub mod wyoming;
 
 use crate::audio::capture::suppress_audio_warnings;
-use crate::config::Config;
+use crate::config::{AudioConfig, Config, DenoiseConfig, SttBackend, VadBackend};
 use crate::error::{Result, VoicshError};
 use crate::ipc::protocol::DaemonEvent;
 use crate::ipc::server::IpcServer;
+use crate::pipeline::live::LiveSettings;
 use crate::pipeline::orchestrator::PipelineHandle;
+use crate::stt::queue::TranscriptionQueue;
 use crate::stt::transcriber::Transcriber;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 #[cfg(feature = "portal")]
@@ -20,8 +27,13 @@ use crate::inject::portal::PortalSession;
 pub struct DaemonState {
     /// Configuration
     pub config: Arc<Mutex<Config>>,
-    /// Loaded transcriber (model stays in memory, can be swapped with write lock)
-    pub transcriber: tokio::sync::RwLock<Arc<dyn Transcriber>>,
+    /// Loaded transcriber (can be swapped with write lock). `None` while
+    /// unloaded after `stt.unload_after` of idle time.
+    pub transcriber: tokio::sync::RwLock<Option<Arc<dyn Transcriber>>>,
+    /// Last time a recording or transcription started or ended
+    pub last_activity: std::sync::Mutex<Instant>,
+    /// Orders dictation chunks ahead of file and API transcriptions on the shared model
+    pub transcription_queue: Arc<TranscriptionQueue>,
     /// Current pipeline handle (Some = recording, None = idle)
     pub pipeline: Arc<Mutex<Option<PipelineHandle>>>,
     /// Portal session for input injection (if available)
@@ -41,6 +53,21 @@ pub struct DaemonState {
     pub allowed_languages: Arc<std::sync::RwLock<Vec<String>>>,
     /// Minimum confidence threshold (live-updatable during recording)
     pub min_confidence: Arc<std::sync::RwLock<f32>>,
+    /// Noise suppression settings (live-updatable during recording)
+    pub denoise: Arc<std::sync::RwLock<DenoiseConfig>>,
+    /// Audio settings a device switch looks its VAD threshold up in
+    /// (live-updatable during recording)
+    pub audio: Arc<std::sync::RwLock<AudioConfig>>,
+    /// Input device the running recording captures from. Held while posting
+    /// a VAD threshold, so the threshold always matches the latest device.
+    pub audio_device: Arc<std::sync::Mutex<Option<String>>>,
+    /// Replacements for the running recording's VAD threshold, filters,
+    /// correction, voice commands and sink
+    pub live: LiveSettings,
+    /// Config file the daemon was started from (used by `ReloadConfig`)
+    pub config_path: Option<PathBuf>,
+    /// Never download missing models, also when reloading them (`--no-download`)
+    pub no_download: bool,
 }
 
 /// Detect GPU device name and memory from nvidia-smi.
@@ -99,10 +126,14 @@ impl DaemonState {
         let allowed_languages =
             Arc::new(std::sync::RwLock::new(config.stt.allowed_languages.clone()));
         let min_confidence = Arc::new(std::sync::RwLock::new(config.stt.min_confidence));
+        let denoise = Arc::new(std::sync::RwLock::new(config.audio.denoise.clone()));
+        let audio = Arc::new(std::sync::RwLock::new(config.audio.clone()));
 
         Self {
             config: Arc::new(Mutex::new(config)),
-            transcriber: tokio::sync::RwLock::new(transcriber),
+            transcriber: tokio::sync::RwLock::new(Some(transcriber)),
+            last_activity: std::sync::Mutex::new(Instant::now()),
+            transcription_queue: Arc::new(TranscriptionQueue::new()),
             pipeline: Arc::new(Mutex::new(None)),
             #[cfg(feature = "portal")]
             portal,
@@ -113,9 +144,27 @@ impl DaemonState {
             device,
             allowed_languages,
             min_confidence,
+            denoise,
+            audio,
+            audio_device: Arc::new(std::sync::Mutex::new(None)),
+            live: LiveSettings::default(),
+            config_path: None,
+            no_download: false,
         }
     }
 
+    /// Set the config file path used when reloading configuration.
+    pub fn with_config_path(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    /// Keep missing models from being downloaded when they are reloaded.
+    pub fn with_no_download(mut self, no_download: bool) -> Self {
+        self.no_download = no_download;
+        self
+    }
+
     /// Returns true if currently recording.
     pub async fn is_recording(&self) -> bool {
         self.pipeline.lock().await.is_some()
@@ -126,6 +175,29 @@ impl DaemonState {
         self.config.lock().await.stt.model.clone()
     }
 
+    /// Returns true when a model is loaded and ready: false after it was
+    /// unloaded while idle, and while it is being reloaded.
+    pub async fn is_model_loaded(&self) -> bool {
+        self.transcriber
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|t| t.is_ready())
+    }
+
+    /// Record activity, restarting the `stt.unload_after` idle timer.
+    pub fn touch(&self) {
+        *self.last_activity.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    /// Time since the last recording or transcription.
+    pub fn idle_for(&self) -> Duration {
+        self.last_activity
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .elapsed()
+    }
+
     /// Returns language setting from config.
     pub async fn language(&self) -> String {
         self.config.lock().await.stt.language.clone()
@@ -155,25 +227,47 @@ impl DaemonState {
 /// * `quiet` - Suppress status messages
 /// * `verbosity` - Verbosity level
 /// * `no_download` - Prevent automatic model download
+/// * `config_path` - Config file to re-read on `ReloadConfig`
+/// * `watch_config` - Reload automatically when the config file changes
+/// * `wyoming` - Also serve the model as a Wyoming ASR service on this address
 ///
 /// # Returns
 /// Ok(()) on graceful shutdown, error otherwise
+#[allow(clippy::too_many_arguments)]
 pub async fn run_daemon(
     config: Config,
     socket_path: Option<PathBuf>,
     quiet: bool,
     verbosity: u8,
     no_download: bool,
+    config_path: Option<PathBuf>,
+    watch_config: bool,
+    wyoming: Option<SocketAddr>,
 ) -> Result<()> {
     // Suppress noisy JACK/ALSA warnings
     suppress_audio_warnings();
 
+    // Unknown keys are silently ignored by serde; surface them (and bad values) once
+    if let Some(path) = &config_path {
+        match crate::config_schema::check_file(path) {
+            Ok(diagnostics) => {
+                for d in diagnostics {
+                    eprintln!("voicsh: {}: {}", path.display(), d);
+                }
+            }
+            Err(e) => eprintln!("voicsh: could not check {}: {}", path.display(), e),
+        }
+    }
+
     // Load model once (this is slow but happens only at daemon startup)
     if !quiet {
         eprintln!("Loading model '{}'...", config.stt.model);
     }
 
     let transcriber = create_transcriber(&config, quiet, verbosity, no_download).await?;
+    if config.audio.vad_backend == VadBackend::Neural {
+        crate::audio::neural_vad::prepare_model(&config.audio.vad_model, quiet, no_download).await;
+    }
 
     if !quiet {
         eprintln!("Model loaded successfully.");
@@ -197,13 +291,19 @@ pub async fn run_daemon(
         }
     };
 
+    let api_config = config.api.clone();
+
     // Create daemon state
-    let state = DaemonState::new(
+    let mut state = DaemonState::new(
         config,
         transcriber,
         #[cfg(feature = "portal")]
         portal,
     );
+    if let Some(path) = config_path.clone() {
+        state = state.with_config_path(path);
+    }
+    state = state.with_no_download(no_download);
 
     // Spawn bridge thread: crossbeam (pipeline OS threads) → tokio broadcast (follow clients)
     let bridge_event_rx = state.pipeline_event_rx.clone();
@@ -236,6 +336,72 @@ pub async fn run_daemon(
     // Create command handler
     let handler = handler::DaemonCommandHandler::new(state, quiet, verbosity);
 
+    // Unload the model after `stt.unload_after` of idle time (checked live)
+    let idle_handler = handler.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            crate::defaults::UNLOAD_CHECK_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            idle_handler.unload_if_idle().await;
+        }
+    });
+
+    // Watch the config file for changes (inotify on its directory)
+    if watch_config {
+        match config_path.as_deref() {
+            Some(path) => match watcher::spawn_config_watcher(path, handler.clone(), quiet) {
+                Ok(()) => {
+                    if !quiet {
+                        eprintln!("Watching {} for changes.", path.display());
+                    }
+                }
+                Err(e) => eprintln!("voicsh: config watcher unavailable: {e}"),
+            },
+            None => eprintln!("voicsh: --watch-config ignored, no config file path"),
+        }
+    }
+
+    // Serve the model to other local tools when enabled
+    let api_handle = if api_config.enabled {
+        match api::ApiServer::bind(&api_config).await {
+            Ok(api_server) => {
+                if !quiet {
+                    eprintln!("Transcription API listening at: {}", api_server.address());
+                }
+                let api_server = api_server.with_audio_budget(server.audio_budget());
+                Some(tokio::spawn(api_server.serve(handler.clone())))
+            }
+            Err(e) => {
+                eprintln!("voicsh: transcription API unavailable: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Serve the model to Home Assistant's voice pipeline when requested
+    let wyoming_handle = match wyoming {
+        Some(addr) => match wyoming::WyomingServer::bind(addr).await {
+            Ok(wyoming_server) => {
+                if !quiet {
+                    eprintln!(
+                        "Wyoming ASR service listening at: {}",
+                        wyoming_server.address()
+                    );
+                }
+                Some(tokio::spawn(wyoming_server.serve(handler.clone())))
+            }
+            Err(e) => {
+                eprintln!("voicsh: Wyoming service unavailable: {e}");
+                None
+            }
+        },
+        None => None,
+    };
+
     // Start IPC server in background task
     let server_clone = Arc::clone(&server);
     let server_handle = tokio::spawn(async move { server_clone.start(handler).await });
@@ -260,6 +426,16 @@ pub async fn run_daemon(
     // Stop IPC server
     server.stop().await?;
 
+    // Dropping the API task closes its listener and removes its socket file
+    if let Some(api_handle) = api_handle {
+        api_handle.abort();
+        api_handle.await.ok();
+    }
+    if let Some(wyoming_handle) = wyoming_handle {
+        wyoming_handle.abort();
+        wyoming_handle.await.ok();
+    }
+
     // Wait for server task to finish
     if let Err(e) = server_handle.await {
         eprintln!("voicsh: daemon server task failed: {e}");
@@ -303,6 +479,13 @@ pub(crate) async fn create_transcriber(
     use crate::stt::fan_out::FanOutTranscriber;
     use crate::stt::whisper::{WhisperConfig, WhisperTranscriber};
 
+    match config.stt.backend {
+        SttBackend::Http => return create_http_transcriber(config, quiet),
+        SttBackend::Deepgram => return create_deepgram_transcriber(config),
+        SttBackend::Candle => return create_candle_transcriber(config),
+        SttBackend::Local => {}
+    }
+
     let model_name = &config.stt.model;
     let language = &config.stt.language;
 
@@ -368,10 +551,12 @@ pub(crate) async fn create_transcriber(
             use_gpu: true,
         })?;
 
-        Ok(Arc::new(FanOutTranscriber::new(vec![
+        let fan_out = FanOutTranscriber::new(vec![
             Arc::new(en_transcriber) as Arc<dyn Transcriber>,
             Arc::new(multilingual_transcriber) as Arc<dyn Transcriber>,
-        ])))
+        ])
+        .with_strategy(config.stt.fan_out_strategy);
+        with_language_routing(config, Arc::new(fan_out), quiet, no_download).await
     } else {
         // Single model mode
         let transcriber = WhisperTranscriber::new(WhisperConfig {
@@ -381,10 +566,163 @@ pub(crate) async fn create_transcriber(
             use_gpu: true,
         })?;
 
-        Ok(Arc::new(transcriber))
+        with_language_routing(config, Arc::new(transcriber), quiet, no_download).await
     }
 }
 
+/// Wrap `transcriber` in a [`RoutingTranscriber`] for `[stt.language_models]`.
+///
+/// Routing needs language detection, so it only applies with
+/// `stt.language = "auto"`. Missing routed models are downloaded up front;
+/// with `no_download` their routes are skipped instead.
+///
+/// [`RoutingTranscriber`]: crate::stt::routing::RoutingTranscriber
+pub(crate) async fn with_language_routing(
+    config: &Config,
+    transcriber: Arc<dyn Transcriber>,
+    quiet: bool,
+    no_download: bool,
+) -> Result<Arc<dyn Transcriber>> {
+    use crate::models::catalog::get_model;
+    use crate::models::download::{download_model, is_model_installed, model_path};
+    use crate::stt::routing::{LanguageRoute, RoutingTranscriber};
+    use crate::stt::whisper::{WhisperConfig, WhisperTranscriber};
+
+    if config.stt.language_models.is_empty() {
+        return Ok(transcriber);
+    }
+    if config.stt.language != crate::defaults::AUTO_LANGUAGE {
+        if !quiet {
+            eprintln!(
+                "Note: [stt.language_models] is only used with language='auto' (current: '{}').",
+                config.stt.language
+            );
+        }
+        return Ok(transcriber);
+    }
+
+    let mut configured: Vec<(&String, &String)> = config.stt.language_models.iter().collect();
+    configured.sort();
+    let mut routes = Vec::with_capacity(configured.len());
+    for (language, model) in configured {
+        if !is_model_installed(model) {
+            if no_download {
+                eprintln!(
+                    "voicsh: model '{model}' for [{language}] not installed (--no-download), not routing {language}"
+                );
+                continue;
+            }
+            if !quiet {
+                eprintln!("Downloading model '{model}' for [{language}]...");
+            }
+            download_model(model, !quiet).await?;
+        }
+        routes.push(LanguageRoute {
+            language: language.clone(),
+            model: model.clone(),
+            size_mb: get_model(model).map_or(0, |m| u64::from(m.size_mb)),
+        });
+    }
+    if routes.is_empty() {
+        return Ok(transcriber);
+    }
+
+    Ok(Arc::new(RoutingTranscriber::new(
+        transcriber,
+        routes,
+        Box::new(|model, language| {
+            Ok(Arc::new(WhisperTranscriber::new(WhisperConfig {
+                model_path: model_path(model),
+                language: language.to_string(),
+                threads: None,
+                use_gpu: true,
+            })?) as Arc<dyn Transcriber>)
+        }),
+        config.stt.language_models_budget_mb,
+    )))
+}
+
+/// Create the `stt.backend = "http"` transcriber.
+///
+/// The local `stt.model` becomes the fallback for an unreachable server when
+/// it is installed; it is never downloaded for this and only loaded on demand.
+pub(crate) fn create_http_transcriber(
+    config: &Config,
+    quiet: bool,
+) -> Result<Arc<dyn Transcriber>> {
+    use crate::models::catalog::resolve_model_for_language;
+    use crate::models::download::{is_model_installed, model_path};
+    use crate::stt::http::{HttpTranscriber, HttpTranscriberConfig};
+    use crate::stt::whisper::{WhisperConfig, WhisperTranscriber};
+
+    let transcriber = HttpTranscriber::new(HttpTranscriberConfig::from_config(&config.stt))?;
+    if !config.stt.http.fallback {
+        return Ok(Arc::new(transcriber));
+    }
+
+    let local_model = resolve_model_for_language(&config.stt.model, &config.stt.language, true);
+    if !is_model_installed(&local_model) {
+        if !quiet {
+            eprintln!(
+                "Note: no local fallback while {} is unreachable ('{}' not installed).",
+                config.stt.http.url, local_model
+            );
+        }
+        return Ok(Arc::new(transcriber));
+    }
+
+    let whisper_config = WhisperConfig {
+        model_path: model_path(&local_model),
+        language: config.stt.language.clone(),
+        threads: None,
+        use_gpu: true,
+    };
+    Ok(Arc::new(transcriber.with_fallback(Box::new(move || {
+        Ok(Arc::new(WhisperTranscriber::new(whisper_config.clone())?) as Arc<dyn Transcriber>)
+    }))))
+}
+
+/// Create the streaming cloud transcriber for `stt.backend = "deepgram"`.
+#[cfg(feature = "cloud-stt")]
+pub(crate) fn create_deepgram_transcriber(config: &Config) -> Result<Arc<dyn Transcriber>> {
+    use crate::stt::deepgram::{DeepgramTranscriber, DeepgramTranscriberConfig};
+
+    let resolved =
+        DeepgramTranscriberConfig::from_config(&config.stt.deepgram, &config.stt.language)?;
+    Ok(Arc::new(DeepgramTranscriber::new(resolved)?))
+}
+
+/// Without the `cloud-stt` feature the streaming backend is not compiled in.
+#[cfg(not(feature = "cloud-stt"))]
+pub(crate) fn create_deepgram_transcriber(_config: &Config) -> Result<Arc<dyn Transcriber>> {
+    Err(VoicshError::ConfigInvalidValue {
+        key: "stt.backend".to_string(),
+        message:
+            "voicsh was built without streaming cloud support; rebuild with --features cloud-stt"
+                .to_string(),
+    })
+}
+
+/// Create the pure-Rust Whisper transcriber for `stt.backend = "candle"`.
+#[cfg(feature = "candle-whisper")]
+pub(crate) fn create_candle_transcriber(config: &Config) -> Result<Arc<dyn Transcriber>> {
+    use crate::stt::candle_whisper::{CandleWhisperConfig, CandleWhisperTranscriber};
+
+    Ok(Arc::new(CandleWhisperTranscriber::new(
+        CandleWhisperConfig::from_config(&config.stt),
+    )?))
+}
+
+/// Without the `candle-whisper` feature the candle backend is not compiled in.
+#[cfg(not(feature = "candle-whisper"))]
+pub(crate) fn create_candle_transcriber(_config: &Config) -> Result<Arc<dyn Transcriber>> {
+    Err(VoicshError::ConfigInvalidValue {
+        key: "stt.backend".to_string(),
+        message: "voicsh was built without candle Whisper; rebuild with --features candle-whisper"
+            .to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -604,6 +942,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::default(),
             corrector_name: None,
+            words: vec![],
         };
         let event3 = DaemonEvent::RecordingStateChanged { recording: false };
 
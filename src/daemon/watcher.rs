@@ -0,0 +1,185 @@
+//! Config file watcher: reloads the daemon config when `config.toml` changes.
+
+use crate::daemon::handler::DaemonCommandHandler;
+use crate::error::{Result, VoicshError};
+use crate::ipc::protocol::{Command, Response};
+use crate::ipc::server::CommandHandler;
+use crate::sys::DirWatcher;
+use std::path::Path;
+use std::time::Duration;
+
+/// Quiet period after the last write before reloading.
+///
+/// Editors often write a file several times per save (truncate, write, chmod);
+/// waiting collapses those into a single reload.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watch `path` and send `ReloadConfig` to `handler` whenever it is written.
+///
+/// The inotify watch runs on a dedicated OS thread for the daemon's lifetime;
+/// reloads are performed on the tokio runtime of the caller.
+pub fn spawn_config_watcher(path: &Path, handler: DaemonCommandHandler, quiet: bool) -> Result<()> {
+    let dir = path
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| VoicshError::ConfigInvalidValue {
+            key: "config".to_string(),
+            message: format!("'{}' is not a file path", path.display()),
+        })?
+        .to_os_string();
+
+    let watcher = DirWatcher::new(dir).map_err(|e| {
+        VoicshError::Other(format!(
+            "Failed to watch config directory '{}': {e}",
+            dir.display()
+        ))
+    })?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+
+    std::thread::spawn(move || {
+        loop {
+            match watcher.read_names() {
+                Ok(names) => {
+                    if names.contains(&file_name) && tx.send(()).is_err() {
+                        break;
+                    }
+                }
+                // A signal interrupted the blocking read; nothing was lost
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    eprintln!("voicsh: config watcher stopped: {e}");
+                    break;
+                }
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        while rx.recv().await.is_some() {
+            tokio::time::sleep(DEBOUNCE).await;
+            while rx.try_recv().is_ok() {}
+
+            let response = handler.handle(Command::ReloadConfig).await;
+            if !quiet {
+                eprintln!("{}", describe_reload(&response));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// One-line summary of a reload response for the daemon log.
+fn describe_reload(response: &Response) -> String {
+    match response {
+        Response::ConfigReloaded {
+            changed,
+            restart_required,
+            model_reloaded,
+        } => {
+            if changed.is_empty() && restart_required.is_empty() {
+                return "Config file changed, no settings differ.".to_string();
+            }
+            let mut line = format!("Config reloaded: {}", changed.join(", "));
+            if *model_reloaded {
+                line.push_str(" (model reloaded)");
+            }
+            if !restart_required.is_empty() {
+                line.push_str(&format!(
+                    "; restart required for: {}",
+                    restart_required.join(", ")
+                ));
+            }
+            line
+        }
+        Response::Error { message } => format!("voicsh: {message}"),
+        other => format!("Config reload: unexpected response {other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::daemon::DaemonState;
+    use crate::ipc::protocol::DaemonEvent;
+    use crate::stt::transcriber::MockTranscriber;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_describe_reload_lists_changes_and_restart_keys() {
+        let line = describe_reload(&Response::ConfigReloaded {
+            changed: vec!["audio.vad_threshold".to_string()],
+            restart_required: vec!["stt.fan_out".to_string()],
+            model_reloaded: false,
+        });
+        assert_eq!(
+            line,
+            "Config reloaded: audio.vad_threshold; restart required for: stt.fan_out"
+        );
+    }
+
+    #[test]
+    fn test_describe_reload_no_changes() {
+        let line = describe_reload(&Response::ConfigReloaded {
+            changed: vec![],
+            restart_required: vec![],
+            model_reloaded: false,
+        });
+        assert_eq!(line, "Config file changed, no settings differ.");
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reloads_on_file_write() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[audio]\nvad_threshold = 0.02\n").unwrap();
+
+        let state = DaemonState::new(
+            Config::default(),
+            Arc::new(MockTranscriber::new("mock-watch-model")),
+            #[cfg(feature = "portal")]
+            None,
+        )
+        .with_config_path(path.clone());
+        let handler = DaemonCommandHandler::new(state, true, 0);
+        let mut events = handler.subscribe();
+
+        spawn_config_watcher(&path, handler.clone(), true).unwrap();
+        std::fs::write(&path, "[audio]\nvad_threshold = 0.5\n").unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Ok(DaemonEvent::ConfigChanged { key, value }) = events.recv().await
+                    && key == "audio.vad_threshold"
+                {
+                    return value;
+                }
+            }
+        })
+        .await
+        .expect("watcher should trigger a reload");
+        assert_eq!(event, "0.5");
+    }
+
+    #[tokio::test]
+    async fn test_watcher_missing_directory_returns_error() {
+        let state = DaemonState::new(
+            Config::default(),
+            Arc::new(MockTranscriber::new("mock-watch-model")),
+            #[cfg(feature = "portal")]
+            None,
+        );
+        let handler = DaemonCommandHandler::new(state, true, 0);
+        let result = spawn_config_watcher(
+            Path::new("/nonexistent_voicsh_dir/config.toml"),
+            handler,
+            true,
+        );
+        assert!(result.is_err(), "expected error for missing directory");
+    }
+}
@@ -0,0 +1,606 @@
+//! Wyoming protocol ASR service.
+//!
+//! Home Assistant's voice pipeline talks to speech-to-text services over the
+//! Wyoming protocol: each event is a JSON header line, optionally followed by
+//! a JSON data block and a binary payload. `voicsh daemon --wyoming ADDR`
+//! answers `describe` with an `info` event for the loaded model, collects the
+//! PCM of `audio-start`/`audio-chunk`/`audio-stop` and replies with a
+//! `transcript`. The audio runs through the same pipeline as
+//! `voicsh transcribe` and queues behind live dictation.
+//!
+//! The protocol has no authentication; bind to loopback unless the network
+//! is trusted.
+
+use crate::audio::raw::{RawPcmAudioSource, RawPcmSpec, RawSampleFormat};
+use crate::daemon::handler::DaemonCommandHandler;
+use crate::error::{Result, VoicshError};
+use crate::pipeline::sink::TextSink;
+use crate::pipeline::types::TranscribedText;
+use crate::transcript::Transcript;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+
+/// Largest accepted header line.
+const MAX_HEADER_BYTES: u64 = 64 * 1024;
+
+/// Largest accepted data block or payload of a single event.
+const MAX_EVENT_BYTES: usize = 4 * 1024 * 1024;
+
+/// Longest utterance buffered between `audio-start` and `audio-stop`.
+const MAX_AUDIO_SECS: usize = 300;
+
+/// Most PCM bytes buffered for one utterance, whatever format was announced
+/// (five minutes of 48 kHz stereo 16-bit audio fit).
+const MAX_AUDIO_BYTES: usize = 64 * 1024 * 1024;
+
+/// Highest sample rate a client may announce.
+const MAX_SAMPLE_RATE: u32 = 192_000;
+
+/// Most channels a client may announce.
+const MAX_CHANNELS: u16 = 8;
+
+/// Maximum number of concurrent Wyoming connections.
+const MAX_CONNECTIONS: usize = 8;
+
+/// Bound Wyoming listener, ready to [`serve`](Self::serve).
+pub struct WyomingServer {
+    listener: TcpListener,
+}
+
+impl WyomingServer {
+    pub async fn bind(addr: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| VoicshError::IpcSocket {
+                message: format!("Failed to bind Wyoming server to {addr}: {e}"),
+            })?;
+        Ok(Self { listener })
+    }
+
+    /// Where clients reach the service, as `tcp://host:port`.
+    pub fn address(&self) -> String {
+        self.listener
+            .local_addr()
+            .map_or_else(|_| "tcp".to_string(), |addr| format!("tcp://{addr}"))
+    }
+
+    /// Accept connections until the task is dropped.
+    pub async fn serve(self, handler: DaemonCommandHandler) {
+        let semaphore = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+        loop {
+            let permit = match semaphore.clone().acquire_owned().await {
+                Ok(permit) => permit,
+                Err(_) => return,
+            };
+            match self.listener.accept().await {
+                Ok((stream, _)) => {
+                    let handler = handler.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, &handler).await {
+                            eprintln!("voicsh: Wyoming connection closed: {e}");
+                        }
+                        drop(permit);
+                    });
+                }
+                Err(e) => {
+                    eprintln!("voicsh: Wyoming server failed to accept connection: {e}");
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            }
+        }
+    }
+}
+
+/// One Wyoming event.
+#[derive(Debug, Default, PartialEq)]
+struct Event {
+    kind: String,
+    data: Map<String, Value>,
+    payload: Vec<u8>,
+}
+
+impl Event {
+    fn new(kind: &str, data: Value) -> Self {
+        Self {
+            kind: kind.to_string(),
+            data: match data {
+                Value::Object(map) => map,
+                _ => Map::new(),
+            },
+            payload: Vec::new(),
+        }
+    }
+
+    fn u64_field(&self, key: &str) -> Option<u64> {
+        self.data.get(key).and_then(Value::as_u64)
+    }
+}
+
+#[derive(Deserialize)]
+struct Header {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    data: Option<Map<String, Value>>,
+    #[serde(default)]
+    data_length: usize,
+    #[serde(default)]
+    payload_length: usize,
+}
+
+fn protocol_error(message: impl Into<String>) -> VoicshError {
+    VoicshError::IpcProtocol {
+        message: message.into(),
+    }
+}
+
+/// Read the next event, or `None` when the peer closed the connection.
+async fn read_event<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<Event>> {
+    let io_error = |e: std::io::Error| protocol_error(format!("Failed to read event: {e}"));
+
+    let mut line = Vec::new();
+    let n = (&mut *reader)
+        .take(MAX_HEADER_BYTES)
+        .read_until(b'\n', &mut line)
+        .await
+        .map_err(io_error)?;
+    if n == 0 {
+        return Ok(None);
+    }
+    if line.last() != Some(&b'\n') {
+        return Err(protocol_error(if n as u64 == MAX_HEADER_BYTES {
+            "Event header too long"
+        } else {
+            "Connection closed mid-event"
+        }));
+    }
+    let header: Header = serde_json::from_slice(&line)
+        .map_err(|e| protocol_error(format!("Invalid event header: {e}")))?;
+    if header.data_length > MAX_EVENT_BYTES || header.payload_length > MAX_EVENT_BYTES {
+        return Err(protocol_error(format!(
+            "Event '{}' exceeds {MAX_EVENT_BYTES} bytes",
+            header.kind
+        )));
+    }
+
+    let mut data = header.data.unwrap_or_default();
+    if header.data_length > 0 {
+        let mut block = vec![0; header.data_length];
+        reader.read_exact(&mut block).await.map_err(io_error)?;
+        let extra: Map<String, Value> = serde_json::from_slice(&block)
+            .map_err(|e| protocol_error(format!("Invalid event data: {e}")))?;
+        data.extend(extra);
+    }
+    let mut payload = vec![0; header.payload_length];
+    reader.read_exact(&mut payload).await.map_err(io_error)?;
+
+    Ok(Some(Event {
+        kind: header.kind,
+        data,
+        payload,
+    }))
+}
+
+/// Write `event` with its data inline in the header line.
+async fn write_event<W: AsyncWrite + Unpin>(writer: &mut W, event: &Event) -> Result<()> {
+    let mut header = json!({ "type": event.kind, "data": event.data });
+    if !event.payload.is_empty() {
+        header["payload_length"] = json!(event.payload.len());
+    }
+    let mut bytes = header.to_string().into_bytes();
+    bytes.push(b'\n');
+    bytes.extend_from_slice(&event.payload);
+    writer
+        .write_all(&bytes)
+        .await
+        .map_err(|e| protocol_error(format!("Failed to write event: {e}")))
+}
+
+/// PCM collected for one utterance.
+struct Utterance {
+    spec: RawPcmSpec,
+    pcm: Vec<u8>,
+}
+
+impl Utterance {
+    /// Start an utterance in the format announced by `event`.
+    fn start(event: &Event) -> std::result::Result<Self, String> {
+        let format = match event.u64_field("width") {
+            Some(2) => RawSampleFormat::S16Le,
+            Some(4) => RawSampleFormat::S32Le,
+            Some(1) => RawSampleFormat::U8,
+            Some(width) => return Err(format!("Unsupported sample width {width}")),
+            None => return Err("Audio event is missing 'width'".to_string()),
+        };
+        let sample_rate = event
+            .u64_field("rate")
+            .and_then(|r| u32::try_from(r).ok())
+            .filter(|&r| r > 0)
+            .ok_or("Audio event is missing a valid 'rate'")?;
+        if sample_rate > MAX_SAMPLE_RATE {
+            return Err(format!(
+                "Unsupported sample rate {sample_rate} (at most {MAX_SAMPLE_RATE})"
+            ));
+        }
+        let channels = event
+            .u64_field("channels")
+            .and_then(|c| u16::try_from(c).ok())
+            .filter(|&c| c > 0)
+            .ok_or("Audio event is missing a valid 'channels'")?;
+        if channels > MAX_CHANNELS {
+            return Err(format!(
+                "Unsupported channel count {channels} (at most {MAX_CHANNELS})"
+            ));
+        }
+        Ok(Self {
+            spec: RawPcmSpec {
+                format,
+                sample_rate,
+                channels,
+            },
+            pcm: Vec::new(),
+        })
+    }
+
+    fn push(&mut self, payload: &[u8]) -> std::result::Result<(), String> {
+        let limit = self.spec.sample_rate as usize * self.spec.frame_bytes() * MAX_AUDIO_SECS;
+        if self.pcm.len() + payload.len() > limit {
+            return Err(format!(
+                "Audio longer than {MAX_AUDIO_SECS} seconds; send audio-stop sooner"
+            ));
+        }
+        if self.pcm.len() + payload.len() > MAX_AUDIO_BYTES {
+            return Err(format!(
+                "Audio larger than {MAX_AUDIO_BYTES} bytes; send audio-stop sooner"
+            ));
+        }
+        self.pcm.extend_from_slice(payload);
+        Ok(())
+    }
+}
+
+/// Serve one client until it disconnects.
+async fn handle_connection(stream: TcpStream, handler: &DaemonCommandHandler) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut utterance: Option<Utterance> = None;
+
+    while let Some(event) = read_event(&mut reader).await? {
+        let reply = match event.kind.as_str() {
+            "describe" => Some(info_event(handler).await),
+            "ping" => Some(Event::new("pong", json!({}))),
+            // The daemon's configured model and language apply
+            "transcribe" => {
+                utterance = None;
+                None
+            }
+            "audio-start" => match Utterance::start(&event) {
+                Ok(started) => {
+                    utterance = Some(started);
+                    None
+                }
+                Err(message) => Some(error_event(message)),
+            },
+            "audio-chunk" => {
+                if utterance.is_none() {
+                    // Tolerate clients that skip audio-start
+                    utterance = Utterance::start(&event).ok();
+                }
+                match utterance.as_mut() {
+                    Some(current) => match current.push(&event.payload) {
+                        Ok(()) => None,
+                        Err(message) => {
+                            utterance = None;
+                            Some(error_event(message))
+                        }
+                    },
+                    None => Some(error_event("audio-chunk without a valid audio format")),
+                }
+            }
+            "audio-stop" => Some(match utterance.take() {
+                Some(current) => transcribe(handler, current).await,
+                None => error_event("audio-stop without audio"),
+            }),
+            // Other services' events (wake, tts, ...) are not ours to answer
+            _ => None,
+        };
+        if let Some(reply) = reply {
+            write_event(&mut writer, &reply).await?;
+        }
+    }
+    Ok(())
+}
+
+/// `info` event describing the loaded model as an ASR program.
+async fn info_event(handler: &DaemonCommandHandler) -> Event {
+    use crate::pipeline::post_processor::SUPPORTED_LANGUAGES;
+
+    let language = handler.language().await;
+    let languages: Vec<&str> = if language == "auto" {
+        SUPPORTED_LANGUAGES.to_vec()
+    } else {
+        vec![language.as_str()]
+    };
+    let attribution = json!({
+        "name": "voicsh",
+        "url": env!("CARGO_PKG_REPOSITORY"),
+    });
+    Event::new(
+        "info",
+        json!({
+            "asr": [{
+                "name": "voicsh",
+                "description": "voicsh speech-to-text daemon",
+                "attribution": attribution,
+                "installed": true,
+                "version": env!("CARGO_PKG_VERSION"),
+                "models": [{
+                    "name": handler.model_name().await,
+                    "description": "Model loaded by the voicsh daemon",
+                    "attribution": attribution,
+                    "installed": true,
+                    "version": null,
+                    "languages": languages,
+                }],
+            }],
+        }),
+    )
+}
+
+/// Transcribe a finished utterance into a `transcript` (or `error`) event.
+async fn transcribe(handler: &DaemonCommandHandler, utterance: Utterance) -> Event {
+    let source = RawPcmAudioSource::new(
+        Box::new(std::io::Cursor::new(utterance.pcm)),
+        utterance.spec,
+    );
+    match handler
+        .transcribe_to_sink(Box::new(source), Box::new(WyomingSink::default()))
+        .await
+    {
+        Ok(data) => {
+            let data = data
+                .and_then(|d| serde_json::from_str(&d).ok())
+                .unwrap_or_else(|| json!({ "text": "" }));
+            Event::new("transcript", data)
+        }
+        Err(message) => error_event(message),
+    }
+}
+
+fn error_event(message: impl Into<String>) -> Event {
+    Event::new(
+        "error",
+        json!({ "text": message.into(), "code": "voicsh-error" }),
+    )
+}
+
+/// Collects an utterance and finishes with the `transcript` event data.
+#[derive(Default)]
+struct WyomingSink {
+    transcript: Transcript,
+}
+
+impl TextSink for WyomingSink {
+    fn handle(&mut self, text: &str) -> Result<()> {
+        self.transcript
+            .push(&TranscribedText::new(text.to_string()));
+        Ok(())
+    }
+
+    fn handle_transcription(&mut self, text: &TranscribedText) -> Result<()> {
+        self.transcript.push(text);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Option<String> {
+        let mut data = json!({ "text": self.transcript.text() });
+        if let Some(language) = self.transcript.language() {
+            data["language"] = json!(language);
+        }
+        Some(data.to_string())
+    }
+
+    fn name(&self) -> &'static str {
+        "wyoming"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::daemon::DaemonState;
+    use crate::stt::transcriber::{MockTranscriber, Transcriber};
+    use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+
+    fn handler() -> DaemonCommandHandler {
+        let transcriber: Arc<dyn Transcriber> = Arc::new(
+            MockTranscriber::new("mock-wyoming-model").with_response("turn on the lights"),
+        );
+        let state = DaemonState::new(
+            Config::default(),
+            transcriber,
+            #[cfg(feature = "portal")]
+            None,
+        );
+        DaemonCommandHandler::new(state, true, 0)
+    }
+
+    /// In-process Wyoming client connected to a freshly started server.
+    struct Client {
+        reader: BufReader<OwnedReadHalf>,
+        writer: OwnedWriteHalf,
+    }
+
+    impl Client {
+        async fn connect() -> Self {
+            let server = WyomingServer::bind("127.0.0.1:0".parse().unwrap())
+                .await
+                .unwrap();
+            let addr = server.listener.local_addr().unwrap();
+            tokio::spawn(server.serve(handler()));
+            let (reader, writer) = TcpStream::connect(addr).await.unwrap().into_split();
+            Self {
+                reader: BufReader::new(reader),
+                writer,
+            }
+        }
+
+        async fn send(&mut self, kind: &str, data: Value, payload: Vec<u8>) {
+            let event = Event {
+                payload,
+                ..Event::new(kind, data)
+            };
+            write_event(&mut self.writer, &event).await.unwrap();
+        }
+
+        async fn receive(&mut self) -> Event {
+            tokio::time::timeout(Duration::from_secs(10), read_event(&mut self.reader))
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap()
+        }
+    }
+
+    /// 100ms chunks of loud 22.05 kHz stereo audio, as a client would stream them.
+    fn loud_chunks(seconds: usize) -> Vec<Vec<u8>> {
+        let frame: Vec<u8> = [8000i16, 8000]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        (0..seconds * 10).map(|_| frame.repeat(2205)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_describe_returns_info() {
+        let mut client = Client::connect().await;
+        client.send("describe", json!({}), Vec::new()).await;
+        let info = client.receive().await;
+        assert_eq!(info.kind, "info");
+        let asr = &info.data["asr"][0];
+        assert_eq!(asr["name"], "voicsh");
+        assert_eq!(asr["installed"], true);
+        let model = &asr["models"][0];
+        assert_eq!(model["name"], "mock-wyoming-model");
+        assert!(model["languages"].as_array().unwrap().len() > 1);
+    }
+
+    #[tokio::test]
+    async fn test_transcribes_streamed_audio() {
+        let mut client = Client::connect().await;
+        let format = json!({ "rate": 22050, "width": 2, "channels": 2 });
+        client
+            .send("transcribe", json!({ "language": "en" }), Vec::new())
+            .await;
+        client.send("audio-start", format.clone(), Vec::new()).await;
+        for chunk in loud_chunks(2) {
+            client.send("audio-chunk", format.clone(), chunk).await;
+        }
+        client.send("audio-stop", json!({}), Vec::new()).await;
+
+        let transcript = client.receive().await;
+        assert_eq!(transcript.kind, "transcript");
+        assert_eq!(transcript.data["text"], "turn on the lights");
+
+        // The connection stays usable for the next request
+        client.send("ping", json!({}), Vec::new()).await;
+        assert_eq!(client.receive().await.kind, "pong");
+    }
+
+    #[tokio::test]
+    async fn test_reports_errors_as_events() {
+        let mut client = Client::connect().await;
+        client.send("audio-stop", json!({}), Vec::new()).await;
+        let error = client.receive().await;
+        assert_eq!(error.kind, "error");
+        assert!(
+            error.data["text"]
+                .as_str()
+                .unwrap()
+                .contains("without audio")
+        );
+
+        client
+            .send(
+                "audio-start",
+                json!({ "rate": 16000, "width": 3, "channels": 1 }),
+                Vec::new(),
+            )
+            .await;
+        let error = client.receive().await;
+        assert_eq!(error.kind, "error");
+        assert!(error.data["text"].as_str().unwrap().contains("width 3"));
+    }
+
+    #[tokio::test]
+    async fn test_reads_separate_data_block() {
+        // Newer clients send data after the header instead of inline
+        let data = br#"{"rate":16000,"width":2,"channels":1}"#;
+        let mut bytes = format!(
+            "{{\"type\":\"audio-chunk\",\"data_length\":{},\"payload_length\":4}}\n",
+            data.len()
+        )
+        .into_bytes();
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(&[1, 0, 2, 0]);
+        bytes.extend_from_slice(b"{\"type\":\"audio-stop\"}\n");
+
+        let mut reader = BufReader::new(bytes.as_slice());
+        let chunk = read_event(&mut reader).await.unwrap().unwrap();
+        assert_eq!(chunk.kind, "audio-chunk");
+        assert_eq!(chunk.u64_field("rate"), Some(16000));
+        assert_eq!(chunk.payload, vec![1, 0, 2, 0]);
+        let stop = read_event(&mut reader).await.unwrap().unwrap();
+        assert_eq!(stop.kind, "audio-stop");
+        assert!(read_event(&mut reader).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rejects_implausible_audio_format() {
+        let start = |rate: u64, channels: u64| {
+            Utterance::start(&Event::new(
+                "audio-start",
+                json!({ "rate": rate, "width": 2, "channels": channels }),
+            ))
+        };
+        assert!(start(48_000, 2).is_ok());
+        let err = start(4_000_000_000, 1).err().unwrap();
+        assert!(err.contains("sample rate 4000000000"), "{err}");
+        let err = start(16_000, 64).err().unwrap();
+        assert!(err.contains("channel count 64"), "{err}");
+    }
+
+    #[test]
+    fn test_utterance_is_capped_in_bytes() {
+        let mut utterance = Utterance::start(&Event::new(
+            "audio-start",
+            json!({ "rate": MAX_SAMPLE_RATE, "width": 4, "channels": MAX_CHANNELS }),
+        ))
+        .unwrap();
+        let chunk = vec![0u8; MAX_EVENT_BYTES];
+        for _ in 0..MAX_AUDIO_BYTES / MAX_EVENT_BYTES {
+            utterance.push(&chunk).unwrap();
+        }
+        let err = utterance.push(&[0; 4]).unwrap_err();
+        assert!(err.contains("bytes"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_oversized_event() {
+        let header = format!(
+            "{{\"type\":\"audio-chunk\",\"payload_length\":{}}}\n",
+            MAX_EVENT_BYTES + 1
+        );
+        let mut reader = BufReader::new(header.as_bytes());
+        assert!(read_event(&mut reader).await.is_err());
+    }
+}
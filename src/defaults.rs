@@ -16,6 +16,15 @@ pub const SAMPLE_RATE: u32 = 16000;
 /// good sensitivity while filtering out background noise.
 pub const VAD_THRESHOLD: f32 = 0.02;
 
+/// Key for the system default input device in `audio.device_thresholds`.
+pub const DEFAULT_DEVICE_KEY: &str = "default";
+
+/// Default number of archived sessions kept by `[debug] archive_audio`.
+pub const ARCHIVE_MAX_SESSIONS: u32 = 50;
+
+/// Default size limit of the session archive in megabytes.
+pub const ARCHIVE_MAX_MB: u32 = 500;
+
 /// Default silence duration in milliseconds before speech is considered ended.
 ///
 /// 1500ms (1.5 seconds) allows for natural pauses in speech without prematurely
@@ -28,6 +37,61 @@ pub const SILENCE_DURATION_MS: u32 = 1500;
 /// Use "base.en" explicitly for English-only optimized transcription.
 pub const DEFAULT_MODEL: &str = "base";
 
+/// Default endpoint of the `stt.backend = "http"` transcription server.
+pub const HTTP_STT_URL: &str = "http://localhost:8000/v1/audio/transcriptions";
+
+/// Default model name sent to the transcription server.
+pub const HTTP_STT_MODEL: &str = "whisper-1";
+
+/// Default timeout in seconds for one transcription request.
+pub const HTTP_STT_TIMEOUT_SECS: u64 = 30;
+
+/// Default number of retries after a failed transcription request.
+pub const HTTP_STT_RETRIES: u32 = 2;
+
+/// Seconds to keep using the local fallback before trying an unreachable server again.
+///
+/// Avoids paying a connect timeout on every chunk while the server is down.
+pub const HTTP_STT_RECHECK_SECS: u64 = 30;
+
+/// Default endpoint of the `stt.backend = "deepgram"` streaming service.
+pub const DEEPGRAM_URL: &str = "wss://api.deepgram.com/v1/listen";
+
+/// Default model requested from the streaming service.
+pub const DEEPGRAM_MODEL: &str = "nova-3";
+
+/// Per-chunk decay of the accumulated language evidence (`stt.language_tracking`).
+/// Lower values forget earlier chunks faster.
+pub const LANGUAGE_TRACKING_DECAY: f32 = 0.5;
+
+/// Chunk duration (ms) that counts as full evidence for language tracking;
+/// shorter chunks are weighted proportionally.
+pub const LANGUAGE_EVIDENCE_FULL_MS: u32 = 3000;
+
+/// Posterior another language needs before the tracked session language switches.
+pub const LANGUAGE_SWITCH_POSTERIOR: f32 = 0.95;
+
+/// How often (seconds) the daemon checks whether `stt.unload_after` has elapsed.
+pub const UNLOAD_CHECK_INTERVAL_SECS: u64 = 30;
+
+/// Default memory budget (MB) for models loaded through `[stt.language_models]`.
+pub const LANGUAGE_MODELS_BUDGET_MB: u64 = 2048;
+
+/// Default HuggingFace repository for `stt.backend = "candle"`.
+pub const CANDLE_WHISPER_REPO: &str = "openai/whisper-base";
+
+/// Default beam width of the candle Whisper decoder.
+pub const CANDLE_WHISPER_BEAM_SIZE: usize = 5;
+
+/// Default address of the daemon's local transcription API (`[api]`).
+pub const API_LISTEN: &str = "127.0.0.1:8765";
+
+/// Default upload limit of the local transcription API in megabytes.
+pub const API_MAX_UPLOAD_MB: u32 = 100;
+
+/// Default neural VAD model name (used with `audio.vad_backend = "neural"`).
+pub const DEFAULT_VAD_MODEL: &str = "silero-v5.1.2";
+
 /// Default language code for transcription.
 ///
 /// "auto" lets Whisper detect the spoken language automatically.
@@ -83,6 +83,14 @@ pub enum VoicshError {
     #[error("Inference error: {message}")]
     Inference { message: String },
 
+    // Remote transcription server errors
+    #[error("Transcription server error: {message}")]
+    TranscriptionServer { message: String },
+
+    // Session audio archive errors
+    #[error("Session archive error: {message}")]
+    SessionArchive { message: String },
+
     // General I/O errors
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -195,6 +203,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_session_archive_display() {
+        let error = VoicshError::SessionArchive {
+            message: "no session matches \"abc\"".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Session archive error: no session matches \"abc\""
+        );
+    }
+
+    #[test]
+    fn test_transcription_server_display() {
+        let error = VoicshError::TranscriptionServer {
+            message: "HTTP 401: invalid API key".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Transcription server error: HTTP 401: invalid API key"
+        );
+    }
+
     #[test]
     fn test_injection_tool_not_found_display() {
         let error = VoicshError::InjectionToolNotFound {
@@ -2,6 +2,7 @@
 
 use crate::error::{Result, VoicshError};
 use crate::ipc::protocol::{Command, DaemonEvent, Response};
+use crate::transcript::TranscriptFormat;
 use std::path::Path;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::UnixStream;
@@ -24,7 +25,7 @@ const IPC_TIMEOUT_SECS: u64 = 5;
 pub async fn send_command(socket_path: &Path, command: Command) -> Result<Response> {
     let timeout = tokio::time::Duration::from_secs(IPC_TIMEOUT_SECS);
 
-    tokio::time::timeout(timeout, send_command_inner(socket_path, command))
+    tokio::time::timeout(timeout, send_command_inner(socket_path, command, None))
         .await
         .map_err(|_| VoicshError::IpcConnection {
             message: format!("Command timed out after {} seconds", IPC_TIMEOUT_SECS),
@@ -38,8 +39,52 @@ fn ipc_io_error(context: &str, e: std::io::Error) -> VoicshError {
     }
 }
 
+/// Check whether a daemon is accepting connections on `socket_path`.
+pub async fn daemon_available(socket_path: &Path) -> bool {
+    UnixStream::connect(socket_path).await.is_ok()
+}
+
+/// Ask the daemon to transcribe an audio file it can read from disk.
+///
+/// No timeout is applied: transcription time grows with the file length.
+pub async fn transcribe_file(
+    socket_path: &Path,
+    path: &Path,
+    format: Option<String>,
+    output_format: TranscriptFormat,
+) -> Result<Response> {
+    let command = Command::TranscribeFile {
+        path: path.to_string_lossy().into_owned(),
+        format,
+        output_format,
+    };
+    send_command_inner(socket_path, command, None).await
+}
+
+/// Send encoded audio bytes to the daemon for transcription.
+///
+/// The bytes follow the command line on the same connection; the write half
+/// is then shut down to mark the end of the audio. No timeout is applied.
+pub async fn transcribe_stream(
+    socket_path: &Path,
+    format: Option<String>,
+    output_format: TranscriptFormat,
+    audio: &[u8],
+) -> Result<Response> {
+    let command = Command::TranscribeStream {
+        format,
+        output_format,
+    };
+    send_command_inner(socket_path, command, Some(audio)).await
+}
+
 /// Internal implementation of send_command without timeout wrapper.
-async fn send_command_inner(socket_path: &Path, command: Command) -> Result<Response> {
+/// `payload` is written after the command line, followed by a write shutdown.
+async fn send_command_inner(
+    socket_path: &Path,
+    command: Command,
+    payload: Option<&[u8]>,
+) -> Result<Response> {
     // Connect to daemon socket
     let stream = UnixStream::connect(socket_path)
         .await
@@ -68,6 +113,23 @@ async fn send_command_inner(socket_path: &Path, command: Command) -> Result<Resp
         .await
         .map_err(|e| ipc_io_error("Failed to flush writer", e))?;
 
+    if let Some(payload) = payload {
+        let sent = match writer.write_all(payload).await {
+            Ok(()) => writer.shutdown().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            // The daemon answers an upload it rejects before closing
+            let mut response_line = String::new();
+            if reader.read_line(&mut response_line).await.is_ok()
+                && let Ok(response) = Response::from_json(response_line.trim())
+            {
+                return Ok(response);
+            }
+            return Err(ipc_io_error("Failed to write audio", e));
+        }
+    }
+
     // Read response
     let mut response_line = String::new();
     reader
@@ -211,8 +273,111 @@ mod tests {
                     enabled: false,
                     backend: Some("symspell".to_string()),
                 },
+                Command::ReloadConfig => Response::ConfigReloaded {
+                    changed: vec![],
+                    restart_required: vec![],
+                    model_reloaded: false,
+                },
+                Command::GetConfig { key } => Response::ConfigValue {
+                    key,
+                    value: serde_json::Value::Null,
+                },
+                Command::SetConfig { key, .. } => Response::Ok {
+                    message: format!("{key} updated"),
+                },
+                Command::TranscribeFile { .. } | Command::TranscribeStream { .. } => {
+                    Response::Transcription {
+                        text: "mock file transcription".to_string(),
+                    }
+                }
             }
         }
+
+        async fn handle_audio(
+            &self,
+            format: Option<String>,
+            output_format: TranscriptFormat,
+            audio: Vec<u8>,
+        ) -> Response {
+            Response::Transcription {
+                text: format!(
+                    "{} bytes as {} to {}",
+                    audio.len(),
+                    format.unwrap_or_default(),
+                    output_format
+                ),
+            }
+        }
+    }
+
+    async fn start_mock_server(socket_path: &Path) {
+        let server_socket_path = socket_path.to_path_buf();
+        tokio::spawn(async move {
+            let server = IpcServer::new(server_socket_path).unwrap();
+            server.start(MockHandler).await
+        });
+        for _ in 0..100 {
+            if UnixStream::connect(socket_path).await.is_ok() {
+                return;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+        panic!("Server did not become ready within 1s");
+    }
+
+    #[tokio::test]
+    async fn test_daemon_available() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket_path = temp_dir.path().join("test.sock");
+        assert!(!daemon_available(&socket_path).await);
+        start_mock_server(&socket_path).await;
+        assert!(daemon_available(&socket_path).await);
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_file_returns_transcription() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket_path = temp_dir.path().join("test.sock");
+        start_mock_server(&socket_path).await;
+
+        let response = transcribe_file(
+            &socket_path,
+            Path::new("/tmp/a.wav"),
+            None,
+            TranscriptFormat::Txt,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            response,
+            Response::Transcription {
+                text: "mock file transcription".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_stream_delivers_whole_payload() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket_path = temp_dir.path().join("test.sock");
+        start_mock_server(&socket_path).await;
+
+        // Larger than any read buffer, so part of it arrives after the command line
+        let audio = vec![7u8; 300_000];
+        let response = transcribe_stream(
+            &socket_path,
+            Some("wav".to_string()),
+            TranscriptFormat::Srt,
+            &audio,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            response,
+            Response::Transcription {
+                text: "300000 bytes as wav to srt".to_string()
+            }
+        );
     }
 
     #[tokio::test]
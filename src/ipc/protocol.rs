@@ -1,5 +1,6 @@
 //! JSON message protocol for IPC communication between CLI and daemon.
 
+use crate::transcript::TranscriptFormat;
 use serde::{Deserialize, Serialize};
 
 /// Commands sent by CLI to the daemon.
@@ -34,6 +35,37 @@ pub enum Command {
     SetCorrectionModel { model: String },
     /// List available error correction models
     ListCorrectionModels,
+    /// Re-read the config file and apply changes without restarting
+    ReloadConfig,
+    /// Read a config value from the running daemon by dotted key
+    GetConfig { key: String },
+    /// Validate and apply a config value at runtime, optionally saving it to the config file
+    SetConfig {
+        key: String,
+        value: String,
+        #[serde(default)]
+        persist: bool,
+    },
+    /// Transcribe an audio file with the loaded model (no text injection)
+    TranscribeFile {
+        path: String,
+        /// Audio format hint (e.g. "wav"); auto-detected when absent
+        #[serde(default)]
+        format: Option<String>,
+        /// Transcript format of the returned text
+        #[serde(default)]
+        output_format: TranscriptFormat,
+    },
+    /// Transcribe audio bytes that follow this command line on the same
+    /// connection, up to the point where the client closes its write half
+    TranscribeStream {
+        /// Audio format hint (e.g. "wav"); auto-detected when absent
+        #[serde(default)]
+        format: Option<String>,
+        /// Transcript format of the returned text
+        #[serde(default)]
+        output_format: TranscriptFormat,
+    },
 }
 
 impl Command {
@@ -120,6 +152,20 @@ pub enum Response {
         enabled: bool,
         backend: Option<String>,
     },
+    /// Result of re-reading the config file
+    ConfigReloaded {
+        /// Dotted keys that changed and were applied
+        changed: Vec<String>,
+        /// Dotted keys that changed but only take effect after a daemon restart
+        restart_required: Vec<String>,
+        /// Whether the transcription model was reloaded
+        model_reloaded: bool,
+    },
+    /// Current value of a config key
+    ConfigValue {
+        key: String,
+        value: serde_json::Value,
+    },
 }
 
 impl Response {
@@ -151,6 +197,9 @@ pub enum DaemonEvent {
         is_speech: bool,
         buffer_used: u16,
         buffer_capacity: u16,
+        /// Level before noise suppression (only when denoising is enabled)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        raw_level: Option<f32>,
     },
     /// Recording state changed
     RecordingStateChanged { recording: bool },
@@ -172,7 +221,12 @@ pub enum DaemonEvent {
         /// Name of the corrector backend that produced the correction.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         corrector_name: Option<String>,
+        /// Words with timestamps (ms from session start) and probabilities.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        words: Vec<crate::stt::transcriber::Word>,
     },
+    /// Interim hypothesis from a streaming backend (empty once finalized)
+    PartialTranscription { text: String },
     /// Transcription dropped by language/confidence filter
     TranscriptionDropped {
         text: String,
@@ -190,6 +244,20 @@ pub enum DaemonEvent {
     ModelLoaded { model: String },
     /// Model loading failed
     ModelLoadFailed { model: String, error: String },
+    /// Recording switched to another input device (unplugged or preferred device returned)
+    AudioDeviceChanged {
+        device: String,
+        previous: String,
+        reason: String,
+    },
+    /// Tracked session language changed (`stt.language_tracking`)
+    LanguageSwitched {
+        from: String,
+        to: String,
+        probability: f32,
+    },
+    /// Language routing switched to another per-language model
+    LanguageRouted { language: String, model: String },
     /// Daemon metadata sent once on follow connect
     DaemonInfo {
         binary_path: String,
@@ -246,6 +314,24 @@ mod tests {
                 model: "flan-t5-base".to_string(),
             },
             Command::ListCorrectionModels,
+            Command::ReloadConfig,
+            Command::GetConfig {
+                key: "audio.vad_threshold".to_string(),
+            },
+            Command::SetConfig {
+                key: "stt.allowed_languages".to_string(),
+                value: "en,de".to_string(),
+                persist: true,
+            },
+            Command::TranscribeFile {
+                path: "/tmp/standup.wav".to_string(),
+                format: Some("wav".to_string()),
+                output_format: TranscriptFormat::Srt,
+            },
+            Command::TranscribeStream {
+                format: None,
+                output_format: TranscriptFormat::Json,
+            },
         ];
 
         for cmd in commands {
@@ -395,6 +481,67 @@ mod tests {
         assert!(json.contains("\"message\":\"Model not found\""));
     }
 
+    #[test]
+    fn test_command_reload_config_json_format() {
+        let json = Command::ReloadConfig.to_json().expect("should serialize");
+        assert_eq!(json, r#"{"type":"reload_config"}"#);
+    }
+
+    #[test]
+    fn test_response_config_reloaded_json_roundtrip() {
+        let resp = Response::ConfigReloaded {
+            changed: vec!["audio.vad_threshold".to_string(), "stt.model".to_string()],
+            restart_required: vec!["stt.fan_out".to_string()],
+            model_reloaded: true,
+        };
+        let json = resp.to_json().expect("should serialize");
+        let deserialized = Response::from_json(&json).expect("should deserialize");
+        assert_eq!(resp, deserialized);
+        assert!(json.contains("\"type\":\"config_reloaded\""));
+        assert!(json.contains("\"restart_required\":[\"stt.fan_out\"]"));
+    }
+
+    #[test]
+    fn test_command_set_config_persist_defaults_to_false() {
+        let json = r#"{"type":"set_config","key":"stt.language","value":"de"}"#;
+        let cmd = Command::from_json(json).expect("should deserialize");
+        assert_eq!(
+            cmd,
+            Command::SetConfig {
+                key: "stt.language".to_string(),
+                value: "de".to_string(),
+                persist: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_command_transcribe_file_format_is_optional() {
+        let json = r#"{"type":"transcribe_file","path":"/tmp/a.wav"}"#;
+        let cmd = Command::from_json(json).expect("should deserialize");
+        assert_eq!(
+            cmd,
+            Command::TranscribeFile {
+                path: "/tmp/a.wav".to_string(),
+                format: None,
+                output_format: TranscriptFormat::Txt,
+            }
+        );
+    }
+
+    #[test]
+    fn test_response_config_value_json_roundtrip() {
+        let resp = Response::ConfigValue {
+            key: "stt.allowed_languages".to_string(),
+            value: serde_json::json!(["en", "de"]),
+        };
+        let json = resp.to_json().expect("should serialize");
+        let deserialized = Response::from_json(&json).expect("should deserialize");
+        assert_eq!(resp, deserialized);
+        assert!(json.contains("\"type\":\"config_value\""));
+        assert!(json.contains("\"value\":[\"en\",\"de\"]"));
+    }
+
     #[test]
     fn test_invalid_json_returns_error() {
         let invalid = r#"{"type": "unknown_command"}"#;
@@ -680,6 +827,7 @@ mod tests {
             is_speech: true,
             buffer_used: 3,
             buffer_capacity: 8,
+            raw_level: None,
         };
         let json = event.to_json().expect("should serialize");
         let deserialized = DaemonEvent::from_json(&json).expect("should deserialize");
@@ -709,6 +857,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::default(),
             corrector_name: None,
+            words: vec![],
         };
         let json = event.to_json().expect("should serialize");
         let deserialized = DaemonEvent::from_json(&json).expect("should deserialize");
@@ -737,6 +886,7 @@ mod tests {
                 is_speech: false,
                 buffer_used: 0,
                 buffer_capacity: 0,
+                raw_level: None,
             },
             DaemonEvent::Level {
                 level: 0.5,
@@ -744,6 +894,7 @@ mod tests {
                 is_speech: true,
                 buffer_used: 5,
                 buffer_capacity: 10,
+                raw_level: None,
             },
             DaemonEvent::RecordingStateChanged { recording: false },
             DaemonEvent::RecordingStateChanged { recording: true },
@@ -756,6 +907,7 @@ mod tests {
                 raw_text: None,
                 text_origin: TextOrigin::default(),
                 corrector_name: None,
+                words: vec![],
             },
             DaemonEvent::Transcription {
                 text: "Hello 👋 World".to_string(),
@@ -766,6 +918,7 @@ mod tests {
                 raw_text: None,
                 text_origin: TextOrigin::default(),
                 corrector_name: None,
+                words: vec![],
             },
             DaemonEvent::TranscriptionDropped {
                 text: "test".to_string(),
@@ -806,6 +959,7 @@ mod tests {
             is_speech: false,
             buffer_used: 0,
             buffer_capacity: 0,
+            raw_level: None,
         };
         let json = event.to_json().expect("should serialize");
         let deserialized = DaemonEvent::from_json(&json).expect("should deserialize");
@@ -817,6 +971,7 @@ mod tests {
                 is_speech,
                 buffer_used,
                 buffer_capacity,
+                raw_level,
             } => {
                 assert!(
                     (level - 0.123456789_f32).abs() < 1e-6,
@@ -829,11 +984,33 @@ mod tests {
                 assert!(!is_speech);
                 assert_eq!(buffer_used, 0);
                 assert_eq!(buffer_capacity, 0);
+                assert!(raw_level.is_none());
             }
             _ => panic!("Expected Level event"),
         }
     }
 
+    #[test]
+    fn test_level_raw_level_optional() {
+        let event = DaemonEvent::Level {
+            level: 0.01,
+            threshold: 0.02,
+            is_speech: false,
+            buffer_used: 0,
+            buffer_capacity: 0,
+            raw_level: Some(0.05),
+        };
+        let json = event.to_json().expect("should serialize");
+        assert!(json.contains(r#""raw_level":0.05"#), "got: {json}");
+        assert_eq!(DaemonEvent::from_json(&json).unwrap(), event);
+
+        let old = r#"{"type":"level","level":0.1,"threshold":0.02,"is_speech":true,"buffer_used":0,"buffer_capacity":0}"#;
+        match DaemonEvent::from_json(old).unwrap() {
+            DaemonEvent::Level { raw_level, .. } => assert!(raw_level.is_none()),
+            _ => panic!("Expected Level event"),
+        }
+    }
+
     // New command variant tests
 
     #[test]
@@ -997,6 +1174,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_daemon_event_audio_device_changed_json_roundtrip() {
+        let event = DaemonEvent::AudioDeviceChanged {
+            device: "default".to_string(),
+            previous: "USB Headset".to_string(),
+            reason: "disconnected".to_string(),
+        };
+        let json = event.to_json().expect("should serialize");
+        let deserialized = DaemonEvent::from_json(&json).expect("should deserialize");
+        assert_eq!(event, deserialized);
+        assert_eq!(
+            json,
+            r#"{"type":"audio_device_changed","device":"default","previous":"USB Headset","reason":"disconnected"}"#
+        );
+    }
+
+    #[test]
+    fn test_daemon_event_partial_transcription_json_roundtrip() {
+        let event = DaemonEvent::PartialTranscription {
+            text: "turn on the".to_string(),
+        };
+        let json = event.to_json().expect("should serialize");
+        let deserialized = DaemonEvent::from_json(&json).expect("should deserialize");
+        assert_eq!(event, deserialized);
+        assert_eq!(
+            json,
+            r#"{"type":"partial_transcription","text":"turn on the"}"#
+        );
+    }
+
     #[test]
     fn test_daemon_event_daemon_info_json_roundtrip() {
         let event = DaemonEvent::DaemonInfo {
@@ -1012,6 +1219,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_daemon_event_language_switched_json_roundtrip() {
+        let event = DaemonEvent::LanguageSwitched {
+            from: "de".to_string(),
+            to: "en".to_string(),
+            probability: 0.5,
+        };
+        let json = event.to_json().expect("should serialize");
+        let deserialized = DaemonEvent::from_json(&json).expect("should deserialize");
+        assert_eq!(event, deserialized);
+        assert_eq!(
+            json,
+            r#"{"type":"language_switched","from":"de","to":"en","probability":0.5}"#
+        );
+    }
+
+    #[test]
+    fn test_daemon_event_language_routed_json_roundtrip() {
+        let event = DaemonEvent::LanguageRouted {
+            language: "de".to_string(),
+            model: "large-v3-turbo-q5_0".to_string(),
+        };
+        let json = event.to_json().expect("should serialize");
+        let deserialized = DaemonEvent::from_json(&json).expect("should deserialize");
+        assert_eq!(event, deserialized);
+        assert_eq!(
+            json,
+            r#"{"type":"language_routed","language":"de","model":"large-v3-turbo-q5_0"}"#
+        );
+    }
+
     #[test]
     fn test_daemon_event_transcription_dropped_json_roundtrip() {
         let event = DaemonEvent::TranscriptionDropped {
@@ -1193,6 +1431,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::Transcription,
             corrector_name: None,
+            words: vec![],
         };
         let json = event.to_json().expect("should serialize");
         assert!(!json.contains("raw_text"), "raw_text should be omitted");
@@ -1215,6 +1454,7 @@ mod tests {
             raw_text: Some("the quik brown fox".to_string()),
             text_origin: TextOrigin::Corrected,
             corrector_name: None,
+            words: vec![],
         };
         let json = event.to_json().expect("should serialize");
         assert!(json.contains(r#""raw_text":"the quik brown fox""#));
@@ -1234,6 +1474,7 @@ mod tests {
             raw_text: Some("period".to_string()),
             text_origin: TextOrigin::VoiceCommand,
             corrector_name: None,
+            words: vec![],
         };
         let json = event.to_json().expect("should serialize");
         assert!(json.contains(r#""raw_text":"period""#));
@@ -1275,6 +1516,7 @@ mod tests {
             raw_text: Some("the quik brown fox".to_string()),
             text_origin: TextOrigin::Corrected,
             corrector_name: Some("T5".to_string()),
+            words: vec![],
         };
         let json = event.to_json().expect("should serialize");
         assert!(json.contains(r#""corrector_name":"T5""#));
@@ -1293,6 +1535,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::Transcription,
             corrector_name: None,
+            words: vec![],
         };
         let json = event.to_json().expect("should serialize");
         assert!(
@@ -2,17 +2,30 @@
 
 use crate::error::{Result, VoicshError};
 use crate::ipc::protocol::{Command, DaemonEvent, Response};
+use crate::transcript::TranscriptFormat;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
-use tokio::sync::Semaphore;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 /// Maximum number of bytes accepted in a single IPC command line (64 KiB).
 /// Prevents a malicious local process from causing unbounded memory growth.
 const MAX_COMMAND_BYTES: u64 = 64 * 1024;
 
+/// Maximum number of audio bytes accepted after a `TranscribeStream` command (32 MiB).
+/// Roughly 17 minutes of 16kHz mono 16-bit PCM; compressed formats go much further.
+const MAX_AUDIO_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Audio bytes buffered across all connections at once (128 MiB).
+/// Uploads are charged as they are read, so concurrent clients cannot make
+/// the daemon hold more than this in total.
+pub(crate) const AUDIO_BUDGET_BYTES: usize = 128 * 1024 * 1024;
+
+/// Socket read size while receiving audio.
+const AUDIO_READ_CHUNK: usize = 64 * 1024;
+
 /// Maximum number of concurrent IPC connections.
 /// Prevents a local DoS via unbounded task spawning.
 const MAX_CONNECTIONS: usize = 32;
@@ -28,6 +41,19 @@ pub trait CommandHandler: Send + Sync {
     fn subscribe(&self) -> Option<tokio::sync::broadcast::Receiver<DaemonEvent>> {
         None
     }
+
+    /// Handle audio bytes streamed after a `TranscribeStream` command.
+    /// Returns an error response if audio streaming is not supported (default).
+    async fn handle_audio(
+        &self,
+        _format: Option<String>,
+        _output_format: TranscriptFormat,
+        _audio: Vec<u8>,
+    ) -> Response {
+        Response::Error {
+            message: "Audio streaming not supported".to_string(),
+        }
+    }
 }
 
 /// State for managing server shutdown.
@@ -57,6 +83,7 @@ pub struct IpcServer {
     socket_path: PathBuf,
     state: ServerState,
     connection_semaphore: Arc<Semaphore>,
+    audio_budget: Arc<Semaphore>,
 }
 
 impl IpcServer {
@@ -66,9 +93,16 @@ impl IpcServer {
             socket_path,
             state: ServerState::new(),
             connection_semaphore: Arc::new(Semaphore::new(MAX_CONNECTIONS)),
+            audio_budget: Arc::new(Semaphore::new(AUDIO_BUDGET_BYTES)),
         })
     }
 
+    /// Budget for audio buffered by uploads, shared with the daemon's other
+    /// upload endpoints.
+    pub fn audio_budget(&self) -> Arc<Semaphore> {
+        Arc::clone(&self.audio_budget)
+    }
+
     /// Get the socket path this server is using.
     pub fn socket_path(&self) -> &Path {
         &self.socket_path
@@ -149,8 +183,9 @@ impl IpcServer {
                         }
                     };
                     let handler = Arc::clone(&handler);
+                    let audio_budget = Arc::clone(&self.audio_budget);
                     tokio::spawn(async move {
-                        if let Err(e) = handle_client(stream, handler).await {
+                        if let Err(e) = handle_client(stream, handler, audio_budget).await {
                             eprintln!("Error handling client: {}", e);
                         }
                         // Permit is released here when dropped at end of task.
@@ -191,7 +226,11 @@ impl IpcServer {
 }
 
 /// Handle a single client connection.
-async fn handle_client<H>(stream: UnixStream, handler: Arc<H>) -> Result<()>
+async fn handle_client<H>(
+    stream: UnixStream,
+    handler: Arc<H>,
+    audio_budget: Arc<Semaphore>,
+) -> Result<()>
 where
     H: CommandHandler,
 {
@@ -230,8 +269,22 @@ where
         return handle_follow_client(writer, handler).await;
     }
 
-    // Handle regular command
-    let response = handler.handle(command).await;
+    let response = match command {
+        // Audio bytes follow the command line until the client shuts down its write half
+        Command::TranscribeStream {
+            format,
+            output_format,
+        } => {
+            match read_audio_payload(reader, &audio_budget).await {
+                // The budget stays charged until the audio has been transcribed
+                Ok((audio, _charge)) => handler.handle_audio(format, output_format, audio).await,
+                // Rejected uploads are answered before the connection closes
+                Err(VoicshError::IpcProtocol { message }) => Response::Error { message },
+                Err(e) => return Err(e),
+            }
+        }
+        command => handler.handle(command).await,
+    };
 
     // Send response
     let response_json = response.to_json().map_err(|e| VoicshError::IpcProtocol {
@@ -281,6 +334,71 @@ async fn write_event(writer: &mut tokio::net::unix::OwnedWriteHalf, event: &Daem
     true
 }
 
+/// Read the raw audio that follows a `TranscribeStream` command line.
+///
+/// Bytes already buffered while reading the command line are kept; the rest
+/// is read from the socket until EOF, capped at [`MAX_AUDIO_BYTES`]. Every
+/// byte is charged against the shared `budget`; the returned permit holds
+/// that charge until dropped.
+async fn read_audio_payload(
+    reader: BufReader<tokio::io::Take<tokio::net::unix::OwnedReadHalf>>,
+    budget: &Arc<Semaphore>,
+) -> Result<(Vec<u8>, Option<OwnedSemaphorePermit>)> {
+    let mut audio = reader.buffer().to_vec();
+    let mut charge = None;
+    charge_audio_budget(budget, &mut charge, audio.len())?;
+
+    let mut socket = reader.into_inner().into_inner();
+    let mut chunk = vec![0u8; AUDIO_READ_CHUNK];
+    loop {
+        let n = tokio::io::AsyncReadExt::read(&mut socket, &mut chunk)
+            .await
+            .map_err(|e| VoicshError::IpcConnection {
+                message: format!("Failed to read audio from client: {}", e),
+            })?;
+        if n == 0 {
+            break;
+        }
+        if (audio.len() + n) as u64 > MAX_AUDIO_BYTES {
+            return Err(VoicshError::IpcProtocol {
+                message: format!(
+                    "Audio upload exceeds the {} MB limit",
+                    MAX_AUDIO_BYTES / (1024 * 1024)
+                ),
+            });
+        }
+        charge_audio_budget(budget, &mut charge, n)?;
+        audio.extend_from_slice(&chunk[..n]);
+    }
+    Ok((audio, charge))
+}
+
+/// Charge `bytes` more against the shared audio budget, failing when other
+/// uploads already use it up.
+pub(crate) fn charge_audio_budget(
+    budget: &Arc<Semaphore>,
+    charge: &mut Option<OwnedSemaphorePermit>,
+    bytes: usize,
+) -> Result<()> {
+    if bytes == 0 {
+        return Ok(());
+    }
+    let permit = u32::try_from(bytes)
+        .ok()
+        .and_then(|n| Arc::clone(budget).try_acquire_many_owned(n).ok())
+        .ok_or_else(|| VoicshError::IpcProtocol {
+            message: format!(
+                "Daemon busy: other uploads already buffer the maximum ({} MB)",
+                AUDIO_BUDGET_BYTES / (1024 * 1024)
+            ),
+        })?;
+    match charge {
+        Some(charge) => charge.merge(permit),
+        None => *charge = Some(permit),
+    }
+    Ok(())
+}
+
 /// Handle a follow client: subscribe to events and stream them as newline-delimited JSON.
 async fn handle_follow_client<H>(
     mut writer: tokio::net::unix::OwnedWriteHalf,
@@ -454,6 +572,23 @@ mod tests {
                     enabled: false,
                     backend: Some("symspell".to_string()),
                 },
+                Command::ReloadConfig => Response::ConfigReloaded {
+                    changed: vec![],
+                    restart_required: vec![],
+                    model_reloaded: false,
+                },
+                Command::GetConfig { key } => Response::ConfigValue {
+                    key,
+                    value: serde_json::Value::Null,
+                },
+                Command::SetConfig { key, .. } => Response::Ok {
+                    message: format!("{key} updated"),
+                },
+                Command::TranscribeFile { .. } | Command::TranscribeStream { .. } => {
+                    Response::Transcription {
+                        text: "mock file transcription".to_string(),
+                    }
+                }
             }
         }
     }
@@ -774,6 +909,83 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_transcribe_stream_unsupported_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket_path = temp_dir.path().join("test.sock");
+
+        let server_socket_path = socket_path.clone();
+        let _server_handle = tokio::spawn(async move {
+            let server = IpcServer::new(server_socket_path).unwrap();
+            server.start(MockCommandHandler).await
+        });
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        let mut stream = UnixStream::connect(&socket_path).await.unwrap();
+        let command = Command::TranscribeStream {
+            format: None,
+            output_format: TranscriptFormat::Txt,
+        };
+        let command_json = format!("{}\n", command.to_json().unwrap());
+        stream.write_all(command_json.as_bytes()).await.unwrap();
+        stream.write_all(b"RIFF....").await.unwrap();
+        stream.shutdown().await.unwrap();
+
+        let mut response_data = Vec::new();
+        stream.read_to_end(&mut response_data).await.unwrap();
+        let response =
+            Response::from_json(String::from_utf8(response_data).unwrap().trim()).unwrap();
+        match response {
+            Response::Error { message } => assert!(message.contains("not supported")),
+            other => panic!("Expected Error response, got: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_upload_gets_error_response() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket_path = temp_dir.path().join("test.sock");
+
+        let server_socket_path = socket_path.clone();
+        let _server_handle = tokio::spawn(async move {
+            let server = IpcServer::new(server_socket_path).unwrap();
+            server.start(MockCommandHandler).await
+        });
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        let audio = vec![0u8; MAX_AUDIO_BYTES as usize + 1];
+        let response = crate::ipc::client::transcribe_stream(
+            &socket_path,
+            None,
+            TranscriptFormat::Txt,
+            &audio,
+        )
+        .await
+        .unwrap();
+        match response {
+            Response::Error { message } => assert!(message.contains("32 MB"), "{message}"),
+            other => panic!("Expected Error response, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_audio_budget_is_shared_between_uploads() {
+        let budget = Arc::new(Semaphore::new(10));
+        let mut first = None;
+        charge_audio_budget(&budget, &mut first, 4).unwrap();
+        charge_audio_budget(&budget, &mut first, 2).unwrap();
+        assert_eq!(budget.available_permits(), 4);
+
+        let mut second = None;
+        let err = charge_audio_budget(&budget, &mut second, 6).unwrap_err();
+        assert!(err.to_string().contains("other uploads"), "{err}");
+
+        // Finishing the first upload frees its share
+        drop(first);
+        charge_audio_budget(&budget, &mut second, 6).unwrap();
+        assert_eq!(budget.available_permits(), 4);
+    }
+
     #[tokio::test]
     async fn test_oversized_command_is_rejected() {
         let temp_dir = TempDir::new().unwrap();
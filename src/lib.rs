@@ -13,6 +13,8 @@ pub mod benchmark;
 #[cfg(feature = "cli")]
 pub mod cli;
 pub mod config;
+pub mod config_migration;
+pub mod config_schema;
 pub mod correction;
 #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
 pub mod daemon;
@@ -31,10 +33,12 @@ pub mod ipc;
 pub mod models;
 pub mod output;
 pub mod pipeline;
+pub mod replay;
 pub mod stt;
 pub mod sys;
 #[cfg(feature = "cli")]
 pub mod systemd;
+pub mod transcript;
 
 // L4 composition root - needs everything
 #[cfg(all(feature = "cpal-audio", feature = "model-download", feature = "cli"))]
@@ -9,7 +9,7 @@ use voicsh::app::{RecordConfig, run_record_command};
 use voicsh::audio::capture::list_devices;
 #[cfg(feature = "model-download")]
 use voicsh::cli::ModelsAction;
-use voicsh::cli::{Cli, ConfigAction, DebugAction};
+use voicsh::cli::{Cli, ConfigAction, DebugAction, SessionsAction};
 use voicsh::config::Config;
 #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
 use voicsh::daemon::run_daemon;
@@ -50,7 +50,7 @@ async fn main() -> Result<()> {
                 })
                 .await?;
             } else {
-                // Pipe mode: stdin has WAV data
+                // Pipe mode: stdin has encoded audio or raw PCM
                 voicsh::app::run_pipe_command(
                     config,
                     cli.model,
@@ -59,6 +59,8 @@ async fn main() -> Result<()> {
                     cli.verbose,
                     cli.no_download,
                     cli.buffer,
+                    cli.output_format,
+                    cli.raw,
                 )
                 .await?;
             }
@@ -73,17 +75,98 @@ async fn main() -> Result<()> {
         Some(voicsh::cli::Commands::Devices) => {
             list_audio_devices()?;
         }
+        #[cfg(feature = "cpal-audio")]
+        Some(voicsh::cli::Commands::Calibrate {
+            silence_secs,
+            speech_secs,
+            dry_run,
+        }) => {
+            handle_calibrate_command(
+                cli.device,
+                silence_secs,
+                speech_secs,
+                dry_run,
+                cli.config.as_deref(),
+            )?;
+        }
         #[cfg(feature = "model-download")]
         Some(voicsh::cli::Commands::Models { action }) => {
             handle_models_command(action, cli.config.as_deref()).await?;
         }
+        Some(voicsh::cli::Commands::Sessions { action }) => {
+            handle_sessions_command(action, cli.config.as_deref())?;
+        }
         Some(voicsh::cli::Commands::Check) => {
             check_dependencies();
         }
         #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
-        Some(voicsh::cli::Commands::Daemon { socket }) => {
+        Some(voicsh::cli::Commands::Daemon {
+            socket,
+            watch_config,
+            wyoming,
+        }) => {
+            let config = load_config(cli.config.as_deref())?;
+            let config_path = cli.config.clone().unwrap_or_else(Config::default_path);
+            run_daemon(
+                config,
+                socket,
+                cli.quiet,
+                cli.verbose,
+                cli.no_download,
+                Some(config_path),
+                watch_config,
+                wyoming,
+            )
+            .await?;
+        }
+        #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+        Some(voicsh::cli::Commands::Transcribe {
+            file,
+            format,
+            output_format,
+            socket,
+            no_daemon,
+        }) => {
             let config = load_config(cli.config.as_deref())?;
-            run_daemon(config, socket, cli.quiet, cli.verbose, cli.no_download).await?;
+            let overrides = TranscribeOverrides {
+                model: cli.model,
+                language: cli.language,
+                quiet: cli.quiet,
+                verbosity: cli.verbose,
+                no_download: cli.no_download,
+                buffer_secs: cli.buffer,
+            };
+            let request = TranscribeRequest {
+                file,
+                format,
+                output_format,
+                socket,
+                no_daemon,
+            };
+            handle_transcribe_command(config, request, overrides).await?;
+        }
+        #[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+        Some(voicsh::cli::Commands::Replay {
+            target,
+            model,
+            speed,
+        }) => {
+            let config = load_config(cli.config.as_deref())?;
+            voicsh::app::run_replay_command(voicsh::app::ReplayConfig {
+                config,
+                target,
+                speed,
+                model: model.or(cli.model),
+                language: cli.language,
+                quiet: cli.quiet,
+                verbosity: cli.verbose,
+                no_download: cli.no_download,
+                buffer_secs: cli.buffer,
+                chunk_secs: cli.chunk_size,
+                pre_speech_ms: cli.pre_speech,
+                post_speech_ms: cli.post_speech,
+            })
+            .await?;
         }
         Some(voicsh::cli::Commands::Start { socket }) => {
             handle_ipc_command(socket, Command::Start).await?;
@@ -97,6 +180,9 @@ async fn main() -> Result<()> {
         Some(voicsh::cli::Commands::Status { socket }) => {
             handle_ipc_command(socket, Command::Status).await?;
         }
+        Some(voicsh::cli::Commands::Reload { socket }) => {
+            handle_ipc_command(socket, Command::ReloadConfig).await?;
+        }
         Some(voicsh::cli::Commands::Follow { socket }) => {
             handle_follow(socket).await?;
         }
@@ -112,6 +198,33 @@ async fn main() -> Result<()> {
         Some(voicsh::cli::Commands::Debug { action }) => {
             handle_debug_command(action);
         }
+        Some(voicsh::cli::Commands::Config {
+            action:
+                ConfigAction::Get {
+                    key,
+                    live: true,
+                    socket,
+                },
+        }) => {
+            handle_ipc_command(socket, Command::GetConfig { key }).await?;
+        }
+        Some(voicsh::cli::Commands::Config {
+            action:
+                ConfigAction::Set {
+                    key,
+                    value,
+                    live: true,
+                    no_save,
+                    socket,
+                },
+        }) => {
+            let command = Command::SetConfig {
+                key,
+                value,
+                persist: !no_save,
+            };
+            handle_ipc_command(socket, command).await?;
+        }
         Some(voicsh::cli::Commands::Config { action }) => {
             handle_config_command(action, cli.config.as_deref())?;
         }
@@ -208,6 +321,208 @@ fn list_audio_devices() -> Result<()> {
     Ok(())
 }
 
+/// Record background noise and a spoken sentence, then store a VAD threshold for the device.
+#[cfg(feature = "cpal-audio")]
+fn handle_calibrate_command(
+    device: Option<String>,
+    silence_secs: u32,
+    speech_secs: u32,
+    dry_run: bool,
+    custom_path: Option<&std::path::Path>,
+) -> Result<()> {
+    use std::time::Duration;
+    use voicsh::AudioSource;
+    use voicsh::audio::calibrate::{CalibrationWarning, SAMPLE_SENTENCE, analyze, record};
+    use voicsh::audio::capture::CpalAudioSource;
+
+    let config_path = custom_path
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_else(Config::default_path);
+    let mut config = Config::load_or_default(&config_path)?;
+    if let Some(d) = device {
+        config.audio.device = Some(d);
+    }
+    let device_key = config.audio.device_key().to_string();
+
+    let rate = voicsh::defaults::SAMPLE_RATE as usize;
+    let timeout = |secs: u32| Duration::from_secs(u64::from(secs) + 3);
+    let mut source = CpalAudioSource::new(config.audio.device.as_deref())?;
+    source.start()?;
+    // Discard the first moments of the stream, which may carry start-up clicks
+    record(&mut source, rate / 4, timeout(0))?;
+
+    println!("Calibrating input device '{device_key}'.");
+    println!("Stay quiet for {silence_secs} seconds...");
+    let silence = record(
+        &mut source,
+        rate * silence_secs as usize,
+        timeout(silence_secs),
+    )?;
+
+    println!();
+    println!("Now read this aloud at your normal volume:");
+    println!();
+    println!("    {}", SAMPLE_SENTENCE.bold());
+    println!();
+    let speech = record(
+        &mut source,
+        rate * speech_secs as usize,
+        timeout(speech_secs),
+    )?;
+    source.stop()?;
+
+    let calibration = analyze(&silence, &speech);
+    println!("Noise floor:  {:.4}", calibration.noise_floor);
+    println!("Speech level: {:.4}", calibration.speech_level);
+    println!("Peak:         {:.0}%", calibration.peak * 100.0);
+    println!(
+        "Threshold:    {:.4} (was {:.4})",
+        calibration.threshold,
+        config.audio.effective_vad_threshold()
+    );
+    for warning in &calibration.warnings {
+        eprintln!("{} {}", "Warning:".yellow(), warning);
+    }
+    if calibration.warnings.contains(&CalibrationWarning::Muted) {
+        anyhow::bail!("No signal from '{device_key}'; the config was not changed");
+    }
+
+    if dry_run {
+        println!("Dry run: the config was not changed.");
+        return Ok(());
+    }
+    Config::update_device_threshold(&config_path, &device_key, calibration.threshold)?;
+    println!(
+        "Saved audio.device_thresholds.\"{}\" = {} to {}",
+        device_key,
+        calibration.threshold,
+        config_path.display()
+    );
+    println!("Run `voicsh reload` to apply it to a running daemon.");
+
+    Ok(())
+}
+
+/// Handle archived session commands.
+fn handle_sessions_command(
+    action: SessionsAction,
+    custom_path: Option<&std::path::Path>,
+) -> Result<()> {
+    use voicsh::pipeline::archive::{find_session, list_sessions, purge};
+
+    let config_path = custom_path
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_else(Config::default_path);
+    let config = Config::load_or_default(&config_path)?;
+    let root = config.debug.archive_path();
+
+    match action {
+        SessionsAction::List => {
+            let sessions = list_sessions(&root)?;
+            if sessions.is_empty() {
+                println!("No archived sessions in {}", root.display());
+                if !config.debug.archive_audio {
+                    println!("Enable with: voicsh config set debug.archive_audio true");
+                }
+                return Ok(());
+            }
+            println!(
+                "{:<18} {:>8} {:>6} {:>8}  TEXT",
+                "ID", "AUDIO", "CHUNKS", "SIZE"
+            );
+            for session in &sessions {
+                let (duration, chunks, text) = match &session.manifest {
+                    Some(m) => (
+                        format!("{:.1}s", m.duration_ms as f64 / 1000.0),
+                        m.chunks.len().to_string(),
+                        m.text(),
+                    ),
+                    None => (
+                        "?".to_string(),
+                        "?".to_string(),
+                        "(no manifest)".to_string(),
+                    ),
+                };
+                let mut preview: String = text.chars().take(50).collect();
+                if preview.len() < text.len() {
+                    preview.push('…');
+                }
+                println!(
+                    "{:<18} {:>8} {:>6} {:>6.1}MB  {}",
+                    session.id,
+                    duration,
+                    chunks,
+                    session.bytes as f64 / (1024.0 * 1024.0),
+                    preview
+                );
+            }
+            println!();
+            println!("{} session(s) in {}", sessions.len(), root.display());
+        }
+        SessionsAction::Show { id } => {
+            let session = find_session(&root, &id)?;
+            println!("Session {}", session.id.bold());
+            println!("  Path:     {}", session.path.display());
+            let Some(m) = session.manifest else {
+                println!("  (manifest missing or unreadable)");
+                return Ok(());
+            };
+            println!("  Model:    {}", m.model);
+            println!(
+                "  VAD:      {} (threshold {}, silence {}ms)",
+                m.vad_backend, m.vad_threshold, m.silence_duration_ms
+            );
+            println!(
+                "  Audio:    {:.1}s at {} Hz",
+                m.duration_ms as f64 / 1000.0,
+                m.sample_rate
+            );
+            println!();
+            println!("Speech ({} segment(s)):", m.speech.len());
+            for segment in &m.speech {
+                println!(
+                    "  {:>8.2}s - {:>8.2}s",
+                    segment.start_ms as f64 / 1000.0,
+                    segment.end_ms as f64 / 1000.0
+                );
+            }
+            println!();
+            println!("Chunks ({}):", m.chunks.len());
+            for chunk in &m.chunks {
+                let timing = chunk
+                    .transcribe_ms
+                    .map_or_else(|| "-".to_string(), |ms| format!("{ms}ms"));
+                let detail = match (&chunk.error, chunk.text.is_empty()) {
+                    (Some(error), _) => error.clone(),
+                    (None, false) => format!("\"{}\"", chunk.text),
+                    (None, true) => String::new(),
+                };
+                println!(
+                    "  #{:<4} {:>8.2}s +{:>5}ms  {:<11} {:>7}  {}  {}",
+                    chunk.sequence,
+                    chunk.start_ms as f64 / 1000.0,
+                    chunk.duration_ms,
+                    chunk.outcome.to_string(),
+                    timing,
+                    chunk.file,
+                    detail
+                );
+            }
+            println!();
+            println!("Text: {}", m.text());
+        }
+        SessionsAction::Purge { keep } => {
+            let removed = purge(&root, keep)?;
+            println!(
+                "Removed {} session(s) from {}",
+                removed.len(),
+                root.display()
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Handle model management commands.
 #[cfg(feature = "model-download")]
 async fn handle_models_command(
@@ -251,7 +566,11 @@ async fn handle_models_command(
             }
         }
         ModelsAction::Install { name } => {
-            let path = download_model(&name, true).await?;
+            let path = if voicsh::models::vad_catalog::get_vad_model(&name).is_some() {
+                voicsh::models::download::install_vad_model(&name, true).await?
+            } else {
+                download_model(&name, true).await?
+            };
             println!("Model '{}' installed successfully", name);
             println!("Location: {}", path.display());
         }
@@ -292,7 +611,7 @@ fn handle_config_command(
         .unwrap_or_else(Config::default_path);
 
     match action {
-        ConfigAction::Get { key } => {
+        ConfigAction::Get { key, .. } => {
             let config = Config::load_or_default(&config_path)?.with_env_overrides();
             match config.get_value_by_path(&key) {
                 Ok(value) => println!("{}", value),
@@ -302,7 +621,7 @@ fn handle_config_command(
                 }
             }
         }
-        ConfigAction::Set { key, value } => {
+        ConfigAction::Set { key, value, .. } => {
             Config::set_value_by_path(&config_path, &key, &value)?;
             println!(
                 "Set {} = {} (saved to {})",
@@ -377,6 +696,75 @@ fn handle_config_command(
         ConfigAction::Dump => {
             print!("{}", Config::dump_template());
         }
+        ConfigAction::Validate => {
+            if !config_path.exists() {
+                println!(
+                    "No config file at {} (using defaults)",
+                    config_path.display()
+                );
+                return Ok(());
+            }
+            let diagnostics = voicsh::config_schema::check_file(&config_path)?;
+            if diagnostics.is_empty() {
+                println!("{} {}", config_path.display(), "is valid".green());
+                return Ok(());
+            }
+            for d in &diagnostics {
+                let line = format!("{}: {}", config_path.display(), d);
+                match d.severity {
+                    voicsh::config_schema::Severity::Error => eprintln!("{}", line.red()),
+                    voicsh::config_schema::Severity::Warning => eprintln!("{}", line.yellow()),
+                }
+            }
+            std::process::exit(1);
+        }
+        ConfigAction::Migrate { dry_run } => {
+            let report = voicsh::config_migration::migrate_file(&config_path, dry_run)?;
+            let migrated = &report.migrated;
+            if migrated.is_noop() {
+                println!(
+                    "{} is already at config version {}",
+                    config_path.display(),
+                    migrated.to
+                );
+                return Ok(());
+            }
+            for line in report.diff.lines() {
+                match line.chars().next() {
+                    Some('-') => println!("{}", line.red()),
+                    Some('+') => println!("{}", line.green()),
+                    _ => println!("{}", line.dimmed()),
+                }
+            }
+            println!();
+            for description in &migrated.applied {
+                println!("  {} {}", "●".green(), description);
+            }
+            match &report.backup {
+                Some(backup) => println!(
+                    "Migrated {} from version {} to {} (backup: {})",
+                    config_path.display(),
+                    migrated.from,
+                    migrated.to,
+                    backup.display()
+                ),
+                None => println!(
+                    "Dry run: {} would be migrated from version {} to {}",
+                    config_path.display(),
+                    migrated.from,
+                    migrated.to
+                ),
+            }
+        }
+        ConfigAction::Schema => {
+            let schema = voicsh::config_schema::json_schema();
+            let json = serde_json::to_string_pretty(&schema).map_err(|e| {
+                voicsh::error::VoicshError::ConfigSerialize {
+                    message: format!("JSON Schema serialization failed: {e}"),
+                }
+            })?;
+            println!("{}", json);
+        }
     }
     Ok(())
 }
@@ -511,8 +899,17 @@ async fn handle_ipc_command(socket: Option<std::path::PathBuf>, command: Command
                     if recording { "yes" } else { "no" }
                 );
                 // Model
-                if model_loaded && let Some(name) = model_name {
-                    println!("  {:<12} {}", "Model:".dimmed(), name);
+                if let Some(name) = model_name {
+                    if model_loaded {
+                        println!("  {:<12} {}", "Model:".dimmed(), name);
+                    } else {
+                        println!(
+                            "  {:<12} {} {}",
+                            "Model:".dimmed(),
+                            name,
+                            "(not loaded: unloaded while idle or still loading)".dimmed()
+                        );
+                    }
                 }
                 // Language
                 if let Some(lang) = language {
@@ -602,6 +999,30 @@ async fn handle_ipc_command(socket: Option<std::path::PathBuf>, command: Command
                     }
                 }
             }
+            Response::ConfigReloaded {
+                changed,
+                restart_required,
+                model_reloaded,
+            } => {
+                if changed.is_empty() && restart_required.is_empty() {
+                    println!("{}", "Config reloaded (no changes)".green());
+                } else {
+                    println!("{}", "Config reloaded:".green());
+                    for key in &changed {
+                        println!("  {} {}", "●".green(), key);
+                    }
+                    if model_reloaded {
+                        println!("  {}", "Model reloaded".dimmed());
+                    }
+                    for key in &restart_required {
+                        println!("  {} {} (restart required)", "○".yellow(), key);
+                    }
+                }
+            }
+            Response::ConfigValue { value, .. } => match value {
+                serde_json::Value::String(s) => println!("{}", s),
+                other => println!("{}", other),
+            },
             Response::Error { message } => {
                 eprintln!("{}", format!("Error: {}", message).red());
                 std::process::exit(1);
@@ -620,6 +1041,111 @@ async fn handle_ipc_command(socket: Option<std::path::PathBuf>, command: Command
     Ok(())
 }
 
+/// Arguments of `voicsh transcribe`.
+#[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+struct TranscribeRequest {
+    file: std::path::PathBuf,
+    format: Option<String>,
+    output_format: voicsh::transcript::TranscriptFormat,
+    socket: Option<std::path::PathBuf>,
+    no_daemon: bool,
+}
+
+/// CLI flags that apply when `voicsh transcribe` runs in-process.
+#[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+struct TranscribeOverrides {
+    model: Option<String>,
+    language: Option<String>,
+    quiet: bool,
+    verbosity: u8,
+    no_download: bool,
+    buffer_secs: u64,
+}
+
+/// Transcribe a file (or stdin with "-") via the daemon, or in-process when
+/// no daemon is running.
+///
+/// `--model` and `--language` would be ignored by the daemon's loaded model,
+/// so either one also forces in-process transcription. `--buffer` only sizes
+/// the in-process chunk queue and does not change the transcript.
+#[cfg(all(feature = "cpal-audio", feature = "model-download"))]
+async fn handle_transcribe_command(
+    config: Config,
+    request: TranscribeRequest,
+    overrides: TranscribeOverrides,
+) -> Result<()> {
+    use std::io::Read;
+    use voicsh::app::print_transcript;
+    use voicsh::audio::decode;
+    use voicsh::ipc::client::{daemon_available, transcribe_file, transcribe_stream};
+
+    let TranscribeRequest {
+        file,
+        format,
+        output_format,
+        socket,
+        no_daemon,
+    } = request;
+
+    let from_stdin = file.as_os_str() == "-";
+    let stdin_bytes = if from_stdin {
+        let mut bytes = Vec::new();
+        std::io::stdin().read_to_end(&mut bytes)?;
+        Some(bytes)
+    } else {
+        None
+    };
+
+    let socket_path = socket.unwrap_or_else(IpcServer::default_socket_path);
+    let overridden = overrides.model.is_some() || overrides.language.is_some();
+    if !no_daemon && !overridden && daemon_available(&socket_path).await {
+        if overrides.verbosity >= 1 {
+            eprintln!(
+                "voicsh: transcribing via daemon at {}",
+                socket_path.display()
+            );
+        }
+        let response = match &stdin_bytes {
+            Some(bytes) => transcribe_stream(&socket_path, format, output_format, bytes).await?,
+            None => {
+                // The daemon may run in a different working directory
+                let path = std::fs::canonicalize(&file).map_err(|e| {
+                    anyhow::anyhow!("Failed to open audio file '{}': {}", file.display(), e)
+                })?;
+                transcribe_file(&socket_path, &path, format, output_format).await?
+            }
+        };
+        match response {
+            Response::Transcription { text } => print_transcript(&text),
+            Response::Error { message } => {
+                eprintln!("{}", format!("Error: {}", message).red());
+                std::process::exit(1);
+            }
+            other => anyhow::bail!("Unexpected response from daemon: {:?}", other),
+        }
+        return Ok(());
+    }
+
+    let audio_source = match stdin_bytes {
+        Some(bytes) => decode::open_bytes(bytes, format.as_deref())?,
+        None => decode::open_file(&file, format.as_deref())?,
+    };
+    let transcript = voicsh::app::run_transcribe_command(
+        config,
+        audio_source,
+        output_format,
+        overrides.model,
+        overrides.language,
+        overrides.quiet,
+        overrides.verbosity,
+        overrides.no_download,
+        overrides.buffer_secs,
+    )
+    .await?;
+    print_transcript(&transcript.unwrap_or_default());
+    Ok(())
+}
+
 /// Follow daemon events and render live output.
 async fn handle_follow(socket: Option<std::path::PathBuf>) -> Result<()> {
     let socket_path = socket.unwrap_or_else(IpcServer::default_socket_path);
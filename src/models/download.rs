@@ -76,6 +76,21 @@ pub fn is_model_installed(name: &str) -> bool {
     model_path(name).exists()
 }
 
+/// Get the full path for a neural VAD model file.
+///
+/// Stored under `vad/` so [`list_installed_models`] only lists Whisper models.
+pub fn vad_model_path(name: &str) -> PathBuf {
+    let filename = crate::models::vad_catalog::get_vad_model(name)
+        .map(|m| m.filename)
+        .unwrap_or("unknown.bin");
+    models_dir().join("vad").join(filename)
+}
+
+/// Check if a neural VAD model is installed.
+pub fn is_vad_model_installed(name: &str) -> bool {
+    vad_model_path(name).exists()
+}
+
 /// Core download: fetch url, save to path, verify sha1 and/or sha256 if non-empty.
 async fn download_to_path(
     name: &str,
@@ -255,6 +270,96 @@ pub async fn download_model(name: &str, progress: bool) -> Result<PathBuf> {
     Ok(path)
 }
 
+/// Download a neural VAD model on first use.
+///
+/// Only models in the VAD catalog with a pinned SHA-256 are downloaded;
+/// unpinned ones must be installed with [`install_vad_model`]. Returns
+/// immediately if the model is already installed.
+///
+/// # Errors
+///
+/// Returns an error if the model is not in the catalog or has no pinned
+/// checksum, the download fails, the SHA-256 checksum doesn't match or the
+/// file cannot be written.
+pub async fn download_vad_model(name: &str, progress: bool) -> Result<PathBuf> {
+    let info = find_vad_model(name)?;
+    let path = vad_model_path(name);
+    if path.exists() {
+        return Ok(path);
+    }
+    let sha256 = info.sha256.ok_or_else(|| VoicshError::ModelDownload {
+        message: format!(
+            "VAD model '{name}' has no pinned checksum and is not downloaded automatically; \
+             install it with `voicsh models install {name}`"
+        ),
+    })?;
+
+    download_to_path(
+        info.display_name,
+        &info.url(),
+        "", // no SHA-1 for VAD models; SHA-256 is used instead
+        sha256,
+        info.size_kb.div_ceil(1024),
+        &path,
+        progress,
+    )
+    .await?;
+
+    Ok(path)
+}
+
+/// Install a neural VAD model on explicit request.
+///
+/// Unlike [`download_vad_model`] this also fetches models whose SHA-256 is
+/// not pinned yet, after a warning that the file cannot be verified.
+///
+/// # Errors
+///
+/// Returns an error if the model is not in the catalog, the download fails,
+/// a pinned SHA-256 checksum doesn't match or the file cannot be written.
+pub async fn install_vad_model(name: &str, progress: bool) -> Result<PathBuf> {
+    let info = find_vad_model(name)?;
+    let path = vad_model_path(name);
+    if path.exists() {
+        return Ok(path);
+    }
+    if info.sha256.is_none() {
+        eprintln!(
+            "voicsh: WARNING: no SHA-256 is pinned for VAD model '{name}'; \
+             the download from {} is NOT verified.",
+            info.url()
+        );
+    }
+
+    download_to_path(
+        info.display_name,
+        &info.url(),
+        "", // no SHA-1 for VAD models; SHA-256 is used instead
+        info.sha256.unwrap_or(""),
+        info.size_kb.div_ceil(1024),
+        &path,
+        progress,
+    )
+    .await?;
+
+    Ok(path)
+}
+
+/// Look up a VAD model for download, listing the catalog when it is unknown.
+fn find_vad_model(name: &str) -> Result<&'static crate::models::vad_catalog::VadModelInfo> {
+    crate::models::vad_catalog::get_vad_model(name).ok_or_else(|| VoicshError::ModelDownload {
+        message: format!(
+            "VAD model '{}' is not in the catalog. Available: {}",
+            name,
+            crate::models::vad_catalog::list_vad_models()
+                .iter()
+                .map(|m| m.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    })
+}
+
 /// Download a SymSpell frequency dictionary.
 ///
 /// Returns the path to the installed dictionary.
@@ -399,6 +504,22 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "model-download")]
+    #[tokio::test]
+    async fn test_download_vad_model_refuses_unpinned_model() {
+        let unpinned = crate::models::vad_catalog::list_vad_models()
+            .iter()
+            .find(|m| m.sha256.is_none() && !is_vad_model_installed(m.name));
+        let Some(model) = unpinned else {
+            return;
+        };
+        let msg = download_vad_model(model.name, false)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(msg.contains("voicsh models install"), "got: {msg}");
+    }
+
     #[test]
     fn test_models_dir_is_valid_path() {
         let dir = models_dir();
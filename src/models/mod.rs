@@ -5,3 +5,4 @@ pub mod correction_catalog;
 pub mod download;
 #[cfg(feature = "model-download")]
 pub mod remote;
+pub mod vad_catalog;
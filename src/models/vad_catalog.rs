@@ -0,0 +1,109 @@
+//! Catalog of neural voice activity detection models.
+
+use crate::defaults;
+
+/// HuggingFace repository with Silero VAD weights converted for whisper.cpp.
+pub const VAD_MODEL_REPO: &str = "ggml-org/whisper-vad";
+
+/// Metadata for a neural VAD model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VadModelInfo {
+    /// Short name used in config (e.g. "silero-v5.1.2").
+    pub name: &'static str,
+    /// Human-readable display name.
+    pub display_name: &'static str,
+    /// Approximate download size in KB.
+    pub size_kb: u32,
+    /// Model filename within [`VAD_MODEL_REPO`].
+    pub filename: &'static str,
+    /// Short description.
+    pub description: &'static str,
+    /// SHA-256 checksum of the model file.
+    ///
+    /// `None` while the upstream hash is not pinned: such a model is never
+    /// downloaded automatically, only by an explicit `voicsh models install`.
+    pub sha256: Option<&'static str>,
+}
+
+impl VadModelInfo {
+    /// Full download URL within [`VAD_MODEL_REPO`].
+    pub fn url(&self) -> String {
+        format!(
+            "https://huggingface.co/{VAD_MODEL_REPO}/resolve/main/{}",
+            self.filename
+        )
+    }
+}
+
+/// Available neural VAD models.
+pub const VAD_MODELS: &[VadModelInfo] = &[VadModelInfo {
+    name: "silero-v5.1.2",
+    display_name: "Silero VAD v5.1.2 (multilingual, 0.9 MB)",
+    size_kb: 885,
+    filename: "ggml-silero-v5.1.2.bin",
+    description: "Small LSTM speech detector, ~1 ms per 32 ms window on CPU.",
+    sha256: None,
+}];
+
+/// Look up a VAD model by name.
+pub fn get_vad_model(name: &str) -> Option<&'static VadModelInfo> {
+    VAD_MODELS.iter().find(|m| m.name == name)
+}
+
+/// Resolve a configured model name to its catalog entry name.
+///
+/// Unknown names fall back to [`defaults::DEFAULT_VAD_MODEL`]; `config validate`
+/// reports them.
+pub fn resolve_vad_model(name: &str) -> &'static str {
+    get_vad_model(name).map_or(defaults::DEFAULT_VAD_MODEL, |m| m.name)
+}
+
+/// List all available VAD models.
+pub fn list_vad_models() -> &'static [VadModelInfo] {
+    VAD_MODELS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_model_is_in_catalog() {
+        let model =
+            get_vad_model(defaults::DEFAULT_VAD_MODEL).expect("default VAD model should exist");
+        assert_eq!(model.filename, "ggml-silero-v5.1.2.bin");
+    }
+
+    #[test]
+    fn test_get_vad_model_nonexistent() {
+        assert!(get_vad_model("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_resolve_vad_model_falls_back_to_default() {
+        assert_eq!(resolve_vad_model("silero-v5.1.2"), "silero-v5.1.2");
+        assert_eq!(resolve_vad_model("bogus"), defaults::DEFAULT_VAD_MODEL);
+    }
+
+    #[test]
+    fn test_url_points_at_repo() {
+        let model = get_vad_model(defaults::DEFAULT_VAD_MODEL).expect("should exist");
+        assert_eq!(
+            model.url(),
+            "https://huggingface.co/ggml-org/whisper-vad/resolve/main/ggml-silero-v5.1.2.bin"
+        );
+    }
+
+    #[test]
+    fn test_sha256_is_unpinned_or_hex() {
+        for model in list_vad_models() {
+            assert!(
+                model.sha256.is_none_or(|hash| {
+                    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+                }),
+                "{} sha256 is not a 64-character hex string",
+                model.name
+            );
+        }
+    }
+}
@@ -2,7 +2,7 @@
 //! Used by both `voicsh follow` and daemon verbose mode.
 
 use crate::ipc::protocol::{DaemonEvent, TextOrigin};
-use crate::stt::transcriber::TokenProbability;
+use crate::stt::transcriber::{TokenProbability, Word, group_words};
 use std::io::{self, Write};
 
 const DIM: &str = "\x1b[2m";
@@ -81,28 +81,17 @@ fn render_tokens_colored(token_probabilities: &[TokenProbability]) {
     }
 }
 
-/// Build a word->minimum-probability map from token probabilities.
-///
-/// Tokens may contain leading whitespace (e.g., " hello"). We split each token
-/// into whitespace-separated words and assign each word the minimum probability
-/// of the tokens that contribute to it.
-fn build_word_prob_map(token_probabilities: &[TokenProbability]) -> Vec<(String, f32)> {
-    let mut result: Vec<(String, f32)> = Vec::new();
-
-    for tp in token_probabilities {
-        let words: Vec<&str> = tp.token.split_whitespace().collect();
-        for word in words {
-            if let Some(last) = result.last_mut()
-                && last.0 == word
-            {
-                last.1 = last.1.min(tp.probability);
-                continue;
-            }
-            result.push((word.to_string(), tp.probability));
-        }
-    }
-
-    result
+/// Words for display: the event's timed words, or words grouped from the
+/// sub-word token probabilities when the backend provided no word timing.
+fn display_words(words: &[Word], token_probabilities: &[TokenProbability]) -> Vec<Word> {
+    if !words.is_empty() {
+        return words.to_vec();
+    }
+    group_words(
+        token_probabilities
+            .iter()
+            .map(|tp| (tp.token.as_str(), tp.probability, 0, 0)),
+    )
 }
 
 /// Compute the longest common subsequence of two word slices.
@@ -193,17 +182,16 @@ fn word_diff<'a>(old_words: &[&'a str], new_words: &[&'a str]) -> Vec<DiffOp<'a>
 }
 
 /// Look up the probability for a word (case-insensitive).
-fn lookup_prob(word: &str, word_probs: &[(String, f32)]) -> f32 {
-    word_probs
+fn lookup_prob(word: &str, words: &[Word]) -> f32 {
+    words
         .iter()
-        .find(|(w, _)| w.eq_ignore_ascii_case(word))
-        .map(|(_, p)| *p)
+        .find(|w| w.text.eq_ignore_ascii_case(word))
+        .map(|w| w.probability)
         .unwrap_or(0.5) // default to medium if not found
 }
 
 /// Render a correction diff: strikethrough original words, then replacement.
-fn render_correction_diff(raw_text: &str, text: &str, token_probabilities: &[TokenProbability]) {
-    let word_probs = build_word_prob_map(token_probabilities);
+fn render_correction_diff(raw_text: &str, text: &str, words: &[Word]) {
     let old_words: Vec<&str> = raw_text.split_whitespace().collect();
     let new_words: Vec<&str> = text.split_whitespace().collect();
     let ops = word_diff(&old_words, &new_words);
@@ -219,7 +207,7 @@ fn render_correction_diff(raw_text: &str, text: &str, token_probabilities: &[Tok
         prev_was_delete = matches!(op, DiffOp::Delete(_));
         match op {
             DiffOp::Equal(w) => {
-                let color = probability_color(lookup_prob(w, &word_probs));
+                let color = probability_color(lookup_prob(w, words));
                 if color.is_empty() {
                     eprint!("{w}");
                 } else {
@@ -227,7 +215,7 @@ fn render_correction_diff(raw_text: &str, text: &str, token_probabilities: &[Tok
                 }
             }
             DiffOp::Delete(w) => {
-                let color = probability_color(lookup_prob(w, &word_probs));
+                let color = probability_color(lookup_prob(w, words));
                 eprint!("{STRIKETHROUGH}{DIM}{color}[{w}]{RESET}");
             }
             DiffOp::Insert(w) => {
@@ -251,15 +239,20 @@ pub fn render_event(event: &DaemonEvent) {
             is_speech,
             buffer_used,
             buffer_capacity,
+            raw_level,
         } => {
             let bar = format_level_bar(*level, *threshold);
+            // The bar shows the denoised level the VAD sees; raw is the mic level
+            let raw = raw_level
+                .map(|raw| format!(" {DIM}raw {raw:.3}{RESET}"))
+                .unwrap_or_default();
             let speech = if *is_speech { " SPEECH" } else { "" };
             let buf = if *buffer_capacity > 0 {
                 format!("  {DIM}buf {buffer_used}/{buffer_capacity}{RESET}")
             } else {
                 String::new()
             };
-            eprint!("\r\x1b[2K{bar}{speech}{buf}");
+            eprint!("\r\x1b[2K{bar}{raw}{speech}{buf}");
             io::stderr().flush().ok();
         }
         DaemonEvent::RecordingStateChanged { recording } => {
@@ -279,6 +272,7 @@ pub fn render_event(event: &DaemonEvent) {
             raw_text,
             text_origin,
             corrector_name,
+            words,
         } => {
             clear_line();
             let lang = if !language.is_empty() && *confidence < 0.99 {
@@ -298,7 +292,7 @@ pub fn render_event(event: &DaemonEvent) {
 
             match (text_origin, raw_text) {
                 (TextOrigin::Corrected, Some(raw)) => {
-                    render_correction_diff(raw, text, token_probabilities);
+                    render_correction_diff(raw, text, &display_words(words, token_probabilities));
                     eprintln!("{lang}{wait}{corrector_tag}");
                 }
                 (TextOrigin::VoiceCommand, Some(raw)) => {
@@ -316,6 +310,11 @@ pub fn render_event(event: &DaemonEvent) {
                 }
             }
         }
+        DaemonEvent::PartialTranscription { text } => {
+            // Rewritten in place until the final transcription replaces it
+            eprint!("\r\x1b[2K{DIM}{text}{RESET}");
+            io::stderr().flush().ok();
+        }
         DaemonEvent::TranscriptionDropped {
             text,
             language,
@@ -358,6 +357,26 @@ pub fn render_event(event: &DaemonEvent) {
             clear_line();
             eprintln!("{RED}Model {model} failed: {error}{RESET}");
         }
+        DaemonEvent::AudioDeviceChanged {
+            device,
+            previous,
+            reason,
+        } => {
+            clear_line();
+            eprintln!("Input device: {previous} → {device} ({reason})");
+        }
+        DaemonEvent::LanguageSwitched {
+            from,
+            to,
+            probability,
+        } => {
+            clear_line();
+            eprintln!("Language: {from} → {to} ({:.0}%)", probability * 100.0);
+        }
+        DaemonEvent::LanguageRouted { language, model } => {
+            clear_line();
+            eprintln!("{DIM}Language [{language}] → {model}{RESET}");
+        }
         DaemonEvent::DaemonInfo {
             binary_path,
             version,
@@ -522,7 +541,7 @@ mod tests {
     // ── word probability map tests ─────────────────────────────────────
 
     #[test]
-    fn build_word_prob_map_basic() {
+    fn display_words_from_tokens_basic() {
         let tokens = vec![
             TokenProbability {
                 token: "the".to_string(),
@@ -537,7 +556,10 @@ mod tests {
                 probability: 0.92,
             },
         ];
-        let map = build_word_prob_map(&tokens);
+        let map: Vec<(String, f32)> = display_words(&[], &tokens)
+            .into_iter()
+            .map(|w| (w.text, w.probability))
+            .collect();
         assert_eq!(map.len(), 3);
         assert_eq!(map[0], ("the".to_string(), 0.95));
         assert_eq!(map[1], ("quick".to_string(), 0.3));
@@ -545,11 +567,45 @@ mod tests {
     }
 
     #[test]
-    fn build_word_prob_map_empty() {
-        let map = build_word_prob_map(&[]);
+    fn display_words_empty() {
+        let map = display_words(&[], &[]);
         assert_eq!(map.len(), 0);
     }
 
+    #[test]
+    fn display_words_merges_subword_tokens() {
+        let tokens = vec![
+            TokenProbability {
+                token: " qu".to_string(),
+                probability: 0.4,
+            },
+            TokenProbability {
+                token: "ick".to_string(),
+                probability: 0.9,
+            },
+        ];
+        let words = display_words(&[], &tokens);
+        assert_eq!(words.len(), 1);
+        assert_eq!(words[0].text, "quick");
+        assert_eq!(words[0].probability, 0.4);
+    }
+
+    #[test]
+    fn display_words_prefers_timed_words() {
+        let timed = vec![Word {
+            text: "quick".to_string(),
+            start_ms: 100,
+            end_ms: 400,
+            probability: 0.25,
+            model: None,
+        }];
+        let tokens = vec![TokenProbability {
+            token: " quick".to_string(),
+            probability: 0.9,
+        }];
+        assert_eq!(display_words(&timed, &tokens), timed);
+    }
+
     // ── LCS tests ──────────────────────────────────────────────────────
 
     #[test]
@@ -654,6 +710,7 @@ mod tests {
             is_speech: true,
             buffer_used: 3,
             buffer_capacity: 8,
+            raw_level: None,
         });
 
         render_event(&DaemonEvent::RecordingStateChanged { recording: true });
@@ -676,6 +733,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::Transcription,
             corrector_name: None,
+            words: vec![],
         });
 
         render_event(&DaemonEvent::TranscriptionDropped {
@@ -708,6 +766,27 @@ mod tests {
             error: "download failed".to_string(),
         });
 
+        render_event(&DaemonEvent::AudioDeviceChanged {
+            device: "default".to_string(),
+            previous: "USB Headset".to_string(),
+            reason: "disconnected".to_string(),
+        });
+
+        render_event(&DaemonEvent::PartialTranscription {
+            text: "turn on the".to_string(),
+        });
+
+        render_event(&DaemonEvent::LanguageSwitched {
+            from: "de".to_string(),
+            to: "en".to_string(),
+            probability: 0.97,
+        });
+
+        render_event(&DaemonEvent::LanguageRouted {
+            language: "de".to_string(),
+            model: "large-v3-turbo-q5_0".to_string(),
+        });
+
         render_event(&DaemonEvent::DaemonInfo {
             binary_path: "/usr/bin/voicsh".to_string(),
             version: "0.1.0+abc1234".to_string(),
@@ -727,6 +806,19 @@ mod tests {
             is_speech: false,
             buffer_used: 0,
             buffer_capacity: 0,
+            raw_level: None,
+        });
+    }
+
+    #[test]
+    fn test_render_level_with_raw_level() {
+        render_event(&DaemonEvent::Level {
+            level: 0.01,
+            threshold: 0.05,
+            is_speech: false,
+            buffer_used: 0,
+            buffer_capacity: 0,
+            raw_level: Some(0.04),
         });
     }
 
@@ -741,6 +833,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::Transcription,
             corrector_name: None,
+            words: vec![],
         });
     }
 
@@ -768,6 +861,7 @@ mod tests {
             raw_text: None,
             text_origin: TextOrigin::Transcription,
             corrector_name: None,
+            words: vec![],
         });
     }
 
@@ -800,6 +894,7 @@ mod tests {
             raw_text: Some("the quik brown fox".to_string()),
             text_origin: TextOrigin::Corrected,
             corrector_name: None,
+            words: vec![],
         });
     }
 
@@ -815,6 +910,7 @@ mod tests {
             raw_text: Some("period".to_string()),
             text_origin: TextOrigin::VoiceCommand,
             corrector_name: None,
+            words: vec![],
         });
     }
 
@@ -847,6 +943,7 @@ mod tests {
             raw_text: Some("the quik brown fox".to_string()),
             text_origin: TextOrigin::Corrected,
             corrector_name: Some("T5".to_string()),
+            words: vec![],
         });
     }
 }
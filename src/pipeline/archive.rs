@@ -0,0 +1,896 @@
+//! Per-session audio archive for debugging.
+//!
+//! With `[debug] archive_audio` enabled, every pipeline session gets its own
+//! directory holding the captured audio (`session.wav`), each chunk sent to
+//! the transcriber (`chunk-0001.wav`, ...) and a `manifest.json` with chunk
+//! boundaries, VAD decisions, timings and the resulting text. Together they
+//! show whether a bad transcription came from the audio, the chunking or the
+//! model. Sessions hold recorded speech, so directories are created 0700
+//! and files 0600.
+
+use crate::audio::vad::VadConfig;
+use crate::config::{DebugConfig, VadBackend};
+use crate::error::{Result, VoicshError};
+use crate::pipeline::error::StationError;
+use crate::pipeline::station::Station;
+use crate::pipeline::types::{AudioChunk, AudioFrame, TranscribedText, VadFrame};
+use crate::pipeline::{TranscriberStation, VadStation};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Manifest file inside a session directory.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Full captured audio inside a session directory.
+pub const SESSION_AUDIO_FILE: &str = "session.wav";
+
+/// Where sessions are archived and how many are kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveSettings {
+    /// Directory holding one subdirectory per session.
+    pub dir: PathBuf,
+    /// Sessions to keep (0 = unlimited).
+    pub max_sessions: usize,
+    /// Total archive size to keep in bytes (0 = unlimited).
+    pub max_bytes: u64,
+}
+
+impl ArchiveSettings {
+    /// Settings from `[debug]`, or `None` when archiving is off.
+    pub fn from_config(debug: &DebugConfig) -> Option<Self> {
+        debug.archive_audio.then(|| Self {
+            dir: debug.archive_path(),
+            max_sessions: debug.max_sessions as usize,
+            max_bytes: u64::from(debug.max_archive_mb) * 1024 * 1024,
+        })
+    }
+}
+
+/// A stretch of audio the VAD classified as speech.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpeechSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// What happened to a chunk in the transcriber station.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkOutcome {
+    /// Still being transcribed when the session ended.
+    #[default]
+    Pending,
+    /// Produced text.
+    Transcribed,
+    /// Discarded: no text, or removed by a hallucination, language or confidence filter.
+    Dropped,
+    /// Transcription failed.
+    Failed,
+}
+
+impl std::fmt::Display for ChunkOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::Transcribed => write!(f, "transcribed"),
+            Self::Dropped => write!(f, "dropped"),
+            Self::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// One chunk sent to the transcriber.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkRecord {
+    pub sequence: u64,
+    /// WAV file of the chunk, relative to the session directory.
+    pub file: String,
+    /// Offset of the chunk in `session.wav`.
+    pub start_ms: u64,
+    pub duration_ms: u32,
+    pub outcome: ChunkOutcome,
+    /// Time spent in the transcriber station.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcribe_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub language: String,
+    #[serde(default)]
+    pub confidence: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Contents of `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionManifest {
+    pub id: String,
+    /// Session start in seconds since the Unix epoch.
+    pub started_at: u64,
+    pub sample_rate: u32,
+    pub model: String,
+    pub vad_backend: VadBackend,
+    pub vad_threshold: f32,
+    pub silence_duration_ms: u32,
+    /// Length of `session.wav`.
+    pub duration_ms: u64,
+    /// Speech detected by the VAD, as offsets into `session.wav`.
+    pub speech: Vec<SpeechSegment>,
+    pub chunks: Vec<ChunkRecord>,
+}
+
+impl SessionManifest {
+    /// Text of all transcribed chunks, in order.
+    pub fn text(&self) -> String {
+        self.chunks
+            .iter()
+            .filter(|c| c.outcome == ChunkOutcome::Transcribed)
+            .map(|c| c.text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+type WavFile = hound::WavWriter<BufWriter<fs::File>>;
+
+fn archive_error(message: impl std::fmt::Display) -> VoicshError {
+    VoicshError::SessionArchive {
+        message: message.to_string(),
+    }
+}
+
+/// Mutable state behind a [`SessionArchive`].
+struct ArchiveWriter {
+    dir: PathBuf,
+    manifest: SessionManifest,
+    audio: Option<WavFile>,
+    audio_samples: u64,
+    /// Samples seen by the VAD, the clock for speech segments.
+    vad_samples: u64,
+    speech_start: Option<u64>,
+    /// Set after the first error; the archive then stops recording.
+    failed: bool,
+}
+
+impl ArchiveWriter {
+    fn ms(&self, samples: u64) -> u64 {
+        samples * 1000 / u64::from(self.manifest.sample_rate.max(1))
+    }
+
+    fn spec(&self) -> hound::WavSpec {
+        hound::WavSpec {
+            channels: 1,
+            sample_rate: self.manifest.sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        }
+    }
+
+    fn write_audio(&mut self, samples: &[i16]) -> Result<()> {
+        if let Some(audio) = self.audio.as_mut() {
+            for &s in samples {
+                audio.write_sample(s).map_err(archive_error)?;
+            }
+            self.audio_samples += samples.len() as u64;
+        }
+        Ok(())
+    }
+
+    fn write_vad(&mut self, samples: usize, is_speech: bool) {
+        let now = self.ms(self.vad_samples);
+        match (self.speech_start, is_speech) {
+            (None, true) => self.speech_start = Some(now),
+            (Some(start_ms), false) => {
+                self.manifest.speech.push(SpeechSegment {
+                    start_ms,
+                    end_ms: now,
+                });
+                self.speech_start = None;
+            }
+            _ => {}
+        }
+        self.vad_samples += samples as u64;
+    }
+
+    fn write_chunk(&mut self, chunk: &AudioChunk) -> Result<()> {
+        let file = format!("chunk-{:04}.wav", chunk.sequence);
+        let mut writer = create_wav(&self.dir.join(&file), self.spec())?;
+        for &s in &chunk.samples {
+            writer.write_sample(s).map_err(archive_error)?;
+        }
+        writer.finalize().map_err(archive_error)?;
+        self.manifest.chunks.push(ChunkRecord {
+            sequence: chunk.sequence,
+            file,
+            start_ms: chunk.start_ms,
+            duration_ms: chunk.duration_ms,
+            outcome: ChunkOutcome::Pending,
+            transcribe_ms: None,
+            text: String::new(),
+            language: String::new(),
+            confidence: 0.0,
+            error: None,
+        });
+        Ok(())
+    }
+
+    fn write_result(
+        &mut self,
+        sequence: u64,
+        result: &std::result::Result<Option<TranscribedText>, StationError>,
+        elapsed: Duration,
+    ) -> Result<()> {
+        if let Some(record) = self
+            .manifest
+            .chunks
+            .iter_mut()
+            .rev()
+            .find(|c| c.sequence == sequence)
+        {
+            record.transcribe_ms = Some(elapsed.as_millis() as u64);
+            match result {
+                Ok(Some(text)) => {
+                    record.outcome = ChunkOutcome::Transcribed;
+                    record.text = text.text.clone();
+                    record.language = text.language.clone();
+                    record.confidence = text.confidence;
+                }
+                Ok(None) => record.outcome = ChunkOutcome::Dropped,
+                Err(e) => {
+                    record.outcome = ChunkOutcome::Failed;
+                    record.error = Some(e.to_string());
+                }
+            }
+        }
+        self.save()
+    }
+
+    /// Update the WAV header and rewrite the manifest, so both stay readable
+    /// even if the session never shuts down cleanly.
+    fn save(&mut self) -> Result<()> {
+        if let Some(audio) = self.audio.as_mut() {
+            audio.flush().map_err(archive_error)?;
+        }
+        self.manifest.duration_ms = self.ms(self.audio_samples);
+        let json = serde_json::to_string_pretty(&self.manifest).map_err(archive_error)?;
+        create_private_file(&self.dir.join(MANIFEST_FILE))?.write_all(json.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl Drop for ArchiveWriter {
+    fn drop(&mut self) {
+        if self.failed {
+            return;
+        }
+        if let Some(start_ms) = self.speech_start.take() {
+            let end_ms = self.ms(self.vad_samples);
+            self.manifest
+                .speech
+                .push(SpeechSegment { start_ms, end_ms });
+        }
+        if let Err(e) = self.save() {
+            eprintln!(
+                "voicsh: failed to finish session archive {}: {e}",
+                self.dir.display()
+            );
+        }
+        if let Some(audio) = self.audio.take()
+            && let Err(e) = audio.finalize()
+        {
+            eprintln!(
+                "voicsh: failed to finish session audio in {}: {e}",
+                self.dir.display()
+            );
+        }
+    }
+}
+
+/// Create (or truncate) a file only the owner can read.
+fn create_private_file(path: &Path) -> std::io::Result<fs::File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+}
+
+/// Start a WAV file only the owner can read.
+fn create_wav(path: &Path, spec: hound::WavSpec) -> Result<WavFile> {
+    let file = create_private_file(path)?;
+    hound::WavWriter::new(BufWriter::new(file), spec).map_err(archive_error)
+}
+
+/// Recorder for one session, shared by the pipeline threads.
+///
+/// Cloning is cheap; the manifest is written after every chunk and once more
+/// when the last clone is dropped. The first write error is reported and
+/// turns the archive off for the rest of the session.
+#[derive(Clone)]
+pub struct SessionArchive {
+    inner: Arc<Mutex<ArchiveWriter>>,
+}
+
+impl std::fmt::Debug for SessionArchive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionArchive")
+            .field("dir", &self.dir())
+            .finish()
+    }
+}
+
+impl SessionArchive {
+    /// Create a new session directory under `settings.dir`, then apply the
+    /// retention limits to older sessions.
+    pub fn create(
+        settings: &ArchiveSettings,
+        sample_rate: u32,
+        model: &str,
+        vad: &VadConfig,
+    ) -> Result<Self> {
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&settings.dir)?;
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let base = format_timestamp(started_at);
+        let mut n = 1;
+        let (id, dir) = loop {
+            let id = if n == 1 {
+                base.clone()
+            } else {
+                format!("{base}-{n}")
+            };
+            let dir = settings.dir.join(&id);
+            match fs::DirBuilder::new().mode(0o700).create(&dir) {
+                Ok(()) => break (id, dir),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => n += 1,
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        let mut writer = ArchiveWriter {
+            dir,
+            manifest: SessionManifest {
+                id,
+                started_at,
+                sample_rate,
+                model: model.to_string(),
+                vad_backend: vad.backend,
+                vad_threshold: vad.speech_threshold,
+                silence_duration_ms: vad.silence_duration_ms,
+                duration_ms: 0,
+                speech: Vec::new(),
+                chunks: Vec::new(),
+            },
+            audio: None,
+            audio_samples: 0,
+            vad_samples: 0,
+            speech_start: None,
+            failed: false,
+        };
+        writer.audio = Some(create_wav(
+            &writer.dir.join(SESSION_AUDIO_FILE),
+            writer.spec(),
+        )?);
+        writer.save()?;
+
+        if let Err(e) = prune(&settings.dir, settings.max_sessions, settings.max_bytes) {
+            eprintln!("voicsh: failed to prune session archive: {e}");
+        }
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(writer)),
+        })
+    }
+
+    /// Directory of this session.
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.inner.lock().ok().map(|w| w.dir.clone())
+    }
+
+    /// Append captured samples to `session.wav`.
+    pub fn record_audio(&self, samples: &[i16]) {
+        self.with(|w| w.write_audio(samples));
+    }
+
+    /// Record the VAD decision for the next `samples` samples.
+    pub fn record_vad(&self, samples: usize, is_speech: bool) {
+        self.with(|w| {
+            w.write_vad(samples, is_speech);
+            Ok(())
+        });
+    }
+
+    /// Save a chunk as WAV and add it to the manifest.
+    pub fn record_chunk(&self, chunk: &AudioChunk) {
+        self.with(|w| w.write_chunk(chunk));
+    }
+
+    /// Record how the transcriber handled chunk `sequence`.
+    pub fn record_result(
+        &self,
+        sequence: u64,
+        result: &std::result::Result<Option<TranscribedText>, StationError>,
+        elapsed: Duration,
+    ) {
+        self.with(|w| w.write_result(sequence, result, elapsed));
+    }
+
+    fn with(&self, f: impl FnOnce(&mut ArchiveWriter) -> Result<()>) {
+        let Ok(mut writer) = self.inner.lock() else {
+            return;
+        };
+        if writer.failed {
+            return;
+        }
+        if let Err(e) = f(&mut writer) {
+            eprintln!(
+                "voicsh: session archive {} stopped: {e}",
+                writer.dir.display()
+            );
+            writer.failed = true;
+            writer.audio = None;
+        }
+    }
+}
+
+/// A station that records its traffic in an optional [`SessionArchive`].
+///
+/// Wraps [`VadStation`] to log speech segments and [`TranscriberStation`] to
+/// save chunks and results. Without an archive it only forwards.
+pub struct ArchiveTap<S> {
+    station: S,
+    archive: Option<SessionArchive>,
+}
+
+impl<S> ArchiveTap<S> {
+    pub fn new(station: S, archive: Option<SessionArchive>) -> Self {
+        Self { station, archive }
+    }
+}
+
+impl Station for ArchiveTap<VadStation> {
+    type Input = AudioFrame;
+    type Output = VadFrame;
+
+    fn process(
+        &mut self,
+        frame: AudioFrame,
+    ) -> std::result::Result<Option<VadFrame>, StationError> {
+        let samples = frame.samples.len();
+        let result = self.station.process(frame);
+        if let (Some(archive), Ok(Some(vad_frame))) = (&self.archive, &result) {
+            archive.record_vad(samples, vad_frame.is_speech);
+        }
+        result
+    }
+
+    fn name(&self) -> &'static str {
+        self.station.name()
+    }
+
+    fn shutdown(&mut self) {
+        self.station.shutdown();
+    }
+}
+
+impl Station for ArchiveTap<TranscriberStation> {
+    type Input = AudioChunk;
+    type Output = TranscribedText;
+
+    fn process(
+        &mut self,
+        chunk: AudioChunk,
+    ) -> std::result::Result<Option<TranscribedText>, StationError> {
+        let Some(archive) = &self.archive else {
+            return self.station.process(chunk);
+        };
+        archive.record_chunk(&chunk);
+        let sequence = chunk.sequence;
+        let started = Instant::now();
+        let result = self.station.process(chunk);
+        archive.record_result(sequence, &result, started.elapsed());
+        result
+    }
+
+    fn name(&self) -> &'static str {
+        self.station.name()
+    }
+
+    fn shutdown(&mut self) {
+        self.station.shutdown();
+    }
+}
+
+/// An archived session on disk.
+#[derive(Debug, Clone)]
+pub struct ArchivedSession {
+    pub id: String,
+    pub path: PathBuf,
+    /// Total size of the session's files.
+    pub bytes: u64,
+    /// `None` when the manifest is missing or unreadable.
+    pub manifest: Option<SessionManifest>,
+}
+
+/// Read a session's manifest.
+pub fn load_manifest(dir: &Path) -> Result<SessionManifest> {
+    let path = dir.join(MANIFEST_FILE);
+    let json = fs::read_to_string(&path)?;
+    serde_json::from_str(&json)
+        .map_err(|e| archive_error(format!("invalid manifest {}: {e}", path.display())))
+}
+
+/// Sessions under `root`, newest first.
+///
+/// Only directories containing a manifest or session audio count as
+/// sessions, so an archive directory shared with other files is safe.
+/// A missing `root` has no sessions.
+pub fn list_sessions(root: &Path) -> Result<Vec<ArchivedSession>> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut sessions = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if !path.join(MANIFEST_FILE).is_file() && !path.join(SESSION_AUDIO_FILE).is_file() {
+            continue;
+        }
+        let Some(id) = path.file_name().and_then(|n| n.to_str()).map(String::from) else {
+            continue;
+        };
+        let bytes = fs::read_dir(&path)?
+            .filter_map(|e| e.ok()?.metadata().ok())
+            .filter(|m| m.is_file())
+            .map(|m| m.len())
+            .sum();
+        sessions.push(ArchivedSession {
+            id,
+            manifest: load_manifest(&path).ok(),
+            path,
+            bytes,
+        });
+    }
+    sessions.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(sessions)
+}
+
+/// Find a session by id, unique id prefix, or `latest`.
+pub fn find_session(root: &Path, id: &str) -> Result<ArchivedSession> {
+    let sessions = list_sessions(root)?;
+    if id == "latest" {
+        return sessions
+            .into_iter()
+            .next()
+            .ok_or_else(|| archive_error(format!("no sessions in {}", root.display())));
+    }
+    if let Some(session) = sessions.iter().find(|s| s.id == id) {
+        return Ok(session.clone());
+    }
+    let mut matches = sessions.into_iter().filter(|s| s.id.starts_with(id));
+    match (matches.next(), matches.next()) {
+        (Some(session), None) => Ok(session),
+        (Some(_), Some(_)) => Err(archive_error(format!(
+            "\"{id}\" matches more than one session"
+        ))),
+        (None, _) => Err(archive_error(format!("no session matches \"{id}\""))),
+    }
+}
+
+/// Remove the oldest sessions beyond `max_sessions` or `max_bytes` in total
+/// (0 = no limit). The newest session is always kept. Returns removed ids.
+pub fn prune(root: &Path, max_sessions: usize, max_bytes: u64) -> Result<Vec<String>> {
+    let mut total = 0;
+    let mut removed = Vec::new();
+    for (i, session) in list_sessions(root)?.into_iter().enumerate() {
+        total += session.bytes;
+        let over_count = max_sessions > 0 && i >= max_sessions;
+        let over_size = max_bytes > 0 && total > max_bytes;
+        if i > 0 && (over_count || over_size) {
+            fs::remove_dir_all(&session.path)?;
+            removed.push(session.id);
+        }
+    }
+    Ok(removed)
+}
+
+/// Remove all but the `keep` newest sessions. Returns removed ids.
+pub fn purge(root: &Path, keep: usize) -> Result<Vec<String>> {
+    let mut removed = Vec::new();
+    for session in list_sessions(root)?.into_iter().skip(keep) {
+        fs::remove_dir_all(&session.path)?;
+        removed.push(session.id);
+    }
+    Ok(removed)
+}
+
+/// Format Unix seconds as a sortable UTC session id (`YYYYMMDD-HHMMSS`).
+fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}{month:02}{day:02}-{:02}{:02}{:02}",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(dir: &Path) -> ArchiveSettings {
+        ArchiveSettings {
+            dir: dir.to_path_buf(),
+            max_sessions: 0,
+            max_bytes: 0,
+        }
+    }
+
+    fn text(s: &str) -> TranscribedText {
+        TranscribedText::new(s.to_string())
+    }
+
+    /// Fake session directory with a manifest and `bytes` of audio.
+    fn fake_session(root: &Path, id: &str, bytes: usize) {
+        let dir = root.join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(SESSION_AUDIO_FILE), vec![0u8; bytes]).unwrap();
+    }
+
+    fn ids(root: &Path) -> Vec<String> {
+        list_sessions(root)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_session_writes_audio_chunks_and_manifest() {
+        let root = tempfile::tempdir().unwrap();
+        let archive = SessionArchive::create(
+            &settings(root.path()),
+            16000,
+            "base.en",
+            &VadConfig::default(),
+        )
+        .unwrap();
+        let dir = archive.dir().unwrap();
+
+        archive.record_audio(&[100; 8000]);
+        archive.record_audio(&[200; 8000]);
+        for speech in [false, true, true, false] {
+            archive.record_vad(4000, speech);
+        }
+        let chunk = AudioChunk::new(vec![7; 4800], 300, 1).with_start_ms(250);
+        archive.record_chunk(&chunk);
+        archive.record_result(1, &Ok(Some(text("hello world"))), Duration::from_millis(42));
+        archive.record_chunk(&AudioChunk::new(vec![0; 1600], 100, 2));
+        archive.record_result(2, &Ok(None), Duration::from_millis(5));
+        archive.record_chunk(&AudioChunk::new(vec![0; 1600], 100, 3));
+        drop(archive);
+
+        let manifest = load_manifest(&dir).unwrap();
+        assert_eq!(manifest.model, "base.en");
+        assert_eq!(manifest.duration_ms, 1000);
+        assert_eq!(
+            manifest.speech,
+            vec![SpeechSegment {
+                start_ms: 250,
+                end_ms: 750
+            }]
+        );
+        let outcomes: Vec<_> = manifest.chunks.iter().map(|c| c.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ChunkOutcome::Transcribed,
+                ChunkOutcome::Dropped,
+                ChunkOutcome::Pending
+            ]
+        );
+        let first = &manifest.chunks[0];
+        assert_eq!((first.start_ms, first.duration_ms), (250, 300));
+        assert_eq!(first.transcribe_ms, Some(42));
+        assert_eq!(manifest.text(), "hello world");
+
+        let session = hound::WavReader::open(dir.join(SESSION_AUDIO_FILE)).unwrap();
+        assert_eq!(session.len(), 16000);
+        let chunk = hound::WavReader::open(dir.join(&first.file)).unwrap();
+        assert_eq!(chunk.spec().sample_rate, 16000);
+        assert_eq!(chunk.len(), 4800);
+    }
+
+    #[test]
+    fn test_session_files_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = tempfile::tempdir().unwrap();
+        let archive_root = root.path().join("sessions");
+        let archive = SessionArchive::create(
+            &settings(&archive_root),
+            16000,
+            "base.en",
+            &VadConfig::default(),
+        )
+        .unwrap();
+        let dir = archive.dir().unwrap();
+        archive.record_chunk(&AudioChunk::new(vec![0; 1600], 100, 1));
+        drop(archive);
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&archive_root), 0o700);
+        assert_eq!(mode(&dir), 0o700);
+        for file in [SESSION_AUDIO_FILE, MANIFEST_FILE, "chunk-0001.wav"] {
+            assert_eq!(mode(&dir.join(file)), 0o600, "{file}");
+        }
+    }
+
+    #[test]
+    fn test_open_speech_segment_closed_on_drop() {
+        let root = tempfile::tempdir().unwrap();
+        let archive =
+            SessionArchive::create(&settings(root.path()), 16000, "tiny", &VadConfig::default())
+                .unwrap();
+        let dir = archive.dir().unwrap();
+        archive.record_vad(1600, true);
+        archive.record_vad(1600, true);
+        drop(archive);
+        let manifest = load_manifest(&dir).unwrap();
+        assert_eq!(
+            manifest.speech,
+            vec![SpeechSegment {
+                start_ms: 0,
+                end_ms: 200
+            }]
+        );
+    }
+
+    #[test]
+    fn test_failed_result_records_error() {
+        let root = tempfile::tempdir().unwrap();
+        let archive =
+            SessionArchive::create(&settings(root.path()), 16000, "tiny", &VadConfig::default())
+                .unwrap();
+        let dir = archive.dir().unwrap();
+        archive.record_chunk(&AudioChunk::new(vec![0; 160], 10, 4));
+        archive.record_result(
+            4,
+            &Err(StationError::Recoverable("model crashed".into())),
+            Duration::ZERO,
+        );
+        // Manifest is current before the archive is dropped
+        let manifest = load_manifest(&dir).unwrap();
+        assert_eq!(manifest.chunks[0].outcome, ChunkOutcome::Failed);
+        assert!(
+            manifest.chunks[0]
+                .error
+                .as_deref()
+                .is_some_and(|e| e.contains("model crashed"))
+        );
+    }
+
+    #[test]
+    fn test_sessions_created_in_same_second_get_unique_ids() {
+        let root = tempfile::tempdir().unwrap();
+        let a =
+            SessionArchive::create(&settings(root.path()), 16000, "tiny", &VadConfig::default())
+                .unwrap();
+        let b =
+            SessionArchive::create(&settings(root.path()), 16000, "tiny", &VadConfig::default())
+                .unwrap();
+        assert_ne!(a.dir(), b.dir());
+        assert_eq!(list_sessions(root.path()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_list_ignores_unrelated_entries_and_sorts_newest_first() {
+        let root = tempfile::tempdir().unwrap();
+        fake_session(root.path(), "20260101-000000", 10);
+        fake_session(root.path(), "20260301-000000", 10);
+        fake_session(root.path(), "20260201-000000", 10);
+        fs::create_dir(root.path().join("unrelated")).unwrap();
+        fs::write(root.path().join("notes.txt"), "x").unwrap();
+        assert_eq!(
+            ids(root.path()),
+            vec!["20260301-000000", "20260201-000000", "20260101-000000"]
+        );
+        assert!(
+            list_sessions(&root.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_prune_by_count_and_size_keeps_newest() {
+        let root = tempfile::tempdir().unwrap();
+        for day in 1..=5 {
+            fake_session(root.path(), &format!("2026010{day}-000000"), 100);
+        }
+        assert_eq!(prune(root.path(), 0, 0).unwrap(), Vec::<String>::new());
+        assert_eq!(prune(root.path(), 4, 0).unwrap(), vec!["20260101-000000"]);
+        assert_eq!(
+            prune(root.path(), 0, 250).unwrap(),
+            vec!["20260103-000000", "20260102-000000"]
+        );
+        // The newest session survives even when it alone exceeds the limit
+        prune(root.path(), 0, 1).unwrap();
+        assert_eq!(ids(root.path()), vec!["20260105-000000"]);
+    }
+
+    #[test]
+    fn test_purge_keeps_requested_count() {
+        let root = tempfile::tempdir().unwrap();
+        for day in 1..=3 {
+            fake_session(root.path(), &format!("2026010{day}-000000"), 1);
+        }
+        assert_eq!(purge(root.path(), 1).unwrap().len(), 2);
+        assert_eq!(ids(root.path()), vec!["20260103-000000"]);
+        purge(root.path(), 0).unwrap();
+        assert!(ids(root.path()).is_empty());
+    }
+
+    #[test]
+    fn test_find_session_by_prefix_and_latest() {
+        let root = tempfile::tempdir().unwrap();
+        fake_session(root.path(), "20260101-120000", 1);
+        fake_session(root.path(), "20260102-120000", 1);
+        assert_eq!(
+            find_session(root.path(), "latest").unwrap().id,
+            "20260102-120000"
+        );
+        assert_eq!(
+            find_session(root.path(), "20260101").unwrap().id,
+            "20260101-120000"
+        );
+        let err = find_session(root.path(), "2026").unwrap_err();
+        assert!(err.to_string().contains("more than one"), "{err}");
+        let err = find_session(root.path(), "1999").unwrap_err();
+        assert!(err.to_string().contains("no session"), "{err}");
+    }
+
+    #[test]
+    fn test_settings_from_config() {
+        let mut debug = DebugConfig::default();
+        assert_eq!(ArchiveSettings::from_config(&debug), None);
+        debug.archive_audio = true;
+        debug.archive_dir = Some("/tmp/archive".to_string());
+        debug.max_archive_mb = 2;
+        let settings = ArchiveSettings::from_config(&debug).unwrap();
+        assert_eq!(settings.dir, PathBuf::from("/tmp/archive"));
+        assert_eq!(settings.max_bytes, 2 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "19700101-000000");
+        assert_eq!(format_timestamp(951_782_400), "20000229-000000");
+        assert_eq!(format_timestamp(1_792_332_245), "20261018-140405");
+    }
+}
@@ -29,6 +29,8 @@ pub struct ChunkerStation {
     first_frame_capture: Option<Instant>,
     /// VAD start time of the first frame in the current chunk.
     first_frame_vad: Option<Instant>,
+    /// Total samples received so far, used to place chunks on the stream timeline.
+    samples_seen: u64,
 }
 
 impl ChunkerStation {
@@ -50,6 +52,7 @@ impl ChunkerStation {
             flush_tx: None,
             first_frame_capture: None,
             first_frame_vad: None,
+            samples_seen: 0,
         }
     }
 
@@ -96,6 +99,9 @@ impl ChunkerStation {
         let duration_ms = self.calculate_duration_ms(samples.len());
         let seq = self.sequence;
         self.sequence += 1;
+        // Chunks are contiguous audio ending at the most recently received sample
+        let start_sample = self.samples_seen.saturating_sub(samples.len() as u64);
+        let start_ms = start_sample * 1000 / u64::from(self.sample_rate.max(1));
 
         let chunk = if self.verbosity >= 1 {
             // Populate timing when verbosity >= 1
@@ -112,7 +118,8 @@ impl ChunkerStation {
         } else {
             // No timing when verbosity < 1
             AudioChunk::new(samples, duration_ms, seq)
-        };
+        }
+        .with_start_ms(start_ms);
 
         // Log chunk emission if verbosity >= 2
         if self.verbosity >= 2 {
@@ -171,6 +178,8 @@ impl Station for ChunkerStation {
         // Get current silence duration
         let silence_ms = self.current_silence_ms();
 
+        self.samples_seen += frame.samples.len() as u64;
+
         // Feed frame to chunker
         let maybe_samples = self
             .chunker
@@ -321,6 +330,26 @@ mod tests {
         assert_eq!(chunk2.unwrap().sequence, 1);
     }
 
+    #[test]
+    fn test_chunk_start_ms_is_stream_offset() {
+        let config = AdaptiveChunkerConfig {
+            pre_speech_ms: 250,
+            ..make_test_config()
+        };
+        let mut station = ChunkerStation::new(config);
+
+        // 2s of silence, then 0.5s of speech
+        for _ in 0..4 {
+            station.process(make_silence_frame(vec![0; 8000])).unwrap();
+        }
+        station.process(make_speech_frame(vec![1; 8000])).unwrap();
+
+        let chunk = station.flush().unwrap();
+        // Chunk starts with the 250ms pre-speech buffer before the speech onset at 2000ms
+        assert_eq!(chunk.start_ms, 1750);
+        assert_eq!(chunk.duration_ms, 750);
+    }
+
     #[test]
     fn test_flush_returns_remaining_audio() {
         let config = make_test_config();
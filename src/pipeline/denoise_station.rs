@@ -0,0 +1,129 @@
+//! Denoise station that suppresses background noise before voice activity detection.
+
+use crate::audio::denoise::{SpectralDenoiser, db_to_gain};
+use crate::audio::vad::calculate_rms;
+use crate::config::DenoiseConfig;
+use crate::pipeline::error::StationError;
+use crate::pipeline::station::Station;
+use crate::pipeline::types::AudioFrame;
+use std::sync::{Arc, RwLock};
+
+/// Denoise station between the audio source and the VAD station.
+///
+/// Reads its settings on every frame, so enabling or tuning noise suppression
+/// takes effect mid-recording. When disabled, frames pass through unchanged.
+pub struct DenoiseStation {
+    denoiser: SpectralDenoiser,
+    config: Arc<RwLock<DenoiseConfig>>,
+    /// Whether the previous frame was denoised (to reset state on re-enable).
+    active: bool,
+}
+
+impl DenoiseStation {
+    /// Creates a new denoise station with shared, live-updatable settings.
+    pub fn new(config: Arc<RwLock<DenoiseConfig>>) -> Self {
+        Self {
+            denoiser: SpectralDenoiser::new(),
+            config,
+            active: false,
+        }
+    }
+}
+
+impl Station for DenoiseStation {
+    type Input = AudioFrame;
+    type Output = AudioFrame;
+
+    fn name(&self) -> &'static str {
+        "denoise"
+    }
+
+    fn process(&mut self, frame: AudioFrame) -> Result<Option<AudioFrame>, StationError> {
+        // If the write-side thread panicked while holding the lock, recover the last
+        // consistent value rather than cascading the panic into the audio hot path.
+        let config = self
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        if !config.enabled {
+            if self.active {
+                // Up to one frame (32ms) of buffered audio is dropped on switch-off
+                self.denoiser.reset();
+                self.active = false;
+            }
+            return Ok(Some(frame));
+        }
+        self.active = true;
+
+        let raw_level = calculate_rms(&frame.samples);
+        let samples = self.denoiser.process(
+            &frame.samples,
+            config.strength,
+            db_to_gain(config.min_gain_db),
+        );
+        if samples.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            AudioFrame::new(samples, frame.timestamp, frame.sequence).with_raw_level(raw_level),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn shared(config: DenoiseConfig) -> Arc<RwLock<DenoiseConfig>> {
+        Arc::new(RwLock::new(config))
+    }
+
+    fn frame(samples: Vec<i16>, sequence: u64) -> AudioFrame {
+        AudioFrame::new(samples, Instant::now(), sequence)
+    }
+
+    #[test]
+    fn test_disabled_passes_frames_through() {
+        let mut station = DenoiseStation::new(shared(DenoiseConfig::default()));
+        let out = station.process(frame(vec![1, 2, 3], 7)).unwrap().unwrap();
+        assert_eq!(out.samples, vec![1, 2, 3]);
+        assert_eq!(out.sequence, 7);
+        assert!(out.raw_level.is_none());
+    }
+
+    #[test]
+    fn test_enabled_emits_denoised_hops_with_raw_level() {
+        let config = shared(DenoiseConfig {
+            enabled: true,
+            ..Default::default()
+        });
+        let mut station = DenoiseStation::new(config);
+        // First frame only fills the analysis window
+        assert!(station.process(frame(vec![500; 256], 0)).unwrap().is_none());
+        let out = station.process(frame(vec![500; 512], 1)).unwrap().unwrap();
+        assert_eq!(out.samples.len(), 512);
+        assert_eq!(out.sequence, 1);
+        let raw = out.raw_level.unwrap();
+        assert!((raw - calculate_rms(&[500; 512])).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_switches_live() {
+        let config = shared(DenoiseConfig::default());
+        let mut station = DenoiseStation::new(config.clone());
+        assert!(station.process(frame(vec![0; 256], 0)).unwrap().is_some());
+
+        config.write().unwrap().enabled = true;
+        assert!(station.process(frame(vec![0; 256], 1)).unwrap().is_none());
+        assert!(station.active);
+
+        config.write().unwrap().enabled = false;
+        let out = station.process(frame(vec![9; 4], 2)).unwrap().unwrap();
+        assert_eq!(out.samples, vec![9; 4]);
+        assert!(!station.active);
+    }
+}
@@ -0,0 +1,228 @@
+//! Settings handed to the stations of a running pipeline.
+//!
+//! A config reload must not restart a recording. Instead the daemon posts
+//! replacements through [`LiveSettings`]; each station picks up its
+//! replacement before processing its next item.
+
+use crate::config::AudioConfig;
+use crate::correction::station::CorrectionStation;
+use crate::pipeline::error::StationError;
+use crate::pipeline::post_processor::PostProcessor;
+use crate::pipeline::sink::TextSink;
+use crate::pipeline::station::Station;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Latest replacement for one component of a running pipeline.
+///
+/// Only the newest value is kept: a station that has not yet taken an
+/// update gets the last one posted.
+pub struct LiveUpdate<T>(Arc<Mutex<Option<T>>>);
+
+impl<T> LiveUpdate<T> {
+    /// Create an empty update slot.
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(None)))
+    }
+
+    /// Post a replacement, superseding one not yet taken.
+    pub fn set(&self, value: T) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(value);
+    }
+
+    /// Take the pending replacement, if any.
+    pub fn take(&self) -> Option<T> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+impl<T> Default for LiveUpdate<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for LiveUpdate<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> fmt::Debug for LiveUpdate<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LiveUpdate")
+    }
+}
+
+/// VAD threshold settings for a running VAD station.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VadThreshold {
+    /// Speech threshold to switch to
+    pub threshold: f32,
+    /// Keep adapting the threshold to the noise floor
+    pub auto_level: bool,
+}
+
+impl VadThreshold {
+    /// Threshold for `device`: a calibrated device keeps its measured value,
+    /// any other adapts from `vad_threshold`.
+    pub fn for_device(audio: &AudioConfig, device: &str) -> Self {
+        Self {
+            threshold: audio.vad_threshold_for(device),
+            auto_level: audio.calibrated_threshold_for(device).is_none(),
+        }
+    }
+}
+
+/// Hallucination filters for a running transcriber station.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhraseFilters {
+    /// Phrases dropped outright (see `resolve_hallucination_filters`)
+    pub hallucination_filters: HashSet<String>,
+    /// Phrases dropped at low confidence (see `resolve_suspect_phrases`)
+    pub suspect_phrases: HashSet<String>,
+}
+
+/// Update slots for every live-reloadable part of a pipeline.
+///
+/// A pipeline started with these always runs the correction and
+/// post-processing stages, so either can be switched on mid-recording.
+#[derive(Debug, Clone, Default)]
+pub struct LiveSettings {
+    pub vad_threshold: LiveUpdate<VadThreshold>,
+    pub filters: LiveUpdate<PhraseFilters>,
+    /// `None` switches correction off
+    pub correction: LiveUpdate<Option<CorrectionStation>>,
+    pub post_processors: LiveUpdate<Vec<Box<dyn PostProcessor>>>,
+    /// Replaces the sink; text a replaced sink accumulated for `finish` is lost
+    pub sink: LiveUpdate<Box<dyn TextSink>>,
+}
+
+impl LiveSettings {
+    /// Drop updates no pipeline has taken, before a new pipeline is built
+    /// from the current config.
+    pub fn clear(&self) {
+        self.vad_threshold.take();
+        self.filters.take();
+        self.correction.take();
+        self.post_processors.take();
+        self.sink.take();
+    }
+}
+
+/// Station that can be replaced or removed while the pipeline runs.
+///
+/// Passes input through unchanged while no inner station is set.
+pub struct Replaceable<S: Station> {
+    inner: Option<S>,
+    updates: LiveUpdate<Option<S>>,
+    name: &'static str,
+}
+
+impl<S: Station> Replaceable<S> {
+    /// Wrap `inner`; `name` identifies the stage while it is empty.
+    pub fn new(inner: Option<S>, updates: LiveUpdate<Option<S>>, name: &'static str) -> Self {
+        Self {
+            inner,
+            updates,
+            name,
+        }
+    }
+}
+
+impl<T: Send + 'static, S: Station<Input = T, Output = T>> Station for Replaceable<S> {
+    type Input = T;
+    type Output = T;
+
+    fn process(&mut self, input: T) -> Result<Option<T>, StationError> {
+        if let Some(replacement) = self.updates.take() {
+            if let Some(old) = self.inner.as_mut() {
+                old.shutdown();
+            }
+            self.inner = replacement;
+        }
+        match self.inner.as_mut() {
+            Some(inner) => inner.process(input),
+            None => Ok(Some(input)),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.shutdown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Appends a fixed suffix to each string.
+    struct Suffix(&'static str);
+
+    impl Station for Suffix {
+        type Input = String;
+        type Output = String;
+
+        fn process(&mut self, input: String) -> Result<Option<String>, StationError> {
+            Ok(Some(input + self.0))
+        }
+
+        fn name(&self) -> &'static str {
+            "suffix"
+        }
+    }
+
+    #[test]
+    fn test_live_update_keeps_only_the_newest_value() {
+        let update = LiveUpdate::new();
+        let station_side = update.clone();
+        update.set(1);
+        update.set(2);
+        assert_eq!(station_side.take(), Some(2));
+        assert_eq!(station_side.take(), None);
+    }
+
+    #[test]
+    fn test_vad_threshold_for_device() {
+        let mut audio = AudioConfig {
+            vad_threshold: 0.05,
+            ..Default::default()
+        };
+        audio.device_thresholds.insert("usb".to_string(), 0.2);
+        assert_eq!(
+            VadThreshold::for_device(&audio, "usb"),
+            VadThreshold {
+                threshold: 0.2,
+                auto_level: false,
+            }
+        );
+        assert_eq!(
+            VadThreshold::for_device(&audio, "default"),
+            VadThreshold {
+                threshold: 0.05,
+                auto_level: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_replaceable_switches_between_items() {
+        let updates = LiveUpdate::new();
+        let mut station = Replaceable::new(None, updates.clone(), "stage");
+        assert_eq!(station.process("a".to_string()).unwrap().unwrap(), "a");
+
+        updates.set(Some(Suffix("!")));
+        assert_eq!(station.process("b".to_string()).unwrap().unwrap(), "b!");
+
+        updates.set(None);
+        assert_eq!(station.process("c".to_string()).unwrap().unwrap(), "c");
+        assert_eq!(station.name(), "stage");
+    }
+}
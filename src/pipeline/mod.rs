@@ -4,27 +4,37 @@
 //! connected by bounded crossbeam channels for backpressure.
 
 pub mod adaptive_chunker;
+pub mod archive;
 pub mod chunker_station;
+pub mod denoise_station;
 pub mod error;
 pub mod latency;
+pub mod live;
 pub mod orchestrator;
 pub mod post_processor;
 pub mod sink;
 pub mod station;
+pub mod streaming_station;
+pub mod text_filter;
 pub mod transcriber_station;
 pub mod types;
 pub mod vad_station;
 
 pub use crate::correction::station::CorrectionStation;
+pub use archive::{ArchiveSettings, ArchiveTap, SessionArchive};
 pub use chunker_station::ChunkerStation;
+pub use denoise_station::DenoiseStation;
 pub use error::{ErrorReporter, LogReporter, StationError};
 pub use latency::{LatencyTracker, TranscriptionTiming};
+pub use live::{LiveSettings, LiveUpdate, PhraseFilters, Replaceable, VadThreshold};
 pub use orchestrator::{Pipeline, PipelineConfig, PipelineHandle};
 pub use post_processor::{
     PostProcessor, PostProcessorStation, VoiceCommandProcessor, build_post_processors,
 };
 pub use sink::{CollectorSink, InjectorSink, TextSink};
 pub use station::{Station, StationRunner};
+pub use streaming_station::StreamingStation;
+pub use text_filter::TextFilter;
 pub use transcriber_station::TranscriberStation;
 pub use types::{AudioChunk, AudioFrame, SinkEvent, TranscribedText, VadFrame};
 pub use vad_station::VadStation;
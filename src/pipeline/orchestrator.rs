@@ -2,18 +2,24 @@
 
 use crate::audio::recorder::AudioSource;
 use crate::audio::vad::{Clock, SystemClock, VadConfig};
+use crate::config::DenoiseConfig;
 use crate::correction::station::CorrectionStation;
 use crate::defaults;
 use crate::error::Result;
 use crate::ipc::protocol::DaemonEvent;
 use crate::pipeline::adaptive_chunker::AdaptiveChunkerConfig;
+use crate::pipeline::archive::{ArchiveSettings, ArchiveTap, SessionArchive};
 use crate::pipeline::error::{ErrorReporter, LogReporter};
 use crate::pipeline::latency::SessionContext;
+use crate::pipeline::live::{LiveSettings, Replaceable};
 use crate::pipeline::post_processor::{PostProcessor, PostProcessorStation};
 use crate::pipeline::sink::{SinkStation, TextSink};
 use crate::pipeline::station::StationRunner;
+use crate::pipeline::text_filter::TextFilter;
 use crate::pipeline::types::AudioFrame;
-use crate::pipeline::{ChunkerStation, TranscriberStation, VadStation};
+use crate::pipeline::{
+    ChunkerStation, DenoiseStation, StreamingStation, TranscriberStation, VadStation,
+};
 use crate::stt::transcriber::Transcriber;
 use crossbeam_channel::bounded;
 use std::collections::HashSet;
@@ -53,6 +59,14 @@ pub struct PipelineConfig {
     pub allowed_languages: Arc<std::sync::RwLock<Vec<String>>>,
     /// Minimum confidence threshold (live-updatable during recording)
     pub min_confidence: Arc<std::sync::RwLock<f32>>,
+    /// Track the session language instead of detecting each chunk alone
+    pub language_tracking: bool,
+    /// Noise suppression settings (live-updatable during recording)
+    pub denoise: Arc<std::sync::RwLock<DenoiseConfig>>,
+    /// Archive each session's audio, chunks and results (`[debug] archive_audio`)
+    pub archive: Option<ArchiveSettings>,
+    /// Update slots for settings changed while running (daemon config reload)
+    pub live: Option<LiveSettings>,
 }
 
 impl Default for PipelineConfig {
@@ -74,6 +88,10 @@ impl Default for PipelineConfig {
             event_tx: None,
             allowed_languages: Arc::new(std::sync::RwLock::new(Vec::new())),
             min_confidence: Arc::new(std::sync::RwLock::new(0.0)),
+            language_tracking: false,
+            denoise: Arc::new(std::sync::RwLock::new(DenoiseConfig::default())),
+            archive: None,
+            live: None,
         }
     }
 }
@@ -105,6 +123,42 @@ impl PipelineHandle {
             .as_ref()
             .and_then(|rx| rx.recv_timeout(Duration::from_secs(5)).ok().flatten());
 
+        self.join_threads();
+        result
+    }
+
+    /// Waits for a finite audio source to be fully processed and returns the sink's result.
+    ///
+    /// Once the source is exhausted the stations shut down one after another
+    /// as their input channels close, and the sink delivers its result last.
+    /// Blocks without a timeout, so only use this with finite sources.
+    pub fn wait(mut self) -> Option<String> {
+        let result = self
+            .result_rx
+            .take()
+            .and_then(|rx| rx.recv().ok().flatten());
+
+        self.running.store(false, Ordering::SeqCst);
+        self.join_threads();
+        result
+    }
+
+    /// Stops capture and waits until all captured audio has been transcribed.
+    ///
+    /// Unlike [`stop`](Self::stop), there is no timeout on in-flight
+    /// transcription, so a slow model still delivers the last chunk.
+    pub fn finish(mut self) -> Option<String> {
+        self.running.store(false, Ordering::SeqCst);
+        let result = self
+            .result_rx
+            .take()
+            .and_then(|rx| rx.recv().ok().flatten());
+        self.join_threads();
+        result
+    }
+
+    /// Join finished threads for up to 1s, then detach the rest.
+    fn join_threads(&mut self) {
         // Wait up to 1s more for threads to finish, joining completed ones
         // to detect panics (CLAUDE.md: "Cleanup/shutdown errors → eprintln! with context").
         let deadline = Instant::now() + Duration::from_secs(1);
@@ -144,8 +198,6 @@ impl PipelineHandle {
 
             thread::sleep(poll_interval);
         }
-
-        result
     }
 
     /// Returns true if the pipeline is running.
@@ -154,7 +206,9 @@ impl PipelineHandle {
     }
 }
 
-/// Audio pipeline: AudioSource → VAD → Chunker → Transcriber → [Correction] → TextSink.
+/// Audio pipeline: AudioSource → Denoise → VAD → Chunker → Transcriber → [Correction] → TextSink.
+///
+/// With a streaming backend, a [`StreamingStation`] takes the place of Chunker → Transcriber.
 pub struct Pipeline {
     config: PipelineConfig,
     error_reporter: Arc<dyn ErrorReporter>,
@@ -222,10 +276,22 @@ impl Pipeline {
 
         // Create channels between stations
         let (audio_tx, audio_rx) = bounded(self.config.audio_buffer);
+        let (denoise_tx, denoise_rx) = bounded(self.config.audio_buffer);
         let (vad_tx, vad_rx) = bounded(self.config.vad_buffer);
         let (chunk_tx, chunk_rx) = bounded(self.config.chunk_buffer);
         let (transcribe_tx, transcribe_rx) = bounded(self.config.transcribe_buffer);
 
+        let archive = self.config.archive.as_ref().and_then(|settings| {
+            SessionArchive::create(
+                settings,
+                self.config.sample_rate,
+                transcriber.model_name(),
+                &self.config.vad,
+            )
+            .inspect_err(|e| eprintln!("voicsh: session archive disabled: {e}"))
+            .ok()
+        });
+
         // Create stations
         let chunk_tx_gauge = chunk_tx.clone();
         let mut vad_station = VadStation::with_clock(self.config.vad, self.clock.clone())
@@ -242,21 +308,8 @@ impl Pipeline {
         if let Some(ref event_tx) = self.config.event_tx {
             vad_station = vad_station.with_event_sender(event_tx.clone());
         }
-
-        let chunker_station = ChunkerStation::with_clock(self.config.chunker, self.clock.clone())
-            .with_sample_rate(self.config.sample_rate)
-            .with_verbosity(self.config.verbosity)
-            .with_flush_tx(chunk_tx.clone());
-
-        let mut transcriber_station = TranscriberStation::new(transcriber.clone())
-            .with_verbose(self.config.verbosity >= 2)
-            .with_hallucination_filters(self.config.hallucination_filters.clone())
-            .with_suspect_phrases(self.config.suspect_phrases.clone())
-            .with_allowed_languages(self.config.allowed_languages.clone())
-            .with_min_confidence(self.config.min_confidence.clone());
-
-        if let Some(ref event_tx) = self.config.event_tx {
-            transcriber_station = transcriber_station.with_event_sender(event_tx.clone());
+        if let Some(ref live) = self.config.live {
+            vad_station = vad_station.with_threshold_updates(live.vad_threshold.clone());
         }
 
         // Create sink station with result channel and session context
@@ -271,28 +324,114 @@ impl Pipeline {
         if let Some(ref event_tx) = self.config.event_tx {
             sink_station = sink_station.with_event_sender(event_tx.clone());
         }
+        if let Some(ref live) = self.config.live {
+            sink_station = sink_station.with_sink_updates(live.sink.clone());
+        }
 
         // Spawn station runners
-        let vad_runner =
-            StationRunner::spawn(vad_station, audio_rx, vad_tx, self.error_reporter.clone());
-
-        let chunker_runner = StationRunner::spawn(
-            chunker_station,
-            vad_rx,
-            chunk_tx,
+        let denoise_runner = StationRunner::spawn(
+            DenoiseStation::new(self.config.denoise.clone()),
+            audio_rx,
+            denoise_tx,
             self.error_reporter.clone(),
         );
 
-        let transcriber_runner = StationRunner::spawn(
-            transcriber_station,
-            chunk_rx,
-            transcribe_tx,
+        let vad_runner = StationRunner::spawn(
+            ArchiveTap::new(vad_station, archive.clone()),
+            denoise_rx,
+            vad_tx,
             self.error_reporter.clone(),
         );
 
-        // Wire correction station between transcriber and post-processors (if present)
+        // Result filters, shared by both transcription paths
+        let mut text_filter = TextFilter::new()
+            .with_verbose(self.config.verbosity >= 2)
+            .with_hallucination_filters(self.config.hallucination_filters.clone())
+            .with_suspect_phrases(self.config.suspect_phrases.clone())
+            .with_allowed_languages(self.config.allowed_languages.clone())
+            .with_min_confidence(self.config.min_confidence.clone());
+        if let Some(ref event_tx) = self.config.event_tx {
+            text_filter = text_filter.with_event_sender(event_tx.clone());
+        }
+        if let Some(ref live) = self.config.live {
+            text_filter = text_filter.with_filter_updates(live.filters.clone());
+        }
+
         let mut extra_threads: Vec<JoinHandle<()>> = Vec::new();
-        let corrected_rx = if let Some(correction_station) = self.correction_station {
+        if transcriber.streaming().is_some() {
+            // Streaming backends get every frame and segment the speech themselves
+            let mut streaming_station = StreamingStation::new(transcriber.clone())
+                .with_sample_rate(self.config.sample_rate)
+                .with_filter(text_filter)
+                .with_flush_tx(transcribe_tx.clone());
+            if let Some(ref event_tx) = self.config.event_tx {
+                streaming_station = streaming_station.with_event_sender(event_tx.clone());
+            }
+            let streaming_runner = StationRunner::spawn(
+                streaming_station,
+                vad_rx,
+                transcribe_tx,
+                self.error_reporter.clone(),
+            );
+            extra_threads.push(thread::spawn(move || {
+                if let Err(msg) = streaming_runner.join() {
+                    eprintln!("voicsh: {msg}");
+                }
+            }));
+        } else {
+            let chunker_station =
+                ChunkerStation::with_clock(self.config.chunker, self.clock.clone())
+                    .with_sample_rate(self.config.sample_rate)
+                    .with_verbosity(self.config.verbosity)
+                    .with_flush_tx(chunk_tx.clone());
+
+            let mut transcriber_station = TranscriberStation::new(transcriber.clone())
+                .with_verbose(self.config.verbosity >= 2)
+                .with_language_tracking(self.config.language_tracking);
+            if let Some(ref event_tx) = self.config.event_tx {
+                transcriber_station = transcriber_station.with_event_sender(event_tx.clone());
+            }
+            let transcriber_station = transcriber_station.with_filter(text_filter);
+
+            let chunker_runner = StationRunner::spawn(
+                chunker_station,
+                vad_rx,
+                chunk_tx,
+                self.error_reporter.clone(),
+            );
+
+            let transcriber_runner = StationRunner::spawn(
+                ArchiveTap::new(transcriber_station, archive.clone()),
+                chunk_rx,
+                transcribe_tx,
+                self.error_reporter.clone(),
+            );
+
+            extra_threads.push(thread::spawn(move || {
+                if let Err(msg) = chunker_runner.join() {
+                    eprintln!("voicsh: {msg}");
+                }
+            }));
+            extra_threads.push(thread::spawn(move || {
+                if let Err(msg) = transcriber_runner.join() {
+                    eprintln!("voicsh: {msg}");
+                }
+            }));
+        }
+
+        // Wire correction station between transcriber and post-processors (if
+        // present, or if it may be switched on while running)
+        let correction_station = match &self.config.live {
+            Some(live) => Some(Replaceable::new(
+                self.correction_station,
+                live.correction.clone(),
+                "correction",
+            )),
+            None => self
+                .correction_station
+                .map(|station| Replaceable::new(Some(station), Default::default(), "correction")),
+        };
+        let corrected_rx = if let Some(correction_station) = correction_station {
             let (correction_tx, correction_rx) = bounded(self.config.transcribe_buffer);
             let correction_runner = StationRunner::spawn(
                 correction_station,
@@ -311,11 +450,14 @@ impl Pipeline {
         };
 
         // Wire post-processor between correction and sink (if any processors provided)
-        let sink_input_rx = if post_processors.is_empty() {
+        let sink_input_rx = if post_processors.is_empty() && self.config.live.is_none() {
             corrected_rx
         } else {
             let (post_tx, post_rx) = bounded(self.config.post_process_buffer);
-            let post_station = PostProcessorStation::new(post_processors);
+            let mut post_station = PostProcessorStation::new(post_processors);
+            if let Some(ref live) = self.config.live {
+                post_station = post_station.with_updates(live.post_processors.clone());
+            }
             let post_runner = StationRunner::spawn(
                 post_station,
                 corrected_rx,
@@ -365,6 +507,7 @@ impl Pipeline {
         // Spawn audio polling thread
         let audio_running = running.clone();
         let audio_sequence = sequence.clone();
+        let audio_archive = archive;
         let audio_handle = thread::spawn(move || {
             // Poll audio source at ~60Hz (every 16ms)
             let poll_interval = Duration::from_millis(16);
@@ -405,6 +548,10 @@ impl Pipeline {
                     continue;
                 }
 
+                if let Some(ref archive) = audio_archive {
+                    archive.record_audio(&samples);
+                }
+
                 // Create audio frame
                 let frame = AudioFrame::new(
                     samples,
@@ -443,17 +590,12 @@ impl Pipeline {
 
         // Wrap runner join handles
         threads.push(thread::spawn(move || {
-            if let Err(msg) = vad_runner.join() {
-                eprintln!("voicsh: {msg}");
-            }
-        }));
-        threads.push(thread::spawn(move || {
-            if let Err(msg) = chunker_runner.join() {
+            if let Err(msg) = denoise_runner.join() {
                 eprintln!("voicsh: {msg}");
             }
         }));
         threads.push(thread::spawn(move || {
-            if let Err(msg) = transcriber_runner.join() {
+            if let Err(msg) = vad_runner.join() {
                 eprintln!("voicsh: {msg}");
             }
         }));
@@ -717,6 +859,166 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pipeline_wait_processes_finite_source_to_end() {
+        let config = PipelineConfig {
+            quiet: true,
+            verbosity: 0,
+            auto_level: false,
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new(config);
+
+        // 20 reads of 100ms loud audio, then the source reports exhaustion
+        let audio_source = Box::new(TestAudioSource::new(vec![10000i16; 1600], 20));
+        let transcriber = Arc::new(MockTranscriber::new("test-model").with_response("hello"));
+        let sink = Box::new(CollectorSink::new());
+
+        let handle = pipeline.start(audio_source, transcriber, sink).unwrap();
+        let result = handle.wait();
+        assert_eq!(result.as_deref(), Some("hello"));
+    }
+
+    /// Streaming backend whose sessions report how much audio they received.
+    struct CountingStream;
+
+    struct CountingSession {
+        samples: usize,
+    }
+
+    impl crate::stt::streaming::StreamingSession for CountingSession {
+        fn send_audio(&mut self, samples: &[i16]) -> Result<()> {
+            self.samples += samples.len();
+            Ok(())
+        }
+
+        fn poll(&mut self) -> Result<Vec<crate::stt::streaming::StreamingUpdate>> {
+            Ok(Vec::new())
+        }
+
+        fn finish(
+            self: Box<Self>,
+            _timeout: Duration,
+        ) -> Result<Vec<crate::stt::streaming::StreamingUpdate>> {
+            let text = format!("streamed {} samples", self.samples);
+            Ok(vec![crate::stt::streaming::StreamingUpdate::Final(
+                crate::stt::transcriber::TranscriptionResult::from_text(text),
+            )])
+        }
+    }
+
+    impl crate::stt::streaming::StreamingTranscriber for CountingStream {
+        fn open(&self) -> Result<Box<dyn crate::stt::streaming::StreamingSession>> {
+            Ok(Box::new(CountingSession { samples: 0 }))
+        }
+    }
+
+    impl Transcriber for CountingStream {
+        fn transcribe(
+            &self,
+            _audio: &[i16],
+        ) -> Result<crate::stt::transcriber::TranscriptionResult> {
+            panic!("streaming backends must not be fed chunks");
+        }
+
+        fn model_name(&self) -> &str {
+            "counting-stream"
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+
+        fn streaming(&self) -> Option<&dyn crate::stt::streaming::StreamingTranscriber> {
+            Some(self)
+        }
+    }
+
+    #[test]
+    fn test_pipeline_streams_every_frame_to_streaming_backend() {
+        let config = PipelineConfig {
+            quiet: true,
+            auto_level: false,
+            ..Default::default()
+        };
+        // Quiet audio: a chunked backend would never see it
+        let audio_source = Box::new(TestAudioSource::new(vec![0i16; 1600], 20));
+        let handle = Pipeline::new(config)
+            .start(
+                audio_source,
+                Arc::new(CountingStream),
+                Box::new(CollectorSink::new()),
+            )
+            .unwrap();
+        assert_eq!(handle.wait().as_deref(), Some("streamed 32000 samples"));
+    }
+
+    #[test]
+    fn test_pipeline_archives_session_when_enabled() {
+        let root = tempfile::tempdir().unwrap();
+        let config = PipelineConfig {
+            quiet: true,
+            verbosity: 0,
+            auto_level: false,
+            archive: Some(ArchiveSettings {
+                dir: root.path().to_path_buf(),
+                max_sessions: 0,
+                max_bytes: 0,
+            }),
+            ..Default::default()
+        };
+
+        let audio_source = Box::new(TestAudioSource::new(vec![10000i16; 1600], 20));
+        let transcriber = Arc::new(MockTranscriber::new("test-model").with_response("hello"));
+        let handle = Pipeline::new(config)
+            .start(audio_source, transcriber, Box::new(CollectorSink::new()))
+            .unwrap();
+        assert_eq!(handle.wait().as_deref(), Some("hello"));
+
+        let sessions = crate::pipeline::archive::list_sessions(root.path()).unwrap();
+        assert_eq!(sessions.len(), 1);
+        let manifest = sessions[0].manifest.as_ref().unwrap();
+        assert_eq!(manifest.model, "test-model");
+        assert_eq!(manifest.duration_ms, 2000);
+        assert!(!manifest.speech.is_empty());
+        assert!(!manifest.chunks.is_empty());
+        assert_eq!(manifest.text(), "hello");
+        for chunk in &manifest.chunks {
+            assert!(sessions[0].path.join(&chunk.file).is_file());
+        }
+    }
+
+    #[test]
+    fn test_pipeline_finish_drains_paced_live_source() {
+        use crate::audio::paced::PacedSource;
+
+        let config = PipelineConfig {
+            quiet: true,
+            verbosity: 0,
+            auto_level: false,
+            ..Default::default()
+        };
+        // 2s of loud audio played back at 20x, looking like a live microphone
+        let file = Box::new(TestAudioSource::new(vec![10000i16; 1600], 20));
+        let source = PacedSource::new(file, 16000, 20.0).unwrap();
+        let finished = source.finished_flag();
+        let transcriber = Arc::new(MockTranscriber::new("test-model").with_response("hello"));
+        let handle = Pipeline::new(config)
+            .start(
+                Box::new(source),
+                transcriber,
+                Box::new(CollectorSink::new()),
+            )
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !finished.load(Ordering::SeqCst) {
+            assert!(Instant::now() < deadline, "paced source never finished");
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(handle.finish().as_deref(), Some("hello"));
+    }
+
     #[test]
     fn test_pipeline_start_and_stop_integration() {
         let config = PipelineConfig {
@@ -6,6 +6,7 @@
 use crate::config::Config;
 use crate::ipc::protocol::TextOrigin;
 use crate::pipeline::error::StationError;
+use crate::pipeline::live::LiveUpdate;
 use crate::pipeline::station::Station;
 use crate::pipeline::types::TranscribedText;
 use std::collections::HashMap;
@@ -32,11 +33,22 @@ pub trait PostProcessor: Send + 'static {
 /// Pipeline station that applies a chain of post-processors to transcribed text.
 pub struct PostProcessorStation {
     processors: Vec<Box<dyn PostProcessor>>,
+    /// Replacement chains posted while running (config reload)
+    updates: Option<LiveUpdate<Vec<Box<dyn PostProcessor>>>>,
 }
 
 impl PostProcessorStation {
     pub fn new(processors: Vec<Box<dyn PostProcessor>>) -> Self {
-        Self { processors }
+        Self {
+            processors,
+            updates: None,
+        }
+    }
+
+    /// Pick up replacement chains posted to `updates` while running.
+    pub fn with_updates(mut self, updates: LiveUpdate<Vec<Box<dyn PostProcessor>>>) -> Self {
+        self.updates = Some(updates);
+        self
     }
 }
 
@@ -48,6 +60,9 @@ impl Station for PostProcessorStation {
         &mut self,
         mut input: TranscribedText,
     ) -> Result<Option<TranscribedText>, StationError> {
+        if let Some(processors) = self.updates.as_ref().and_then(LiveUpdate::take) {
+            self.processors = processors;
+        }
         for processor in &mut self.processors {
             let (new_text, events) = processor.process_with_events(&input.text);
             if new_text != input.text {
@@ -245,7 +260,7 @@ impl VoiceCommandProcessor {
                 (phrase, chars, action)
             })
             .collect();
-        commands.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        commands.sort_by_key(|c| std::cmp::Reverse(c.0.len()));
 
         Self {
             commands,
@@ -4,8 +4,10 @@ use crate::ipc::protocol::DaemonEvent;
 use crate::output::render_event;
 use crate::pipeline::error::StationError;
 use crate::pipeline::latency::{LatencyTracker, SessionContext, TranscriptionTiming};
+use crate::pipeline::live::LiveUpdate;
 use crate::pipeline::station::Station;
 use crate::pipeline::types::{SinkEvent, TranscribedText};
+use crate::transcript::{Transcript, TranscriptFormat};
 #[cfg(feature = "portal")]
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -26,6 +28,16 @@ pub trait TextSink: Send + 'static {
         Ok(())
     }
 
+    /// Handle a full transcription result, including timing and segments.
+    /// Default implementation dispatches to `handle_events` or `handle`.
+    fn handle_transcription(&mut self, text: &TranscribedText) -> crate::error::Result<()> {
+        if !text.events.is_empty() {
+            self.handle_events(&text.events)
+        } else {
+            self.handle(&text.text)
+        }
+    }
+
     /// Called on pipeline shutdown. Return accumulated text if applicable.
     fn finish(&mut self) -> Option<String> {
         None
@@ -47,6 +59,8 @@ pub(crate) struct SinkStation {
     latency_tracker: LatencyTracker,
     transcription_count: usize,
     event_tx: Option<crossbeam_channel::Sender<DaemonEvent>>,
+    /// Replacement sinks posted while running (config reload)
+    sink_updates: Option<LiveUpdate<Box<dyn TextSink>>>,
 }
 
 impl SinkStation {
@@ -64,9 +78,16 @@ impl SinkStation {
             latency_tracker: LatencyTracker::new(),
             transcription_count: 0,
             event_tx: None,
+            sink_updates: None,
         }
     }
 
+    /// Switch to sinks posted to `updates` while running.
+    pub(crate) fn with_sink_updates(mut self, updates: LiveUpdate<Box<dyn TextSink>>) -> Self {
+        self.sink_updates = Some(updates);
+        self
+    }
+
     pub(crate) fn with_session_context(mut self, context: SessionContext) -> Self {
         self.latency_tracker = LatencyTracker::with_context(context);
         self
@@ -89,6 +110,7 @@ fn transcription_event(text: &TranscribedText, wait_ms: Option<u32>) -> DaemonEv
         raw_text: text.raw_text.clone(),
         text_origin: text.text_origin.clone(),
         corrector_name: text.corrector_name.clone(),
+        words: text.words.clone(),
     }
 }
 
@@ -101,16 +123,16 @@ impl Station for SinkStation {
     }
 
     fn process(&mut self, text: TranscribedText) -> Result<Option<()>, StationError> {
+        if let Some(sink) = self.sink_updates.as_ref().and_then(LiveUpdate::take) {
+            self.sink = sink;
+        }
+
         // Skip if both text and events are empty
         if text.text.trim().is_empty() && text.events.is_empty() {
             return Ok(None);
         }
 
-        let handle_result = if !text.events.is_empty() {
-            self.sink.handle_events(&text.events)
-        } else {
-            self.sink.handle(&text.text)
-        };
+        let handle_result = self.sink.handle_transcription(&text);
 
         match handle_result {
             Ok(()) => {
@@ -328,6 +350,43 @@ impl TextSink for CollectorSink {
     }
 }
 
+/// Transcript sink — collects timed segments and renders them on finish
+/// (SRT, WebVTT, JSON, TSV or plain text).
+pub struct TranscriptSink {
+    transcript: Transcript,
+    format: TranscriptFormat,
+}
+
+impl TranscriptSink {
+    pub fn new(format: TranscriptFormat) -> Self {
+        Self {
+            transcript: Transcript::new(),
+            format,
+        }
+    }
+}
+
+impl TextSink for TranscriptSink {
+    fn handle(&mut self, text: &str) -> crate::error::Result<()> {
+        self.transcript
+            .push(&TranscribedText::new(text.to_string()));
+        Ok(())
+    }
+
+    fn handle_transcription(&mut self, text: &TranscribedText) -> crate::error::Result<()> {
+        self.transcript.push(text);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Option<String> {
+        Some(self.transcript.render(self.format))
+    }
+
+    fn name(&self) -> &'static str {
+        "transcript"
+    }
+}
+
 /// Pipe mode sink — writes transcribed text to stdout.
 pub struct StdoutSink;
 
@@ -576,6 +635,44 @@ mod tests {
         assert_eq!(station.name(), "collector");
     }
 
+    #[test]
+    fn transcript_sink_renders_segments_on_finish() {
+        let mut sink = TranscriptSink::new(TranscriptFormat::Srt);
+        let mut text = TranscribedText::new("Good morning.".to_string());
+        text.segments = vec![crate::stt::transcriber::Segment {
+            start_ms: 61_000,
+            end_ms: 62_500,
+            text: "Good morning.".to_string(),
+            tokens: vec![],
+        }];
+        sink.handle_transcription(&text).unwrap();
+        assert_eq!(
+            sink.finish().as_deref(),
+            Some("1\n00:01:01,000 --> 00:01:02,500\nGood morning.\n\n")
+        );
+        assert_eq!(sink.name(), "transcript");
+    }
+
+    #[test]
+    fn sink_station_passes_segments_to_sink() {
+        let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+        let sink = TranscriptSink::new(TranscriptFormat::Tsv);
+        let mut station = SinkStation::new(Box::new(sink), true, 0, result_tx);
+        let mut text = TranscribedText::new("hi".to_string());
+        text.segments = vec![crate::stt::transcriber::Segment {
+            start_ms: 500,
+            end_ms: 900,
+            text: "hi".to_string(),
+            tokens: vec![],
+        }];
+        station.process(text).unwrap();
+        station.shutdown();
+        assert_eq!(
+            result_rx.recv().unwrap().as_deref(),
+            Some("start\tend\ttext\n500\t900\thi\n")
+        );
+    }
+
     #[test]
     fn collector_sink_name() {
         let sink = CollectorSink::new();
@@ -0,0 +1,436 @@
+//! Streaming transcription station.
+//!
+//! Replaces the chunker and transcriber stations when the backend recognizes
+//! a continuous stream (see [`crate::stt::streaming`]): every VAD frame is
+//! forwarded to an open session, interim hypotheses go out as
+//! [`DaemonEvent::PartialTranscription`] and final results continue down the
+//! pipeline as [`TranscribedText`], through the same [`TextFilter`] as
+//! chunked results.
+
+use crate::ipc::protocol::DaemonEvent;
+use crate::pipeline::error::StationError;
+use crate::pipeline::station::Station;
+use crate::pipeline::text_filter::TextFilter;
+use crate::pipeline::types::{TranscribedText, VadFrame};
+use crate::stt::streaming::{StreamingSession, StreamingUpdate};
+use crate::stt::transcriber::{Segment, Transcriber};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Wait between attempts to (re)open a failed stream.
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// How long shutdown waits for the backend's last results.
+///
+/// Stays below the 5s [`PipelineHandle::stop`](crate::pipeline::PipelineHandle::stop)
+/// allows for the sink result.
+const FINISH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Streams audio frames to a streaming backend.
+pub struct StreamingStation {
+    transcriber: Arc<dyn Transcriber>,
+    session: Option<Box<dyn StreamingSession>>,
+    /// Stream offset (ms) at which the current session started.
+    session_start_ms: u64,
+    /// Earliest time to try opening a session after a failure.
+    retry_at: Option<Instant>,
+    sample_rate: u32,
+    samples_seen: u64,
+    /// Final results not yet passed on (at most one leaves per frame).
+    pending: VecDeque<TranscribedText>,
+    last_partial: String,
+    filter: TextFilter,
+    event_tx: Option<crossbeam_channel::Sender<DaemonEvent>>,
+    /// Output channel for results that arrive during shutdown.
+    flush_tx: Option<crossbeam_channel::Sender<TranscribedText>>,
+}
+
+impl StreamingStation {
+    pub fn new(transcriber: Arc<dyn Transcriber>) -> Self {
+        Self {
+            transcriber,
+            session: None,
+            session_start_ms: 0,
+            retry_at: None,
+            sample_rate: crate::defaults::SAMPLE_RATE,
+            samples_seen: 0,
+            pending: VecDeque::new(),
+            last_partial: String::new(),
+            filter: TextFilter::new(),
+            event_tx: None,
+            flush_tx: None,
+        }
+    }
+
+    /// Sets a custom sample rate (used to place results on the stream timeline).
+    pub fn with_sample_rate(mut self, rate: u32) -> Self {
+        self.sample_rate = rate;
+        self
+    }
+
+    /// Drop final results that `filter` rejects.
+    pub fn with_filter(mut self, filter: TextFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sends interim results as partial-transcription events.
+    pub fn with_event_sender(mut self, tx: crossbeam_channel::Sender<DaemonEvent>) -> Self {
+        self.event_tx = Some(tx);
+        self
+    }
+
+    /// Set the output channel used for results that arrive during shutdown.
+    pub fn with_flush_tx(mut self, tx: crossbeam_channel::Sender<TranscribedText>) -> Self {
+        self.flush_tx = Some(tx);
+        self
+    }
+
+    fn stream_ms(&self) -> u64 {
+        self.samples_seen * 1000 / u64::from(self.sample_rate.max(1))
+    }
+
+    /// Open a session unless one is open or the last failure is too recent.
+    fn ensure_session(&mut self) -> Result<(), StationError> {
+        if self.session.is_some() || self.retry_at.is_some_and(|at| Instant::now() < at) {
+            return Ok(());
+        }
+        let Some(streaming) = self.transcriber.streaming() else {
+            return Err(StationError::fatal(format!(
+                "{} does not support streaming",
+                self.transcriber.model_name()
+            )));
+        };
+        match streaming.open() {
+            Ok(session) => {
+                self.session = Some(session);
+                self.session_start_ms = self.stream_ms();
+                self.retry_at = None;
+                Ok(())
+            }
+            Err(e) => Err(self.fail(format!("Streaming transcription unavailable: {e}"))),
+        }
+    }
+
+    /// Drop the session and hold off reconnecting for a while.
+    fn fail(&mut self, message: String) -> StationError {
+        self.session = None;
+        self.retry_at = Some(Instant::now() + RECONNECT_DELAY);
+        StationError::recoverable(message)
+    }
+
+    fn handle_updates(&mut self, updates: Vec<StreamingUpdate>) {
+        for update in updates {
+            match update {
+                StreamingUpdate::Interim(text) => self.send_partial(text),
+                StreamingUpdate::Final(result) => {
+                    self.send_partial(String::new());
+                    let text = result.text.trim().to_string();
+                    if !self
+                        .filter
+                        .passes(&text, &result.language, result.confidence)
+                    {
+                        continue;
+                    }
+                    let offset = self.session_start_ms;
+                    let mut transcribed = TranscribedText::new(text);
+                    transcribed.language = result.language;
+                    transcribed.confidence = result.confidence;
+                    transcribed.token_probabilities = result.token_probabilities;
+                    transcribed.segments = result
+                        .segments
+                        .into_iter()
+                        .map(|segment| Segment {
+                            start_ms: segment.start_ms + offset,
+                            end_ms: segment.end_ms + offset,
+                            ..segment
+                        })
+                        .collect();
+                    transcribed.words = result
+                        .words
+                        .into_iter()
+                        .map(|mut word| {
+                            word.start_ms += offset;
+                            word.end_ms += offset;
+                            word
+                        })
+                        .collect();
+                    self.pending.push_back(transcribed);
+                }
+            }
+        }
+    }
+
+    /// Report the current hypothesis (empty once it was finalized).
+    fn send_partial(&mut self, text: String) {
+        if text == self.last_partial {
+            return;
+        }
+        if let Some(ref tx) = self.event_tx
+            && tx
+                .try_send(DaemonEvent::PartialTranscription { text: text.clone() })
+                .is_err()
+        {
+            // Channel full or closed - OK to ignore
+        }
+        self.last_partial = text;
+    }
+}
+
+impl Station for StreamingStation {
+    type Input = VadFrame;
+    type Output = TranscribedText;
+
+    fn name(&self) -> &'static str {
+        "streaming"
+    }
+
+    fn process(&mut self, frame: VadFrame) -> Result<Option<TranscribedText>, StationError> {
+        let opened = self.ensure_session();
+        self.samples_seen += frame.samples.len() as u64;
+        opened?;
+
+        if let Some(session) = self.session.as_mut() {
+            let polled = session
+                .send_audio(&frame.samples)
+                .and_then(|()| session.poll());
+            match polled {
+                Ok(updates) => self.handle_updates(updates),
+                Err(e) => return Err(self.fail(format!("Streaming transcription failed: {e}"))),
+            }
+        }
+        Ok(self.pending.pop_front())
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(session) = self.session.take() {
+            match session.finish(FINISH_TIMEOUT) {
+                Ok(updates) => self.handle_updates(updates),
+                Err(e) => eprintln!("voicsh: streaming transcription did not finish: {e}"),
+            }
+        }
+        let Some(tx) = self.flush_tx.take() else {
+            return;
+        };
+        for text in self.pending.drain(..) {
+            if tx.send(text).is_err() {
+                eprintln!("voicsh: streaming shutdown — output receiver already dropped");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Result, VoicshError};
+    use crate::stt::streaming::StreamingTranscriber;
+    use crate::stt::transcriber::{TranscriptionResult, Word};
+    use std::sync::Mutex;
+
+    /// Scripted backend: each session replays `updates` per call to `poll`.
+    struct ScriptedBackend {
+        updates: Arc<Mutex<VecDeque<Result<Vec<StreamingUpdate>>>>>,
+        at_finish: Vec<StreamingUpdate>,
+        opened: Arc<Mutex<u32>>,
+    }
+
+    struct ScriptedSession {
+        updates: Arc<Mutex<VecDeque<Result<Vec<StreamingUpdate>>>>>,
+        at_finish: Vec<StreamingUpdate>,
+    }
+
+    impl StreamingSession for ScriptedSession {
+        fn send_audio(&mut self, _samples: &[i16]) -> Result<()> {
+            Ok(())
+        }
+
+        fn poll(&mut self) -> Result<Vec<StreamingUpdate>> {
+            self.updates
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or(Ok(Vec::new()))
+        }
+
+        fn finish(self: Box<Self>, _timeout: Duration) -> Result<Vec<StreamingUpdate>> {
+            Ok(self.at_finish)
+        }
+    }
+
+    impl StreamingTranscriber for ScriptedBackend {
+        fn open(&self) -> Result<Box<dyn StreamingSession>> {
+            *self.opened.lock().unwrap() += 1;
+            Ok(Box::new(ScriptedSession {
+                updates: self.updates.clone(),
+                at_finish: self.at_finish.clone(),
+            }))
+        }
+    }
+
+    impl Transcriber for ScriptedBackend {
+        fn transcribe(&self, _audio: &[i16]) -> Result<TranscriptionResult> {
+            unreachable!("streaming backends are fed frames")
+        }
+
+        fn model_name(&self) -> &str {
+            "scripted"
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+
+        fn streaming(&self) -> Option<&dyn StreamingTranscriber> {
+            Some(self)
+        }
+    }
+
+    fn backend(
+        updates: Vec<Result<Vec<StreamingUpdate>>>,
+        at_finish: Vec<StreamingUpdate>,
+    ) -> (Arc<ScriptedBackend>, Arc<Mutex<u32>>) {
+        let opened = Arc::new(Mutex::new(0));
+        let backend = Arc::new(ScriptedBackend {
+            updates: Arc::new(Mutex::new(updates.into())),
+            at_finish,
+            opened: opened.clone(),
+        });
+        (backend, opened)
+    }
+
+    fn final_result(text: &str, start_ms: u64) -> StreamingUpdate {
+        let mut result = TranscriptionResult::from_text(text.to_string());
+        result.language = "en".to_string();
+        result.segments = vec![Segment {
+            start_ms,
+            end_ms: start_ms + 500,
+            text: text.to_string(),
+            tokens: vec![],
+        }];
+        result.words = vec![Word {
+            text: text.to_string(),
+            start_ms,
+            end_ms: start_ms + 500,
+            probability: 0.9,
+            model: None,
+        }];
+        StreamingUpdate::Final(result)
+    }
+
+    /// 100ms of audio at 16 kHz.
+    fn frame() -> VadFrame {
+        VadFrame::new(vec![0; 1600], Instant::now(), true, 0.5)
+    }
+
+    #[test]
+    fn test_interims_become_partial_events_and_finals_flow_on() {
+        let (backend, _) = backend(
+            vec![
+                Ok(vec![StreamingUpdate::Interim("hel".to_string())]),
+                Ok(vec![
+                    StreamingUpdate::Interim("hello".to_string()),
+                    final_result("hello world", 0),
+                ]),
+            ],
+            vec![],
+        );
+        let (event_tx, event_rx) = crossbeam_channel::unbounded();
+        let mut station = StreamingStation::new(backend).with_event_sender(event_tx);
+
+        assert!(station.process(frame()).unwrap().is_none());
+        let text = station.process(frame()).unwrap().unwrap();
+        assert_eq!(text.text, "hello world");
+        assert_eq!(text.language, "en");
+
+        let partials: Vec<String> = event_rx
+            .try_iter()
+            .map(|event| match event {
+                DaemonEvent::PartialTranscription { text } => text,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        // The final clears the hypothesis
+        assert_eq!(partials, vec!["hel", "hello", ""]);
+    }
+
+    #[test]
+    fn test_reconnects_and_offsets_results_onto_the_stream() {
+        let (backend, opened) = backend(
+            vec![
+                Err(VoicshError::TranscriptionServer {
+                    message: "connection reset".to_string(),
+                }),
+                Ok(vec![final_result("again", 100)]),
+            ],
+            vec![],
+        );
+        let mut station = StreamingStation::new(backend);
+
+        assert!(station.process(frame()).is_err());
+        // Within the reconnect delay frames are dropped quietly
+        assert!(station.process(frame()).unwrap().is_none());
+        assert_eq!(*opened.lock().unwrap(), 1);
+
+        station.retry_at = Some(Instant::now());
+        let text = station.process(frame()).unwrap().unwrap();
+        assert_eq!(*opened.lock().unwrap(), 2);
+        // The new session started 200ms into the stream
+        assert_eq!(text.segments[0].start_ms, 300);
+        assert_eq!(text.words[0].end_ms, 800);
+    }
+
+    #[test]
+    fn test_shutdown_flushes_final_results() {
+        let (backend, _) = backend(
+            vec![],
+            vec![final_result("first", 0), final_result("second", 600)],
+        );
+        let (flush_tx, flush_rx) = crossbeam_channel::unbounded();
+        let mut station = StreamingStation::new(backend).with_flush_tx(flush_tx);
+
+        assert!(station.process(frame()).unwrap().is_none());
+        station.shutdown();
+        let flushed: Vec<String> = flush_rx.try_iter().map(|t| t.text).collect();
+        assert_eq!(flushed, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_finals_go_through_text_filter() {
+        let (backend, _) = backend(
+            vec![Ok(vec![
+                final_result("Thank you.", 0),
+                final_result("bonjour", 500),
+                final_result("keep this", 1000),
+            ])],
+            vec![],
+        );
+        let (event_tx, event_rx) = crossbeam_channel::unbounded();
+        let filter = TextFilter::new()
+            .with_hallucination_filters(["thank you".to_string()].into())
+            .with_allowed_languages(Arc::new(std::sync::RwLock::new(vec!["fr".to_string()])))
+            .with_event_sender(event_tx);
+        let mut station = StreamingStation::new(backend).with_filter(filter);
+
+        // All finals are tagged "en", so the allowlist drops what the filter keeps
+        assert!(station.process(frame()).unwrap().is_none());
+        assert!(station.process(frame()).unwrap().is_none());
+        let reasons: Vec<String> = event_rx
+            .try_iter()
+            .filter_map(|event| match event {
+                DaemonEvent::TranscriptionDropped { reason, .. } => Some(reason),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "hallucination filter",
+                "language 'en' not in allowlist",
+                "language 'en' not in allowlist",
+            ]
+        );
+    }
+}
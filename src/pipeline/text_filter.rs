@@ -0,0 +1,192 @@
+//! Filters applied to transcribed text before it leaves the transcription stage.
+//!
+//! Shared by [`TranscriberStation`](crate::pipeline::TranscriberStation) and
+//! [`StreamingStation`](crate::pipeline::StreamingStation), so chunked and
+//! streaming backends drop the same hallucinations, languages and
+//! low-confidence results.
+
+use crate::config::FILTER_PUNCTUATION;
+use crate::ipc::protocol::DaemonEvent;
+use crate::output::render_event;
+use crate::pipeline::live::{LiveUpdate, PhraseFilters};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+/// At or above this confidence, suspect words pass through as real speech.
+/// Below this threshold, suspect words are dropped; the display tier (silent
+/// vs. strikethrough) is handled by `HALLUCINATION_SUPPRESS_CONFIDENCE` in output.rs.
+const SUSPECT_PASS_THRESHOLD: f32 = 0.90;
+
+/// Decides which transcription results are kept.
+pub struct TextFilter {
+    verbose: bool,
+    hallucination_filters: HashSet<String>,
+    suspect_phrases: HashSet<String>,
+    allowed_languages: Arc<RwLock<Vec<String>>>,
+    min_confidence: Arc<RwLock<f32>>,
+    event_tx: Option<crossbeam_channel::Sender<DaemonEvent>>,
+    /// Filter changes posted while running (config reload)
+    filter_updates: Option<LiveUpdate<PhraseFilters>>,
+}
+
+impl Default for TextFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextFilter {
+    /// Creates a filter that keeps everything except empty and punctuation-only text.
+    pub fn new() -> Self {
+        Self {
+            verbose: false,
+            hallucination_filters: HashSet::new(),
+            suspect_phrases: HashSet::new(),
+            allowed_languages: Arc::new(RwLock::new(Vec::new())),
+            min_confidence: Arc::new(RwLock::new(0.0)),
+            event_tx: None,
+            filter_updates: None,
+        }
+    }
+
+    /// Print dropped results to stderr.
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Set pre-resolved hallucination filter phrases.
+    ///
+    /// Expects an already-normalized `HashSet` (lowercased, punctuation-stripped
+    /// variants included) as produced by `resolve_hallucination_filters`.
+    pub fn with_hallucination_filters(mut self, filters: HashSet<String>) -> Self {
+        self.hallucination_filters = filters;
+        self
+    }
+
+    /// Set pre-resolved suspect phrases for confidence-gated soft filtering.
+    ///
+    /// Expects an already-normalized `HashSet` (lowercased, punctuation-stripped
+    /// variants included) as produced by `resolve_suspect_phrases`.
+    pub fn with_suspect_phrases(mut self, phrases: HashSet<String>) -> Self {
+        self.suspect_phrases = phrases;
+        self
+    }
+
+    /// Pick up hallucination filter changes posted to `updates` while running.
+    pub fn with_filter_updates(mut self, updates: LiveUpdate<PhraseFilters>) -> Self {
+        self.filter_updates = Some(updates);
+        self
+    }
+
+    /// Set allowed languages for filtering.
+    pub fn with_allowed_languages(mut self, langs: Arc<RwLock<Vec<String>>>) -> Self {
+        self.allowed_languages = langs;
+        self
+    }
+
+    /// Set minimum confidence threshold.
+    pub fn with_min_confidence(mut self, min: Arc<RwLock<f32>>) -> Self {
+        self.min_confidence = min;
+        self
+    }
+
+    /// Emit a `TranscriptionDropped` event for every dropped result.
+    pub fn with_event_sender(mut self, tx: crossbeam_channel::Sender<DaemonEvent>) -> Self {
+        self.event_tx = Some(tx);
+        self
+    }
+
+    /// Current language allowlist (empty: all languages).
+    pub fn allowed_languages(&self) -> Vec<String> {
+        // If the write-side thread panicked while holding the lock, recover the last
+        // consistent value rather than cascading the panic into the audio hot path.
+        self.allowed_languages
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Whether `text` (already stripped of annotations) should be kept.
+    ///
+    /// Dropped results are reported, except empty and punctuation-only text.
+    pub fn passes(&mut self, text: &str, language: &str, confidence: f32) -> bool {
+        if let Some(filters) = self.filter_updates.as_ref().and_then(LiveUpdate::take) {
+            self.hallucination_filters = filters.hallucination_filters;
+            self.suspect_phrases = filters.suspect_phrases;
+        }
+
+        // Skip empty or punctuation-only results (e.g. "...", "…", ",,")
+        if text
+            .trim_matches(|c: char| {
+                c.is_ascii_punctuation()
+                    || c == '…'
+                    || c == '。'
+                    || c == '、'
+                    || c == '！'
+                    || c == '？'
+            })
+            .is_empty()
+        {
+            return false;
+        }
+
+        match self.drop_reason(text, language, confidence) {
+            Some(reason) => {
+                let event = DaemonEvent::TranscriptionDropped {
+                    text: text.to_string(),
+                    language: language.to_string(),
+                    confidence,
+                    reason,
+                };
+                if self.verbose {
+                    render_event(&event);
+                }
+                if let Some(ref tx) = self.event_tx {
+                    // Channel full or closed - OK to ignore
+                    tx.try_send(event).ok();
+                }
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn drop_reason(&self, text: &str, language: &str, confidence: f32) -> Option<String> {
+        // Hallucinated phrases and suspect words (O(1) lookup; both original and
+        // stripped forms are pre-stored in the sets by resolve_*).
+        let lower = text.to_lowercase();
+        let stripped = lower.trim_end_matches(FILTER_PUNCTUATION);
+        if self.hallucination_filters.contains(&lower)
+            || self.hallucination_filters.contains(stripped)
+        {
+            return Some("hallucination filter".into());
+        }
+        // Short filler words that could be real speech but are likely
+        // hallucinations when confidence is low.
+        if (self.suspect_phrases.contains(&lower) || self.suspect_phrases.contains(stripped))
+            && confidence < SUSPECT_PASS_THRESHOLD
+        {
+            return Some("suspect word".into());
+        }
+
+        let allowed_languages = self.allowed_languages();
+        if !allowed_languages.is_empty()
+            && !language.is_empty()
+            && !allowed_languages.iter().any(|l| l == language)
+        {
+            return Some(format!("language '{language}' not in allowlist"));
+        }
+
+        let min_confidence = *self
+            .min_confidence
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        if min_confidence > 0.0 && confidence < min_confidence {
+            return Some(format!(
+                "confidence {confidence:.2} below threshold {min_confidence:.2}"
+            ));
+        }
+        None
+    }
+}
@@ -1,21 +1,18 @@
 //! Transcriber station that converts audio chunks to text via Whisper.
 
-use crate::config::FILTER_PUNCTUATION;
 use crate::ipc::protocol::DaemonEvent;
 use crate::output::{clear_line, render_event};
 use crate::pipeline::error::StationError;
+use crate::pipeline::live::{LiveUpdate, PhraseFilters};
 use crate::pipeline::station::Station;
+use crate::pipeline::text_filter::TextFilter;
 use crate::pipeline::types::{AudioChunk, TranscribedText};
-use crate::stt::transcriber::Transcriber;
+use crate::stt::language_tracker::LanguageTracker;
+use crate::stt::transcriber::{Route, Segment, Transcriber, TranscriptionResult, Word};
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
 
-/// At or above this confidence, suspect words pass through as real speech.
-/// Below this threshold, suspect words are dropped; the display tier (silent
-/// vs. strikethrough) is handled by `HALLUCINATION_SUPPRESS_CONFIDENCE` in output.rs.
-const SUSPECT_PASS_THRESHOLD: f32 = 0.90;
-
 /// Strips Whisper non-speech annotations in any language.
 ///
 /// Whisper wraps annotations in `[…]`, `*…*`, or `(…)` — these never contain
@@ -82,11 +79,12 @@ pub struct TranscriberStation {
     transcriber: Arc<dyn Transcriber>,
     verbose: bool,
     warned_backpressure: bool,
-    hallucination_filters: HashSet<String>,
-    suspect_phrases: HashSet<String>,
-    allowed_languages: Arc<std::sync::RwLock<Vec<String>>>,
-    min_confidence: Arc<std::sync::RwLock<f32>>,
+    filter: TextFilter,
     event_tx: Option<crossbeam_channel::Sender<DaemonEvent>>,
+    /// Last routing decision, so only switches are reported.
+    last_route: Option<Route>,
+    /// Session language estimate, when language tracking is on.
+    language_tracker: Option<LanguageTracker>,
 }
 
 impl TranscriberStation {
@@ -96,11 +94,10 @@ impl TranscriberStation {
             transcriber,
             verbose: false,
             warned_backpressure: false,
-            hallucination_filters: HashSet::new(),
-            suspect_phrases: HashSet::new(),
-            allowed_languages: Arc::new(std::sync::RwLock::new(Vec::new())),
-            min_confidence: Arc::new(std::sync::RwLock::new(0.0)),
+            filter: TextFilter::new(),
             event_tx: None,
+            last_route: None,
+            language_tracker: None,
         }
     }
 
@@ -109,6 +106,7 @@ impl TranscriberStation {
     /// When verbose is true, diagnostic info is logged during transcription.
     pub fn with_verbose(mut self, verbose: bool) -> Self {
         self.verbose = verbose;
+        self.filter = self.filter.with_verbose(verbose);
         self
     }
 
@@ -117,7 +115,7 @@ impl TranscriberStation {
     /// Expects an already-normalized `HashSet` (lowercased, punctuation-stripped
     /// variants included) as produced by `resolve_hallucination_filters`.
     pub fn with_hallucination_filters(mut self, filters: HashSet<String>) -> Self {
-        self.hallucination_filters = filters;
+        self.filter = self.filter.with_hallucination_filters(filters);
         self
     }
 
@@ -126,27 +124,161 @@ impl TranscriberStation {
     /// Expects an already-normalized `HashSet` (lowercased, punctuation-stripped
     /// variants included) as produced by `resolve_suspect_phrases`.
     pub fn with_suspect_phrases(mut self, phrases: HashSet<String>) -> Self {
-        self.suspect_phrases = phrases;
+        self.filter = self.filter.with_suspect_phrases(phrases);
+        self
+    }
+
+    /// Pick up hallucination filter changes posted to `updates` while running.
+    pub fn with_filter_updates(mut self, updates: LiveUpdate<PhraseFilters>) -> Self {
+        self.filter = self.filter.with_filter_updates(updates);
         self
     }
 
     /// Set allowed languages for filtering.
     pub fn with_allowed_languages(mut self, langs: Arc<std::sync::RwLock<Vec<String>>>) -> Self {
-        self.allowed_languages = langs;
+        self.filter = self.filter.with_allowed_languages(langs);
         self
     }
 
     /// Set minimum confidence threshold.
     pub fn with_min_confidence(mut self, min: Arc<std::sync::RwLock<f32>>) -> Self {
-        self.min_confidence = min;
+        self.filter = self.filter.with_min_confidence(min);
+        self
+    }
+
+    /// Use `filter` for the results, replacing the filter settings above.
+    pub fn with_filter(mut self, filter: TextFilter) -> Self {
+        self.filter = filter;
         self
     }
 
     /// Set event sender for emitting daemon events.
     pub fn with_event_sender(mut self, tx: crossbeam_channel::Sender<DaemonEvent>) -> Self {
+        self.filter = self.filter.with_event_sender(tx.clone());
         self.event_tx = Some(tx);
         self
     }
+
+    /// Track the session language across chunks.
+    ///
+    /// Each chunk's language probabilities (restricted to the allowed
+    /// languages) update a running estimate, and the chunk is transcribed in
+    /// the session language. Has no effect on backends without a
+    /// detect-only pass.
+    pub fn with_language_tracking(mut self, enabled: bool) -> Self {
+        self.language_tracker = enabled.then(LanguageTracker::new);
+        self
+    }
+
+    /// Transcribe a chunk, in the tracked session language when tracking is on.
+    fn transcribe_chunk(
+        &mut self,
+        chunk: &AudioChunk,
+    ) -> crate::error::Result<TranscriptionResult> {
+        let Some(tracker) = self.language_tracker.as_mut() else {
+            return self.transcriber.transcribe(&chunk.samples);
+        };
+        let allowed = self.filter.allowed_languages();
+        let mut switched = None;
+        let result = self
+            .transcriber
+            .detect_and_transcribe(&chunk.samples, &mut |probabilities| {
+                switched = tracker.observe(probabilities, &allowed, chunk.duration_ms);
+                tracker.language().map(str::to_string)
+            });
+
+        if let Some(switch) = switched {
+            let event = DaemonEvent::LanguageSwitched {
+                from: switch.from,
+                to: switch.to,
+                probability: switch.probability,
+            };
+            if self.verbose {
+                render_event(&event);
+            }
+            if let Some(ref tx) = self.event_tx {
+                tx.try_send(event).ok();
+            }
+        }
+        result.unwrap_or_else(|| self.transcriber.transcribe(&chunk.samples))
+    }
+}
+
+/// Place the transcriber's segments on the stream timeline using the chunk offset.
+///
+/// Backends without timestamps get a single segment spanning the whole chunk.
+fn stream_segments(chunk: &AudioChunk, text: &str, result: &TranscriptionResult) -> Vec<Segment> {
+    let chunk_end_ms = chunk.start_ms + u64::from(chunk.duration_ms);
+    let segments: Vec<Segment> = result
+        .segments
+        .iter()
+        .filter_map(|segment| {
+            let text = clean_transcription(&segment.text);
+            (!text.is_empty()).then(|| Segment {
+                start_ms: (chunk.start_ms + segment.start_ms).min(chunk_end_ms),
+                end_ms: (chunk.start_ms + segment.end_ms).min(chunk_end_ms),
+                text,
+                tokens: segment.tokens.clone(),
+            })
+        })
+        .collect();
+
+    if segments.is_empty() {
+        vec![Segment {
+            start_ms: chunk.start_ms,
+            end_ms: chunk_end_ms,
+            text: text.to_string(),
+            tokens: result.token_probabilities.clone(),
+        }]
+    } else {
+        segments
+    }
+}
+
+/// Place the transcriber's words on the stream timeline using the chunk offset.
+///
+/// Words inside Whisper annotations (`[BLANK_AUDIO]`, `(upbeat music)`) are
+/// dropped, matching [`clean_transcription`].
+fn stream_words(chunk: &AudioChunk, result: &TranscriptionResult) -> Vec<Word> {
+    let chunk_end_ms = chunk.start_ms + u64::from(chunk.duration_ms);
+    let words = &result.words;
+    let mut kept = Vec::with_capacity(words.len());
+    let mut i = 0;
+
+    while i < words.len() {
+        let text = &words[i].text;
+        let close = match text.chars().next() {
+            Some('[') => Some(']'),
+            Some('(') => Some(')'),
+            Some('*') => Some('*'),
+            _ => None,
+        };
+        // Skip through the word that closes the annotation; unmatched openers are kept
+        if let Some(close) = close {
+            let closes_here = text[1..].contains(close);
+            let end = if closes_here {
+                Some(i)
+            } else {
+                (i + 1..words.len()).find(|&j| words[j].text.contains(close))
+            };
+            if let Some(end) = end {
+                i = end + 1;
+                continue;
+            }
+        }
+
+        let word = &words[i];
+        kept.push(Word {
+            text: word.text.clone(),
+            start_ms: (chunk.start_ms + word.start_ms).min(chunk_end_ms),
+            end_ms: (chunk.start_ms + word.end_ms).min(chunk_end_ms),
+            probability: word.probability,
+            model: word.model.clone(),
+        });
+        i += 1;
+    }
+
+    kept
 }
 
 impl Station for TranscriberStation {
@@ -175,8 +307,7 @@ impl Station for TranscriberStation {
 
         // Attempt transcription
         let result = self
-            .transcriber
-            .transcribe(&chunk.samples)
+            .transcribe_chunk(&chunk)
             .map_err(|e| StationError::recoverable(format!("Transcription failed: {}", e)))?;
 
         // Backpressure detection: warn once if transcription is slower than real-time
@@ -203,168 +334,43 @@ impl Station for TranscriberStation {
             }
         }
 
-        // Clean Whisper markers
-        let cleaned_text = clean_transcription(&result.text);
-
-        // Skip empty or punctuation-only results (e.g. "...", "…", ",,")
-        if cleaned_text.is_empty()
-            || cleaned_text
-                .trim_matches(|c: char| {
-                    c.is_ascii_punctuation()
-                        || c == '…'
-                        || c == '。'
-                        || c == '、'
-                        || c == '！'
-                        || c == '？'
-                })
-                .is_empty()
+        // Report language routing switches
+        if let Some(route) = &result.route
+            && self.last_route.as_ref() != Some(route)
         {
-            return Ok(None);
-        }
-
-        // Filter hallucinated phrases (O(1) lookup; both original and stripped forms
-        // are pre-stored in the set by resolve_hallucination_filters).
-        if !self.hallucination_filters.is_empty() {
-            let lower = cleaned_text.to_lowercase();
-            let stripped = lower.trim_end_matches(FILTER_PUNCTUATION);
-            if self.hallucination_filters.contains(&lower)
-                || self.hallucination_filters.contains(stripped)
-            {
-                if self.verbose {
-                    render_event(&DaemonEvent::TranscriptionDropped {
-                        text: cleaned_text.clone(),
-                        language: result.language.clone(),
-                        confidence: result.confidence,
-                        reason: "hallucination filter".into(),
-                    });
-                }
-                if let Some(ref tx) = self.event_tx
-                    && tx
-                        .try_send(DaemonEvent::TranscriptionDropped {
-                            text: cleaned_text,
-                            language: result.language,
-                            confidence: result.confidence,
-                            reason: "hallucination filter".into(),
-                        })
-                        .is_err()
-                {
-                    // Channel full or closed - OK to ignore
-                }
-                return Ok(None);
+            let event = DaemonEvent::LanguageRouted {
+                language: route.language.clone(),
+                model: route.model.clone(),
+            };
+            if self.verbose {
+                render_event(&event);
             }
-        }
-
-        // Suspect-word confidence gate: short filler words that could be real speech
-        // but are likely hallucinations when Whisper confidence is low.
-        // O(1) lookup; both original and stripped forms are pre-stored in the set.
-        if !self.suspect_phrases.is_empty() {
-            let lower = cleaned_text.to_lowercase();
-            let stripped = lower.trim_end_matches(FILTER_PUNCTUATION);
-            if (self.suspect_phrases.contains(&lower) || self.suspect_phrases.contains(stripped))
-                && result.confidence < SUSPECT_PASS_THRESHOLD
-            {
-                let reason = "suspect word".to_string();
-                if self.verbose {
-                    render_event(&DaemonEvent::TranscriptionDropped {
-                        text: cleaned_text.clone(),
-                        language: result.language.clone(),
-                        confidence: result.confidence,
-                        reason: reason.clone(),
-                    });
-                }
-                if let Some(ref tx) = self.event_tx
-                    && tx
-                        .try_send(DaemonEvent::TranscriptionDropped {
-                            text: cleaned_text,
-                            language: result.language,
-                            confidence: result.confidence,
-                            reason,
-                        })
-                        .is_err()
-                {
-                    // Channel full or closed - OK to ignore
-                }
-                return Ok(None);
+            if let Some(ref tx) = self.event_tx {
+                tx.try_send(event).ok();
             }
-            // ≥ SUSPECT_PASS_THRESHOLD: real speech, fall through
+            self.last_route = Some(route.clone());
         }
 
-        // Language allowlist filter
-        // If the write-side thread panicked while holding the lock, recover the last
-        // consistent value rather than cascading the panic into the audio hot path.
-        let allowed_languages = self
-            .allowed_languages
-            .read()
-            .unwrap_or_else(|e| e.into_inner());
-        if !allowed_languages.is_empty()
-            && !result.language.is_empty()
-            && !allowed_languages.iter().any(|l| l == &result.language)
+        // Clean Whisper markers
+        let cleaned_text = clean_transcription(&result.text);
+
+        if !self
+            .filter
+            .passes(&cleaned_text, &result.language, result.confidence)
         {
-            let reason = format!("language '{}' not in allowlist", result.language);
-            if self.verbose {
-                render_event(&DaemonEvent::TranscriptionDropped {
-                    text: cleaned_text.clone(),
-                    language: result.language.clone(),
-                    confidence: result.confidence,
-                    reason: reason.clone(),
-                });
-            }
-            if let Some(ref tx) = self.event_tx
-                && tx
-                    .try_send(DaemonEvent::TranscriptionDropped {
-                        text: cleaned_text,
-                        language: result.language,
-                        confidence: result.confidence,
-                        reason,
-                    })
-                    .is_err()
-            {
-                // Channel full or closed - OK to ignore
-            }
-            return Ok(None);
-        }
-        drop(allowed_languages);
-
-        // Confidence threshold filter
-        // If the write-side thread panicked while holding the lock, recover the last
-        // consistent value rather than cascading the panic into the audio hot path.
-        let min_confidence = *self
-            .min_confidence
-            .read()
-            .unwrap_or_else(|e| e.into_inner());
-        if min_confidence > 0.0 && result.confidence < min_confidence {
-            let reason = format!(
-                "confidence {:.2} below threshold {:.2}",
-                result.confidence, min_confidence
-            );
-            if self.verbose {
-                render_event(&DaemonEvent::TranscriptionDropped {
-                    text: cleaned_text.clone(),
-                    language: result.language.clone(),
-                    confidence: result.confidence,
-                    reason: reason.clone(),
-                });
-            }
-            if let Some(ref tx) = self.event_tx
-                && tx
-                    .try_send(DaemonEvent::TranscriptionDropped {
-                        text: cleaned_text,
-                        language: result.language,
-                        confidence: result.confidence,
-                        reason,
-                    })
-                    .is_err()
-            {
-                // Channel full or closed - OK to ignore
-            }
             return Ok(None);
         }
 
+        let segments = stream_segments(&chunk, &cleaned_text, &result);
+        let words = stream_words(&chunk, &result);
+
         // Carry language and confidence into the result
         let mut transcribed = TranscribedText::with_timing(cleaned_text, chunk.timing);
         transcribed.language = result.language;
         transcribed.confidence = result.confidence;
         transcribed.token_probabilities = result.token_probabilities;
+        transcribed.segments = segments;
+        transcribed.words = words;
         Ok(Some(transcribed))
     }
 }
@@ -413,6 +419,72 @@ mod tests {
         assert_eq!(text.text, "Hello world");
     }
 
+    #[test]
+    fn test_untimed_result_gets_segment_spanning_chunk() {
+        let transcriber = Arc::new(MockTranscriber::new("mock").with_response("Hello world"));
+        let mut station = TranscriberStation::new(transcriber);
+
+        let chunk = AudioChunk::new(vec![100i16; 100], 1_200, 1).with_start_ms(30_000);
+        let text = station.process(chunk).unwrap().unwrap();
+        assert_eq!(text.segments.len(), 1);
+        assert_eq!(text.segments[0].start_ms, 30_000);
+        assert_eq!(text.segments[0].end_ms, 31_200);
+        assert_eq!(text.segments[0].text, "Hello world");
+    }
+
+    #[test]
+    fn test_stream_segments_offsets_and_cleans_whisper_segments() {
+        let chunk = AudioChunk::new(vec![], 3_000, 0).with_start_ms(10_000);
+        let mut result = TranscriptionResult::from_text("Hi there. [BLANK_AUDIO]".to_string());
+        result.segments = vec![
+            Segment {
+                start_ms: 0,
+                end_ms: 1_500,
+                text: "Hi there.".to_string(),
+                tokens: vec![],
+            },
+            Segment {
+                start_ms: 1_500,
+                end_ms: 3_400,
+                text: "[BLANK_AUDIO]".to_string(),
+                tokens: vec![],
+            },
+        ];
+
+        let segments = stream_segments(&chunk, "Hi there.", &result);
+        assert_eq!(segments.len(), 1);
+        assert_eq!((segments[0].start_ms, segments[0].end_ms), (10_000, 11_500));
+    }
+
+    fn word(text: &str, start_ms: u64, end_ms: u64) -> Word {
+        Word {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            probability: 0.5,
+            model: None,
+        }
+    }
+
+    #[test]
+    fn test_stream_words_offsets_and_drops_annotations() {
+        let chunk = AudioChunk::new(vec![], 4_000, 0).with_start_ms(5_000);
+        let mut result = TranscriptionResult::from_text(String::new());
+        result.words = vec![
+            word("[BLANK_AUDIO]", 0, 500),
+            word("Hello", 500, 900),
+            word("(upbeat", 900, 1_200),
+            word("music)", 1_200, 1_500),
+            word("(world", 1_500, 4_500),
+        ];
+
+        let words = stream_words(&chunk, &result);
+        assert_eq!(
+            words,
+            vec![word("Hello", 5_500, 5_900), word("(world", 6_500, 9_000)]
+        );
+    }
+
     #[test]
     fn test_error_handling_returns_recoverable() {
         let transcriber = Arc::new(MockTranscriber::new("mock").with_failure());
@@ -691,6 +763,21 @@ mod tests {
         assert!(result.is_none(), "Hallucinated phrase should be discarded");
     }
 
+    #[test]
+    fn test_hallucination_filter_posted_while_running() {
+        let transcriber = Arc::new(MockTranscriber::new("mock").with_response("Thank you."));
+        let updates = LiveUpdate::new();
+        let mut station = TranscriberStation::new(transcriber).with_filter_updates(updates.clone());
+        let chunk = || AudioChunk::new(vec![100i16; 100], 100, 1);
+        assert!(station.process(chunk()).unwrap().is_some());
+
+        updates.set(PhraseFilters {
+            hallucination_filters: filter_set(&["thank you."]),
+            suspect_phrases: HashSet::new(),
+        });
+        assert!(station.process(chunk()).unwrap().is_none());
+    }
+
     #[test]
     fn test_hallucination_filter_case_insensitive() {
         let transcriber = Arc::new(MockTranscriber::new("mock").with_response("THANK YOU."));
@@ -990,6 +1077,148 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_language_routed_event_emitted_on_switch_only() {
+        use crate::stt::routing::{LanguageRoute, RoutingTranscriber};
+
+        let (tx, rx) = crossbeam_channel::bounded(16);
+        let detector = Arc::new(
+            MockTranscriber::new("base")
+                .with_response("hallo")
+                .with_language("de"),
+        );
+        let router = RoutingTranscriber::new(
+            detector,
+            vec![LanguageRoute {
+                language: "de".to_string(),
+                model: "medium".to_string(),
+                size_mb: 1500,
+            }],
+            Box::new(|model, language| {
+                Ok(Arc::new(
+                    MockTranscriber::new(model)
+                        .with_response("hallo welt")
+                        .with_language(language),
+                ) as Arc<dyn Transcriber>)
+            }),
+            2048,
+        );
+        let mut station = TranscriberStation::new(Arc::new(router)).with_event_sender(tx);
+        for _ in 0..2 {
+            let chunk = AudioChunk::new(vec![655i16; 16000], 1000, 0);
+            let result = station.process(chunk).unwrap().unwrap();
+            assert_eq!(result.text, "hallo welt");
+        }
+
+        let events: Vec<DaemonEvent> = rx.try_iter().collect();
+        assert_eq!(
+            events,
+            vec![DaemonEvent::LanguageRouted {
+                language: "de".to_string(),
+                model: "medium".to_string(),
+            }]
+        );
+    }
+
+    /// Transcriber whose detect-only pass replays scripted probabilities.
+    struct ScriptedDetector {
+        inner: MockTranscriber,
+        detections: std::sync::Mutex<std::collections::VecDeque<Vec<(&'static str, f32)>>>,
+    }
+
+    impl Transcriber for ScriptedDetector {
+        fn transcribe(&self, audio: &[i16]) -> crate::error::Result<TranscriptionResult> {
+            self.inner.transcribe(audio)
+        }
+
+        fn transcribe_with_language(
+            &self,
+            audio: &[i16],
+            language: &str,
+        ) -> crate::error::Result<TranscriptionResult> {
+            self.inner.transcribe_with_language(audio, language)
+        }
+
+        fn model_name(&self) -> &str {
+            self.inner.model_name()
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+
+        fn detect_language(
+            &self,
+            _audio: &[i16],
+        ) -> Option<crate::error::Result<Vec<crate::stt::transcriber::LanguageProbability>>>
+        {
+            let detection = self.detections.lock().unwrap().pop_front()?;
+            Some(Ok(detection
+                .into_iter()
+                .map(
+                    |(language, probability)| crate::stt::transcriber::LanguageProbability {
+                        language: language.to_string(),
+                        probability,
+                    },
+                )
+                .collect()))
+        }
+    }
+
+    #[test]
+    fn test_language_tracking_keeps_session_language_and_reports_switch() {
+        let german = vec![("de", 0.9), ("nl", 0.05), ("en", 0.05)];
+        let english = vec![("en", 0.97), ("de", 0.02), ("nl", 0.01)];
+        let detector = ScriptedDetector {
+            inner: MockTranscriber::new("base").with_response("text"),
+            detections: std::sync::Mutex::new(
+                vec![
+                    german.clone(),
+                    german,
+                    vec![("nl", 0.6), ("de", 0.2), ("en", 0.2)],
+                    english.clone(),
+                    english,
+                ]
+                .into(),
+            ),
+        };
+        let (tx, rx) = crossbeam_channel::bounded(16);
+        let mut station = TranscriberStation::new(Arc::new(detector))
+            .with_allowed_languages(arc_langs(vec!["de", "en", "nl"]))
+            .with_language_tracking(true)
+            .with_event_sender(tx);
+
+        let mut languages = Vec::new();
+        for duration_ms in [4000, 4000, 500, 4000, 4000] {
+            let chunk = AudioChunk::new(vec![655i16; 16000], duration_ms, 0);
+            languages.push(station.process(chunk).unwrap().unwrap().language);
+        }
+        // The short "OK." chunk stays German; the sustained English switches
+        assert_eq!(languages, vec!["de", "de", "de", "de", "en"]);
+
+        let events: Vec<DaemonEvent> = rx.try_iter().collect();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            DaemonEvent::LanguageSwitched { from, to, .. } => {
+                assert_eq!(from, "de");
+                assert_eq!(to, "en");
+            }
+            other => panic!("Expected LanguageSwitched, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_language_tracking_off_transcribes_as_detected() {
+        let transcriber = Arc::new(
+            MockTranscriber::new("mock")
+                .with_response("hello")
+                .with_language("nl"),
+        );
+        let mut station = TranscriberStation::new(transcriber).with_language_tracking(false);
+        let chunk = AudioChunk::new(vec![655i16; 16000], 500, 0);
+        assert_eq!(station.process(chunk).unwrap().unwrap().language, "nl");
+    }
+
     // ── Suspect word confidence-gated filter tests ──────────────────────
 
     #[test]
@@ -52,6 +52,8 @@ pub struct AudioFrame {
     pub timestamp: Instant,
     /// Sequence number for ordering and gap detection.
     pub sequence: u64,
+    /// Level of the frame before noise suppression (set by the denoise station).
+    pub raw_level: Option<f32>,
 }
 
 impl AudioFrame {
@@ -61,8 +63,15 @@ impl AudioFrame {
             samples,
             timestamp,
             sequence,
+            raw_level: None,
         }
     }
+
+    /// Records the level the frame had before noise suppression.
+    pub fn with_raw_level(mut self, level: f32) -> Self {
+        self.raw_level = Some(level);
+        self
+    }
 }
 
 /// An audio frame with voice activity detection results.
@@ -119,6 +128,8 @@ pub struct AudioChunk {
     pub duration_ms: u32,
     /// Sequence number for ordering.
     pub sequence: u64,
+    /// Offset of the first sample from the start of the audio stream, in milliseconds.
+    pub start_ms: u64,
     /// Timing information (only populated when verbosity >= 1).
     pub timing: Option<Box<ChunkTiming>>,
 }
@@ -130,6 +141,7 @@ impl AudioChunk {
             samples,
             duration_ms,
             sequence,
+            start_ms: 0,
             timing: None,
         }
     }
@@ -147,6 +159,7 @@ impl AudioChunk {
             samples,
             duration_ms,
             sequence,
+            start_ms: 0,
             timing: Some(Box::new(ChunkTiming {
                 capture_start,
                 vad_start,
@@ -155,6 +168,12 @@ impl AudioChunk {
             })),
         }
     }
+
+    /// Sets the chunk's offset in the audio stream.
+    pub fn with_start_ms(mut self, start_ms: u64) -> Self {
+        self.start_ms = start_ms;
+        self
+    }
 }
 
 /// Transcribed text with timing information.
@@ -180,6 +199,10 @@ pub struct TranscribedText {
     pub text_origin: TextOrigin,
     /// Name of the corrector backend that produced the correction. None if uncorrected.
     pub corrector_name: Option<String>,
+    /// Timed segments on the stream timeline (Whisper output, before post-processing).
+    pub segments: Vec<crate::stt::transcriber::Segment>,
+    /// Words on the stream timeline (Whisper output, before post-processing).
+    pub words: Vec<crate::stt::transcriber::Word>,
 }
 
 impl TranscribedText {
@@ -196,6 +219,8 @@ impl TranscribedText {
             raw_text: None,
             text_origin: TextOrigin::default(),
             corrector_name: None,
+            segments: Vec::new(),
+            words: Vec::new(),
         }
     }
 
@@ -212,6 +237,8 @@ impl TranscribedText {
             raw_text: None,
             text_origin: TextOrigin::default(),
             corrector_name: None,
+            segments: Vec::new(),
+            words: Vec::new(),
         }
     }
 }
@@ -1,9 +1,10 @@
 //! VAD station that detects voice activity in audio frames.
 
-use crate::audio::vad::{Clock, SystemClock, Vad, VadConfig};
+use crate::audio::vad::{Clock, SystemClock, VadConfig, VoiceDetector, create_detector};
 use crate::ipc::protocol::DaemonEvent;
 use crate::output::render_event;
 use crate::pipeline::error::StationError;
+use crate::pipeline::live::{LiveUpdate, VadThreshold};
 use crate::pipeline::station::Station;
 use crate::pipeline::types::{AudioFrame, VadFrame};
 use std::collections::VecDeque;
@@ -20,7 +21,7 @@ pub type BufferGauge = Box<dyn Fn() -> (usize, usize) + Send + Sync>;
 
 /// VAD station that processes audio frames and annotates them with speech detection.
 pub struct VadStation {
-    vad: Vad<Arc<dyn Clock>>,
+    vad: Box<dyn VoiceDetector>,
     show_levels: bool,
     auto_level: bool,
     level_history: VecDeque<f32>,
@@ -38,10 +39,12 @@ pub struct VadStation {
     event_tx: Option<crossbeam_channel::Sender<DaemonEvent>>,
     /// Throttle counter for level events (emit every 4th frame)
     level_event_counter: u64,
+    /// Threshold changes posted while running (config reload, device switch)
+    threshold_updates: Option<LiveUpdate<VadThreshold>>,
 }
 
 impl VadStation {
-    /// Creates a new VAD station with the given configuration.
+    /// Creates a new VAD station using the detector selected in `config.backend`.
     pub fn new(config: VadConfig) -> Self {
         Self::with_clock(config, Arc::new(SystemClock))
     }
@@ -49,7 +52,7 @@ impl VadStation {
     /// Creates a new VAD station with an injectable clock.
     pub fn with_clock(config: VadConfig, clock: Arc<dyn Clock>) -> Self {
         Self {
-            vad: Vad::with_clock(config, clock),
+            vad: create_detector(config, clock),
             show_levels: false,
             auto_level: false,
             level_history: VecDeque::new(),
@@ -61,6 +64,7 @@ impl VadStation {
             buffer_gauge: None,
             event_tx: None,
             level_event_counter: 0,
+            threshold_updates: None,
         }
     }
 
@@ -94,6 +98,12 @@ impl VadStation {
         self
     }
 
+    /// Picks up threshold changes posted to `updates` while running.
+    pub fn with_threshold_updates(mut self, updates: LiveUpdate<VadThreshold>) -> Self {
+        self.threshold_updates = Some(updates);
+        self
+    }
+
     /// Adjusts the VAD threshold based on the noise floor estimate.
     fn adjust_threshold(&mut self) {
         if self.level_history.len() < 10 {
@@ -149,6 +159,12 @@ impl Station for VadStation {
 
         self.frames_processed += 1;
 
+        if let Some(update) = self.threshold_updates.as_ref().and_then(LiveUpdate::take) {
+            self.vad.set_threshold(update.threshold);
+            self.auto_level = update.auto_level;
+            self.level_history.clear();
+        }
+
         // Process VAD
         let result = self.vad.process_with_info(&frame.samples, self.sample_rate);
 
@@ -190,6 +206,7 @@ impl Station for VadStation {
                 is_speech,
                 buffer_used: buf_used,
                 buffer_capacity: buf_cap,
+                raw_level: frame.raw_level,
             });
         }
 
@@ -203,6 +220,7 @@ impl Station for VadStation {
                     is_speech,
                     buffer_used: buf_used,
                     buffer_capacity: buf_cap,
+                    raw_level: frame.raw_level,
                 })
                 .ok();
             }
@@ -252,6 +270,7 @@ mod tests {
             speech_threshold: 0.02,
             silence_duration_ms: 1000,
             min_speech_ms: 300,
+            ..Default::default()
         };
         let mut station = VadStation::new(config);
 
@@ -272,6 +291,7 @@ mod tests {
             speech_threshold: 0.02,
             silence_duration_ms: 1000,
             min_speech_ms: 300,
+            ..Default::default()
         };
         let mut station = VadStation::new(config);
 
@@ -287,12 +307,34 @@ mod tests {
         assert!(vad_frame.level > 0.02);
     }
 
+    #[test]
+    fn test_vad_station_applies_posted_threshold() {
+        let config = VadConfig {
+            speech_threshold: 0.02,
+            silence_duration_ms: 1000,
+            min_speech_ms: 300,
+            ..Default::default()
+        };
+        let updates = LiveUpdate::new();
+        let mut station = VadStation::new(config).with_threshold_updates(updates.clone());
+        updates.set(VadThreshold {
+            threshold: 0.5,
+            auto_level: false,
+        });
+
+        // RMS ~0.09 is below the new threshold
+        let frame = AudioFrame::new(make_speech(1000, 3000), Instant::now(), 1);
+        let vad_frame = station.process(frame).unwrap().unwrap();
+        assert!(!vad_frame.is_speech);
+    }
+
     #[test]
     fn test_vad_station_continues_speech() {
         let config = VadConfig {
             speech_threshold: 0.02,
             silence_duration_ms: 1000,
             min_speech_ms: 300,
+            ..Default::default()
         };
         let mut station = VadStation::new(config);
 
@@ -309,6 +351,25 @@ mod tests {
         assert!(result2.unwrap().is_speech);
     }
 
+    #[test]
+    fn test_vad_station_uses_configured_backend() {
+        // Alternating full-scale samples: loud, but all energy at Nyquist
+        let buzz: Vec<i16> = (0..1000)
+            .map(|i| if i % 2 == 0 { 3000 } else { -3000 })
+            .collect();
+
+        let mut rms = VadStation::new(VadConfig::default());
+        let frame = AudioFrame::new(buzz.clone(), Instant::now(), 1);
+        assert!(rms.process(frame).unwrap().unwrap().is_speech);
+
+        let mut spectral = VadStation::new(VadConfig {
+            backend: crate::config::VadBackend::Spectral,
+            ..Default::default()
+        });
+        let frame = AudioFrame::new(buzz, Instant::now(), 1);
+        assert!(!spectral.process(frame).unwrap().unwrap().is_speech);
+    }
+
     #[test]
     fn test_vad_station_never_filters() {
         let config = VadConfig::default();
@@ -329,6 +390,7 @@ mod tests {
             speech_threshold: 0.02,
             silence_duration_ms: 1000,
             min_speech_ms: 300,
+            ..Default::default()
         };
         let mut station = VadStation::new(config).with_auto_level(true);
 
@@ -0,0 +1,422 @@
+//! Replay recorded audio and compare transcripts.
+//!
+//! `voicsh replay` feeds an archived session (see [`crate::pipeline::archive`])
+//! or any audio file back through the pipeline, typically with another model
+//! or config, and prints the new text next to the originally produced text.
+
+use crate::error::{Result, VoicshError};
+use crate::pipeline::archive::{MANIFEST_FILE, SESSION_AUDIO_FILE, find_session, load_manifest};
+use std::path::{Path, PathBuf};
+
+/// Column width of each side in [`render_side_by_side`].
+const COLUMN_WIDTH: usize = 38;
+
+/// Audio to replay and the text it originally produced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayInput {
+    pub audio: PathBuf,
+    /// Text from the session manifest, if the audio came from an archive.
+    pub original_text: Option<String>,
+    /// Model that produced `original_text`.
+    pub original_model: Option<String>,
+}
+
+impl ReplayInput {
+    /// Resolve a session directory, an audio file, or an archived session id
+    /// (including `latest`) under `archive_root`.
+    ///
+    /// A chunk file from a session directory is compared against that chunk's text.
+    pub fn resolve(target: &str, archive_root: &Path) -> Result<Self> {
+        let path = Path::new(target);
+        if path.is_dir() {
+            return Self::from_session_dir(path);
+        }
+        if path.is_file() {
+            return Ok(Self::from_file(path));
+        }
+        let session =
+            find_session(archive_root, target).map_err(|e| VoicshError::SessionArchive {
+                message: format!("{target} is not a file or session directory, and {e}"),
+            })?;
+        Self::from_session_dir(&session.path)
+    }
+
+    fn from_session_dir(dir: &Path) -> Result<Self> {
+        let audio = dir.join(SESSION_AUDIO_FILE);
+        if !audio.is_file() {
+            return Err(VoicshError::SessionArchive {
+                message: format!("{} has no {SESSION_AUDIO_FILE}", dir.display()),
+            });
+        }
+        let manifest = load_manifest(dir).ok();
+        Ok(Self {
+            audio,
+            original_text: manifest.as_ref().map(|m| m.text()),
+            original_model: manifest.map(|m| m.model),
+        })
+    }
+
+    fn from_file(path: &Path) -> Self {
+        let manifest = path
+            .parent()
+            .filter(|dir| dir.join(MANIFEST_FILE).is_file())
+            .and_then(|dir| load_manifest(dir).ok());
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let original_text = manifest.as_ref().and_then(|m| {
+            if file_name == SESSION_AUDIO_FILE {
+                Some(m.text())
+            } else {
+                m.chunks
+                    .iter()
+                    .find(|c| c.file == file_name)
+                    .map(|c| c.text.trim().to_string())
+            }
+        });
+        Self {
+            audio: path.to_path_buf(),
+            original_model: original_text
+                .is_some()
+                .then(|| manifest.map(|m| m.model))
+                .flatten(),
+            original_text,
+        }
+    }
+}
+
+/// One step of a word-level diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordDiff<'a> {
+    Same(&'a str),
+    /// Only in the original.
+    Removed(&'a str),
+    /// Only in the replay.
+    Added(&'a str),
+}
+
+/// Word-level diff of `original` against `replay` (longest common subsequence).
+///
+/// Uses Hirschberg's algorithm, so memory stays linear in the text length
+/// even for long sessions. Within each changed run, removals come before
+/// additions.
+pub fn diff_words<'a>(original: &'a str, replay: &'a str) -> Vec<WordDiff<'a>> {
+    let a: Vec<&str> = original.split_whitespace().collect();
+    let b: Vec<&str> = replay.split_whitespace().collect();
+    let mut ops = Vec::with_capacity(a.len().max(b.len()));
+    diff_slices(&a, &b, &mut ops);
+
+    // Recursion may split a changed run as added-then-removed; reorder
+    let mut start = 0;
+    while start < ops.len() {
+        let len = ops[start..]
+            .iter()
+            .position(|op| matches!(op, WordDiff::Same(_)))
+            .unwrap_or(ops.len() - start);
+        ops[start..start + len].sort_by_key(|op| matches!(op, WordDiff::Added(_)));
+        start += len + 1;
+    }
+    ops
+}
+
+/// Append the diff of `a` against `b` to `ops` (Hirschberg recursion).
+fn diff_slices<'a>(a: &[&'a str], b: &[&'a str], ops: &mut Vec<WordDiff<'a>>) {
+    match (a, b) {
+        ([], _) => ops.extend(b.iter().map(|w| WordDiff::Added(w))),
+        (_, []) => ops.extend(a.iter().map(|w| WordDiff::Removed(w))),
+        ([word], _) => match b.iter().position(|w| w == word) {
+            Some(k) => {
+                ops.extend(b[..k].iter().map(|w| WordDiff::Added(w)));
+                ops.push(WordDiff::Same(word));
+                ops.extend(b[k + 1..].iter().map(|w| WordDiff::Added(w)));
+            }
+            None => {
+                ops.push(WordDiff::Removed(word));
+                ops.extend(b.iter().map(|w| WordDiff::Added(w)));
+            }
+        },
+        _ => {
+            let mid = a.len() / 2;
+            let head = lcs_lengths(a[..mid].iter(), b.iter());
+            let tail = lcs_lengths(a[mid..].iter().rev(), b.iter().rev());
+            // Split `b` where the two halves together keep the longest LCS
+            let split = (0..=b.len())
+                .max_by_key(|&k| (head[k] + tail[b.len() - k], std::cmp::Reverse(k)))
+                .unwrap_or(0);
+            diff_slices(&a[..mid], &b[..split], ops);
+            diff_slices(&a[mid..], &b[split..], ops);
+        }
+    }
+}
+
+/// LCS length of `a` with every prefix of `b`, in one row of memory.
+fn lcs_lengths<'w, A, B>(a: A, b: B) -> Vec<usize>
+where
+    A: Iterator<Item = &'w &'w str>,
+    B: Iterator<Item = &'w &'w str> + Clone,
+{
+    let mut row = vec![0usize; b.clone().count() + 1];
+    for wa in a {
+        let mut diagonal = 0;
+        for (j, wb) in b.clone().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if wa == wb {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row
+}
+
+/// Word error rate of `replay` against `original` as reference
+/// (substitutions + deletions + insertions over reference words).
+///
+/// Returns 0.0 for two empty texts and 1.0 when only the reference is empty.
+pub fn word_error_rate(original: &str, replay: &str) -> f32 {
+    let a: Vec<&str> = original.split_whitespace().collect();
+    let b: Vec<&str> = replay.split_whitespace().collect();
+    if a.is_empty() {
+        return if b.is_empty() { 0.0 } else { 1.0 };
+    }
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, wa) in a.iter().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, wb) in b.iter().enumerate() {
+            let cost = usize::from(wa != wb);
+            row[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+    prev[b.len()] as f32 / a.len() as f32
+}
+
+/// Render a two-column diff in the style of `diff -y`.
+///
+/// Unchanged words appear on both sides; changed runs are marked `|`,
+/// removals `<` and additions `>`.
+pub fn render_side_by_side(
+    left_title: &str,
+    original: &str,
+    right_title: &str,
+    replay: &str,
+) -> String {
+    let mut out = format!(
+        "{:<w$}   {}\n{:-<w$}   {:-<w$}\n",
+        left_title,
+        right_title,
+        "",
+        "",
+        w = COLUMN_WIDTH
+    );
+
+    let ops = diff_words(original, replay);
+    let mut rest = ops.as_slice();
+    while let Some(first) = rest.first() {
+        let same = matches!(first, WordDiff::Same(_));
+        let len = rest
+            .iter()
+            .position(|op| matches!(op, WordDiff::Same(_)) != same)
+            .unwrap_or(rest.len());
+        let (hunk, tail) = rest.split_at(len);
+        rest = tail;
+
+        let left: Vec<&str> = hunk
+            .iter()
+            .filter_map(|op| match op {
+                WordDiff::Same(w) | WordDiff::Removed(w) => Some(*w),
+                WordDiff::Added(_) => None,
+            })
+            .collect();
+        let right: Vec<&str> = hunk
+            .iter()
+            .filter_map(|op| match op {
+                WordDiff::Same(w) | WordDiff::Added(w) => Some(*w),
+                WordDiff::Removed(_) => None,
+            })
+            .collect();
+        let marker = match (same, left.is_empty(), right.is_empty()) {
+            (true, _, _) => ' ',
+            (false, false, true) => '<',
+            (false, true, false) => '>',
+            (false, _, _) => '|',
+        };
+
+        let left = wrap_words(&left, COLUMN_WIDTH);
+        let right = wrap_words(&right, COLUMN_WIDTH);
+        for row in 0..left.len().max(right.len()) {
+            let l = left.get(row).map_or("", String::as_str);
+            let r = right.get(row).map_or("", String::as_str);
+            let line = format!("{l:<w$} {marker} {r}", w = COLUMN_WIDTH);
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Greedy word wrap; words longer than `width` get a line of their own.
+fn wrap_words(words: &[&str], width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in words {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::archive::{ChunkOutcome, ChunkRecord, SessionManifest};
+    use std::fs;
+
+    fn write_session(dir: &Path) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(SESSION_AUDIO_FILE), b"RIFF").unwrap();
+        let chunk = |sequence: u64, text: &str| ChunkRecord {
+            sequence,
+            file: format!("chunk-{sequence:04}.wav"),
+            start_ms: 0,
+            duration_ms: 1000,
+            outcome: ChunkOutcome::Transcribed,
+            transcribe_ms: Some(100),
+            text: text.to_string(),
+            language: "en".to_string(),
+            confidence: 0.9,
+            error: None,
+        };
+        let manifest = SessionManifest {
+            id: "20260101-120000".to_string(),
+            started_at: 0,
+            sample_rate: 16000,
+            model: "base.en".to_string(),
+            vad_backend: Default::default(),
+            vad_threshold: 0.02,
+            silence_duration_ms: 1500,
+            duration_ms: 2000,
+            speech: Vec::new(),
+            chunks: vec![chunk(0, "hello there"), chunk(1, "general kenobi")],
+        };
+        fs::write(
+            dir.join(MANIFEST_FILE),
+            serde_json::to_string(&manifest).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_resolve_session_dir_id_and_chunk_file() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("20260101-120000");
+        write_session(&dir);
+
+        let by_dir = ReplayInput::resolve(dir.to_str().unwrap(), root.path()).unwrap();
+        assert_eq!(by_dir.audio, dir.join(SESSION_AUDIO_FILE));
+        assert_eq!(
+            by_dir.original_text.as_deref(),
+            Some("hello there general kenobi")
+        );
+        assert_eq!(by_dir.original_model.as_deref(), Some("base.en"));
+
+        let by_id = ReplayInput::resolve("latest", root.path()).unwrap();
+        assert_eq!(by_id, by_dir);
+
+        let chunk = dir.join("chunk-0001.wav");
+        fs::write(&chunk, b"RIFF").unwrap();
+        let by_chunk = ReplayInput::resolve(chunk.to_str().unwrap(), root.path()).unwrap();
+        assert_eq!(by_chunk.original_text.as_deref(), Some("general kenobi"));
+    }
+
+    #[test]
+    fn test_resolve_plain_file_has_no_original() {
+        let root = tempfile::tempdir().unwrap();
+        let wav = root.path().join("clip.wav");
+        fs::write(&wav, b"RIFF").unwrap();
+        let input = ReplayInput::resolve(wav.to_str().unwrap(), root.path()).unwrap();
+        assert_eq!(input.original_text, None);
+        assert_eq!(input.original_model, None);
+
+        let err = ReplayInput::resolve("nope", root.path()).unwrap_err();
+        assert!(err.to_string().contains("not a file"), "{err}");
+    }
+
+    #[test]
+    fn test_diff_words() {
+        use WordDiff::*;
+        assert_eq!(
+            diff_words("the quick brown fox", "the quack brown fox jumps"),
+            vec![
+                Same("the"),
+                Removed("quick"),
+                Added("quack"),
+                Same("brown"),
+                Same("fox"),
+                Added("jumps")
+            ]
+        );
+        assert!(diff_words("", "").is_empty());
+    }
+
+    #[test]
+    fn test_diff_words_keeps_lcs_on_long_text() {
+        let original: Vec<String> = (0..3000).map(|i| format!("w{i}")).collect();
+        let mut replay = original.clone();
+        replay[10] = "changed".to_string();
+        replay.remove(2000);
+        let (original, replay) = (original.join(" "), replay.join(" "));
+        let ops = diff_words(&original, &replay);
+        let same = ops
+            .iter()
+            .filter(|op| matches!(op, WordDiff::Same(_)))
+            .count();
+        assert_eq!(same, 2998);
+        assert_eq!(ops.len(), 3001);
+    }
+
+    #[test]
+    fn test_word_error_rate() {
+        assert_eq!(word_error_rate("a b c d", "a b c d"), 0.0);
+        assert_eq!(word_error_rate("a b c d", "a x c"), 0.5);
+        assert_eq!(word_error_rate("", ""), 0.0);
+        assert_eq!(word_error_rate("", "a"), 1.0);
+    }
+
+    #[test]
+    fn test_render_side_by_side_marks_changes() {
+        let out = render_side_by_side(
+            "original",
+            "hello world how are you",
+            "replay",
+            "hello word how are you today",
+        );
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[0].starts_with("original"));
+        assert!(lines[0].ends_with("replay"));
+        assert_eq!(
+            lines[2].split_whitespace().collect::<Vec<_>>(),
+            ["hello", "hello"]
+        );
+        assert!(lines[3].contains(" | "), "{out}");
+        assert!(lines[3].starts_with("world") && lines[3].ends_with("word"));
+        assert!(lines[5].trim_start().starts_with("> today"), "{out}");
+    }
+
+    #[test]
+    fn test_wrap_words() {
+        assert_eq!(
+            wrap_words(&["aaaa", "bbbb", "cc", "dddddddddd"], 9),
+            vec!["aaaa bbbb", "cc", "dddddddddd"]
+        );
+        assert!(wrap_words(&[], 10).is_empty());
+    }
+}
@@ -0,0 +1,903 @@
+//! Pure-Rust Whisper transcription with candle.
+//!
+//! Loads a safetensors Whisper model (`config.json`, `tokenizer.json`,
+//! `model.safetensors`) from the HuggingFace cache, downloading it on first
+//! use, and decodes with beam search. Unlike whisper.cpp this keeps every
+//! finished beam, so results carry real n-best alternatives and per-token
+//! log-probabilities.
+//!
+//! # Feature Gate
+//!
+//! This module requires the `candle-whisper` feature. It needs neither cmake
+//! nor a C++ toolchain:
+//!
+//! ```bash
+//! cargo build --no-default-features --features cli,cpal-audio,candle-whisper
+//! ```
+
+use crate::config::SttConfig;
+use crate::defaults;
+use crate::error::{Result, VoicshError};
+use crate::stt::transcriber::{
+    Hypothesis, LanguageProbability, Segment, TokenProbability, Transcriber, TranscriptionResult,
+};
+use candle_core::{D, Device, IndexOp, Tensor};
+use candle_nn::VarBuilder;
+use candle_transformers::models::whisper::{
+    self as m, Config as ModelConfig, audio, model::Whisper,
+};
+use hf_hub::api::sync::Api;
+use std::sync::Mutex;
+use tokenizers::Tokenizer;
+
+/// Files read from the model repository.
+const CONFIG_FILE: &str = "config.json";
+const TOKENIZER_FILE: &str = "tokenizer.json";
+const WEIGHTS_FILE: &str = "model.safetensors";
+
+/// Settings for [`CandleWhisperTranscriber`].
+#[derive(Debug, Clone)]
+pub struct CandleWhisperConfig {
+    /// HuggingFace repository holding the model.
+    pub repo: String,
+    /// Language code, or "auto" to detect it per chunk.
+    pub language: String,
+    /// Beam width; also the number of hypotheses kept per window.
+    pub beam_size: usize,
+}
+
+impl CandleWhisperConfig {
+    /// Build from the `[stt]` section.
+    pub fn from_config(stt: &SttConfig) -> Self {
+        Self {
+            repo: stt.candle.repo.clone(),
+            language: stt.language.clone(),
+            beam_size: stt.candle.beam_size.max(1),
+        }
+    }
+}
+
+/// Token ids the decoder prompt and search depend on.
+#[derive(Debug)]
+struct SpecialTokens {
+    sot: u32,
+    eot: u32,
+    transcribe: u32,
+    no_timestamps: u32,
+    /// `(code, token)` for every `<|xx|>` language token, by id.
+    /// Empty for English-only models.
+    languages: Vec<(String, u32)>,
+}
+
+impl SpecialTokens {
+    fn from_tokenizer(tokenizer: &Tokenizer) -> Result<Self> {
+        let id = |token: &str| {
+            tokenizer
+                .token_to_id(token)
+                .ok_or_else(|| VoicshError::Transcription {
+                    message: format!("Tokenizer has no {token} token"),
+                })
+        };
+        let mut languages: Vec<(String, u32)> = tokenizer
+            .get_vocab(true)
+            .into_iter()
+            .filter_map(|(token, id)| language_code(&token).map(|code| (code.to_string(), id)))
+            .collect();
+        languages.sort_by_key(|(_, id)| *id);
+        Ok(Self {
+            sot: id(m::SOT_TOKEN)?,
+            eot: id(m::EOT_TOKEN)?,
+            transcribe: id(m::TRANSCRIBE_TOKEN)?,
+            no_timestamps: id(m::NO_TIMESTAMPS_TOKEN)?,
+            languages,
+        })
+    }
+
+    fn language_token(&self, code: &str) -> Option<u32> {
+        self.languages
+            .iter()
+            .find(|(c, _)| c == code)
+            .map(|(_, id)| *id)
+    }
+}
+
+/// The language code of a `<|xx|>` / `<|xxx|>` token.
+fn language_code(token: &str) -> Option<&str> {
+    let code = token.strip_prefix("<|")?.strip_suffix("|>")?;
+    let plausible = (2..=3).contains(&code.len()) && code.chars().all(|c| c.is_ascii_lowercase());
+    plausible.then_some(code)
+}
+
+/// Whisper transcriber running on candle.
+///
+/// The model keeps a cross-attention cache, so inference is serialized
+/// through a `Mutex`, as in the whisper.cpp backend.
+pub struct CandleWhisperTranscriber {
+    model: Mutex<Whisper>,
+    tokenizer: Tokenizer,
+    tokens: SpecialTokens,
+    /// Additive logit mask: 0 for allowed tokens, -inf for suppressed ones.
+    suppress: Tensor,
+    mel_filters: Vec<f32>,
+    device: Device,
+    config: CandleWhisperConfig,
+    model_name: String,
+}
+
+impl std::fmt::Debug for CandleWhisperTranscriber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CandleWhisperTranscriber")
+            .field("config", &self.config)
+            .field("model_name", &self.model_name)
+            .finish()
+    }
+}
+
+impl CandleWhisperTranscriber {
+    /// Load the model, downloading it into the HuggingFace cache if needed.
+    pub fn new(config: CandleWhisperConfig) -> Result<Self> {
+        let api = Api::new().map_err(|e| VoicshError::Transcription {
+            message: format!("HF Hub API init: {e}"),
+        })?;
+        let repo = api.model(config.repo.clone());
+        let fetch = |file: &str| {
+            repo.get(file)
+                .map_err(|e| VoicshError::TranscriptionModelNotFound {
+                    path: format!("{}/{file} ({e})", config.repo),
+                })
+        };
+        let config_path = fetch(CONFIG_FILE)?;
+        let tokenizer_path = fetch(TOKENIZER_FILE)?;
+        let weights_path = fetch(WEIGHTS_FILE)?;
+
+        let config_bytes = std::fs::read(&config_path).map_err(|e| VoicshError::Transcription {
+            message: format!("Read {}: {e}", config_path.display()),
+        })?;
+        let model_config: ModelConfig =
+            serde_json::from_slice(&config_bytes).map_err(|e| VoicshError::Transcription {
+                message: format!("Parse Whisper config {}: {e}", config_path.display()),
+            })?;
+        let tokenizer =
+            Tokenizer::from_file(&tokenizer_path).map_err(|e| VoicshError::Transcription {
+                message: format!("Load tokenizer {}: {e}", tokenizer_path.display()),
+            })?;
+        let tokens = SpecialTokens::from_tokenizer(&tokenizer)?;
+
+        if config.language != defaults::AUTO_LANGUAGE
+            && !tokens.languages.is_empty()
+            && tokens.language_token(&config.language).is_none()
+        {
+            return Err(VoicshError::ConfigInvalidValue {
+                key: "stt.language".to_string(),
+                message: format!("'{}' is not a language of {}", config.language, config.repo),
+            });
+        }
+
+        let device = Device::Cpu;
+        let weights = std::fs::read(&weights_path).map_err(|e| VoicshError::Transcription {
+            message: format!("Read {}: {e}", weights_path.display()),
+        })?;
+        let vb = VarBuilder::from_buffered_safetensors(weights, m::DTYPE, &device)
+            .map_err(load_error)?;
+        let suppress = suppress_mask(&model_config, tokens.eot);
+        let suppress = Tensor::new(suppress.as_slice(), &device).map_err(load_error)?;
+        let mel_filters = mel_filters(model_config.num_mel_bins);
+        let model = Whisper::load(&vb, model_config).map_err(load_error)?;
+
+        let model_name = format!(
+            "{} (candle)",
+            config.repo.rsplit('/').next().unwrap_or(&config.repo)
+        );
+        Ok(Self {
+            model: Mutex::new(model),
+            tokenizer,
+            tokens,
+            suppress,
+            mel_filters,
+            device,
+            config,
+            model_name,
+        })
+    }
+
+    /// Get the transcriber configuration.
+    pub fn config(&self) -> &CandleWhisperConfig {
+        &self.config
+    }
+
+    /// Language probabilities after `<|startoftranscript|>`, most likely first.
+    ///
+    /// A softmax over the language-token logits only, so the probabilities
+    /// sum to one across languages.
+    fn language_probabilities(
+        &self,
+        model: &mut Whisper,
+        features: &Tensor,
+    ) -> Result<Vec<LanguageProbability>> {
+        let sot = Tensor::new(&[[self.tokens.sot]], &self.device).map_err(inference_error)?;
+        let ys = model
+            .decoder
+            .forward(&sot, features, true)
+            .map_err(inference_error)?;
+        let logits = model
+            .decoder
+            .final_linear(&ys.i((.., 0..1)).map_err(inference_error)?)
+            .and_then(|l| l.i((0, 0)))
+            .and_then(|l| l.to_vec1::<f32>())
+            .map_err(inference_error)?;
+        let languages: Vec<(&str, f32)> = self
+            .tokens
+            .languages
+            .iter()
+            .filter_map(|(code, id)| Some((code.as_str(), *logits.get(*id as usize)?)))
+            .collect();
+        Ok(language_softmax(&languages))
+    }
+
+    /// Pick the most probable language token after `<|startoftranscript|>`.
+    fn detect_language(&self, model: &mut Whisper, features: &Tensor) -> Result<(String, u32)> {
+        self.language_probabilities(model, features)?
+            .into_iter()
+            .next()
+            .and_then(|best| {
+                let token = self.tokens.language_token(&best.language)?;
+                Some((best.language, token))
+            })
+            .ok_or_else(|| VoicshError::TranscriptionInferenceFailed {
+                message: "Model has no language tokens".to_string(),
+            })
+    }
+
+    /// Decode one 30-second window, given its encoder output, into ranked hypotheses.
+    fn decode_window(
+        &self,
+        model: &mut Whisper,
+        features: &Tensor,
+        language: &str,
+    ) -> Result<(String, Vec<Hypothesis>)> {
+        let mut prompt = vec![self.tokens.sot];
+        let language = if self.tokens.languages.is_empty() {
+            "en".to_string()
+        } else {
+            let forced = (language != defaults::AUTO_LANGUAGE)
+                .then(|| self.tokens.language_token(language))
+                .flatten();
+            let (code, token) = match forced {
+                Some(token) => (language.to_string(), token),
+                None => self.detect_language(model, features)?,
+            };
+            prompt.extend([token, self.tokens.transcribe]);
+            code
+        };
+        prompt.push(self.tokens.no_timestamps);
+
+        let beam_size = self.config.beam_size;
+        let batch_features = features
+            .repeat((beam_size, 1, 1))
+            .map_err(inference_error)?;
+        let max_tokens = (model.config.max_target_positions / 2).saturating_sub(prompt.len());
+        let mut first = true;
+        let beams = beam_search(&prompt, self.tokens.eot, beam_size, max_tokens, |seqs| {
+            let flush = std::mem::take(&mut first);
+            self.next_log_probs(model, &batch_features, seqs, flush)
+                .map_err(inference_error)
+        })?;
+
+        let hypotheses = beams
+            .into_iter()
+            .map(|beam| self.hypothesis(&beam))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((language, hypotheses))
+    }
+
+    /// Log-probabilities of the next token for each sequence.
+    fn next_log_probs(
+        &self,
+        model: &mut Whisper,
+        features: &Tensor,
+        seqs: &[Vec<u32>],
+        flush: bool,
+    ) -> candle_core::Result<Vec<Vec<f32>>> {
+        let len = seqs.first().map_or(0, Vec::len);
+        let flat: Vec<u32> = seqs.iter().flatten().copied().collect();
+        let tokens = Tensor::from_vec(flat, (seqs.len(), len), &self.device)?;
+        let ys = model.decoder.forward(&tokens, features, flush)?;
+        let logits = model.decoder.final_linear(&ys.i((.., len - 1..))?)?;
+        let logits = logits.squeeze(1)?.broadcast_add(&self.suppress)?;
+        candle_nn::ops::log_softmax(&logits, D::Minus1)?.to_vec2::<f32>()
+    }
+
+    fn hypothesis(&self, beam: &Beam) -> Result<Hypothesis> {
+        let decode = |ids: &[u32]| {
+            self.tokenizer.decode(ids, true).map_err(|e| {
+                VoicshError::TranscriptionInferenceFailed {
+                    message: format!("Decode tokens: {e}"),
+                }
+            })
+        };
+        let tokens = beam
+            .tokens
+            .iter()
+            .zip(&beam.log_probs)
+            .map(|(id, log_prob)| {
+                Ok(TokenProbability {
+                    token: decode(&[*id])?,
+                    probability: log_prob.exp(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Hypothesis {
+            text: decode(&beam.tokens)?.trim().to_string(),
+            log_probability: beam.log_probability,
+            tokens,
+        })
+    }
+}
+
+impl CandleWhisperTranscriber {
+    fn lock_model(&self) -> Result<std::sync::MutexGuard<'_, Whisper>> {
+        self.model
+            .lock()
+            .map_err(|e| VoicshError::TranscriptionInferenceFailed {
+                message: format!("Failed to acquire model lock: {e}"),
+            })
+    }
+
+    /// Log-mel spectrogram of `audio` and its number of content frames.
+    fn mel(&self, model: &Whisper, audio: &[i16]) -> Result<(Tensor, usize)> {
+        let pcm: Vec<f32> = audio.iter().map(|&s| s as f32 / 32768.0).collect();
+        let n_mels = model.config.num_mel_bins;
+        let mel = audio::pcm_to_mel(&model.config, &pcm, &self.mel_filters);
+        let mel_frames = mel.len() / n_mels;
+        let mel = Tensor::from_vec(mel, (1, n_mels, mel_frames), &self.device)
+            .map_err(inference_error)?;
+        Ok((mel, pcm.len() / m::HOP_LENGTH))
+    }
+
+    /// Encoder output for the first 30-second window of `mel`.
+    fn encode_first_window(&self, model: &mut Whisper, mel: &Tensor) -> Result<Tensor> {
+        let frames = mel.dim(2).map_err(inference_error)?.min(m::N_FRAMES);
+        let window = mel.narrow(2, 0, frames).map_err(inference_error)?;
+        model
+            .encoder
+            .forward(&window, true)
+            .map_err(inference_error)
+    }
+
+    /// Transcribe in `language`, detecting it per window for "auto" (or a
+    /// language the model does not know).
+    fn transcribe_in(&self, audio: &[i16], language: &str) -> Result<TranscriptionResult> {
+        let mut model = self.lock_model()?;
+        let (mel, content_frames) = self.mel(&model, audio)?;
+        self.transcribe_mel(&mut model, &mel, content_frames, language, None)
+    }
+
+    /// Transcribe a spectrogram window by window; `first_features` is the
+    /// encoder output of the first window, if already computed.
+    fn transcribe_mel(
+        &self,
+        model: &mut Whisper,
+        mel: &Tensor,
+        content_frames: usize,
+        requested: &str,
+        mut first_features: Option<Tensor>,
+    ) -> Result<TranscriptionResult> {
+        let mel_frames = mel.dim(2).map_err(inference_error)?;
+        let mut language = String::new();
+        let mut windows: Vec<Vec<Hypothesis>> = Vec::new();
+        let mut segments = Vec::new();
+        for seek in (0..content_frames.max(1)).step_by(m::N_FRAMES) {
+            let size = m::N_FRAMES.min(mel_frames - seek);
+            let features = match first_features.take() {
+                Some(features) => features,
+                None => {
+                    let window = mel.narrow(2, seek, size).map_err(inference_error)?;
+                    model
+                        .encoder
+                        .forward(&window, true)
+                        .map_err(inference_error)?
+                }
+            };
+            let (window_language, hypotheses) = self.decode_window(model, &features, requested)?;
+            if language.is_empty() {
+                language = window_language;
+            }
+            if let Some(best) = hypotheses.first() {
+                segments.push(Segment {
+                    start_ms: (seek * m::HOP_LENGTH * 1000 / m::SAMPLE_RATE) as u64,
+                    end_ms: ((seek + size).min(content_frames) * m::HOP_LENGTH * 1000
+                        / m::SAMPLE_RATE) as u64,
+                    text: best.text.clone(),
+                    tokens: best.tokens.clone(),
+                });
+            }
+            windows.push(hypotheses);
+        }
+
+        let mut ranked = combine_windows(&windows).into_iter();
+        let Some(best) = ranked.next() else {
+            return Ok(TranscriptionResult {
+                language,
+                confidence: 0.0,
+                ..TranscriptionResult::from_text(String::new())
+            });
+        };
+        let mut seen = vec![best.text.clone()];
+        let alternatives = ranked
+            .filter(|h| {
+                let new = !seen.contains(&h.text);
+                if new {
+                    seen.push(h.text.clone());
+                }
+                new
+            })
+            .collect();
+        let confidence = if best.tokens.is_empty() {
+            0.0
+        } else {
+            best.tokens.iter().map(|t| t.probability).sum::<f32>() / best.tokens.len() as f32
+        };
+
+        Ok(TranscriptionResult {
+            text: best.text,
+            language,
+            confidence: confidence.clamp(0.0, 1.0),
+            token_probabilities: best.tokens,
+            segments,
+            words: Vec::new(),
+            alternatives,
+            route: None,
+        })
+    }
+}
+
+impl Transcriber for CandleWhisperTranscriber {
+    fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+        self.transcribe_in(audio, &self.config.language)
+    }
+
+    fn transcribe_with_language(
+        &self,
+        audio: &[i16],
+        language: &str,
+    ) -> Result<TranscriptionResult> {
+        self.transcribe_in(audio, language)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+
+    /// Detection runs on the first 30-second window. English-only models
+    /// have no language tokens and no detection pass.
+    fn detect_language(&self, audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+        if self.tokens.languages.is_empty() {
+            return None;
+        }
+        let detect = || {
+            let mut model = self.lock_model()?;
+            let (mel, _) = self.mel(&model, audio)?;
+            let features = self.encode_first_window(&mut model, &mel)?;
+            self.language_probabilities(&mut model, &features)
+        };
+        Some(detect())
+    }
+
+    /// Detects on the first window's encoder output and reuses it for
+    /// transcription, so tracking costs one decoder step per chunk.
+    fn detect_and_transcribe(
+        &self,
+        audio: &[i16],
+        choose: &mut dyn FnMut(&[LanguageProbability]) -> Option<String>,
+    ) -> Option<Result<TranscriptionResult>> {
+        if self.tokens.languages.is_empty() {
+            return None;
+        }
+        let mut run = || {
+            let mut model = self.lock_model()?;
+            let (mel, content_frames) = self.mel(&model, audio)?;
+            let features = self.encode_first_window(&mut model, &mel)?;
+            let probabilities = self.language_probabilities(&mut model, &features)?;
+            let language = choose(&probabilities).unwrap_or_else(|| self.config.language.clone());
+            self.transcribe_mel(&mut model, &mel, content_frames, &language, Some(features))
+        };
+        Some(run())
+    }
+}
+
+/// Softmax over `(code, logit)` pairs, most probable first.
+fn language_softmax(logits: &[(&str, f32)]) -> Vec<LanguageProbability> {
+    let max = logits
+        .iter()
+        .map(|(_, logit)| *logit)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let exps: Vec<f32> = logits.iter().map(|(_, l)| (l - max).exp()).collect();
+    let total: f32 = exps.iter().sum();
+    let mut probabilities: Vec<LanguageProbability> = logits
+        .iter()
+        .zip(exps)
+        .map(|((code, _), exp)| LanguageProbability {
+            language: code.to_string(),
+            probability: exp / total,
+        })
+        .collect();
+    probabilities.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+    probabilities
+}
+
+fn load_error(e: candle_core::Error) -> VoicshError {
+    VoicshError::Transcription {
+        message: format!("Load Whisper model: {e}"),
+    }
+}
+
+fn inference_error(e: candle_core::Error) -> VoicshError {
+    VoicshError::TranscriptionInferenceFailed {
+        message: format!("Candle Whisper inference failed: {e}"),
+    }
+}
+
+/// Logit mask that suppresses the model's `suppress_tokens` and every special
+/// token except end-of-text (timestamps, language and task tokens).
+fn suppress_mask(config: &ModelConfig, eot: u32) -> Vec<f32> {
+    (0..config.vocab_size as u32)
+        .map(|id| {
+            if id > eot || config.suppress_tokens.contains(&id) {
+                f32::NEG_INFINITY
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// One decoding path: generated tokens and their log-probabilities.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Beam {
+    tokens: Vec<u32>,
+    log_probs: Vec<f32>,
+    /// Sum of `log_probs`, plus end-of-text once finished.
+    log_probability: f32,
+}
+
+impl Beam {
+    /// Length-normalized score used to rank finished beams.
+    fn score(&self) -> f32 {
+        self.log_probability / self.tokens.len().max(1) as f32
+    }
+}
+
+/// Beam search as in OpenAI's Whisper decoder.
+///
+/// `step` gets `beam_size` equally long sequences (prompt plus generated
+/// tokens) and returns next-token log-probabilities for each. Keeping the
+/// batch size fixed lets the model reuse its cross-attention cache. Search
+/// stops once `beam_size` beams emitted `eot` or after `max_tokens` steps;
+/// the result holds the finished beams (topped up with unfinished ones),
+/// best first by length-normalized log-probability.
+fn beam_search<F>(
+    prompt: &[u32],
+    eot: u32,
+    beam_size: usize,
+    max_tokens: usize,
+    mut step: F,
+) -> Result<Vec<Beam>>
+where
+    F: FnMut(&[Vec<u32>]) -> Result<Vec<Vec<f32>>>,
+{
+    let mut active = vec![Beam::default(); beam_size];
+    let mut finished: Vec<Beam> = Vec::new();
+
+    for i in 0..max_tokens {
+        let seqs: Vec<Vec<u32>> = active
+            .iter()
+            .map(|beam| prompt.iter().chain(&beam.tokens).copied().collect())
+            .collect();
+        let rows = step(&seqs)?;
+
+        // All beams start out identical; expand only one of them at first.
+        let sources = if i == 0 { 1 } else { active.len() };
+        let mut candidates: Vec<(f32, usize, u32, f32)> = Vec::new();
+        for (source, row) in rows.iter().take(sources).enumerate() {
+            let base = active[source].log_probability;
+            for (token, log_prob) in top_k(row, beam_size + 1) {
+                candidates.push((base + log_prob, source, token, log_prob));
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut next = Vec::with_capacity(beam_size);
+        for (total, source, token, log_prob) in candidates {
+            if total == f32::NEG_INFINITY || next.len() == beam_size {
+                break;
+            }
+            let mut beam = active[source].clone();
+            beam.log_probability = total;
+            if token == eot {
+                if finished.len() < beam_size {
+                    finished.push(beam);
+                }
+            } else {
+                beam.tokens.push(token);
+                beam.log_probs.push(log_prob);
+                next.push(beam);
+            }
+        }
+        if finished.len() >= beam_size || next.len() < beam_size {
+            active = next;
+            break;
+        }
+        active = next;
+    }
+
+    if finished.len() < beam_size {
+        active.sort_by(|a, b| b.log_probability.total_cmp(&a.log_probability));
+        finished.extend(active.into_iter().take(beam_size - finished.len()));
+    }
+    finished.sort_by(|a, b| b.score().total_cmp(&a.score()));
+    Ok(finished)
+}
+
+/// Indices and values of the `k` largest entries, largest first.
+fn top_k(values: &[f32], k: usize) -> Vec<(u32, f32)> {
+    let mut indexed: Vec<(u32, f32)> = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i as u32, v))
+        .collect();
+    let k = k.min(indexed.len());
+    if k < indexed.len() {
+        indexed.select_nth_unstable_by(k, |a, b| b.1.total_cmp(&a.1));
+        indexed.truncate(k);
+    }
+    indexed.sort_by(|a, b| b.1.total_cmp(&a.1));
+    indexed
+}
+
+/// Join per-window n-best lists into whole-chunk hypotheses.
+///
+/// The n-th result joins every window's n-th hypothesis, or its last one
+/// when a window kept fewer.
+fn combine_windows(windows: &[Vec<Hypothesis>]) -> Vec<Hypothesis> {
+    let ranks = windows.iter().map(Vec::len).max().unwrap_or(0);
+    if windows.iter().any(Vec::is_empty) {
+        return Vec::new();
+    }
+    (0..ranks)
+        .map(|rank| {
+            let parts: Vec<&Hypothesis> = windows
+                .iter()
+                .filter_map(|w| w.get(rank).or_else(|| w.last()))
+                .collect();
+            Hypothesis {
+                text: parts
+                    .iter()
+                    .map(|h| h.text.as_str())
+                    .filter(|t| !t.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                log_probability: parts.iter().map(|h| h.log_probability).sum(),
+                tokens: parts.iter().flat_map(|h| h.tokens.clone()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Slaney-style mel filterbank as used by Whisper (librosa
+/// `filters.mel(sr=16000, n_fft=400, norm="slaney")`), `n_mels` rows of
+/// `N_FFT / 2 + 1` weights.
+fn mel_filters(n_mels: usize) -> Vec<f32> {
+    const MIN_LOG_HZ: f64 = 1000.0;
+    const F_SP: f64 = 200.0 / 3.0;
+    let min_log_mel = MIN_LOG_HZ / F_SP;
+    let log_step = 6.4f64.ln() / 27.0;
+    let hz_to_mel = |hz: f64| {
+        if hz < MIN_LOG_HZ {
+            hz / F_SP
+        } else {
+            min_log_mel + (hz / MIN_LOG_HZ).ln() / log_step
+        }
+    };
+    let mel_to_hz = |mel: f64| {
+        if mel < min_log_mel {
+            mel * F_SP
+        } else {
+            MIN_LOG_HZ * ((mel - min_log_mel) * log_step).exp()
+        }
+    };
+
+    let n_freqs = m::N_FFT / 2 + 1;
+    let nyquist = m::SAMPLE_RATE as f64 / 2.0;
+    let max_mel = hz_to_mel(nyquist);
+    let points: Vec<f64> = (0..n_mels + 2)
+        .map(|i| mel_to_hz(max_mel * i as f64 / (n_mels + 1) as f64))
+        .collect();
+
+    let mut filters = vec![0f32; n_mels * n_freqs];
+    for band in 0..n_mels {
+        let (low, center, high) = (points[band], points[band + 1], points[band + 2]);
+        let norm = 2.0 / (high - low);
+        for bin in 0..n_freqs {
+            let freq = bin as f64 * m::SAMPLE_RATE as f64 / m::N_FFT as f64;
+            let rising = (freq - low) / (center - low);
+            let falling = (high - freq) / (high - center);
+            filters[band * n_freqs + bin] = (rising.min(falling).max(0.0) * norm) as f32;
+        }
+    }
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EOT: u32 = 0;
+
+    /// Log-probability rows where `table[last token]` gives the probability
+    /// of each next token (vocabulary of 4: eot, 1, 2, 3).
+    fn markov(table: &[[f32; 4]; 4]) -> impl FnMut(&[Vec<u32>]) -> Result<Vec<Vec<f32>>> + '_ {
+        move |seqs| {
+            Ok(seqs
+                .iter()
+                .map(|seq| {
+                    let last = *seq.last().unwrap_or(&EOT) as usize;
+                    table[last].iter().map(|p| p.ln()).collect()
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn beam_search_keeps_n_best_with_token_log_probs() {
+        // From the prompt (token 3): "1" is likelier than "2", then eot.
+        let table = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.9, 0.05, 0.05, 0.0],
+            [0.8, 0.1, 0.1, 0.0],
+            [0.0, 0.6, 0.4, 0.0],
+        ];
+        let beams = beam_search(&[3], EOT, 2, 10, markov(&table)).unwrap();
+
+        assert_eq!(beams.len(), 2);
+        assert_eq!(beams[0].tokens, vec![1]);
+        assert_eq!(beams[1].tokens, vec![2]);
+        assert!((beams[0].log_probs[0] - 0.6f32.ln()).abs() < 1e-5);
+        assert!((beams[0].log_probability - (0.6f32 * 0.9).ln()).abs() < 1e-5);
+        assert!(beams[0].score() > beams[1].score());
+    }
+
+    #[test]
+    fn beam_search_keeps_batch_size_constant() {
+        let table = [[0.25; 4]; 4];
+        let mut sizes = Vec::new();
+        let mut step = markov(&table);
+        beam_search(&[3], EOT, 3, 4, |seqs| {
+            sizes.push(seqs.len());
+            step(seqs)
+        })
+        .unwrap();
+        assert!(!sizes.is_empty());
+        assert!(sizes.iter().all(|&n| n == 3), "batch sizes: {sizes:?}");
+    }
+
+    #[test]
+    fn beam_search_returns_unfinished_beams_at_token_limit() {
+        // eot is impossible, so every beam runs into the limit.
+        let table = [[0.0, 0.5, 0.3, 0.2]; 4];
+        let beams = beam_search(&[3], EOT, 2, 3, markov(&table)).unwrap();
+        assert_eq!(beams.len(), 2);
+        assert!(beams.iter().all(|b| b.tokens.len() == 3));
+        assert_eq!(beams[0].tokens, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn top_k_returns_largest_first() {
+        assert_eq!(top_k(&[0.1, 0.7, 0.2, 0.5], 2), vec![(1, 0.7), (3, 0.5)]);
+        assert_eq!(top_k(&[0.1], 3), vec![(0, 0.1)]);
+    }
+
+    #[test]
+    fn combine_windows_joins_ranks_and_sums_log_probs() {
+        let hyp = |text: &str, log_probability: f32| Hypothesis {
+            text: text.to_string(),
+            log_probability,
+            tokens: vec![TokenProbability {
+                token: text.to_string(),
+                probability: log_probability.exp(),
+            }],
+        };
+        let combined = combine_windows(&[
+            vec![hyp("hello", -0.1), hyp("yellow", -0.5)],
+            vec![hyp("world", -0.2)],
+        ]);
+        assert_eq!(combined.len(), 2);
+        assert_eq!(combined[0].text, "hello world");
+        assert_eq!(combined[1].text, "yellow world");
+        assert!((combined[1].log_probability + 0.7).abs() < 1e-6);
+        assert_eq!(combined[1].tokens.len(), 2);
+        assert!(combine_windows(&[]).is_empty());
+    }
+
+    #[test]
+    fn mel_filters_are_normalized_triangles() {
+        let n_freqs = m::N_FFT / 2 + 1;
+        for n_mels in [80, 128] {
+            let filters = mel_filters(n_mels);
+            assert_eq!(filters.len(), n_mels * n_freqs);
+            assert!(filters.iter().all(|w| *w >= 0.0));
+            for band in 0..n_mels {
+                let row = &filters[band * n_freqs..(band + 1) * n_freqs];
+                assert!(row.iter().any(|w| *w > 0.0), "band {band} is empty");
+            }
+            // Slaney norm: each triangle has unit area in Hz, so wide
+            // (high) bands sum to about 1 / bin width.
+            let bin_hz = m::SAMPLE_RATE as f32 / m::N_FFT as f32;
+            let last = &filters[(n_mels - 1) * n_freqs..];
+            let area: f32 = last.iter().sum::<f32>() * bin_hz;
+            assert!((area - 1.0).abs() < 0.1, "area {area}");
+        }
+    }
+
+    #[test]
+    fn language_code_accepts_only_language_tokens() {
+        assert_eq!(language_code("<|de|>"), Some("de"));
+        assert_eq!(language_code("<|haw|>"), Some("haw"));
+        assert_eq!(language_code("<|transcribe|>"), None);
+        assert_eq!(language_code("<|0.00|>"), None);
+        assert_eq!(language_code("de"), None);
+    }
+
+    #[test]
+    fn language_softmax_normalizes_and_ranks() {
+        let probabilities = language_softmax(&[("en", 1.0), ("de", 3.0), ("fr", 1.0)]);
+        let codes: Vec<&str> = probabilities.iter().map(|p| p.language.as_str()).collect();
+        assert_eq!(codes[0], "de");
+        let total: f32 = probabilities.iter().map(|p| p.probability).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        assert!((probabilities[1].probability - probabilities[2].probability).abs() < 1e-6);
+        assert!(language_softmax(&[]).is_empty());
+    }
+
+    #[test]
+    fn suppress_mask_allows_text_and_eot_only() {
+        let config = ModelConfig {
+            num_mel_bins: 80,
+            max_source_positions: 1500,
+            d_model: 4,
+            encoder_attention_heads: 1,
+            encoder_layers: 1,
+            vocab_size: 6,
+            max_target_positions: 448,
+            decoder_attention_heads: 1,
+            decoder_layers: 1,
+            suppress_tokens: vec![1],
+        };
+        let mask = suppress_mask(&config, 3);
+        assert_eq!(mask[0], 0.0);
+        assert_eq!(mask[1], f32::NEG_INFINITY);
+        assert_eq!(mask[3], 0.0);
+        assert_eq!(mask[4], f32::NEG_INFINITY);
+        assert_eq!(mask[5], f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn config_from_stt_section() {
+        let mut stt = SttConfig::default();
+        stt.candle.beam_size = 0;
+        stt.language = "de".to_string();
+        let config = CandleWhisperConfig::from_config(&stt);
+        assert_eq!(config.repo, defaults::CANDLE_WHISPER_REPO);
+        assert_eq!(config.language, "de");
+        assert_eq!(config.beam_size, 1);
+    }
+
+    #[test]
+    fn candle_whisper_transcriber_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CandleWhisperTranscriber>();
+    }
+}
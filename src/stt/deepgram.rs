@@ -0,0 +1,792 @@
+//! Streaming cloud transcription over a Deepgram-compatible WebSocket.
+//!
+//! Opt-in (`cloud-stt` feature, `stt.backend = "deepgram"`): audio leaves the
+//! machine, which goes against voicsh's offline-first default. While a
+//! recording runs, a session keeps one WebSocket open to `/v1/listen`,
+//! streams 16 kHz linear PCM as binary frames and turns the service's
+//! `Results` messages into interim hypotheses and final results.
+//!
+//! Each session runs its socket on a small worker thread with its own tokio
+//! runtime, so the pipeline thread that feeds it never blocks on the network.
+
+use crate::config::DeepgramSttConfig;
+use crate::defaults;
+use crate::error::{Result, VoicshError};
+use crate::stt::streaming::{StreamingSession, StreamingTranscriber, StreamingUpdate};
+use crate::stt::transcriber::{Segment, Transcriber, TranscriptionResult, Word};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+
+/// Time allowed for the WebSocket handshake.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Interval of keep-alive messages; the service closes idle streams after ~10s.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Asks the service to flush its final results and close the stream.
+const CLOSE_STREAM: &str = r#"{"type":"CloseStream"}"#;
+
+const KEEPALIVE: &str = r#"{"type":"KeepAlive"}"#;
+
+/// Environment variables checked for the API key, in order.
+const API_KEY_VARS: [&str; 2] = ["VOICSH_DEEPGRAM_API_KEY", "DEEPGRAM_API_KEY"];
+
+/// Resolved settings for [`DeepgramTranscriber`].
+#[derive(Debug, Clone)]
+pub struct DeepgramTranscriberConfig {
+    pub url: String,
+    pub model: String,
+    pub api_key: String,
+    /// Language code, or "auto" for the service's multilingual mode.
+    pub language: String,
+    pub interim_results: bool,
+}
+
+impl DeepgramTranscriberConfig {
+    /// Build from `[stt.deepgram]`, reading the API key from the environment
+    /// or `api_key_file`.
+    pub fn from_config(config: &DeepgramSttConfig, language: &str) -> Result<Self> {
+        let api_key = resolve_api_key(
+            |name| std::env::var(name).ok(),
+            config.api_key_file.as_deref(),
+        )?;
+        Ok(Self {
+            url: config.url.clone(),
+            model: config.model.clone(),
+            api_key,
+            language: language.to_string(),
+            interim_results: config.interim_results,
+        })
+    }
+
+    /// Listen URL with the stream parameters as query arguments.
+    fn listen_url(&self) -> String {
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+        let language = if self.language == "auto" {
+            "multi"
+        } else {
+            &self.language
+        };
+        format!(
+            "{}{separator}encoding=linear16&sample_rate={}&channels=1&model={}&language={}&interim_results={}&punctuate=true&smart_format=true",
+            self.url,
+            defaults::SAMPLE_RATE,
+            encode_query_value(&self.model),
+            encode_query_value(language),
+            self.interim_results,
+        )
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters.
+fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// First non-empty key from `env` (see [`API_KEY_VARS`]), else the contents of `file`.
+fn resolve_api_key(env: impl Fn(&str) -> Option<String>, file: Option<&str>) -> Result<String> {
+    if let Some(key) = API_KEY_VARS
+        .iter()
+        .filter_map(|name| env(name))
+        .map(|key| key.trim().to_string())
+        .find(|key| !key.is_empty())
+    {
+        return Ok(key);
+    }
+    let invalid = |message: String| VoicshError::ConfigInvalidValue {
+        key: "stt.deepgram.api_key_file".to_string(),
+        message,
+    };
+    let Some(path) = file else {
+        return Err(invalid(format!(
+            "No API key: set {} or {}, or point api_key_file at a file containing it",
+            API_KEY_VARS[0], API_KEY_VARS[1]
+        )));
+    };
+    let key = std::fs::read_to_string(path)
+        .map_err(|e| invalid(format!("Failed to read {path}: {e}")))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(invalid(format!("{path} is empty")));
+    }
+    Ok(key.to_string())
+}
+
+/// Streaming transcriber backed by a Deepgram-compatible service.
+pub struct DeepgramTranscriber {
+    config: DeepgramTranscriberConfig,
+    model_name: String,
+}
+
+impl DeepgramTranscriber {
+    pub fn new(config: DeepgramTranscriberConfig) -> Result<Self> {
+        if !(config.url.starts_with("wss://") || config.url.starts_with("ws://")) {
+            return Err(VoicshError::ConfigInvalidValue {
+                key: "stt.deepgram.url".to_string(),
+                message: format!("'{}' is not a ws:// or wss:// URL", config.url),
+            });
+        }
+        let model_name = format!("{} (deepgram)", config.model);
+        Ok(Self { config, model_name })
+    }
+}
+
+impl StreamingTranscriber for DeepgramTranscriber {
+    fn open(&self) -> Result<Box<dyn StreamingSession>> {
+        Ok(Box::new(DeepgramSession::open(&self.config)?))
+    }
+}
+
+impl Transcriber for DeepgramTranscriber {
+    /// Stream a finished chunk and join its final results.
+    fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+        let mut session = self.open()?;
+        for block in audio.chunks(defaults::SAMPLE_RATE as usize / 10) {
+            session.send_audio(block)?;
+        }
+        let finals: Vec<TranscriptionResult> = session
+            .finish(CONNECT_TIMEOUT)?
+            .into_iter()
+            .filter_map(|update| match update {
+                StreamingUpdate::Final(result) => Some(result),
+                StreamingUpdate::Interim(_) => None,
+            })
+            .collect();
+
+        let mut joined = TranscriptionResult::from_text(
+            finals
+                .iter()
+                .map(|r| r.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        if !finals.is_empty() {
+            joined.confidence =
+                finals.iter().map(|r| r.confidence).sum::<f32>() / finals.len() as f32;
+        }
+        for result in finals {
+            if joined.language.is_empty() {
+                joined.language = result.language;
+            }
+            joined.segments.extend(result.segments);
+            joined.words.extend(result.words);
+        }
+        Ok(joined)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+
+    fn streaming(&self) -> Option<&dyn StreamingTranscriber> {
+        Some(self)
+    }
+}
+
+enum Outgoing {
+    Audio(Vec<u8>),
+    Finish,
+}
+
+type Incoming = std::result::Result<StreamingUpdate, String>;
+
+/// One open WebSocket stream.
+struct DeepgramSession {
+    outgoing: mpsc::UnboundedSender<Outgoing>,
+    incoming: crossbeam_channel::Receiver<Incoming>,
+    failure: Option<String>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl DeepgramSession {
+    /// Connect and wait for the handshake.
+    fn open(config: &DeepgramTranscriberConfig) -> Result<Self> {
+        let server_error = |message: String| VoicshError::TranscriptionServer { message };
+        let mut request = config
+            .listen_url()
+            .into_client_request()
+            .map_err(|e| server_error(format!("Invalid streaming URL: {e}")))?;
+        let authorization = HeaderValue::from_str(&format!("Token {}", config.api_key))
+            .map_err(|_| server_error("API key contains invalid characters".to_string()))?;
+        request.headers_mut().insert("Authorization", authorization);
+
+        let language = if config.language == "auto" {
+            String::new()
+        } else {
+            config.language.clone()
+        };
+        let (outgoing, outgoing_rx) = mpsc::unbounded_channel();
+        let (incoming_tx, incoming) = crossbeam_channel::unbounded();
+        let (ready_tx, ready_rx) = crossbeam_channel::bounded(1);
+
+        let worker = std::thread::Builder::new()
+            .name("voicsh-deepgram".to_string())
+            .spawn(move || {
+                let runtime = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime,
+                    Err(e) => {
+                        ready_tx
+                            .send(Err(format!("Failed to start runtime: {e}")))
+                            .ok();
+                        return;
+                    }
+                };
+                runtime.block_on(run_stream(
+                    request,
+                    language,
+                    outgoing_rx,
+                    incoming_tx,
+                    ready_tx,
+                ));
+            })
+            .map_err(|e| server_error(format!("Failed to start streaming thread: {e}")))?;
+
+        match ready_rx.recv_timeout(CONNECT_TIMEOUT + Duration::from_secs(1)) {
+            Ok(Ok(())) => Ok(Self {
+                outgoing,
+                incoming,
+                failure: None,
+                worker: Some(worker),
+            }),
+            Ok(Err(message)) => Err(server_error(message)),
+            Err(_) => Err(server_error(
+                "Timed out connecting to the streaming service".to_string(),
+            )),
+        }
+    }
+
+    /// Move whatever arrived into `updates`, remembering a failure.
+    fn drain(&mut self, updates: &mut Vec<StreamingUpdate>) {
+        while let Ok(incoming) = self.incoming.try_recv() {
+            match incoming {
+                Ok(update) => updates.push(update),
+                Err(message) => self.failure = Some(message),
+            }
+        }
+    }
+}
+
+impl StreamingSession for DeepgramSession {
+    fn send_audio(&mut self, samples: &[i16]) -> Result<()> {
+        let bytes = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        self.outgoing
+            .send(Outgoing::Audio(bytes))
+            .map_err(|_| VoicshError::TranscriptionServer {
+                message: self
+                    .failure
+                    .clone()
+                    .unwrap_or_else(|| "Streaming connection closed".to_string()),
+            })
+    }
+
+    fn poll(&mut self) -> Result<Vec<StreamingUpdate>> {
+        let mut updates = Vec::new();
+        self.drain(&mut updates);
+        match &self.failure {
+            Some(message) if updates.is_empty() => Err(VoicshError::TranscriptionServer {
+                message: message.clone(),
+            }),
+            _ => Ok(updates),
+        }
+    }
+
+    fn finish(mut self: Box<Self>, timeout: Duration) -> Result<Vec<StreamingUpdate>> {
+        let mut updates = Vec::new();
+        self.drain(&mut updates);
+        self.outgoing.send(Outgoing::Finish).ok();
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.incoming.recv_timeout(remaining) {
+                Ok(Ok(update)) => updates.push(update),
+                Ok(Err(message)) => self.failure = Some(message),
+                // The worker is done (stream closed) or out of time
+                Err(_) => break,
+            }
+        }
+        if let Some(worker) = self.worker.take()
+            && worker.is_finished()
+        {
+            worker.join().ok();
+        }
+        match self.failure.take() {
+            Some(message) if updates.is_empty() => {
+                Err(VoicshError::TranscriptionServer { message })
+            }
+            _ => Ok(updates),
+        }
+    }
+}
+
+/// Make ring the process-wide rustls provider unless one is set already.
+///
+/// rustls picks no default itself when another crate (e.g. reqwest with
+/// aws-lc-rs) compiles in a second provider, and `wss://` connects would fail.
+fn install_crypto_provider() {
+    if rustls::crypto::CryptoProvider::get_default().is_none() {
+        // Losing a race to another thread installing one is fine
+        rustls::crypto::ring::default_provider()
+            .install_default()
+            .ok();
+    }
+}
+
+/// Socket loop of one session, run on its worker thread.
+async fn run_stream(
+    request: tokio_tungstenite::tungstenite::handshake::client::Request,
+    language: String,
+    mut outgoing: mpsc::UnboundedReceiver<Outgoing>,
+    incoming: crossbeam_channel::Sender<Incoming>,
+    ready: crossbeam_channel::Sender<std::result::Result<(), String>>,
+) {
+    install_crypto_provider();
+    let connected =
+        tokio::time::timeout(CONNECT_TIMEOUT, tokio_tungstenite::connect_async(request)).await;
+    let stream = match connected {
+        Ok(Ok((stream, _))) => stream,
+        Ok(Err(e)) => {
+            ready
+                .send(Err(format!("Failed to connect to streaming service: {e}")))
+                .ok();
+            return;
+        }
+        Err(_) => {
+            ready
+                .send(Err(
+                    "Timed out connecting to the streaming service".to_string()
+                ))
+                .ok();
+            return;
+        }
+    };
+    ready.send(Ok(())).ok();
+
+    let (mut sink, mut source) = stream.split();
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await;
+    let mut closing = false;
+
+    loop {
+        tokio::select! {
+            command = outgoing.recv(), if !closing => {
+                let message = match command {
+                    Some(Outgoing::Audio(bytes)) => Message::binary(bytes),
+                    // Finished, or the session was dropped
+                    Some(Outgoing::Finish) | None => {
+                        closing = true;
+                        Message::text(CLOSE_STREAM)
+                    }
+                };
+                if let Err(e) = sink.send(message).await {
+                    incoming.send(Err(format!("Streaming connection lost: {e}"))).ok();
+                    return;
+                }
+            }
+            _ = keepalive.tick(), if !closing => {
+                if let Err(e) = sink.send(Message::text(KEEPALIVE)).await {
+                    incoming.send(Err(format!("Streaming connection lost: {e}"))).ok();
+                    return;
+                }
+            }
+            message = source.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Some(update) = parse_message(&text, &language) {
+                        incoming.send(Ok(update)).ok();
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => return,
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    incoming.send(Err(format!("Streaming connection lost: {e}"))).ok();
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ServiceMessage {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    is_final: bool,
+    #[serde(default)]
+    start: f64,
+    #[serde(default)]
+    duration: f64,
+    channel: Option<Channel>,
+}
+
+#[derive(Deserialize)]
+struct Channel {
+    #[serde(default)]
+    alternatives: Vec<Alternative>,
+}
+
+#[derive(Deserialize)]
+struct Alternative {
+    #[serde(default)]
+    transcript: String,
+    #[serde(default)]
+    confidence: f32,
+    #[serde(default)]
+    words: Vec<ServiceWord>,
+    #[serde(default)]
+    languages: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ServiceWord {
+    word: String,
+    punctuated_word: Option<String>,
+    start: f64,
+    end: f64,
+    #[serde(default)]
+    confidence: f32,
+}
+
+fn secs_to_ms(secs: f64) -> u64 {
+    (secs.max(0.0) * 1000.0).round() as u64
+}
+
+/// Turn a `Results` message into an update; other messages and empty
+/// transcripts yield `None`. `language` is used when the service reports none.
+fn parse_message(text: &str, language: &str) -> Option<StreamingUpdate> {
+    let message: ServiceMessage = serde_json::from_str(text).ok()?;
+    if message.kind != "Results" {
+        return None;
+    }
+    let alternative = message.channel?.alternatives.into_iter().next()?;
+    let transcript = alternative.transcript.trim().to_string();
+    if transcript.is_empty() {
+        return None;
+    }
+    if !message.is_final {
+        return Some(StreamingUpdate::Interim(transcript));
+    }
+
+    let mut result = TranscriptionResult::from_text(transcript.clone());
+    result.language = alternative
+        .languages
+        .first()
+        .cloned()
+        .unwrap_or_else(|| language.to_string());
+    result.confidence = alternative.confidence;
+    result.segments = vec![Segment {
+        start_ms: secs_to_ms(message.start),
+        end_ms: secs_to_ms(message.start + message.duration),
+        text: transcript,
+        tokens: Vec::new(),
+    }];
+    result.words = alternative
+        .words
+        .into_iter()
+        .map(|w| Word {
+            text: w.punctuated_word.unwrap_or(w.word),
+            start_ms: secs_to_ms(w.start),
+            end_ms: secs_to_ms(w.end),
+            probability: w.confidence,
+            model: None,
+        })
+        .collect();
+    Some(StreamingUpdate::Final(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+
+    const KEY: &str = "test-key";
+
+    /// What the mock service saw of a session.
+    #[derive(Default)]
+    struct Seen {
+        uri: String,
+        audio_bytes: usize,
+        closed_stream: bool,
+    }
+
+    fn results(text: &str, is_final: bool, start: f64) -> String {
+        json!({
+            "type": "Results",
+            "is_final": is_final,
+            "start": start,
+            "duration": 1.0,
+            "channel": {"alternatives": [{
+                "transcript": text,
+                "confidence": 0.9,
+                "words": text.split(' ').enumerate().map(|(i, w)| json!({
+                    "word": w.to_lowercase(),
+                    "punctuated_word": w,
+                    "start": start + i as f64 * 0.3,
+                    "end": start + i as f64 * 0.3 + 0.25,
+                    "confidence": 0.9,
+                })).collect::<Vec<_>>(),
+            }]},
+        })
+        .to_string()
+    }
+
+    /// Local Deepgram stand-in: rejects other keys, sends an interim result
+    /// once audio arrives and the final result on `CloseStream`.
+    fn mock_service() -> (String, Arc<Mutex<Seen>>) {
+        let seen = Arc::new(Mutex::new(Seen::default()));
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let url = format!("ws://{}/v1/listen", listener.local_addr().unwrap());
+        let service_seen = seen.clone();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                loop {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    let seen = service_seen.clone();
+                    tokio::spawn(async move {
+                        let check = |request: &Request, response: Response| {
+                            seen.lock().unwrap().uri = request.uri().to_string();
+                            let authorized = request
+                                .headers()
+                                .get("Authorization")
+                                .is_some_and(|v| v == format!("Token {KEY}").as_str());
+                            if authorized {
+                                Ok(response)
+                            } else {
+                                let mut denied = ErrorResponse::new(None);
+                                *denied.status_mut() =
+                                    tokio_tungstenite::tungstenite::http::StatusCode::UNAUTHORIZED;
+                                Err(denied)
+                            }
+                        };
+                        let Ok(mut socket) =
+                            tokio_tungstenite::accept_hdr_async(stream, check).await
+                        else {
+                            return;
+                        };
+                        let mut sent_interim = false;
+                        while let Some(Ok(message)) = socket.next().await {
+                            match message {
+                                Message::Binary(bytes) => {
+                                    seen.lock().unwrap().audio_bytes += bytes.len();
+                                    if !sent_interim {
+                                        sent_interim = true;
+                                        socket
+                                            .send(Message::text(results("Turn on", false, 0.0)))
+                                            .await
+                                            .unwrap();
+                                    }
+                                }
+                                Message::Text(text) if text.as_str() == CLOSE_STREAM => {
+                                    seen.lock().unwrap().closed_stream = true;
+                                    let fin = results("Turn on the lights.", true, 0.0);
+                                    socket.send(Message::text(fin)).await.unwrap();
+                                    let metadata = json!({"type": "Metadata"}).to_string();
+                                    socket.send(Message::text(metadata)).await.unwrap();
+                                    socket.close(None).await.ok();
+                                    return;
+                                }
+                                _ => {}
+                            }
+                        }
+                    });
+                }
+            });
+        });
+        (url, seen)
+    }
+
+    fn transcriber(url: &str, api_key: &str) -> DeepgramTranscriber {
+        DeepgramTranscriber::new(DeepgramTranscriberConfig {
+            url: url.to_string(),
+            model: "nova-3".to_string(),
+            api_key: api_key.to_string(),
+            language: "en".to_string(),
+            interim_results: true,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_crypto_provider_is_installed_for_tls() {
+        install_crypto_provider();
+        assert!(rustls::crypto::CryptoProvider::get_default().is_some());
+    }
+
+    #[test]
+    fn test_session_streams_audio_and_receives_interim_and_final() {
+        let (url, seen) = mock_service();
+        let transcriber = transcriber(&url, KEY);
+        let mut session = transcriber.streaming().unwrap().open().unwrap();
+
+        session.send_audio(&[100; 1600]).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let interim = loop {
+            let updates = session.poll().unwrap();
+            if let Some(StreamingUpdate::Interim(text)) = updates.into_iter().next() {
+                break text;
+            }
+            assert!(Instant::now() < deadline, "no interim result");
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(interim, "Turn on");
+
+        let updates = session.finish(Duration::from_secs(5)).unwrap();
+        let [StreamingUpdate::Final(result)] = updates.as_slice() else {
+            panic!("expected one final result, got {updates:?}");
+        };
+        assert_eq!(result.text, "Turn on the lights.");
+        assert_eq!(result.language, "en");
+        assert_eq!(result.words.len(), 4);
+        assert_eq!(result.words[3].text, "lights.");
+        assert_eq!(result.words[1].start_ms, 300);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.audio_bytes, 3200);
+        assert!(seen.closed_stream);
+        assert!(seen.uri.contains("encoding=linear16"));
+        assert!(seen.uri.contains("sample_rate=16000"));
+        assert!(seen.uri.contains("model=nova-3"));
+        assert!(seen.uri.contains("language=en"));
+    }
+
+    #[test]
+    fn test_transcribe_joins_final_results() {
+        let (url, _) = mock_service();
+        let result = transcriber(&url, KEY).transcribe(&[100; 8000]).unwrap();
+        assert_eq!(result.text, "Turn on the lights.");
+        assert!((result.confidence - 0.9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rejected_key_fails_to_open() {
+        let (url, _) = mock_service();
+        let error = transcriber(&url, "wrong-key")
+            .streaming()
+            .unwrap()
+            .open()
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("401"), "{error}");
+    }
+
+    #[test]
+    fn test_rejects_non_websocket_url() {
+        let config = DeepgramTranscriberConfig {
+            url: "https://api.deepgram.com/v1/listen".to_string(),
+            model: "nova-3".to_string(),
+            api_key: KEY.to_string(),
+            language: "auto".to_string(),
+            interim_results: true,
+        };
+        assert!(DeepgramTranscriber::new(config.clone()).is_err());
+
+        let config = DeepgramTranscriberConfig {
+            url: "wss://example.test/v1/listen?tier=fast".to_string(),
+            ..config
+        };
+        let url = config.listen_url();
+        assert!(url.starts_with("wss://example.test/v1/listen?tier=fast&encoding=linear16"));
+        assert!(
+            url.contains("language=multi"),
+            "auto uses multilingual mode"
+        );
+
+        let config = DeepgramTranscriberConfig {
+            model: "nova-3&keywords=x y".to_string(),
+            ..config
+        };
+        assert!(
+            config
+                .listen_url()
+                .contains("&model=nova-3%26keywords%3Dx%20y&language=multi&")
+        );
+    }
+
+    #[test]
+    fn test_api_key_from_environment_or_file() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            resolve_api_key(env(&[("DEEPGRAM_API_KEY", "generic")]), None).unwrap(),
+            "generic"
+        );
+        assert_eq!(
+            resolve_api_key(
+                env(&[
+                    ("VOICSH_DEEPGRAM_API_KEY", "specific"),
+                    ("DEEPGRAM_API_KEY", "generic")
+                ]),
+                None
+            )
+            .unwrap(),
+            "specific"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deepgram");
+        std::fs::write(&path, "from-file\n").unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(resolve_api_key(env(&[]), Some(path)).unwrap(), "from-file");
+        assert!(resolve_api_key(env(&[("DEEPGRAM_API_KEY", " ")]), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_message_ignores_other_messages() {
+        assert!(parse_message(r#"{"type":"Metadata"}"#, "en").is_none());
+        assert!(parse_message(&results("", true, 0.0), "en").is_none());
+        assert!(parse_message("not json", "en").is_none());
+
+        let detected = json!({
+            "type": "Results",
+            "is_final": true,
+            "start": 2.5,
+            "duration": 1.5,
+            "channel": {"alternatives": [{
+                "transcript": "Hallo Welt",
+                "confidence": 0.8,
+                "languages": ["de"],
+            }]},
+        })
+        .to_string();
+        let Some(StreamingUpdate::Final(result)) = parse_message(&detected, "") else {
+            panic!("expected a final result");
+        };
+        assert_eq!(result.language, "de");
+        assert_eq!(result.segments[0].start_ms, 2500);
+        assert_eq!(result.segments[0].end_ms, 4000);
+    }
+}
@@ -0,0 +1,138 @@
+//! Transcriber that stands in for a model still being loaded.
+//!
+//! A pipeline can start with a [`DeferredTranscriber`] right away: audio is
+//! captured and chunked as usual while calls into the transcriber wait
+//! until [`DeferredTranscriber::complete`] hands over the loaded model.
+
+use crate::error::{Result, VoicshError};
+use crate::stt::streaming::StreamingTranscriber;
+use crate::stt::transcriber::{LanguageProbability, Transcriber, TranscriptionResult};
+use std::sync::{Arc, OnceLock};
+
+/// Placeholder transcriber that blocks until its model is loaded.
+pub struct DeferredTranscriber {
+    name: String,
+    model: OnceLock<std::result::Result<Arc<dyn Transcriber>, String>>,
+}
+
+impl DeferredTranscriber {
+    /// Create a placeholder for the model `name`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            model: OnceLock::new(),
+        }
+    }
+
+    /// Hand over the loaded model, or the error that prevented loading it.
+    ///
+    /// Wakes every waiting caller. Only the first call has an effect.
+    pub fn complete(&self, model: Result<Arc<dyn Transcriber>>) {
+        self.model.set(model.map_err(|e| e.to_string())).ok();
+    }
+
+    /// The loaded model, once loading succeeded.
+    pub fn loaded(&self) -> Option<Arc<dyn Transcriber>> {
+        self.model.get()?.as_ref().ok().cloned()
+    }
+
+    /// Block until loading finished.
+    fn wait(&self) -> Result<&Arc<dyn Transcriber>> {
+        self.model
+            .wait()
+            .as_ref()
+            .map_err(|message| VoicshError::Transcription {
+                message: format!("Model '{}' failed to load: {message}", self.name),
+            })
+    }
+}
+
+impl Transcriber for DeferredTranscriber {
+    fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+        self.wait()?.transcribe(audio)
+    }
+
+    fn transcribe_with_language(
+        &self,
+        audio: &[i16],
+        language: &str,
+    ) -> Result<TranscriptionResult> {
+        self.wait()?.transcribe_with_language(audio, language)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_ready(&self) -> bool {
+        self.loaded().is_some_and(|model| model.is_ready())
+    }
+
+    /// Streaming backends are only recognized once loaded.
+    fn streaming(&self) -> Option<&dyn StreamingTranscriber> {
+        self.model.get()?.as_ref().ok()?.streaming()
+    }
+
+    fn detect_language(&self, audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+        match self.wait() {
+            Ok(model) => model.detect_language(audio),
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    fn detect_and_transcribe(
+        &self,
+        audio: &[i16],
+        choose: &mut dyn FnMut(&[LanguageProbability]) -> Option<String>,
+    ) -> Option<Result<TranscriptionResult>> {
+        match self.wait() {
+            Ok(model) => model.detect_and_transcribe(audio, choose),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::transcriber::MockTranscriber;
+    use std::time::Duration;
+
+    #[test]
+    fn transcribe_waits_for_the_model() {
+        let deferred = Arc::new(DeferredTranscriber::new("base"));
+        assert!(!deferred.is_ready());
+
+        let waiting = deferred.clone();
+        let handle = std::thread::spawn(move || waiting.transcribe(&[0i16; 100]));
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!handle.is_finished(), "should block until loaded");
+
+        deferred.complete(Ok(Arc::new(
+            MockTranscriber::new("base").with_response("loaded"),
+        )));
+        assert_eq!(handle.join().unwrap().unwrap().text, "loaded");
+        assert!(deferred.is_ready());
+        assert_eq!(deferred.model_name(), "base");
+    }
+
+    #[test]
+    fn failed_load_fails_waiting_calls() {
+        let deferred = DeferredTranscriber::new("large");
+        deferred.complete(Err(VoicshError::TranscriptionModelNotFound {
+            path: "ggml-large.bin".to_string(),
+        }));
+        let err = deferred.transcribe(&[0i16; 100]).unwrap_err().to_string();
+        assert!(err.contains("'large' failed to load"), "{err}");
+        assert!(deferred.loaded().is_none());
+        assert!(!deferred.is_ready());
+    }
+
+    #[test]
+    fn only_the_first_completion_counts() {
+        let deferred = DeferredTranscriber::new("base");
+        deferred.complete(Ok(Arc::new(MockTranscriber::new("first"))));
+        deferred.complete(Ok(Arc::new(MockTranscriber::new("second"))));
+        assert_eq!(deferred.loaded().unwrap().model_name(), "first");
+    }
+}
@@ -1,21 +1,26 @@
 //! Fan-out transcriber that runs multiple models in parallel.
 //!
 //! Spawns one thread per transcriber, collects results, and picks the one
-//! with the highest confidence. Useful for running an English-optimized
+//! with the highest confidence, or merges them by per-word voting (see
+//! [`rover`](crate::stt::rover)). Useful for running an English-optimized
 //! and a multilingual model side-by-side.
 
+use crate::config::FanOutStrategy;
 use crate::error::Result;
+use crate::stt::rover;
 use crate::stt::transcriber::{Transcriber, TranscriptionResult};
 use std::sync::Arc;
 use std::thread;
 
 /// Transcriber that fans out to multiple child transcribers in parallel.
 ///
-/// Each child receives the same audio. The result with the highest confidence
-/// (and non-empty text) is returned.
+/// Each child receives the same audio. With [`FanOutStrategy::Best`] the
+/// result with the highest confidence (and non-empty text) is returned; with
+/// [`FanOutStrategy::Vote`] the non-empty results are merged word by word.
 pub struct FanOutTranscriber {
     transcribers: Vec<Arc<dyn Transcriber>>,
     name: String,
+    strategy: FanOutStrategy,
 }
 
 impl FanOutTranscriber {
@@ -30,7 +35,17 @@ impl FanOutTranscriber {
             .map(|t| t.model_name())
             .collect::<Vec<_>>()
             .join("+");
-        Self { transcribers, name }
+        Self {
+            transcribers,
+            name,
+            strategy: FanOutStrategy::default(),
+        }
+    }
+
+    /// Set how the children's results are combined.
+    pub fn with_strategy(mut self, strategy: FanOutStrategy) -> Self {
+        self.strategy = strategy;
+        self
     }
 }
 
@@ -57,23 +72,35 @@ impl Transcriber for FanOutTranscriber {
                 .collect()
         });
 
-        // Pick the best result: highest confidence among non-empty successes
-        let mut best: Option<TranscriptionResult> = None;
+        // Keep the non-empty successes, tagged with the model that produced them
+        let mut candidates: Vec<(String, TranscriptionResult)> = Vec::new();
         let mut last_err: Option<crate::error::VoicshError> = None;
 
-        for result in results {
+        for (transcriber, result) in self.transcribers.iter().zip(results) {
             match result {
                 Ok(tr) if !tr.text.is_empty() => {
-                    if best.as_ref().is_none_or(|b| tr.confidence > b.confidence) {
-                        best = Some(tr);
-                    }
+                    candidates.push((transcriber.model_name().to_string(), tr));
                 }
                 Ok(_) => {} // empty text, skip
                 Err(e) => last_err = Some(e),
             }
         }
 
-        best.ok_or_else(|| {
+        let combined = match self.strategy {
+            FanOutStrategy::Vote => rover::vote(&candidates),
+            // Pick the best result: highest confidence, first one on ties
+            FanOutStrategy::Best => {
+                let mut best: Option<TranscriptionResult> = None;
+                for (_, tr) in candidates {
+                    if best.as_ref().is_none_or(|b| tr.confidence > b.confidence) {
+                        best = Some(tr);
+                    }
+                }
+                best
+            }
+        };
+
+        combined.ok_or_else(|| {
             last_err.unwrap_or_else(|| crate::error::VoicshError::Transcription {
                 message: format!(
                     "All transcribers returned empty text (models: {})",
@@ -199,6 +226,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vote_strategy_merges_words_and_records_models() {
+        let en = Arc::new(
+            MockTranscriber::new("base.en")
+                .with_response("call mom tomorow")
+                .with_confidence(0.9),
+        ) as Arc<dyn Transcriber>;
+        let ml = Arc::new(
+            MockTranscriber::new("base")
+                .with_response("call mom tomorrow")
+                .with_confidence(0.6),
+        ) as Arc<dyn Transcriber>;
+        let failed = Arc::new(MockTranscriber::new("tiny").with_failure()) as Arc<dyn Transcriber>;
+
+        let fan = FanOutTranscriber::new(vec![en.clone(), ml.clone(), failed])
+            .with_strategy(FanOutStrategy::Vote);
+        let result = fan.transcribe(&[0i16; 100]).unwrap();
+        // Without word timings each word carries its result's confidence
+        assert_eq!(result.text, "call mom tomorow");
+        let models: Vec<_> = result
+            .words
+            .iter()
+            .map(|w| w.model.as_deref().unwrap())
+            .collect();
+        assert_eq!(models, vec!["base.en", "base.en", "base.en"]);
+
+        let best = FanOutTranscriber::new(vec![en, ml]);
+        assert!(best.transcribe(&[0i16; 100]).unwrap().words.is_empty());
+    }
+
     #[test]
     fn test_confidence_with_language() {
         let de = Arc::new(
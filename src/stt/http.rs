@@ -0,0 +1,728 @@
+//! Transcription through an OpenAI-compatible HTTP server.
+//!
+//! [`HttpTranscriber`] posts each chunk as a 16 kHz mono WAV to a
+//! `/v1/audio/transcriptions` endpoint (OpenAI, faster-whisper-server,
+//! whisper.cpp's `server`, ...) and reads the `verbose_json` response into a
+//! [`TranscriptionResult`]. While the server is unreachable, chunks can be
+//! handed to a local fallback transcriber instead.
+
+use crate::config::SttConfig;
+use crate::defaults;
+use crate::error::{Result, VoicshError};
+use crate::stt::transcriber::{Segment, Transcriber, TranscriptionResult, Word};
+use serde::Deserialize;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Upper bound for establishing the TCP/TLS connection, so a server that is
+/// down fails fast even with a long request timeout.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pause before the first retry; doubles with each further attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Builds the local transcriber used while the server is unreachable.
+///
+/// Called at most once, on the first chunk that cannot reach the server.
+pub type FallbackLoader = Box<dyn Fn() -> Result<Arc<dyn Transcriber>> + Send + Sync>;
+
+/// Connection settings for [`HttpTranscriber`].
+#[derive(Debug, Clone)]
+pub struct HttpTranscriberConfig {
+    /// Full endpoint URL, e.g. `http://gpu-box:8000/v1/audio/transcriptions`.
+    pub url: String,
+    /// Model name sent with each request.
+    pub model: String,
+    /// Sent as `Authorization: Bearer <key>` when set.
+    pub api_key: Option<String>,
+    /// Language code sent with each request (`auto` = server detects).
+    pub language: String,
+    /// Timeout for one request.
+    pub timeout: Duration,
+    /// Retries after a timeout, connection error, 429 or 5xx response.
+    pub retries: u32,
+    /// How long to stay on the fallback before trying the server again.
+    pub recheck_after: Duration,
+}
+
+impl HttpTranscriberConfig {
+    /// Settings from `[stt]` and `[stt.http]`.
+    pub fn from_config(stt: &SttConfig) -> Self {
+        Self {
+            url: stt.http.url.clone(),
+            model: stt.http.model.clone(),
+            api_key: stt.http.api_key.clone().filter(|k| !k.is_empty()),
+            language: stt.language.clone(),
+            timeout: Duration::from_secs(stt.http.timeout_secs),
+            retries: stt.http.retries,
+            recheck_after: Duration::from_secs(defaults::HTTP_STT_RECHECK_SECS),
+        }
+    }
+}
+
+/// Why a request failed.
+enum Failure {
+    /// Server could not be reached or is temporarily failing; worth retrying.
+    Unreachable(String),
+    /// Server answered but refused the request; retrying will not help.
+    Rejected(String),
+}
+
+/// Transcriber backed by an OpenAI-compatible transcription server.
+pub struct HttpTranscriber {
+    config: HttpTranscriberConfig,
+    name: String,
+    /// Built on first use: the blocking client must not be created inside the
+    /// async runtime that loads the transcriber.
+    client: OnceLock<reqwest::blocking::Client>,
+    fallback: Option<Fallback>,
+}
+
+/// Local transcriber used while the server is down.
+struct Fallback {
+    loader: FallbackLoader,
+    transcriber: Mutex<Option<Arc<dyn Transcriber>>>,
+    /// When the server was last found unreachable (`None` = server is up).
+    down_since: Mutex<Option<Instant>>,
+}
+
+impl HttpTranscriber {
+    /// Create a transcriber for `config`. No connection is made until the
+    /// first chunk is transcribed.
+    pub fn new(config: HttpTranscriberConfig) -> Result<Self> {
+        if !(config.url.starts_with("http://") || config.url.starts_with("https://")) {
+            return Err(VoicshError::ConfigInvalidValue {
+                key: "stt.http.url".to_string(),
+                message: format!("'{}' is not an http:// or https:// URL", config.url),
+            });
+        }
+        let name = format!("{} (http)", config.model);
+        Ok(Self {
+            config,
+            name,
+            client: OnceLock::new(),
+            fallback: None,
+        })
+    }
+
+    /// Transcribe locally with the transcriber built by `loader` while the
+    /// server is unreachable.
+    pub fn with_fallback(mut self, loader: FallbackLoader) -> Self {
+        self.fallback = Some(Fallback {
+            loader,
+            transcriber: Mutex::new(None),
+            down_since: Mutex::new(None),
+        });
+        self
+    }
+
+    fn client(&self) -> Result<&reqwest::blocking::Client> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
+        }
+        let client = reqwest::blocking::Client::builder()
+            .timeout(self.config.timeout)
+            .connect_timeout(CONNECT_TIMEOUT.min(self.config.timeout))
+            .build()
+            .map_err(|e| VoicshError::TranscriptionServer {
+                message: format!("Failed to create HTTP client: {e}"),
+            })?;
+        Ok(self.client.get_or_init(|| client))
+    }
+
+    /// Send `audio` to the server, retrying transient failures.
+    fn transcribe_remote(
+        &self,
+        audio: &[i16],
+    ) -> std::result::Result<TranscriptionResult, Failure> {
+        let client = self
+            .client()
+            .map_err(|e| Failure::Rejected(e.to_string()))?;
+        let wav = encode_wav(audio).map_err(|e| Failure::Rejected(e.to_string()))?;
+
+        let mut attempt = 0;
+        loop {
+            match self.request(client, &wav) {
+                Err(Failure::Unreachable(_)) if attempt < self.config.retries => {
+                    std::thread::sleep(RETRY_BACKOFF * 2u32.pow(attempt.min(4)));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn request(
+        &self,
+        client: &reqwest::blocking::Client,
+        wav: &[u8],
+    ) -> std::result::Result<TranscriptionResult, Failure> {
+        let (content_type, body) = multipart_body(&self.form_fields(), wav);
+        let mut request = client
+            .post(&self.config.url)
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(body);
+        if let Some(key) = &self.config.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .map_err(|e| Failure::Unreachable(format!("{}: {e}", self.config.url)))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .map_err(|e| Failure::Unreachable(format!("Failed to read response: {e}")))?;
+
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(Failure::Unreachable(format!(
+                "HTTP {status}: {}",
+                text.trim()
+            )));
+        }
+        if !status.is_success() {
+            return Err(Failure::Rejected(format!("HTTP {status}: {}", text.trim())));
+        }
+        parse_response(&text, &self.config.language).map_err(|e| Failure::Rejected(e.to_string()))
+    }
+
+    /// Text fields of the multipart form (the audio file is added separately).
+    fn form_fields(&self) -> Vec<(&'static str, &str)> {
+        let mut fields = vec![
+            ("model", self.config.model.as_str()),
+            ("response_format", "verbose_json"),
+            ("timestamp_granularities[]", "segment"),
+        ];
+        if self.config.language != defaults::AUTO_LANGUAGE {
+            fields.push(("language", self.config.language.as_str()));
+        }
+        fields
+    }
+}
+
+impl Fallback {
+    /// Whether the server was found unreachable less than `recheck_after` ago.
+    fn active(&self, recheck_after: Duration) -> bool {
+        self.down_since
+            .lock()
+            .ok()
+            .and_then(|since| *since)
+            .is_some_and(|since| since.elapsed() < recheck_after)
+    }
+
+    /// Record the server state; returns true if it changed.
+    fn set_down(&self, down: bool) -> bool {
+        let Ok(mut since) = self.down_since.lock() else {
+            return false;
+        };
+        let was_down = since.is_some();
+        *since = down.then(Instant::now);
+        was_down != down
+    }
+
+    fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+        let transcriber = {
+            let mut loaded = self
+                .transcriber
+                .lock()
+                .map_err(|_| VoicshError::Transcription {
+                    message: "Fallback transcriber lock poisoned".to_string(),
+                })?;
+            match loaded.as_ref() {
+                Some(t) => t.clone(),
+                None => loaded.insert((self.loader)()?).clone(),
+            }
+        };
+        transcriber.transcribe(audio)
+    }
+}
+
+impl Transcriber for HttpTranscriber {
+    fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+        if let Some(fallback) = &self.fallback
+            && fallback.active(self.config.recheck_after)
+        {
+            return fallback.transcribe(audio);
+        }
+
+        match self.transcribe_remote(audio) {
+            Ok(result) => {
+                if let Some(fallback) = &self.fallback
+                    && fallback.set_down(false)
+                {
+                    eprintln!("voicsh: transcription server is reachable again");
+                }
+                Ok(result)
+            }
+            Err(Failure::Unreachable(message)) => match &self.fallback {
+                Some(fallback) => {
+                    if fallback.set_down(true) {
+                        eprintln!(
+                            "voicsh: transcription server unreachable ({message}), using local model"
+                        );
+                    }
+                    fallback
+                        .transcribe(audio)
+                        .map_err(|e| VoicshError::TranscriptionServer {
+                            message: format!("{message}; local fallback failed: {e}"),
+                        })
+                }
+                None => Err(VoicshError::TranscriptionServer { message }),
+            },
+            Err(Failure::Rejected(message)) => Err(VoicshError::TranscriptionServer { message }),
+        }
+    }
+
+    fn model_name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+/// Encode 16 kHz mono samples as a WAV file.
+fn encode_wav(audio: &[i16]) -> Result<Vec<u8>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: defaults::SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let wav_error = |e: hound::Error| VoicshError::TranscriptionServer {
+        message: format!("Failed to encode WAV: {e}"),
+    };
+    let mut cursor = Cursor::new(Vec::with_capacity(44 + audio.len() * 2));
+    let mut writer = hound::WavWriter::new(&mut cursor, spec).map_err(wav_error)?;
+    for &sample in audio {
+        writer.write_sample(sample).map_err(wav_error)?;
+    }
+    writer.finalize().map_err(wav_error)?;
+    Ok(cursor.into_inner())
+}
+
+/// Build a `multipart/form-data` body with `fields` and `wav` as the `file` part.
+///
+/// Returns the content type (including the boundary) and the body.
+pub(crate) fn multipart_body(fields: &[(&str, &str)], wav: &[u8]) -> (String, Vec<u8>) {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let boundary = format!("voicsh-{nanos:x}");
+
+    let mut body = Vec::with_capacity(wav.len() + 512);
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"audio.wav\"\r\n\
+             Content-Type: audio/wav\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(wav);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+    (format!("multipart/form-data; boundary={boundary}"), body)
+}
+
+/// `verbose_json` transcription response. Plain `json` responses only carry `text`.
+#[derive(Deserialize)]
+struct VerboseResponse {
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<ResponseSegment>,
+    #[serde(default)]
+    words: Vec<ResponseWord>,
+}
+
+#[derive(Deserialize)]
+struct ResponseSegment {
+    #[serde(default)]
+    start: f64,
+    #[serde(default)]
+    end: f64,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct ResponseWord {
+    word: String,
+    start: f64,
+    end: f64,
+    #[serde(default)]
+    probability: Option<f32>,
+}
+
+/// Parse a transcription response body.
+///
+/// `requested_language` is reported when the server does not name one.
+/// Confidence is the duration-weighted mean of `exp(avg_logprob)` over the
+/// segments, else the mean word probability, else 1.0.
+fn parse_response(body: &str, requested_language: &str) -> Result<TranscriptionResult> {
+    let response: VerboseResponse =
+        serde_json::from_str(body).map_err(|e| VoicshError::TranscriptionServer {
+            message: format!("Unexpected response ({e}): {}", body.trim()),
+        })?;
+
+    let language = response
+        .language
+        .as_deref()
+        .map(language_code)
+        .filter(|code| !code.is_empty())
+        .or_else(|| {
+            (requested_language != defaults::AUTO_LANGUAGE).then(|| requested_language.to_string())
+        })
+        .unwrap_or_default();
+
+    let segments: Vec<Segment> = response
+        .segments
+        .iter()
+        .map(|s| Segment {
+            start_ms: seconds_to_ms(s.start),
+            end_ms: seconds_to_ms(s.end),
+            text: s.text.clone(),
+            tokens: Vec::new(),
+        })
+        .collect();
+
+    let words: Vec<Word> = response
+        .words
+        .iter()
+        .map(|w| Word {
+            text: w.word.trim().to_string(),
+            start_ms: seconds_to_ms(w.start),
+            end_ms: seconds_to_ms(w.end),
+            probability: w.probability.unwrap_or(1.0),
+            model: None,
+        })
+        .filter(|w| !w.text.is_empty())
+        .collect();
+
+    let scored: Vec<(f64, f64)> = response
+        .segments
+        .iter()
+        .filter_map(|s| {
+            s.avg_logprob
+                .map(|lp| (lp.exp().clamp(0.0, 1.0), (s.end - s.start).max(0.01)))
+        })
+        .collect();
+    let confidence = if !scored.is_empty() {
+        let total: f64 = scored.iter().map(|(_, d)| d).sum();
+        (scored.iter().map(|(p, d)| p * d).sum::<f64>() / total) as f32
+    } else if response.words.iter().any(|w| w.probability.is_some()) {
+        words.iter().map(|w| w.probability).sum::<f32>() / words.len().max(1) as f32
+    } else {
+        1.0
+    };
+
+    Ok(TranscriptionResult {
+        text: response.text.trim().to_string(),
+        language,
+        confidence,
+        token_probabilities: Vec::new(),
+        segments,
+        words,
+        alternatives: Vec::new(),
+        route: None,
+    })
+}
+
+fn seconds_to_ms(seconds: f64) -> u64 {
+    (seconds.max(0.0) * 1000.0).round() as u64
+}
+
+/// Map a reported language to its code.
+///
+/// OpenAI reports Whisper's English names (`"german"`), most self-hosted
+/// servers report codes (`"de"`). Unknown names map to an empty string.
+fn language_code(language: &str) -> String {
+    let lower = language.trim().to_lowercase();
+    if lower.len() == 2 {
+        return lower;
+    }
+    let code = match lower.as_str() {
+        "english" => "en",
+        "german" => "de",
+        "spanish" => "es",
+        "french" => "fr",
+        "portuguese" => "pt",
+        "italian" => "it",
+        "dutch" => "nl",
+        "polish" => "pl",
+        "russian" => "ru",
+        "ukrainian" => "uk",
+        "czech" => "cs",
+        "swedish" => "sv",
+        "danish" => "da",
+        "norwegian" => "no",
+        "finnish" => "fi",
+        "greek" => "el",
+        "hungarian" => "hu",
+        "romanian" => "ro",
+        "turkish" => "tr",
+        "arabic" => "ar",
+        "hebrew" => "he",
+        "hindi" => "hi",
+        "japanese" => "ja",
+        "chinese" => "zh",
+        "korean" => "ko",
+        "vietnamese" => "vi",
+        "indonesian" => "id",
+        _ => "",
+    };
+    code.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::transcriber::MockTranscriber;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    const VERBOSE_RESPONSE: &str = r#"{
+        "task": "transcribe",
+        "language": "german",
+        "duration": 2.0,
+        "text": " Hallo Welt",
+        "segments": [
+            {"id": 0, "start": 0.0, "end": 1.5, "text": " Hallo Welt", "avg_logprob": -0.1, "no_speech_prob": 0.01}
+        ],
+        "words": [
+            {"word": "Hallo", "start": 0.0, "end": 0.6},
+            {"word": "Welt", "start": 0.7, "end": 1.5}
+        ]
+    }"#;
+
+    /// Serve one canned `(status, body)` response per connection, in order.
+    /// Returns the endpoint URL and the raw requests received.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/v1/audio/transcriptions",
+            listener.local_addr().unwrap()
+        );
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut head = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    head.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut payload = vec![0u8; content_length];
+                reader.read_exact(&mut payload).unwrap();
+                requests.push(head + &String::from_utf8_lossy(&payload));
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    /// URL of a port nothing listens on.
+    fn closed_port_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!(
+            "http://{}/v1/audio/transcriptions",
+            listener.local_addr().unwrap()
+        )
+    }
+
+    fn config(url: String) -> HttpTranscriberConfig {
+        HttpTranscriberConfig {
+            url,
+            model: "large-v3".to_string(),
+            api_key: None,
+            language: "auto".to_string(),
+            timeout: Duration::from_secs(5),
+            retries: 0,
+            recheck_after: Duration::from_secs(60),
+        }
+    }
+
+    fn counting_fallback(loads: Arc<AtomicUsize>) -> FallbackLoader {
+        Box::new(move || {
+            loads.fetch_add(1, Ordering::SeqCst);
+            Ok(
+                Arc::new(MockTranscriber::new("base").with_response("local text"))
+                    as Arc<dyn Transcriber>,
+            )
+        })
+    }
+
+    #[test]
+    fn test_transcribes_via_server_and_parses_verbose_json() {
+        let (url, server) = mock_server(vec![(200, VERBOSE_RESPONSE)]);
+        let mut cfg = config(url);
+        cfg.api_key = Some("secret".to_string());
+        cfg.language = "de".to_string();
+        let transcriber = HttpTranscriber::new(cfg).unwrap();
+
+        let result = transcriber.transcribe(&[100i16; 1600]).unwrap();
+        assert_eq!(result.text, "Hallo Welt");
+        assert_eq!(result.language, "de");
+        assert!((result.confidence - (-0.1f32).exp()).abs() < 1e-4);
+        assert_eq!(result.segments.len(), 1);
+        assert_eq!(result.segments[0].end_ms, 1500);
+        assert_eq!(result.words.len(), 2);
+        assert_eq!(result.words[1].text, "Welt");
+        assert_eq!(result.words[1].start_ms, 700);
+        assert_eq!(transcriber.model_name(), "large-v3 (http)");
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("POST /v1/audio/transcriptions"));
+        assert!(request.contains("authorization: Bearer secret"));
+        assert!(request.contains("name=\"model\"\r\n\r\nlarge-v3"));
+        assert!(request.contains("name=\"response_format\"\r\n\r\nverbose_json"));
+        assert!(request.contains("name=\"language\"\r\n\r\nde"));
+        assert!(request.contains("filename=\"audio.wav\""));
+        assert!(request.contains("RIFF"));
+    }
+
+    #[test]
+    fn test_retries_server_errors() {
+        let (url, server) = mock_server(vec![
+            (503, r#"{"error": "loading model"}"#),
+            (200, r#"{"text": "second try"}"#),
+        ]);
+        let mut cfg = config(url);
+        cfg.retries = 1;
+        let transcriber = HttpTranscriber::new(cfg).unwrap();
+
+        let result = transcriber.transcribe(&[0i16; 160]).unwrap();
+        assert_eq!(result.text, "second try");
+        assert!(
+            result.language.is_empty(),
+            "auto without a reported language"
+        );
+        assert_eq!(result.confidence, 1.0);
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[0].contains("name=\"language\""));
+    }
+
+    #[test]
+    fn test_client_error_is_not_retried_or_masked_by_fallback() {
+        let (url, server) = mock_server(vec![(401, r#"{"error": "invalid api key"}"#)]);
+        let mut cfg = config(url);
+        cfg.retries = 3;
+        let loads = Arc::new(AtomicUsize::new(0));
+        let transcriber = HttpTranscriber::new(cfg)
+            .unwrap()
+            .with_fallback(counting_fallback(loads.clone()));
+
+        let err = transcriber.transcribe(&[0i16; 160]).unwrap_err();
+        assert!(matches!(err, VoicshError::TranscriptionServer { .. }));
+        assert!(err.to_string().contains("401"), "{err}");
+        assert!(err.to_string().contains("invalid api key"), "{err}");
+        assert_eq!(server.join().unwrap().len(), 1);
+        assert_eq!(loads.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_unreachable_server_without_fallback_errors() {
+        let transcriber = HttpTranscriber::new(config(closed_port_url())).unwrap();
+        let err = transcriber.transcribe(&[0i16; 160]).unwrap_err();
+        assert!(matches!(err, VoicshError::TranscriptionServer { .. }));
+    }
+
+    #[test]
+    fn test_unreachable_server_falls_back_to_local_model() {
+        let loads = Arc::new(AtomicUsize::new(0));
+        let transcriber = HttpTranscriber::new(config(closed_port_url()))
+            .unwrap()
+            .with_fallback(counting_fallback(loads.clone()));
+
+        assert_eq!(
+            transcriber.transcribe(&[0i16; 160]).unwrap().text,
+            "local text"
+        );
+        // Within recheck_after the server is skipped and the loaded model reused
+        assert_eq!(
+            transcriber.transcribe(&[0i16; 160]).unwrap().text,
+            "local text"
+        );
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_returns_to_server_after_recheck_interval() {
+        let (url, server) = mock_server(vec![(200, r#"{"text": "remote"}"#)]);
+        let mut cfg = config(url);
+        cfg.recheck_after = Duration::ZERO;
+        let loads = Arc::new(AtomicUsize::new(0));
+        let transcriber = HttpTranscriber::new(cfg)
+            .unwrap()
+            .with_fallback(counting_fallback(loads));
+        if let Some(fallback) = &transcriber.fallback {
+            fallback.set_down(true);
+        }
+
+        assert_eq!(transcriber.transcribe(&[0i16; 160]).unwrap().text, "remote");
+        assert_eq!(server.join().unwrap().len(), 1);
+        let fallback = transcriber.fallback.as_ref().unwrap();
+        assert!(!fallback.set_down(false), "server marked reachable again");
+    }
+
+    #[test]
+    fn test_rejects_non_http_url() {
+        let err = HttpTranscriber::new(config("gpu-box:8000".to_string()))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("stt.http.url"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_response_language_and_word_confidence() {
+        assert_eq!(language_code("English"), "en");
+        assert_eq!(language_code("de"), "de");
+        assert_eq!(language_code("klingon"), "");
+
+        let body = r#"{"text": "hi there", "language": "en",
+            "words": [{"word": " hi", "start": 0.0, "end": 0.2, "probability": 0.9},
+                      {"word": " there", "start": 0.2, "end": 0.5, "probability": 0.5}]}"#;
+        let result = parse_response(body, "auto").unwrap();
+        assert_eq!(result.language, "en");
+        assert_eq!(result.words[0].text, "hi");
+        assert!((result.confidence - 0.7).abs() < 1e-6);
+
+        assert!(parse_response("<html>502</html>", "auto").is_err());
+    }
+}
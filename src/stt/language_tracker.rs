@@ -0,0 +1,227 @@
+//! Session language tracking for `stt.language = "auto"`.
+//!
+//! Detecting each chunk on its own lets a short "OK." come out as Dutch or
+//! Welsh. The tracker instead accumulates whisper's per-language
+//! probabilities over the session: every chunk adds its log-probabilities,
+//! weighted by its duration, to a decaying score per language. The session
+//! language only changes when another language's posterior (the softmax of
+//! the scores) passes [`defaults::LANGUAGE_SWITCH_POSTERIOR`].
+
+use crate::defaults;
+use crate::stt::transcriber::LanguageProbability;
+
+/// Probability assumed for languages missing from a detection result.
+const PROBABILITY_FLOOR: f32 = 1e-4;
+
+/// A change of session language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageSwitch {
+    pub from: String,
+    pub to: String,
+    /// Posterior of the new language at the switch.
+    pub probability: f32,
+}
+
+/// Running language posterior over a recording session.
+#[derive(Debug, Clone, Default)]
+pub struct LanguageTracker {
+    /// Accumulated log-evidence per language.
+    scores: Vec<(String, f32)>,
+    current: Option<String>,
+}
+
+impl LanguageTracker {
+    /// Create a tracker without evidence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The session language, once any chunk has been observed.
+    pub fn language(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Posterior probability of `language` given the chunks so far.
+    pub fn posterior(&self, language: &str) -> f32 {
+        let max = self
+            .scores
+            .iter()
+            .map(|(_, s)| *s)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let total: f32 = self.scores.iter().map(|(_, s)| (s - max).exp()).sum();
+        self.scores
+            .iter()
+            .find(|(l, _)| l == language)
+            .map_or(0.0, |(_, s)| (s - max).exp() / total)
+    }
+
+    /// Add one chunk's detection result.
+    ///
+    /// Only languages in `allowed` take part (all when empty). Returns the
+    /// switch when the session language changed; the first chunk sets the
+    /// session language without one.
+    pub fn observe(
+        &mut self,
+        probabilities: &[LanguageProbability],
+        allowed: &[String],
+        duration_ms: u32,
+    ) -> Option<LanguageSwitch> {
+        let candidates: Vec<&LanguageProbability> = probabilities
+            .iter()
+            .filter(|p| allowed.is_empty() || allowed.contains(&p.language))
+            .collect();
+        let total: f32 = candidates.iter().map(|p| p.probability).sum();
+        if candidates.is_empty() || total <= 0.0 {
+            return None;
+        }
+
+        // Short chunks carry little evidence about the language
+        let weight = (duration_ms as f32 / defaults::LANGUAGE_EVIDENCE_FULL_MS as f32).min(1.0);
+        let log_probability =
+            |language: &str| match candidates.iter().find(|p| p.language == language) {
+                Some(p) => (p.probability / total).max(PROBABILITY_FLOOR).ln(),
+                None => PROBABILITY_FLOOR.ln(),
+            };
+
+        // Languages no longer allowed drop out of the posterior; new ones
+        // start level with the least likely language so far
+        self.scores
+            .retain(|(l, _)| allowed.is_empty() || allowed.contains(l));
+        let baseline = self.scores.iter().map(|(_, s)| *s).fold(0.0_f32, f32::min);
+        for p in &candidates {
+            if !self.scores.iter().any(|(l, _)| *l == p.language) {
+                self.scores.push((p.language.clone(), baseline));
+            }
+        }
+        for (language, score) in &mut self.scores {
+            *score =
+                *score * defaults::LANGUAGE_TRACKING_DECAY + weight * log_probability(language);
+        }
+
+        let (top, _) = self
+            .scores
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))?
+            .clone();
+        let current = match self.current.as_deref() {
+            Some(current) if allowed.is_empty() || allowed.iter().any(|l| l == current) => {
+                current.to_string()
+            }
+            // First chunk, or the session language was disallowed meanwhile
+            _ => {
+                self.current = Some(top);
+                return None;
+            }
+        };
+        if top == current {
+            return None;
+        }
+        let probability = self.posterior(&top);
+        if probability < defaults::LANGUAGE_SWITCH_POSTERIOR {
+            return None;
+        }
+        self.current = Some(top.clone());
+        Some(LanguageSwitch {
+            from: current,
+            to: top,
+            probability,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(probabilities: &[(&str, f32)]) -> Vec<LanguageProbability> {
+        probabilities
+            .iter()
+            .map(|(language, probability)| LanguageProbability {
+                language: language.to_string(),
+                probability: *probability,
+            })
+            .collect()
+    }
+
+    fn german_session() -> LanguageTracker {
+        let mut tracker = LanguageTracker::new();
+        for _ in 0..5 {
+            tracker.observe(
+                &detection(&[("de", 0.9), ("nl", 0.05), ("en", 0.05)]),
+                &[],
+                4000,
+            );
+        }
+        tracker
+    }
+
+    #[test]
+    fn first_chunk_sets_the_session_language() {
+        let mut tracker = LanguageTracker::new();
+        assert_eq!(tracker.language(), None);
+        let switch = tracker.observe(&detection(&[("de", 0.8), ("en", 0.2)]), &[], 2000);
+        assert_eq!(switch, None);
+        assert_eq!(tracker.language(), Some("de"));
+    }
+
+    #[test]
+    fn short_ambiguous_chunk_keeps_the_session_language() {
+        let mut tracker = german_session();
+        let switch = tracker.observe(
+            &detection(&[("nl", 0.5), ("cy", 0.3), ("de", 0.1), ("en", 0.1)]),
+            &[],
+            600,
+        );
+        assert_eq!(switch, None);
+        assert_eq!(tracker.language(), Some("de"));
+        assert!(tracker.posterior("de") > tracker.posterior("nl"));
+    }
+
+    #[test]
+    fn sustained_evidence_switches_language() {
+        let mut tracker = german_session();
+        let english = detection(&[("en", 0.97), ("de", 0.02), ("nl", 0.01)]);
+        let mut switch = None;
+        for _ in 0..3 {
+            switch = switch.or(tracker.observe(&english, &[], 4000));
+        }
+        let switch = switch.expect("should switch to English");
+        assert_eq!(switch.from, "de");
+        assert_eq!(switch.to, "en");
+        assert!(switch.probability >= defaults::LANGUAGE_SWITCH_POSTERIOR);
+        assert_eq!(tracker.language(), Some("en"));
+    }
+
+    #[test]
+    fn detection_is_restricted_to_allowed_languages() {
+        let allowed = vec!["en".to_string(), "de".to_string()];
+        let mut tracker = LanguageTracker::new();
+        tracker.observe(
+            &detection(&[("nl", 0.6), ("de", 0.3), ("en", 0.1)]),
+            &allowed,
+            800,
+        );
+        assert_eq!(tracker.language(), Some("de"));
+        assert_eq!(tracker.posterior("nl"), 0.0);
+    }
+
+    #[test]
+    fn no_allowed_language_in_detection_is_ignored() {
+        let mut tracker = LanguageTracker::new();
+        let switch = tracker.observe(&detection(&[("en", 1.0)]), &["de".to_string()], 3000);
+        assert_eq!(switch, None);
+        assert_eq!(tracker.language(), None);
+    }
+
+    #[test]
+    fn disallowed_session_language_is_replaced_without_switch() {
+        let mut tracker = german_session();
+        let switch = tracker.observe(
+            &detection(&[("de", 0.9), ("en", 0.1)]),
+            &["en".to_string()],
+            1000,
+        );
+        assert_eq!(switch, None);
+        assert_eq!(tracker.language(), Some("en"));
+    }
+}
@@ -1,6 +1,17 @@
 //! Speech-to-text transcription.
 
+#[cfg(feature = "candle-whisper")]
+pub mod candle_whisper;
+#[cfg(feature = "cloud-stt")]
+pub mod deepgram;
+pub mod deferred;
 pub mod fan_out;
+#[cfg(feature = "model-download")]
+pub mod http;
+pub mod language_tracker;
+pub mod queue;
+pub mod routing;
+pub mod rover;
+pub mod streaming;
 pub mod transcriber;
-#[cfg(feature = "whisper")]
 pub mod whisper;
@@ -0,0 +1,252 @@
+//! Priority queue in front of a shared transcriber.
+//!
+//! The daemon's model serves live dictation and background requests (file
+//! transcription, the local API). [`TranscriptionQueue`] runs one transcription
+//! at a time and lets waiting live chunks go before any background request, so
+//! a long upload never delays dictation by more than the chunk already running.
+
+use crate::error::{Result, VoicshError};
+use crate::stt::streaming::StreamingTranscriber;
+use crate::stt::transcriber::{LanguageProbability, Transcriber, TranscriptionResult};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Who is asking for a transcription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    /// Live dictation: runs as soon as the model is free.
+    Live,
+    /// Everything else: runs only when no live chunk is waiting.
+    Background,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    busy: bool,
+    live_waiting: usize,
+}
+
+/// Serializes transcriptions on a shared model, live chunks first.
+#[derive(Debug, Default)]
+pub struct TranscriptionQueue {
+    state: Mutex<QueueState>,
+    ready: Condvar,
+}
+
+impl TranscriptionQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap `inner` so every call waits its turn at `priority`.
+    pub fn wrap(
+        self: &Arc<Self>,
+        inner: Arc<dyn Transcriber>,
+        priority: Priority,
+    ) -> Arc<dyn Transcriber> {
+        Arc::new(QueuedTranscriber {
+            inner,
+            queue: Arc::clone(self),
+            priority,
+        })
+    }
+
+    /// Block until the model is free for `priority`.
+    fn acquire(&self, priority: Priority) -> Result<Turn<'_>> {
+        let poisoned = |_| VoicshError::Transcription {
+            message: "Transcription queue lock poisoned".to_string(),
+        };
+        let mut state = self.state.lock().map_err(poisoned)?;
+        match priority {
+            Priority::Live => {
+                state.live_waiting += 1;
+                state = self.ready.wait_while(state, |s| s.busy).map_err(poisoned)?;
+                state.live_waiting -= 1;
+            }
+            Priority::Background => {
+                state = self
+                    .ready
+                    .wait_while(state, |s| s.busy || s.live_waiting > 0)
+                    .map_err(poisoned)?;
+            }
+        }
+        state.busy = true;
+        Ok(Turn { queue: self })
+    }
+}
+
+/// Holds the model until dropped.
+struct Turn<'a> {
+    queue: &'a TranscriptionQueue,
+}
+
+impl Drop for Turn<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.queue.state.lock() {
+            state.busy = false;
+        }
+        self.queue.ready.notify_all();
+    }
+}
+
+/// Transcriber that takes its turn in a [`TranscriptionQueue`].
+pub struct QueuedTranscriber {
+    inner: Arc<dyn Transcriber>,
+    queue: Arc<TranscriptionQueue>,
+    priority: Priority,
+}
+
+impl Transcriber for QueuedTranscriber {
+    fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+        let _turn = self.queue.acquire(self.priority)?;
+        self.inner.transcribe(audio)
+    }
+
+    fn transcribe_with_language(
+        &self,
+        audio: &[i16],
+        language: &str,
+    ) -> Result<TranscriptionResult> {
+        let _turn = self.queue.acquire(self.priority)?;
+        self.inner.transcribe_with_language(audio, language)
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+
+    // Streaming backends run remotely and do not hold the local model
+    fn streaming(&self) -> Option<&dyn StreamingTranscriber> {
+        self.inner.streaming()
+    }
+
+    fn detect_language(&self, audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+        let _turn = match self.queue.acquire(self.priority) {
+            Ok(turn) => turn,
+            Err(e) => return Some(Err(e)),
+        };
+        self.inner.detect_language(audio)
+    }
+
+    /// Holds one turn for both passes, so nothing runs in between.
+    fn detect_and_transcribe(
+        &self,
+        audio: &[i16],
+        choose: &mut dyn FnMut(&[LanguageProbability]) -> Option<String>,
+    ) -> Option<Result<TranscriptionResult>> {
+        let _turn = match self.queue.acquire(self.priority) {
+            Ok(turn) => turn,
+            Err(e) => return Some(Err(e)),
+        };
+        self.inner.detect_and_transcribe(audio, choose)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::transcriber::MockTranscriber;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Records the order in which transcriptions finish.
+    struct Recording {
+        name: &'static str,
+        log: Arc<Mutex<Vec<&'static str>>>,
+        delay: Duration,
+    }
+
+    impl Transcriber for Recording {
+        fn transcribe(&self, _audio: &[i16]) -> Result<TranscriptionResult> {
+            thread::sleep(self.delay);
+            self.log.lock().unwrap().push(self.name);
+            Ok(TranscriptionResult::from_text(self.name.to_string()))
+        }
+
+        fn model_name(&self) -> &str {
+            "recording"
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+
+        fn detect_language(&self, _audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+            Some(Ok(vec![LanguageProbability {
+                language: "en".to_string(),
+                probability: 1.0,
+            }]))
+        }
+    }
+
+    #[test]
+    fn test_wrapped_transcriber_delegates() {
+        let queue = Arc::new(TranscriptionQueue::new());
+        let inner: Arc<dyn Transcriber> =
+            Arc::new(MockTranscriber::new("base").with_response("hello"));
+        let queued = queue.wrap(inner, Priority::Background);
+        assert_eq!(queued.transcribe(&[0; 10]).unwrap().text, "hello");
+        assert_eq!(queued.model_name(), "base");
+        assert!(queued.is_ready());
+        // The turn is released after each call
+        assert_eq!(queued.transcribe(&[0; 10]).unwrap().text, "hello");
+    }
+
+    #[test]
+    fn test_waiting_live_chunk_runs_before_background() {
+        let queue = Arc::new(TranscriptionQueue::new());
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let transcriber = |name, delay_ms| -> Arc<dyn Transcriber> {
+            Arc::new(Recording {
+                name,
+                log: log.clone(),
+                delay: Duration::from_millis(delay_ms),
+            })
+        };
+        let first = queue.wrap(transcriber("first", 200), Priority::Background);
+        let background = queue.wrap(transcriber("background", 0), Priority::Background);
+        let live = queue.wrap(transcriber("live", 0), Priority::Live);
+
+        let running = thread::spawn(move || first.transcribe(&[]).unwrap());
+        thread::sleep(Duration::from_millis(50));
+        // Both queue up while `first` holds the model; background asks first
+        let waiting_background = thread::spawn(move || background.transcribe(&[]).unwrap());
+        thread::sleep(Duration::from_millis(50));
+        let waiting_live = thread::spawn(move || live.transcribe(&[]).unwrap());
+
+        running.join().unwrap();
+        waiting_background.join().unwrap();
+        waiting_live.join().unwrap();
+        assert_eq!(*log.lock().unwrap(), vec!["first", "live", "background"]);
+    }
+
+    #[test]
+    fn test_detect_and_transcribe_holds_one_turn() {
+        let queue = Arc::new(TranscriptionQueue::new());
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let transcriber = |name| -> Arc<dyn Transcriber> {
+            Arc::new(Recording {
+                name,
+                log: log.clone(),
+                delay: Duration::ZERO,
+            })
+        };
+        let tracked = queue.wrap(transcriber("tracked"), Priority::Background);
+        let live = queue.wrap(transcriber("live"), Priority::Live);
+
+        let mut waiting = None;
+        let result = tracked.detect_and_transcribe(&[], &mut |_| {
+            // A live chunk arriving between detection and transcription waits
+            let live = live.clone();
+            waiting = Some(thread::spawn(move || live.transcribe(&[]).unwrap()));
+            thread::sleep(Duration::from_millis(50));
+            None
+        });
+        assert_eq!(result.unwrap().unwrap().text, "tracked");
+        waiting.unwrap().join().unwrap();
+        assert_eq!(*log.lock().unwrap(), vec!["tracked", "live"]);
+    }
+}
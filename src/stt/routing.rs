@@ -0,0 +1,416 @@
+//! Language-routing transcriber for `[stt.language_models]`.
+//!
+//! Each chunk first goes through a detector (the `stt.model` transcriber):
+//! whisper's detect-only pass when the backend offers one, a full
+//! transcription otherwise. The chunk is then transcribed by the model
+//! configured for the detected language, or by the detector itself when no
+//! route matches. Routed models are loaded on first use and kept in a
+//! least-recently-used cache within a memory budget.
+
+use crate::error::Result;
+use crate::stt::transcriber::{LanguageProbability, Route, Transcriber, TranscriptionResult};
+use std::sync::{Arc, Mutex};
+
+/// Loads the transcriber for `(model, language)`.
+pub type ModelLoader = Box<dyn Fn(&str, &str) -> Result<Arc<dyn Transcriber>> + Send + Sync>;
+
+/// A configured language → model route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageRoute {
+    /// Whisper language code, e.g. `"de"`.
+    pub language: String,
+    /// Catalog model name, e.g. `"large-v3-turbo-q5_0"`.
+    pub model: String,
+    /// Approximate memory the loaded model takes, in MB.
+    pub size_mb: u64,
+}
+
+/// A loaded routed model.
+struct CachedModel {
+    model: String,
+    language: String,
+    size_mb: u64,
+    transcriber: Arc<dyn Transcriber>,
+}
+
+/// Transcriber that dispatches each chunk to a per-language model.
+pub struct RoutingTranscriber {
+    detector: Arc<dyn Transcriber>,
+    routes: Vec<LanguageRoute>,
+    loader: ModelLoader,
+    budget_mb: u64,
+    /// Loaded models, least recently used first.
+    cache: Mutex<Vec<CachedModel>>,
+    name: String,
+}
+
+impl RoutingTranscriber {
+    /// Create a routing transcriber.
+    ///
+    /// `detector` identifies the language of each chunk and transcribes the
+    /// languages without a route. `loader` builds routed models on demand.
+    pub fn new(
+        detector: Arc<dyn Transcriber>,
+        routes: Vec<LanguageRoute>,
+        loader: ModelLoader,
+        budget_mb: u64,
+    ) -> Self {
+        let mut name = detector.model_name().to_string();
+        if !routes.is_empty() {
+            let routed = routes
+                .iter()
+                .map(|r| format!("{}:{}", r.language, r.model))
+                .collect::<Vec<_>>()
+                .join(",");
+            name = format!("{name} + {routed}");
+        }
+        Self {
+            detector,
+            routes,
+            loader,
+            budget_mb,
+            cache: Mutex::new(Vec::new()),
+            name,
+        }
+    }
+
+    /// Models currently loaded, least recently used first.
+    pub fn loaded_models(&self) -> Vec<String> {
+        self.cache
+            .lock()
+            .map(|cache| cache.iter().map(|c| c.model.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// The cached transcriber for `route`, loading it when needed.
+    ///
+    /// The entry moves to the most recently used end; older entries are
+    /// evicted until the cache fits the budget. The newest model always
+    /// stays, even when it alone exceeds the budget.
+    fn routed_transcriber(&self, route: &LanguageRoute) -> Result<Arc<dyn Transcriber>> {
+        let mut cache =
+            self.cache
+                .lock()
+                .map_err(|_| crate::error::VoicshError::Transcription {
+                    message: "language model cache poisoned".to_string(),
+                })?;
+
+        let position = cache
+            .iter()
+            .position(|c| c.model == route.model && c.language == route.language);
+        let entry = match position {
+            Some(index) => cache.remove(index),
+            None => CachedModel {
+                model: route.model.clone(),
+                language: route.language.clone(),
+                size_mb: route.size_mb,
+                transcriber: (self.loader)(&route.model, &route.language)?,
+            },
+        };
+        let transcriber = entry.transcriber.clone();
+        cache.push(entry);
+
+        while cache.len() > 1 && cache.iter().map(|c| c.size_mb).sum::<u64>() > self.budget_mb {
+            cache.remove(0);
+        }
+        Ok(transcriber)
+    }
+
+    /// Transcribe `audio` with the model routed for `language`.
+    ///
+    /// `detected` is the detector's own result when it already transcribed
+    /// the chunk, reused when the language has no (loadable) route.
+    fn dispatch(
+        &self,
+        audio: &[i16],
+        language: &str,
+        detected: Option<TranscriptionResult>,
+    ) -> Result<TranscriptionResult> {
+        let unrouted = |detected: Option<TranscriptionResult>| {
+            let result = match detected {
+                Some(result) => result,
+                None => self.detector.transcribe_with_language(audio, language)?,
+            };
+            Ok(self.detector_result(result))
+        };
+
+        let Some(route) = self.routes.iter().find(|r| r.language == language) else {
+            return unrouted(detected);
+        };
+
+        let transcriber = match self.routed_transcriber(route) {
+            Ok(transcriber) => transcriber,
+            Err(e) => {
+                eprintln!(
+                    "voicsh: failed to load '{}' for [{}], using '{}': {e}",
+                    route.model,
+                    route.language,
+                    self.detector.model_name()
+                );
+                return unrouted(detected);
+            }
+        };
+
+        let mut result = transcriber.transcribe(audio)?;
+        if result.language.is_empty() {
+            result.language = language.to_string();
+        }
+        result.route = Some(Route {
+            language: route.language.clone(),
+            model: route.model.clone(),
+        });
+        Ok(result)
+    }
+
+    /// Record that the detector's own result was used.
+    fn detector_result(&self, mut result: TranscriptionResult) -> TranscriptionResult {
+        result.route = Some(Route {
+            language: result.language.clone(),
+            model: self.detector.model_name().to_string(),
+        });
+        result
+    }
+}
+
+impl Transcriber for RoutingTranscriber {
+    fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+        // Prefer the cheap detect-only pass; otherwise transcribe and reuse
+        // the detector's result when the language has no route.
+        let (language, detected) = match self.detector.detect_language(audio) {
+            Some(Ok(probabilities)) => match probabilities.first() {
+                Some(top) => (top.language.clone(), None),
+                None => {
+                    return self
+                        .detector
+                        .transcribe(audio)
+                        .map(|r| self.detector_result(r));
+                }
+            },
+            Some(Err(e)) => return Err(e),
+            None => {
+                let result = self.detector.transcribe(audio)?;
+                (result.language.clone(), Some(result))
+            }
+        };
+
+        self.dispatch(audio, &language, detected)
+    }
+
+    /// Skip detection: the caller already knows the language.
+    fn transcribe_with_language(
+        &self,
+        audio: &[i16],
+        language: &str,
+    ) -> Result<TranscriptionResult> {
+        self.dispatch(audio, language, None)
+    }
+
+    fn model_name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_ready(&self) -> bool {
+        self.detector.is_ready()
+    }
+
+    fn detect_language(&self, audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+        self.detector.detect_language(audio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::VoicshError;
+    use crate::stt::transcriber::MockTranscriber;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Detector reporting a fixed language through the detect-only pass.
+    struct Detector {
+        inner: MockTranscriber,
+        language: &'static str,
+        transcriptions: AtomicUsize,
+    }
+
+    impl Detector {
+        fn new(language: &'static str) -> Self {
+            Self {
+                inner: MockTranscriber::new("tiny")
+                    .with_response("detector text")
+                    .with_language(language),
+                language,
+                transcriptions: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl Transcriber for Detector {
+        fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+            self.transcriptions.fetch_add(1, Ordering::SeqCst);
+            self.inner.transcribe(audio)
+        }
+
+        fn model_name(&self) -> &str {
+            self.inner.model_name()
+        }
+
+        fn is_ready(&self) -> bool {
+            true
+        }
+
+        fn detect_language(&self, _audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+            Some(Ok(vec![LanguageProbability {
+                language: self.language.to_string(),
+                probability: 0.9,
+            }]))
+        }
+    }
+
+    fn route(language: &str, model: &str, size_mb: u64) -> LanguageRoute {
+        LanguageRoute {
+            language: language.to_string(),
+            model: model.to_string(),
+            size_mb,
+        }
+    }
+
+    /// Loader returning a mock that answers with its model name, counting loads.
+    fn counting_loader(loads: Arc<AtomicUsize>) -> ModelLoader {
+        Box::new(move |model, language| {
+            loads.fetch_add(1, Ordering::SeqCst);
+            Ok(Arc::new(
+                MockTranscriber::new(model)
+                    .with_response(&format!("{model} text"))
+                    .with_language(language),
+            ) as Arc<dyn Transcriber>)
+        })
+    }
+
+    #[test]
+    fn routes_detected_language_to_its_model() {
+        let detector = Arc::new(Detector::new("de"));
+        let loads = Arc::new(AtomicUsize::new(0));
+        let router = RoutingTranscriber::new(
+            detector.clone(),
+            vec![
+                route("de", "large-v3-turbo-q5_0", 574),
+                route("en", "small.en", 466),
+            ],
+            counting_loader(loads.clone()),
+            2048,
+        );
+
+        let result = router.transcribe(&[0i16; 100]).unwrap();
+        assert_eq!(result.text, "large-v3-turbo-q5_0 text");
+        assert_eq!(result.language, "de");
+        assert_eq!(
+            result.route,
+            Some(Route {
+                language: "de".to_string(),
+                model: "large-v3-turbo-q5_0".to_string(),
+            })
+        );
+        // Detection alone: the detector never transcribed
+        assert_eq!(detector.transcriptions.load(Ordering::SeqCst), 0);
+
+        router.transcribe(&[0i16; 100]).unwrap();
+        assert_eq!(
+            loads.load(Ordering::SeqCst),
+            1,
+            "second chunk uses the cache"
+        );
+    }
+
+    #[test]
+    fn unrouted_language_uses_the_detector() {
+        let detector = Arc::new(Detector::new("fr"));
+        let loads = Arc::new(AtomicUsize::new(0));
+        let router = RoutingTranscriber::new(
+            detector.clone(),
+            vec![route("de", "large-v3-turbo-q5_0", 574)],
+            counting_loader(loads.clone()),
+            2048,
+        );
+
+        let result = router.transcribe(&[0i16; 100]).unwrap();
+        assert_eq!(result.text, "detector text");
+        assert_eq!(result.route.unwrap().model, "tiny");
+        assert_eq!(loads.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn detector_without_detect_pass_transcribes_once_for_unrouted_language() {
+        let detector = Arc::new(
+            MockTranscriber::new("base")
+                .with_response("bonjour")
+                .with_language("fr"),
+        );
+        let router = RoutingTranscriber::new(
+            detector,
+            vec![route("de", "medium", 1500)],
+            counting_loader(Arc::new(AtomicUsize::new(0))),
+            2048,
+        );
+        let result = router.transcribe(&[0i16; 100]).unwrap();
+        assert_eq!(result.text, "bonjour");
+        assert_eq!(result.language, "fr");
+    }
+
+    #[test]
+    fn load_failure_falls_back_to_the_detector() {
+        let detector = Arc::new(Detector::new("de"));
+        let router = RoutingTranscriber::new(
+            detector,
+            vec![route("de", "missing", 100)],
+            Box::new(|model, _| {
+                Err(VoicshError::TranscriptionModelNotFound {
+                    path: model.to_string(),
+                })
+            }),
+            2048,
+        );
+        let result = router.transcribe(&[0i16; 100]).unwrap();
+        assert_eq!(result.text, "detector text");
+        assert_eq!(result.route.unwrap().model, "tiny");
+        assert!(router.loaded_models().is_empty());
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used_beyond_budget() {
+        let loads = Arc::new(AtomicUsize::new(0));
+        let router = RoutingTranscriber::new(
+            Arc::new(Detector::new("de")),
+            vec![
+                route("de", "medium", 1500),
+                route("en", "small.en", 466),
+                route("fr", "large-v3-turbo", 1600),
+            ],
+            counting_loader(loads.clone()),
+            2048,
+        );
+
+        router.routed_transcriber(&router.routes[0]).unwrap();
+        router.routed_transcriber(&router.routes[1]).unwrap();
+        assert_eq!(router.loaded_models(), vec!["medium", "small.en"]);
+
+        // Touch medium so small.en becomes the oldest
+        router.routed_transcriber(&router.routes[0]).unwrap();
+        assert_eq!(router.loaded_models(), vec!["small.en", "medium"]);
+
+        router.routed_transcriber(&router.routes[2]).unwrap();
+        assert_eq!(router.loaded_models(), vec!["large-v3-turbo"]);
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn model_name_lists_routes() {
+        let router = RoutingTranscriber::new(
+            Arc::new(MockTranscriber::new("base")),
+            vec![route("de", "medium", 1500), route("en", "small.en", 466)],
+            counting_loader(Arc::new(AtomicUsize::new(0))),
+            2048,
+        );
+        assert_eq!(router.model_name(), "base + de:medium,en:small.en");
+        assert!(router.is_ready());
+    }
+}
@@ -0,0 +1,362 @@
+//! Word-level voting across transcription results (ROVER).
+//!
+//! The word sequences of several results are aligned into slots, one
+//! hypothesis word (or a gap) per model each, by edit distance. Each slot
+//! then elects the word with the highest summed probability; a model that
+//! has no word in a slot votes for leaving it out with [`GAP_WEIGHT`]. The
+//! merged result keeps the winning words in order and records the model
+//! each one came from.
+
+use crate::stt::transcriber::{TranscriptionResult, Word};
+
+/// Weight of a model's vote to leave a slot empty.
+///
+/// A word only one model heard survives when its probability beats this,
+/// so confident insertions are kept and shaky ones are dropped.
+pub const GAP_WEIGHT: f32 = 0.5;
+
+/// A column of the alignment: one entry per result aligned so far.
+type Slot = Vec<Option<Word>>;
+
+/// Merge `(model name, result)` pairs by per-word voting.
+///
+/// Only results in the same language as the most confident one take part;
+/// word sequences of different languages do not align. Results without
+/// word timings contribute their whitespace-separated text, each word
+/// weighted by the result's confidence.
+///
+/// Returns `None` when `results` is empty.
+pub fn vote(results: &[(String, TranscriptionResult)]) -> Option<TranscriptionResult> {
+    let (_, lead) = results
+        .iter()
+        .max_by(|a, b| a.1.confidence.total_cmp(&b.1.confidence))?;
+    let voters: Vec<(&str, &TranscriptionResult)> = results
+        .iter()
+        .filter(|(_, r)| {
+            r.language.is_empty() || lead.language.is_empty() || r.language == lead.language
+        })
+        .map(|(model, r)| (model.as_str(), r))
+        .collect();
+
+    let mut slots: Vec<Slot> = Vec::new();
+    for (index, (model, result)) in voters.iter().enumerate() {
+        slots = align(slots, index, voter_words(model, result));
+    }
+
+    let words: Vec<Word> = slots.into_iter().filter_map(elect).collect();
+    let text = words
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let confidence = if words.is_empty() {
+        0.0
+    } else {
+        words.iter().map(|w| w.probability).sum::<f32>() / words.len() as f32
+    };
+
+    Some(TranscriptionResult {
+        text,
+        language: lead.language.clone(),
+        confidence,
+        token_probabilities: Vec::new(),
+        segments: Vec::new(),
+        words,
+        alternatives: Vec::new(),
+        route: None,
+    })
+}
+
+/// The words of one result, tagged with the model that produced them.
+fn voter_words(model: &str, result: &TranscriptionResult) -> Vec<Word> {
+    let words = if result.words.is_empty() {
+        result
+            .text
+            .split_whitespace()
+            .map(|text| Word {
+                text: text.to_string(),
+                start_ms: 0,
+                end_ms: 0,
+                probability: result.confidence,
+                model: None,
+            })
+            .collect()
+    } else {
+        result.words.clone()
+    };
+    words
+        .into_iter()
+        .map(|word| Word {
+            model: Some(model.to_string()),
+            ..word
+        })
+        .collect()
+}
+
+/// Comparison key: lowercase, without surrounding punctuation.
+fn normalize(text: &str) -> String {
+    text.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Align `words` (the `index`-th result) against the existing slots.
+///
+/// Classic edit distance: a word matching any word already in a slot costs
+/// nothing, a substitution, gap or new slot costs one.
+fn align(slots: Vec<Slot>, index: usize, words: Vec<Word>) -> Vec<Slot> {
+    let keys: Vec<String> = words.iter().map(|w| normalize(&w.text)).collect();
+    let slot_keys: Vec<Vec<String>> = slots
+        .iter()
+        .map(|slot| slot.iter().flatten().map(|w| normalize(&w.text)).collect())
+        .collect();
+    let (n, m) = (slots.len(), words.len());
+
+    let mut cost = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in cost.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    cost[0] = (0..=m).collect();
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitution = usize::from(!slot_keys[i - 1].contains(&keys[j - 1]));
+            cost[i][j] = (cost[i - 1][j - 1] + substitution)
+                .min(cost[i - 1][j] + 1)
+                .min(cost[i][j - 1] + 1);
+        }
+    }
+
+    // Walk back from the end, then reverse.
+    let mut slots: Vec<Option<Slot>> = slots.into_iter().map(Some).collect();
+    let mut words: Vec<Option<Word>> = words.into_iter().map(Some).collect();
+    let mut merged: Vec<Slot> = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        let diagonal = i > 0
+            && j > 0
+            && cost[i][j]
+                == cost[i - 1][j - 1] + usize::from(!slot_keys[i - 1].contains(&keys[j - 1]));
+        if diagonal {
+            let mut slot = slots[i - 1].take().unwrap_or_default();
+            slot.push(words[j - 1].take());
+            merged.push(slot);
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && (j == 0 || cost[i][j] == cost[i - 1][j] + 1) {
+            let mut slot = slots[i - 1].take().unwrap_or_default();
+            slot.push(None);
+            merged.push(slot);
+            i -= 1;
+        } else {
+            let mut slot: Slot = vec![None; index];
+            slot.push(words[j - 1].take());
+            merged.push(slot);
+            j -= 1;
+        }
+    }
+    merged.reverse();
+    merged
+}
+
+/// The winning word of a slot, or `None` when leaving it out wins.
+///
+/// Ties go to the candidate first proposed by an earlier result.
+fn elect(slot: Slot) -> Option<Word> {
+    let gap_score = slot.iter().filter(|w| w.is_none()).count() as f32 * GAP_WEIGHT;
+
+    // (key, summed probability, best-scoring word)
+    let mut candidates: Vec<(String, f32, Word)> = Vec::new();
+    for word in slot.into_iter().flatten() {
+        let key = normalize(&word.text);
+        match candidates.iter_mut().find(|(k, _, _)| *k == key) {
+            Some((_, score, best)) => {
+                *score += word.probability;
+                if word.probability > best.probability {
+                    *best = word;
+                }
+            }
+            None => candidates.push((key, word.probability, word)),
+        }
+    }
+
+    let mut winner: Option<(f32, Word)> = None;
+    for (_, score, word) in candidates {
+        if winner.as_ref().is_none_or(|(best, _)| score > *best) {
+            winner = Some((score, word));
+        }
+    }
+    winner
+        .filter(|(score, _)| *score > gap_score)
+        .map(|(_, word)| word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, probability: f32) -> Word {
+        Word {
+            text: text.to_string(),
+            start_ms: 0,
+            end_ms: 0,
+            probability,
+            model: None,
+        }
+    }
+
+    fn result(language: &str, words: &[(&str, f32)]) -> TranscriptionResult {
+        let words: Vec<Word> = words.iter().map(|(t, p)| word(t, *p)).collect();
+        let confidence = words.iter().map(|w| w.probability).sum::<f32>() / words.len() as f32;
+        TranscriptionResult {
+            text: words
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            language: language.to_string(),
+            confidence,
+            words,
+            ..TranscriptionResult::from_text(String::new())
+        }
+    }
+
+    fn models(result: &TranscriptionResult) -> Vec<&str> {
+        result
+            .words
+            .iter()
+            .map(|w| w.model.as_deref().unwrap_or(""))
+            .collect()
+    }
+
+    #[test]
+    fn combines_the_confident_halves_of_two_results() {
+        let merged = vote(&[
+            (
+                "base.en".to_string(),
+                result(
+                    "en",
+                    &[("Send", 0.9), ("the", 0.9), ("mail", 0.3), ("tomorow", 0.2)],
+                ),
+            ),
+            (
+                "base".to_string(),
+                result(
+                    "en",
+                    &[
+                        ("Sent", 0.4),
+                        ("the", 0.8),
+                        ("email", 0.9),
+                        ("tomorrow.", 0.9),
+                    ],
+                ),
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(merged.text, "Send the email tomorrow.");
+        assert_eq!(merged.language, "en");
+        assert_eq!(models(&merged), vec!["base.en", "base.en", "base", "base"]);
+    }
+
+    #[test]
+    fn matching_ignores_case_and_punctuation() {
+        let merged = vote(&[
+            (
+                "a".to_string(),
+                result("en", &[("Hello,", 0.6), ("world", 0.9)]),
+            ),
+            (
+                "b".to_string(),
+                result("en", &[("hello", 0.7), ("world.", 0.8)]),
+            ),
+        ])
+        .unwrap();
+        // Each slot holds one candidate; its most probable spelling wins.
+        assert_eq!(merged.text, "hello world");
+        assert_eq!(models(&merged), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn majority_outweighs_single_confident_word() {
+        let merged = vote(&[
+            ("a".to_string(), result("en", &[("right", 0.95)])),
+            ("b".to_string(), result("en", &[("write", 0.6)])),
+            ("c".to_string(), result("en", &[("write", 0.6)])),
+        ])
+        .unwrap();
+        assert_eq!(merged.text, "write");
+    }
+
+    #[test]
+    fn insertion_survives_only_when_confident() {
+        let results = |p: f32| {
+            vec![
+                (
+                    "a".to_string(),
+                    result("en", &[("open", 0.9), ("now", p), ("please", 0.9)]),
+                ),
+                (
+                    "b".to_string(),
+                    result("en", &[("open", 0.9), ("please", 0.9)]),
+                ),
+            ]
+        };
+        assert_eq!(vote(&results(0.9)).unwrap().text, "open now please");
+        assert_eq!(vote(&results(0.2)).unwrap().text, "open please");
+    }
+
+    #[test]
+    fn results_in_another_language_do_not_vote() {
+        let merged = vote(&[
+            (
+                "base".to_string(),
+                result("de", &[("Guten", 0.9), ("Morgen", 0.9)]),
+            ),
+            (
+                "base.en".to_string(),
+                result("en", &[("Good", 0.5), ("morning", 0.5)]),
+            ),
+        ])
+        .unwrap();
+        assert_eq!(merged.text, "Guten Morgen");
+        assert_eq!(merged.language, "de");
+    }
+
+    #[test]
+    fn results_without_word_timings_vote_with_their_confidence() {
+        let mut plain = TranscriptionResult::from_text("turn the lights off".to_string());
+        plain.confidence = 0.8;
+        let merged = vote(&[
+            ("http".to_string(), plain),
+            (
+                "base".to_string(),
+                result(
+                    "",
+                    &[("turn", 0.9), ("the", 0.9), ("light", 0.4), ("off", 0.9)],
+                ),
+            ),
+        ])
+        .unwrap();
+        assert_eq!(merged.text, "turn the lights off");
+        assert_eq!(merged.words[2].model.as_deref(), Some("http"));
+    }
+
+    #[test]
+    fn empty_input_returns_none() {
+        assert!(vote(&[]).is_none());
+    }
+
+    #[test]
+    fn align_keeps_one_entry_per_result_in_every_slot() {
+        let slots = align(Vec::new(), 0, vec![word("a", 1.0), word("b", 1.0)]);
+        let slots = align(
+            slots,
+            1,
+            vec![word("x", 1.0), word("a", 1.0), word("b", 1.0)],
+        );
+        let slots = align(slots, 2, vec![word("a", 1.0)]);
+        assert_eq!(slots.len(), 3);
+        assert!(slots.iter().all(|slot| slot.len() == 3));
+        assert!(slots[0][0].is_none());
+        assert_eq!(slots[1][2].as_ref().map(|w| w.text.as_str()), Some("a"));
+    }
+}
@@ -0,0 +1,49 @@
+//! Streaming speech recognition.
+//!
+//! Chunked backends (Whisper, the HTTP backend) get a finished chunk of audio
+//! and return its text. Streaming backends keep a connection open while the
+//! user speaks, receive audio as it is captured and answer with interim
+//! hypotheses and final results as they go. A [`Transcriber`] offers this by
+//! returning a [`StreamingTranscriber`] from [`Transcriber::streaming`]; the
+//! pipeline then skips its own chunker and feeds every frame to a
+//! [`StreamingSession`] instead.
+//!
+//! [`Transcriber`]: crate::stt::transcriber::Transcriber
+//! [`Transcriber::streaming`]: crate::stt::transcriber::Transcriber::streaming
+
+use crate::error::Result;
+use crate::stt::transcriber::TranscriptionResult;
+use std::time::Duration;
+
+/// Something the recognizer reported about the audio streamed so far.
+#[derive(Debug, Clone)]
+pub enum StreamingUpdate {
+    /// Current hypothesis for the words not yet finalized; replaced by the
+    /// next interim or final result.
+    Interim(String),
+    /// Settled text for a stretch of audio. Timestamps are relative to the
+    /// start of the session.
+    Final(TranscriptionResult),
+}
+
+/// Backend that recognizes a continuous audio stream.
+pub trait StreamingTranscriber: Send + Sync {
+    /// Open a session for one recording.
+    fn open(&self) -> Result<Box<dyn StreamingSession>>;
+}
+
+/// One open stream to a streaming backend.
+pub trait StreamingSession: Send {
+    /// Send 16 kHz mono PCM. Must not block on the network.
+    fn send_audio(&mut self, samples: &[i16]) -> Result<()>;
+
+    /// Updates received since the last call, without blocking.
+    ///
+    /// Fails once the stream broke; updates that arrived before the failure
+    /// are returned first.
+    fn poll(&mut self) -> Result<Vec<StreamingUpdate>>;
+
+    /// Signal the end of the audio and wait up to `timeout` for the
+    /// remaining results.
+    fn finish(self: Box<Self>, timeout: Duration) -> Result<Vec<StreamingUpdate>>;
+}
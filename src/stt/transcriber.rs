@@ -1,5 +1,6 @@
 use crate::defaults;
 use crate::error::{Result, VoicshError};
+use crate::stt::streaming::StreamingTranscriber;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -10,6 +11,103 @@ pub struct TokenProbability {
     pub probability: f32,
 }
 
+/// A transcribed word with timing and confidence.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Word {
+    /// Word text, including attached punctuation.
+    pub text: String,
+    /// Start time in milliseconds.
+    pub start_ms: u64,
+    /// End time in milliseconds.
+    pub end_ms: u64,
+    /// Lowest probability among the word's tokens (0.0..1.0).
+    pub probability: f32,
+    /// Model that produced the word, when results of several models were merged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Group sub-word tokens `(text, probability, start_ms, end_ms)` into words.
+///
+/// Whisper tokens are BPE fragments: a fragment preceded by whitespace starts
+/// a new word, any other fragment (word piece, punctuation) extends the
+/// current one. A word spans all of its fragments and takes the lowest
+/// fragment probability.
+pub fn group_words<'a, I>(tokens: I) -> Vec<Word>
+where
+    I: IntoIterator<Item = (&'a str, f32, u64, u64)>,
+{
+    let mut words: Vec<Word> = Vec::new();
+    let mut boundary = true;
+
+    for (text, probability, start_ms, end_ms) in tokens {
+        let starts_new = boundary || text.starts_with(char::is_whitespace);
+        for (i, piece) in text.split_whitespace().enumerate() {
+            match words.last_mut() {
+                Some(last) if i == 0 && !starts_new => {
+                    last.text.push_str(piece);
+                    last.probability = last.probability.min(probability);
+                    last.end_ms = last.end_ms.max(end_ms);
+                }
+                _ => words.push(Word {
+                    text: piece.to_string(),
+                    start_ms,
+                    end_ms,
+                    probability,
+                    model: None,
+                }),
+            }
+        }
+        if !text.is_empty() {
+            boundary = text.ends_with(char::is_whitespace);
+        }
+    }
+
+    words
+}
+
+/// A timed span of transcribed text (e.g. one Whisper segment).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Segment {
+    /// Start time in milliseconds, relative to the start of the transcribed audio.
+    pub start_ms: u64,
+    /// End time in milliseconds, relative to the start of the transcribed audio.
+    pub end_ms: u64,
+    /// Segment text.
+    pub text: String,
+    /// Per-token probability scores for this segment (empty if not available).
+    pub tokens: Vec<TokenProbability>,
+}
+
+/// One complete decoding of the audio, as kept by beam search.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Hypothesis {
+    /// Hypothesis text.
+    pub text: String,
+    /// Sum of the natural-log probabilities of its tokens.
+    pub log_probability: f32,
+    /// Per-token probabilities, in order.
+    pub tokens: Vec<TokenProbability>,
+}
+
+/// Probability that the audio is in one language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageProbability {
+    /// Language code (e.g., "en", "de").
+    pub language: String,
+    /// Probability in 0.0..1.0.
+    pub probability: f32,
+}
+
+/// Which model a routing transcriber dispatched a chunk to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// Detected language that decided the route.
+    pub language: String,
+    /// Model that transcribed the chunk.
+    pub model: String,
+}
+
 /// Result of a transcription, including detected language and confidence.
 #[derive(Debug, Clone)]
 pub struct TranscriptionResult {
@@ -21,6 +119,14 @@ pub struct TranscriptionResult {
     pub confidence: f32,
     /// Per-token probability scores (empty if not available).
     pub token_probabilities: Vec<TokenProbability>,
+    /// Timed segments (empty if the backend has no timestamps).
+    pub segments: Vec<Segment>,
+    /// Words with timestamps (empty if the backend has no token timestamps).
+    pub words: Vec<Word>,
+    /// Runner-up hypotheses, best first (empty unless the backend keeps an n-best list).
+    pub alternatives: Vec<Hypothesis>,
+    /// Routing decision, when a language-routing transcriber produced the result.
+    pub route: Option<Route>,
 }
 
 impl TranscriptionResult {
@@ -31,6 +137,10 @@ impl TranscriptionResult {
             language: String::new(),
             confidence: 1.0,
             token_probabilities: Vec::new(),
+            segments: Vec::new(),
+            words: Vec::new(),
+            alternatives: Vec::new(),
+            route: None,
         }
     }
 }
@@ -48,11 +158,54 @@ pub trait Transcriber: Send + Sync {
     /// Transcription result with text, language, and confidence — or error
     fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult>;
 
+    /// Transcribe audio in the given language instead of the configured one.
+    ///
+    /// Backends that cannot force a language transcribe as usual.
+    fn transcribe_with_language(
+        &self,
+        audio: &[i16],
+        _language: &str,
+    ) -> Result<TranscriptionResult> {
+        self.transcribe(audio)
+    }
+
     /// Get the name of the loaded model
     fn model_name(&self) -> &str;
 
     /// Check if the transcriber is ready
     fn is_ready(&self) -> bool;
+
+    /// Streaming interface, for backends that recognize audio as it is
+    /// captured rather than in chunks.
+    fn streaming(&self) -> Option<&dyn StreamingTranscriber> {
+        None
+    }
+
+    /// Spoken-language probabilities for the audio, most likely first, from
+    /// a detection pass that does not transcribe. `None` if the backend has
+    /// no such pass.
+    fn detect_language(&self, _audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+        None
+    }
+
+    /// Detect the spoken language, let `choose` pick the language to
+    /// transcribe in from the probabilities (`None`: as configured), and
+    /// transcribe, as one request on the model. `None` if the backend has no
+    /// detect-only pass.
+    fn detect_and_transcribe(
+        &self,
+        audio: &[i16],
+        choose: &mut dyn FnMut(&[LanguageProbability]) -> Option<String>,
+    ) -> Option<Result<TranscriptionResult>> {
+        let probabilities = match self.detect_language(audio)? {
+            Ok(probabilities) => probabilities,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(match choose(&probabilities) {
+            Some(language) => self.transcribe_with_language(audio, &language),
+            None => self.transcribe(audio),
+        })
+    }
 }
 
 /// Implement `Transcriber` for `Arc<T>` to allow sharing across sessions.
@@ -61,6 +214,14 @@ impl<T: Transcriber + ?Sized> Transcriber for Arc<T> {
         (**self).transcribe(audio)
     }
 
+    fn transcribe_with_language(
+        &self,
+        audio: &[i16],
+        language: &str,
+    ) -> Result<TranscriptionResult> {
+        (**self).transcribe_with_language(audio, language)
+    }
+
     fn model_name(&self) -> &str {
         (**self).model_name()
     }
@@ -68,6 +229,22 @@ impl<T: Transcriber + ?Sized> Transcriber for Arc<T> {
     fn is_ready(&self) -> bool {
         (**self).is_ready()
     }
+
+    fn streaming(&self) -> Option<&dyn StreamingTranscriber> {
+        (**self).streaming()
+    }
+
+    fn detect_language(&self, audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+        (**self).detect_language(audio)
+    }
+
+    fn detect_and_transcribe(
+        &self,
+        audio: &[i16],
+        choose: &mut dyn FnMut(&[LanguageProbability]) -> Option<String>,
+    ) -> Option<Result<TranscriptionResult>> {
+        (**self).detect_and_transcribe(audio, choose)
+    }
 }
 
 /// Configuration for transcriber initialization
@@ -156,10 +333,24 @@ impl Transcriber for MockTranscriber {
                 language: self.language.clone(),
                 confidence: self.confidence,
                 token_probabilities: Vec::new(),
+                segments: Vec::new(),
+                words: Vec::new(),
+                alternatives: Vec::new(),
+                route: None,
             })
         }
     }
 
+    fn transcribe_with_language(
+        &self,
+        audio: &[i16],
+        language: &str,
+    ) -> Result<TranscriptionResult> {
+        let mut result = self.transcribe(audio)?;
+        result.language = language.to_string();
+        Ok(result)
+    }
+
     fn model_name(&self) -> &str {
         &self.model_name
     }
@@ -367,4 +558,57 @@ mod tests {
         let result = TranscriptionResult::from_text("test".to_string());
         assert!(result.token_probabilities.is_empty());
     }
+
+    #[test]
+    fn test_group_words_merges_subword_tokens() {
+        let words = group_words([
+            ("The", 0.9, 0, 200),
+            (" qu", 0.5, 200, 300),
+            ("ick", 0.75, 300, 450),
+            (" fox", 0.875, 450, 700),
+            (".", 0.95, 700, 720),
+        ]);
+        assert_eq!(
+            words,
+            vec![
+                Word {
+                    text: "The".to_string(),
+                    start_ms: 0,
+                    end_ms: 200,
+                    probability: 0.9,
+                    model: None,
+                },
+                Word {
+                    text: "quick".to_string(),
+                    start_ms: 200,
+                    end_ms: 450,
+                    probability: 0.5,
+                    model: None,
+                },
+                Word {
+                    text: "fox.".to_string(),
+                    start_ms: 450,
+                    end_ms: 720,
+                    probability: 0.875,
+                    model: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_words_whitespace_only_token_breaks_word() {
+        let words = group_words([
+            ("hello", 0.5, 0, 10),
+            (" ", 1.0, 10, 10),
+            ("world", 0.5, 10, 20),
+        ]);
+        let texts: Vec<&str> = words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(texts, vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn test_group_words_empty() {
+        assert!(group_words(std::iter::empty()).is_empty());
+    }
 }
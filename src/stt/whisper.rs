@@ -13,9 +13,13 @@
 
 use crate::defaults;
 use crate::error::{Result, VoicshError};
-use crate::stt::transcriber::{TokenProbability, Transcriber, TranscriptionResult};
-use std::path::{Path, PathBuf};
+use crate::stt::transcriber::{Transcriber, TranscriptionResult};
+use std::path::PathBuf;
 
+#[cfg(feature = "whisper")]
+use crate::stt::transcriber::{LanguageProbability, Segment, TokenProbability, group_words};
+#[cfg(feature = "whisper")]
+use std::path::Path;
 #[cfg(feature = "whisper")]
 use std::sync::{Mutex, Once};
 #[cfg(feature = "whisper")]
@@ -26,6 +30,14 @@ use whisper_rs::{
 #[cfg(feature = "whisper")]
 static LOGGING_HOOKS_INSTALLED: Once = Once::new();
 
+/// Route whisper.cpp output through the `log` crate (only once), keeping it off stderr.
+#[cfg(feature = "whisper")]
+pub(crate) fn suppress_whisper_logging() {
+    LOGGING_HOOKS_INSTALLED.call_once(|| {
+        install_logging_hooks();
+    });
+}
+
 /// Configuration for Whisper transcriber.
 #[derive(Debug, Clone)]
 pub struct WhisperConfig {
@@ -109,10 +121,7 @@ impl WhisperTranscriber {
     /// Returns `VoicshError::TranscriptionModelNotFound` if the model file doesn't exist
     /// Returns `VoicshError::TranscriptionInferenceFailed` if model loading fails
     pub fn new(config: WhisperConfig) -> Result<Self> {
-        // Install logging hooks to suppress whisper.cpp output (only once)
-        LOGGING_HOOKS_INSTALLED.call_once(|| {
-            install_logging_hooks();
-        });
+        suppress_whisper_logging();
 
         // Validate that the model file exists
         if !config.model_path.exists() {
@@ -188,6 +197,57 @@ impl WhisperTranscriber {
             .map(|&sample| sample as f32 / 32768.0)
             .collect()
     }
+
+    /// Run whisper.cpp's language detection (encoder plus one decoder step)
+    /// on the start of the audio.
+    fn language_probabilities(&self, audio: &[i16]) -> Result<Vec<LanguageProbability>> {
+        let context =
+            self.context
+                .lock()
+                .map_err(|e| VoicshError::TranscriptionInferenceFailed {
+                    message: format!("Failed to acquire context lock: {}", e),
+                })?;
+
+        // English-only models have no language tokens to compare
+        if !context.is_multilingual() {
+            return Ok(vec![LanguageProbability {
+                language: defaults::ENGLISH_LANGUAGE.to_string(),
+                probability: 1.0,
+            }]);
+        }
+
+        let mut state =
+            context
+                .create_state()
+                .map_err(|e| VoicshError::TranscriptionInferenceFailed {
+                    message: format!("Failed to create Whisper state: {}", e),
+                })?;
+        let threads = self
+            .config
+            .threads
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get().min(4)));
+        let detect_failed =
+            |e: whisper_rs::WhisperError| VoicshError::TranscriptionInferenceFailed {
+                message: format!("Whisper language detection failed: {}", e),
+            };
+        state
+            .pcm_to_mel(&Self::convert_audio(audio), threads)
+            .map_err(detect_failed)?;
+        let (_, probabilities) = state.lang_detect(0, threads).map_err(detect_failed)?;
+
+        let mut languages: Vec<LanguageProbability> = probabilities
+            .into_iter()
+            .enumerate()
+            .filter_map(|(id, probability)| {
+                whisper_rs::get_lang_str(id as i32).map(|language| LanguageProbability {
+                    language: language.to_string(),
+                    probability,
+                })
+            })
+            .collect();
+        languages.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+        Ok(languages)
+    }
 }
 
 #[cfg(not(feature = "whisper"))]
@@ -231,6 +291,14 @@ impl WhisperTranscriber {
 #[cfg(feature = "whisper")]
 impl Transcriber for WhisperTranscriber {
     fn transcribe(&self, audio: &[i16]) -> Result<TranscriptionResult> {
+        self.transcribe_with_language(audio, &self.config.language)
+    }
+
+    fn transcribe_with_language(
+        &self,
+        audio: &[i16],
+        language: &str,
+    ) -> Result<TranscriptionResult> {
         // Convert audio format from i16 to f32
         let audio_f32 = Self::convert_audio(audio);
 
@@ -254,10 +322,10 @@ impl Transcriber for WhisperTranscriber {
         let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
 
         // Set language
-        if self.config.language == crate::defaults::AUTO_LANGUAGE {
+        if language == crate::defaults::AUTO_LANGUAGE {
             params.set_language(None);
         } else {
-            params.set_language(Some(&self.config.language));
+            params.set_language(Some(language));
         }
 
         // Set number of threads if specified
@@ -271,6 +339,9 @@ impl Transcriber for WhisperTranscriber {
         params.set_print_realtime(false);
         params.set_print_timestamps(false);
 
+        // Per-token timestamps for word timing
+        params.set_token_timestamps(true);
+
         // Run inference
         state
             .full(params, &audio_f32)
@@ -290,11 +361,17 @@ impl Transcriber for WhisperTranscriber {
         let mut prob_sum = 0.0_f64;
         let mut token_count = 0u32;
         let mut token_probs: Vec<TokenProbability> = Vec::new();
+        let mut segments: Vec<Segment> = Vec::new();
+        // (text, probability, start_ms, end_ms) for word grouping
+        let mut timed_tokens: Vec<(String, f32, u64, u64)> = Vec::new();
 
         for segment in state.as_iter() {
-            if let Ok(text) = segment.to_str_lossy() {
-                transcription.push_str(&text);
-            }
+            let segment_text = segment
+                .to_str_lossy()
+                .map(|t| t.into_owned())
+                .unwrap_or_default();
+            transcription.push_str(&segment_text);
+            let first_token = token_probs.len();
             for i in 0..segment.n_tokens() {
                 if let Some(token) = segment.get_token(i) {
                     let prob = token.token_probability();
@@ -313,12 +390,27 @@ impl Transcriber for WhisperTranscriber {
                     {
                         continue;
                     }
+                    // Whisper timestamps are in centiseconds
+                    let data = token.token_data();
+                    timed_tokens.push((
+                        token_text.clone(),
+                        prob,
+                        data.t0.max(0) as u64 * 10,
+                        data.t1.max(0) as u64 * 10,
+                    ));
                     token_probs.push(TokenProbability {
                         token: token_text,
                         probability: prob,
                     });
                 }
             }
+            // Whisper timestamps are in centiseconds
+            segments.push(Segment {
+                start_ms: segment.start_timestamp().max(0) as u64 * 10,
+                end_ms: segment.end_timestamp().max(0) as u64 * 10,
+                text: segment_text.trim().to_string(),
+                tokens: token_probs[first_token..].to_vec(),
+            });
         }
 
         let confidence = if token_count > 0 {
@@ -328,12 +420,21 @@ impl Transcriber for WhisperTranscriber {
         };
 
         let token_probabilities = token_probs;
+        let words = group_words(
+            timed_tokens
+                .iter()
+                .map(|(text, prob, start, end)| (text.as_str(), *prob, *start, *end)),
+        );
 
         Ok(TranscriptionResult {
             text: transcription.trim().to_string(),
             language,
             confidence,
             token_probabilities,
+            segments,
+            words,
+            alternatives: Vec::new(),
+            route: None,
         })
     }
 
@@ -345,6 +446,10 @@ impl Transcriber for WhisperTranscriber {
         // The transcriber is ready if we successfully created it
         true
     }
+
+    fn detect_language(&self, audio: &[i16]) -> Option<Result<Vec<LanguageProbability>>> {
+        Some(self.language_probabilities(audio))
+    }
 }
 
 #[cfg(not(feature = "whisper"))]
@@ -2,7 +2,8 @@
 //!
 //! All `libc` syscall wrappers and `std::env` unsafe calls live here.
 //! The only other `unsafe` in the codebase is `unsafe impl Send` for
-//! `SendableStream` in `audio::capture` (required by the CPAL stream API).
+//! `SendableStream` in `audio::capture` (required by the CPAL stream API) and
+//! for `SileroModel` in `audio::neural_vad` (whisper.cpp VAD context).
 
 use std::ffi::CStr;
 use std::sync::Mutex;
@@ -92,6 +93,96 @@ pub fn suppress_audio_warnings() {
     set_env("PW_LOG", "0");
 }
 
+/// Blocking inotify watch on a single directory.
+///
+/// Watching the directory (rather than the file) catches editors that save
+/// by writing a temp file and renaming it over the original.
+pub struct DirWatcher {
+    fd: std::os::fd::OwnedFd,
+}
+
+impl DirWatcher {
+    /// Start watching `dir` for files being written or moved into it.
+    pub fn new(dir: &std::path::Path) -> std::io::Result<Self> {
+        use std::os::fd::FromRawFd;
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_dir = std::ffi::CString::new(dir.as_os_str().as_bytes())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+        // SAFETY: inotify_init1 has no preconditions; we check the return value
+        // and take ownership of the fd immediately so it is closed on drop.
+        let raw = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+        if raw < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // SAFETY: `raw` is a freshly created, valid fd that nothing else owns.
+        let fd = unsafe { std::os::fd::OwnedFd::from_raw_fd(raw) };
+
+        let mask = libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO;
+        // SAFETY: fd is a valid inotify fd and c_dir is a valid NUL-terminated path.
+        let wd = unsafe {
+            libc::inotify_add_watch(std::os::fd::AsRawFd::as_raw_fd(&fd), c_dir.as_ptr(), mask)
+        };
+        if wd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(Self { fd })
+    }
+
+    /// Block until at least one event arrives, then return the file names it touched.
+    pub fn read_names(&self) -> std::io::Result<Vec<std::ffi::OsString>> {
+        use std::os::unix::ffi::OsStrExt;
+
+        let mut buf = [0u8; 4096];
+        // SAFETY: fd is a valid inotify fd and buf is a writable buffer of the given length.
+        let n = unsafe {
+            libc::read(
+                std::os::fd::AsRawFd::as_raw_fd(&self.fd),
+                buf.as_mut_ptr().cast(),
+                buf.len(),
+            )
+        };
+        if n < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(parse_inotify_names(&buf[..n as usize])
+            .into_iter()
+            .map(|name| std::ffi::OsStr::from_bytes(name).to_os_string())
+            .collect())
+    }
+}
+
+/// Parse file names out of a buffer of packed `struct inotify_event` records.
+///
+/// Each record is `wd: i32, mask: u32, cookie: u32, len: u32` followed by
+/// `len` bytes of NUL-padded name.
+fn parse_inotify_names(buf: &[u8]) -> Vec<&[u8]> {
+    const HEADER: usize = 16;
+    let mut names = Vec::new();
+    let mut offset = 0;
+    while offset + HEADER <= buf.len() {
+        let len_bytes: [u8; 4] = [
+            buf[offset + 12],
+            buf[offset + 13],
+            buf[offset + 14],
+            buf[offset + 15],
+        ];
+        let len = u32::from_ne_bytes(len_bytes) as usize;
+        let start = offset + HEADER;
+        let end = (start + len).min(buf.len());
+        let raw = &buf[start..end];
+        let name_len = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+        if name_len > 0 {
+            names.push(&raw[..name_len]);
+        }
+        offset = start + len;
+    }
+    names
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,4 +234,50 @@ mod tests {
             "var should be removed after remove_env"
         );
     }
+
+    fn inotify_record(name: &[u8], padded_len: usize) -> Vec<u8> {
+        let mut rec = Vec::new();
+        rec.extend_from_slice(&1i32.to_ne_bytes());
+        rec.extend_from_slice(&libc::IN_CLOSE_WRITE.to_ne_bytes());
+        rec.extend_from_slice(&0u32.to_ne_bytes());
+        rec.extend_from_slice(&(padded_len as u32).to_ne_bytes());
+        let mut padded = name.to_vec();
+        padded.resize(padded_len, 0);
+        rec.extend_from_slice(&padded);
+        rec
+    }
+
+    #[test]
+    fn parse_inotify_names_reads_packed_records() {
+        let mut buf = inotify_record(b"config.toml", 16);
+        buf.extend(inotify_record(b".config.toml.swp", 32));
+        let names = parse_inotify_names(&buf);
+        assert_eq!(names, vec![&b"config.toml"[..], &b".config.toml.swp"[..]]);
+    }
+
+    #[test]
+    fn parse_inotify_names_ignores_truncated_header() {
+        let mut buf = inotify_record(b"config.toml", 16);
+        buf.extend_from_slice(&[0u8; 7]);
+        let names = parse_inotify_names(&buf);
+        assert_eq!(names, vec![&b"config.toml"[..]]);
+    }
+
+    #[test]
+    fn dir_watcher_reports_written_file() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let watcher = DirWatcher::new(dir.path()).expect("inotify watch");
+        std::fs::write(dir.path().join("config.toml"), "x = 1").expect("write file");
+        let names = watcher.read_names().expect("read events");
+        assert!(
+            names.iter().any(|n| n == "config.toml"),
+            "expected config.toml in {names:?}"
+        );
+    }
+
+    #[test]
+    fn dir_watcher_missing_dir_returns_error() {
+        let result = DirWatcher::new(std::path::Path::new("/nonexistent_dir_for_voicsh_test"));
+        assert!(result.is_err(), "expected error for missing directory");
+    }
 }
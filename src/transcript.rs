@@ -0,0 +1,459 @@
+//! Transcript export: plain text, SRT, WebVTT, JSON and TSV.
+//!
+//! Segment times are on the stream timeline (milliseconds from the start of
+//! the audio), as produced by the transcriber station.
+
+use crate::pipeline::types::TranscribedText;
+use crate::stt::transcriber::{TokenProbability, Word};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Output format for transcripts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    /// Plain text, no timestamps.
+    #[default]
+    Txt,
+    /// SubRip subtitles.
+    Srt,
+    /// WebVTT subtitles.
+    Vtt,
+    /// JSON with segments, token probabilities and detected language.
+    Json,
+    /// Tab-separated `start`, `end` (milliseconds) and `text`.
+    Tsv,
+}
+
+impl std::fmt::Display for TranscriptFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Txt => write!(f, "txt"),
+            Self::Srt => write!(f, "srt"),
+            Self::Vtt => write!(f, "vtt"),
+            Self::Json => write!(f, "json"),
+            Self::Tsv => write!(f, "tsv"),
+        }
+    }
+}
+
+impl std::str::FromStr for TranscriptFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "txt" | "text" => Ok(Self::Txt),
+            "srt" => Ok(Self::Srt),
+            "vtt" | "webvtt" => Ok(Self::Vtt),
+            "json" => Ok(Self::Json),
+            "tsv" => Ok(Self::Tsv),
+            other => Err(format!(
+                "Unknown output format '{}'. Valid options: txt, srt, vtt, json, tsv",
+                other
+            )),
+        }
+    }
+}
+
+/// One timed entry of a transcript.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    /// Detected language code. Empty if unknown.
+    pub language: String,
+    /// Confidence of the chunk this segment came from (0.0..1.0).
+    pub confidence: f32,
+    /// Per-token probability scores.
+    pub tokens: Vec<TokenProbability>,
+    /// Timed words, when the backend provides token timestamps.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<Word>,
+}
+
+/// Accumulates transcribed chunks and renders them in any [`TranscriptFormat`].
+#[derive(Debug, Default)]
+pub struct Transcript {
+    segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a transcribed chunk.
+    ///
+    /// Whisper segments are used as-is when they still match the final text.
+    /// If post-processing or correction changed the text, the chunk becomes a
+    /// single segment spanning all of its Whisper segments.
+    pub fn push(&mut self, text: &TranscribedText) {
+        let final_text = text.text.trim();
+        if final_text.is_empty() {
+            return;
+        }
+
+        let joined = text
+            .segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !text.segments.is_empty()
+            && normalize_whitespace(&joined) == normalize_whitespace(final_text)
+        {
+            let mut words_per_segment = vec![Vec::new(); text.segments.len()];
+            for word in &text.words {
+                let index = text
+                    .segments
+                    .iter()
+                    .position(|s| s.end_ms > word.start_ms)
+                    .unwrap_or(text.segments.len() - 1);
+                words_per_segment[index].push(word.clone());
+            }
+            self.segments.extend(
+                text.segments
+                    .iter()
+                    .zip(words_per_segment)
+                    .map(|(s, words)| TranscriptSegment {
+                        start_ms: s.start_ms,
+                        end_ms: s.end_ms,
+                        text: s.text.clone(),
+                        language: text.language.clone(),
+                        confidence: text.confidence,
+                        tokens: s.tokens.clone(),
+                        words,
+                    }),
+            );
+            return;
+        }
+
+        let last_end = self.segments.last().map_or(0, |s| s.end_ms);
+        let (start_ms, end_ms) = match (text.segments.first(), text.segments.last()) {
+            (Some(first), Some(last)) => (first.start_ms, last.end_ms),
+            _ => (last_end, last_end),
+        };
+        self.segments.push(TranscriptSegment {
+            start_ms,
+            end_ms,
+            text: final_text.to_string(),
+            language: text.language.clone(),
+            confidence: text.confidence,
+            tokens: text.token_probabilities.clone(),
+            words: text.words.clone(),
+        });
+    }
+
+    pub fn segments(&self) -> &[TranscriptSegment] {
+        &self.segments
+    }
+
+    /// Full text of the transcript, segments joined by spaces.
+    pub fn text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Most frequent detected language, or `None` if no segment has one.
+    pub fn language(&self) -> Option<String> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for segment in &self.segments {
+            if !segment.language.is_empty() {
+                *counts.entry(segment.language.as_str()).or_default() += 1;
+            }
+        }
+        // Ties resolve to the language that appeared first
+        let mut best: Option<(&str, usize)> = None;
+        for segment in &self.segments {
+            if let Some(&count) = counts.get(segment.language.as_str())
+                && best.is_none_or(|(_, c)| count > c)
+            {
+                best = Some((segment.language.as_str(), count));
+            }
+        }
+        best.map(|(lang, _)| lang.to_string())
+    }
+
+    /// Render the transcript in the given format.
+    pub fn render(&self, format: TranscriptFormat) -> String {
+        match format {
+            TranscriptFormat::Txt => self.text(),
+            TranscriptFormat::Srt => self.render_srt(),
+            TranscriptFormat::Vtt => self.render_vtt(),
+            TranscriptFormat::Json => self.render_json(),
+            TranscriptFormat::Tsv => self.render_tsv(),
+        }
+    }
+
+    fn render_srt(&self) -> String {
+        let mut out = String::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            out.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                format_timestamp(segment.start_ms, ','),
+                format_timestamp(segment.end_ms, ','),
+                segment.text
+            ));
+        }
+        out
+    }
+
+    fn render_vtt(&self) -> String {
+        let mut out = String::from("WEBVTT\n\n");
+        for segment in &self.segments {
+            out.push_str(&format!(
+                "{} --> {}\n{}\n\n",
+                format_timestamp(segment.start_ms, '.'),
+                format_timestamp(segment.end_ms, '.'),
+                escape_vtt(&segment.text)
+            ));
+        }
+        out
+    }
+
+    fn render_tsv(&self) -> String {
+        let mut out = String::from("start\tend\ttext\n");
+        for segment in &self.segments {
+            let text = segment.text.replace(['\t', '\n', '\r'], " ");
+            out.push_str(&format!(
+                "{}\t{}\t{}\n",
+                segment.start_ms, segment.end_ms, text
+            ));
+        }
+        out
+    }
+
+    fn render_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Document<'a> {
+            text: String,
+            language: Option<String>,
+            segments: &'a [TranscriptSegment],
+        }
+
+        let document = Document {
+            text: self.text(),
+            language: self.language(),
+            segments: &self.segments,
+        };
+        // Serializing plain strings and numbers cannot fail
+        serde_json::to_string_pretty(&document).unwrap_or_default()
+    }
+}
+
+/// Format milliseconds as `HH:MM:SS<sep>mmm` (`,` for SRT, `.` for WebVTT).
+fn format_timestamp(ms: u64, separator: char) -> String {
+    let hours = ms / 3_600_000;
+    let minutes = (ms / 60_000) % 60;
+    let seconds = (ms / 1000) % 60;
+    let millis = ms % 1000;
+    format!("{hours:02}:{minutes:02}:{seconds:02}{separator}{millis:03}")
+}
+
+/// Escape characters that have meaning in WebVTT cue text.
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stt::transcriber::Segment;
+
+    fn segment(start_ms: u64, end_ms: u64, text: &str) -> Segment {
+        Segment {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+            tokens: vec![TokenProbability {
+                token: text.to_string(),
+                probability: 0.5,
+            }],
+        }
+    }
+
+    fn chunk(text: &str, language: &str, segments: Vec<Segment>) -> TranscribedText {
+        let mut t = TranscribedText::new(text.to_string());
+        t.language = language.to_string();
+        t.segments = segments;
+        t
+    }
+
+    fn sample_transcript() -> Transcript {
+        let mut transcript = Transcript::new();
+        transcript.push(&chunk(
+            "Hello everyone. Let's start.",
+            "en",
+            vec![
+                segment(1_000, 2_500, "Hello everyone."),
+                segment(2_500, 4_000, "Let's start."),
+            ],
+        ));
+        transcript.push(&chunk(
+            "Yesterday I fixed the build.",
+            "en",
+            vec![segment(
+                3_725_040,
+                3_728_000,
+                "Yesterday I fixed the build.",
+            )],
+        ));
+        transcript
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("SRT".parse::<TranscriptFormat>(), Ok(TranscriptFormat::Srt));
+        assert_eq!(
+            "webvtt".parse::<TranscriptFormat>(),
+            Ok(TranscriptFormat::Vtt)
+        );
+        assert!("docx".parse::<TranscriptFormat>().is_err());
+        assert_eq!(TranscriptFormat::default(), TranscriptFormat::Txt);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(3_725_040, '.'), "01:02:05.040");
+    }
+
+    #[test]
+    fn test_render_srt() {
+        let srt = sample_transcript().render(TranscriptFormat::Srt);
+        assert_eq!(
+            srt,
+            "1\n00:00:01,000 --> 00:00:02,500\nHello everyone.\n\n\
+             2\n00:00:02,500 --> 00:00:04,000\nLet's start.\n\n\
+             3\n01:02:05,040 --> 01:02:08,000\nYesterday I fixed the build.\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_vtt_escapes_markup() {
+        let mut transcript = Transcript::new();
+        transcript.push(&chunk("a <b> & c", "", vec![segment(0, 900, "a <b> & c")]));
+        let vtt = transcript.render(TranscriptFormat::Vtt);
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\n00:00:00.000 --> 00:00:00.900\na &lt;b&gt; &amp; c\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_tsv() {
+        let tsv = sample_transcript().render(TranscriptFormat::Tsv);
+        let lines: Vec<&str> = tsv.lines().collect();
+        assert_eq!(lines[0], "start\tend\ttext");
+        assert_eq!(lines[1], "1000\t2500\tHello everyone.");
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_render_txt() {
+        assert_eq!(
+            sample_transcript().render(TranscriptFormat::Txt),
+            "Hello everyone. Let's start. Yesterday I fixed the build."
+        );
+    }
+
+    #[test]
+    fn test_render_json_includes_language_and_tokens() {
+        let json = sample_transcript().render(TranscriptFormat::Json);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["language"], "en");
+        assert_eq!(value["segments"].as_array().unwrap().len(), 3);
+        assert_eq!(value["segments"][0]["start_ms"], 1000);
+        assert_eq!(value["segments"][0]["tokens"][0]["probability"], 0.5);
+        assert_eq!(value["segments"][1]["language"], "en");
+    }
+
+    fn word(text: &str, start_ms: u64, end_ms: u64) -> Word {
+        Word {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            probability: 0.9,
+            model: None,
+        }
+    }
+
+    #[test]
+    fn test_push_assigns_words_to_segments() {
+        let mut transcript = Transcript::new();
+        let mut text = chunk(
+            "Hello everyone. Let's start.",
+            "en",
+            vec![
+                segment(1_000, 2_500, "Hello everyone."),
+                segment(2_500, 4_000, "Let's start."),
+            ],
+        );
+        text.words = vec![
+            word("Hello", 1_000, 1_400),
+            word("everyone.", 1_400, 2_500),
+            word("Let's", 2_500, 3_000),
+            word("start.", 3_000, 4_000),
+        ];
+        transcript.push(&text);
+
+        let segments = transcript.segments();
+        assert_eq!(segments[0].words.len(), 2);
+        assert_eq!(segments[1].words[0].text, "Let's");
+        assert_eq!(segments[1].words.len(), 2);
+
+        let json = transcript.render(TranscriptFormat::Json);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["segments"][1]["words"][1]["start_ms"], 3000);
+        assert_eq!(value["segments"][1]["words"][1]["text"], "start.");
+    }
+
+    #[test]
+    fn test_render_json_omits_missing_words() {
+        let json = sample_transcript().render(TranscriptFormat::Json);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["segments"][0].get("words").is_none());
+    }
+
+    #[test]
+    fn test_push_modified_text_becomes_single_segment() {
+        let mut transcript = Transcript::new();
+        transcript.push(&chunk(
+            "Hello, world!",
+            "en",
+            vec![segment(0, 1_000, "hello"), segment(1_000, 2_000, "world")],
+        ));
+        assert_eq!(transcript.segments().len(), 1);
+        let only = &transcript.segments()[0];
+        assert_eq!((only.start_ms, only.end_ms), (0, 2_000));
+        assert_eq!(only.text, "Hello, world!");
+    }
+
+    #[test]
+    fn test_push_skips_empty_text() {
+        let mut transcript = Transcript::new();
+        transcript.push(&chunk("  ", "en", vec![]));
+        assert!(transcript.segments().is_empty());
+        assert_eq!(transcript.language(), None);
+    }
+
+    #[test]
+    fn test_language_majority() {
+        let mut transcript = Transcript::new();
+        transcript.push(&chunk("eins", "de", vec![segment(0, 1, "eins")]));
+        transcript.push(&chunk("one", "en", vec![segment(1, 2, "one")]));
+        transcript.push(&chunk("two", "en", vec![segment(2, 3, "two")]));
+        assert_eq!(transcript.language().as_deref(), Some("en"));
+    }
+}